@@ -0,0 +1,129 @@
+use std::fmt::{ Display, Formatter, Result };
+use crate::tokenize::{ Span, SyntaxToken };
+
+/* @version 0.3.0 */
+
+/*
+  Error of parsing or planning a query, rendered against the query text:
+
+    Syntax Error: Node `RETURN`
+     --> line 1, column 10
+      |
+    1 | MATCH (n RETURN n
+      |          ^^^^^^ expected `)`, found `RETURN`
+*/
+#[derive( Debug, PartialEq, Clone )]
+pub struct QueryError
+{
+  pub message: String,
+  pub expected: Option<String>,
+  pub found: Option<String>,
+  pub span: Option<Span>, // None when the error has no place in the query
+}
+
+impl QueryError
+{
+  pub fn new ( message: String ) -> QueryError
+  {
+    QueryError { message: message, expected: None, found: None, span: None }
+  }
+
+  /// Error at a span, found is the text written there
+  pub fn at ( message: String, span: Span, found: Option<String> ) -> QueryError
+  {
+    QueryError { message: message, expected: None, found: found, span: Some( span ) }
+  }
+
+  /// Error at the token at pos, or just after the last token when pos is past the end of the query
+  pub fn at_token ( message: String, tokens: &[SyntaxToken], pos: usize ) -> QueryError
+  {
+    match tokens.get( pos )
+    {
+      Some( token ) => QueryError::at( message, token.span, Some( format!( "`{}`", token.val ))),
+      None =>
+      {
+        let end = tokens.last().map( | t | t.span ).unwrap_or_default();
+        let span = Span { start: end.end, end: end.end, line: end.line, column: end.column + end.end - end.start };
+        QueryError::at( message, span, Some( String::from( "end of query" )))
+      }
+    }
+  }
+
+  /// Error that has no place yet points at the token, the keyword of the clause it was raised for
+  pub fn or_at ( mut self, token: &SyntaxToken ) -> QueryError
+  {
+    if self.span.is_some() { return self; }
+    self.span = Some( token.span );
+    if self.found.is_none() { self.found = Some( format!( "`{}`", token.val )); }
+    self
+  }
+
+  /// Message with the line of the query and carets under the span
+  pub fn render ( &self, query: &str ) -> String
+  {
+    if self.span.is_none() { return self.message.clone(); }
+    let span = self.span.unwrap();
+
+    let line_text = query.split( '\n' ).nth( span.line - 1 ).unwrap_or( "" ).trim_end_matches( '\r' );
+    let gutter = " ".repeat( span.line.to_string().len() );
+    let line_start: usize = query.split( '\n' ).take( span.line - 1 ).map( | line | line.len() + 1 ).sum();
+    let line_end = line_start + line_text.len();
+
+    // spans past the end of the line are underlined to its end, a span at the end of the query gets one caret
+    let width = query.get( span.start..span.end.min( line_end ).max( span.start )).map( | s | s.chars().count() ).unwrap_or( 0 );
+    let carets = "^".repeat( width.max( 1 ));
+    let offset: String = line_text.chars().take( span.column - 1 ).map( | c | if c == '\t' { '\t' } else { ' ' } ).collect();
+
+    let mut rendered = format!( "{}\n{}--> line {}, column {}\n", self.message, gutter, span.line, span.column );
+    rendered.push_str( &format!( "{} |\n{} | {}\n{} | {}{}", gutter, span.line, line_text, gutter, offset, carets ));
+    let note = self.note();
+    if note.is_some() { rendered.push_str( &format!( " {}", note.unwrap() )); }
+    rendered
+  }
+
+  /// expected `x`, found `y`
+  pub fn note ( &self ) -> Option<String>
+  {
+    match ( &self.expected, &self.found )
+    {
+      ( Some( expected ), Some( found )) => Some( format!( "expected {}, found {}", expected, found )),
+      ( Some( expected ), None ) => Some( format!( "expected {}", expected )),
+      ( None, Some( found )) => Some( format!( "found {}", found )),
+      ( None, None ) => None,
+    }
+  }
+}
+
+impl Display for QueryError
+{
+  fn fmt( &self, f: &mut Formatter ) -> Result
+  {
+    if self.span.is_none() { return write!( f, "{}", self.message ); }
+    let span = self.span.unwrap();
+    write!( f, "{} at line {}, column {}", self.message, span.line, span.column )
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  #[test]
+  fn test_render ()
+  {
+    let span = Span { start: 9, end: 15, line: 1, column: 10 };
+    let mut err = QueryError::at( String::from( "Syntax Error: Node `RETURN`" ), span, Some( String::from( "`RETURN`" )));
+    err.expected = Some( String::from( "`)`" ));
+    assert_eq!( err.render( "MATCH (n RETURN n" ), String::from(
+      "Syntax Error: Node `RETURN`\n --> line 1, column 10\n  |\n1 | MATCH (n RETURN n\n  |          ^^^^^^ expected `)`, found `RETURN`" ));
+    assert_eq!( err.to_string(), String::from( "Syntax Error: Node `RETURN` at line 1, column 10" ));
+
+    // second line, a span at the end of the query gets a single caret
+    let end = Span { start: 12, end: 12, line: 2, column: 7 };
+    let err1 = QueryError::at( String::from( "Syntax Error: Node" ), end, Some( String::from( "end of query" )));
+    assert_eq!( err1.render( "MATCH\n  (n:A" ).ends_with( "2 |   (n:A\n  |       ^ found end of query" ), true );
+
+    assert_eq!( QueryError::new( String::from( "Syntax Error: Create" )).render( "CREATE" ), String::from( "Syntax Error: Create" ));
+  }
+}
@@ -0,0 +1,1305 @@
+pub mod error;
+
+use crate::ast::error::QueryError;
+use crate::cmd::{ HopRange, MergeAction };
+use crate::cmd::aggregate::Aggregate;
+use crate::cmd::label_expression::LabelExpression;
+use crate::cmd::predicate::{ Operand, Predicate };
+use crate::common::DirectionType;
+use crate::parser::literal::parse_literal;
+use crate::result_set::Value;
+use crate::tokenize::{ Span, SyntaxToken, SyntaxTokenType };
+
+/* @version 0.3.0 */
+
+/*
+  Typed query tree built from the tokens of parse_syntax(), the planner lowers it into a Transaction.
+
+  Query         := Clause*                       FROM name may stand between any two clauses
+  Clause        := [OPTIONAL] MATCH Pattern+ [WHERE predicate]
+                 | MERGE Pattern+ ( ON CREATE SET SetItem+ | ON MATCH SET SetItem+ )*
+                 | CREATE [GRAPH name] Pattern*
+                 | WITH Projection [WHERE predicate]
+                 | UNWIND operand AS variable
+                 | SET SetItem+
+                 | REMOVE RemoveItem+
+                 | [DETACH] DELETE ( GRAPH name | variable+ )
+                 | RETURN Projection
+  Pattern       := [variable =] [shortestPath( | allShortestPaths(] Node ( Relationship Node )* [)]
+  Node          := ( [variable] [:Label ... | :expression] [{ key: value ... } | $map] [WHERE predicate] )
+  Relationship  := [<] - [ '[' [variable] [:TYPE] [*hops] [{ key: value ... } | $map] [WHERE predicate] ']' ] - [>]
+  Projection    := [DISTINCT] Item+ [ORDER BY operand [ASC | DESC] ...] [SKIP count] [LIMIT count]
+  Item          := ( aggregate( [DISTINCT] operand | * [, percentile] ) | operand ) [AS alias]
+
+  The tokenizer drops commas, patterns and items simply follow each other. Predicates and operands are parsed
+  by cmd::predicate. Errors point at the token parsing stopped on and say what was
+  expected there, patterns, nodes, relationships and property values keep their spans for the errors of planning.
+
+  split_statements()
+  parse_query()
+    :: take_from()
+    :: Parser
+        :: parse_clause()
+            :: parse_match()
+            :: parse_merge()
+            :: parse_create()
+            :: parse_with()
+            :: parse_unwind()
+            :: parse_set_items()
+            :: parse_remove()
+            :: parse_delete()
+            :: parse_projection()
+                :: parse_item()
+                    :: parse_aggregate()
+                :: parse_sort_keys()
+                :: parse_count()
+            :: parse_where()
+        :: parse_patterns()
+            :: parse_pattern()
+                :: parse_node()
+                :: parse_relationship()
+                :: parse_properties()
+                    :: parse_value()
+                        :: parse_list()
+                :: parse_map_param()
+*/
+
+// ---------------------------------------------------------------------------------------------------------------------
+/// Clauses of a query in the order they are written
+#[derive( Debug, Clone )]
+pub struct Query
+{
+  pub graph: Option<String>, // FROM name
+  pub clauses: Vec<Clause>,
+  pub spans: Vec<Span>, // where each clause is written
+  pub keywords: Vec<SyntaxToken>, // the keyword each clause starts with, errors of planning a clause point at it
+  pub parameters: Vec<( String, Span )>, // every $name as written, checked against the parameter map before planning
+}
+
+#[derive( Debug, Clone )]
+pub enum Clause
+{
+  Match { optional: bool, patterns: Vec<Pattern>, predicate: Option<Predicate> },
+  Merge { patterns: Vec<Pattern>, actions: Vec<( MergeAction, Vec<SetItem> )> },
+  Create { graph: Option<String>, patterns: Vec<Pattern> },
+  With { projection: Projection, predicate: Option<Predicate> },
+  Unwind { operand: Operand, variable: String },
+  Set( Vec<SetItem> ),
+  Remove( Vec<RemoveItem> ),
+  Delete { detach: bool, graph: Option<String>, variables: Vec<String> },
+  Return( Projection ),
+}
+
+/// (a)-[r]->(b), relationships[i] joins nodes[i] and nodes[i + 1]
+#[derive( Debug, Clone )]
+pub struct Pattern
+{
+  pub variable: Option<String>, // p = ( ... )
+  pub shortest: Option<bool>, // shortestPath( ... ), true for allShortestPaths
+  pub nodes: Vec<NodePattern>,
+  pub relationships: Vec<RelationshipPattern>,
+  pub span: Span,
+}
+
+#[derive( Debug, Clone )]
+pub struct NodePattern
+{
+  pub variable: Option<String>,
+  pub labels: Vec<String>, // the first is the primary label
+  pub label_expression: Option<LabelExpression>,
+  pub properties: Vec<( String, Operand, Span )>, // literals, lists, parameters and variables, with the span of the value
+  pub properties_param: Option<( String, Span )>, // ( n $props ), a map parameter in place of the { }
+  pub predicate: Option<Predicate>, // inline WHERE
+  pub span: Span,
+}
+
+#[derive( Debug, Clone )]
+pub struct RelationshipPattern
+{
+  pub variable: Option<String>,
+  pub rel_type: Option<String>,
+  pub direction: DirectionType,
+  pub hops: Option<HopRange>,
+  pub properties: Vec<( String, Operand, Span )>,
+  pub properties_param: Option<( String, Span )>,
+  pub predicate: Option<Predicate>, // inline WHERE
+  pub span: Span,
+}
+
+/// n.key = value, n += { key: value }, n += $props or n:Label
+#[derive( Debug, Clone )]
+pub enum SetItem
+{
+  Property { variable: String, key: String, value: Operand, span: Span }, // span of the value
+  Merge { variable: String, properties: Vec<( String, Operand, Span )>, properties_param: Option<( String, Span )> },
+  Labels { variable: String, labels: Vec<String> },
+}
+
+/// n.key or n:Label
+#[derive( Debug, Clone )]
+pub enum RemoveItem
+{
+  Property { variable: String, key: String },
+  Labels { variable: String, labels: Vec<String> },
+}
+
+/// Items of RETURN or WITH with their DISTINCT, ORDER BY, SKIP and LIMIT
+#[derive( Debug, Clone )]
+pub struct Projection
+{
+  pub distinct: bool,
+  pub items: Vec<ProjectionItem>,
+  pub order_by: Vec<SortKey>,
+  pub skip: Option<( Count, Span )>,
+  pub limit: Option<( Count, Span )>,
+}
+
+/// n.name AS name, count(DISTINCT n) or [x IN n.tags | x][0]
+#[derive( Debug, Clone )]
+pub struct ProjectionItem
+{
+  pub expression: String, // as written, the column name when there is no alias
+  pub operand: Operand, // the aggregated value of an aggregate
+  pub aggregate: Option<Aggregate>,
+  pub alias: Option<String>,
+  pub span: Span,
+}
+
+/// ORDER BY key, a returned column or an operand of the rows before the projection
+#[derive( Debug, Clone )]
+pub struct SortKey
+{
+  pub expression: String,
+  pub operand: Operand,
+  pub descending: bool,
+  pub span: Span,
+}
+
+/// Rows of SKIP or LIMIT
+#[derive( Debug, Clone, PartialEq )]
+pub enum Count { Value( usize ), Parameter( String ) }
+
+// ---------------------------------------------------------------------------------------------------------------------
+/// Tokens of each ;-separated statement of a script, empty statements are left out
+pub fn split_statements ( tokens: &Vec<SyntaxToken> ) -> Vec<Vec<SyntaxToken>>
+{
+  tokens.split( | t | t.token_type == SyntaxTokenType::EndStatement )
+    .filter( | statement | statement.len() > 0 )
+    .map( | statement | statement.to_vec() )
+    .collect()
+}
+
+/// Query tree of the tokens of a query, the first syntax error ends parsing. A query may end with ;
+pub fn parse_query ( tokens: &Vec<SyntaxToken> ) -> Result<Query, QueryError>
+{
+  // a /* without */ commented out the rest of the query
+  let unclosed = tokens.iter().find( | t | t.token_type == SyntaxTokenType::UnclosedComment );
+  if unclosed.is_some()
+  {
+    let mut err = QueryError::at( String::from( "Syntax Error: Comment `/*`" ), unclosed.unwrap().span, Some( String::from( "end of query" )));
+    err.expected = Some( String::from( "`*/`" ));
+    return Err( err );
+  }
+
+  let end = tokens.iter().rposition( | t | t.token_type != SyntaxTokenType::EndStatement ).map( | p | p + 1 ).unwrap_or( 0 );
+  let ( graph, tokens ) = take_from( &tokens[..end].to_vec() )?;
+  let parameters: Vec<( String, Span )> = tokens.iter()
+    .filter( | t | t.token_type == SyntaxTokenType::Parameter )
+    .map( | t | ( t.val.clone(), t.span ))
+    .collect();
+  let mut parser = Parser { tokens: tokens, pos: 0 };
+  let mut clauses: Vec<Clause> = Vec::new();
+  let mut spans: Vec<Span> = Vec::new();
+  let mut keywords: Vec<SyntaxToken> = Vec::new();
+  while parser.peek().is_some() 
+  { 
+    let keyword = parser.peek().unwrap().clone();
+    let start = parser.pos;
+    clauses.push( parser.parse_clause()? );
+    spans.push( parser.span_from( start ));
+    keywords.push( keyword );
+  }
+  Ok( Query { graph: graph, clauses: clauses, spans: spans, keywords: keywords, parameters: parameters })
+}
+
+/// FROM name may follow any clause, it is taken out before the clauses are parsed
+fn take_from ( tokens: &Vec<SyntaxToken> ) -> Result<( Option<String>, Vec<SyntaxToken> ), QueryError>
+{
+  let mut graph: Option<String> = None;
+  let mut rest: Vec<SyntaxToken> = Vec::new();
+  let mut i = 0;
+  while i < tokens.len()
+  {
+    if tokens[i].token_type != SyntaxTokenType::KeywordFrom
+    {
+      rest.push( tokens[i].clone() );
+      i += 1;
+      continue;
+    }
+
+    let name = tokens.get( i + 1 );
+    if graph.is_some() || name.is_none() || name.unwrap().token_type != SyntaxTokenType::Label
+    {
+      let found = name.map( | t | format!( "`{}`", t.val )).unwrap_or( String::from( "end of query" ));
+      let mut err = QueryError::at( String::from( "Syntax Error: From" ), tokens[i].span, Some( found ));
+      if graph.is_none() { err.expected = Some( String::from( "a graph name" )); }
+      return Err( err );
+    }
+    graph = Some( name.unwrap().val.clone() );
+    i += 2;
+  }
+  Ok(( graph, rest ))
+}
+
+/// Keywords that start a clause, they end the WHERE, items or patterns before them
+fn is_clause_start ( token: &SyntaxToken ) -> bool
+{
+  match token.token_type
+  {
+    SyntaxTokenType::KeywordMatch | SyntaxTokenType::KeywordOptional | SyntaxTokenType::KeywordMerge |
+    SyntaxTokenType::KeywordCreate | SyntaxTokenType::KeywordWith | SyntaxTokenType::KeywordUnwind |
+    SyntaxTokenType::KeywordSet | SyntaxTokenType::KeywordRemove | SyntaxTokenType::KeywordDetach |
+    SyntaxTokenType::KeywordDelete | SyntaxTokenType::KeywordReturn => true,
+    _ => false
+  }
+}
+
+/// Tokens that start a RETURN or WITH item: n, n.key, $name or a [ list ]
+fn starts_item ( token: &SyntaxToken ) -> bool
+{
+  matches!( token.token_type, SyntaxTokenType::Label | SyntaxTokenType::Parameter | SyntaxTokenType::OpenBracket )
+}
+
+/// Tokens of RETURN and WITH items, ORDER BY, SKIP and LIMIT
+fn is_projection_token ( token: &SyntaxToken ) -> bool
+{
+  match token.token_type
+  {
+    SyntaxTokenType::Label | SyntaxTokenType::Value | SyntaxTokenType::Parameter |
+    SyntaxTokenType::KeywordAs | SyntaxTokenType::KeywordDistinct |
+    SyntaxTokenType::OpenNode | SyntaxTokenType::CloseNode |
+    SyntaxTokenType::OpenBracket | SyntaxTokenType::OpenIndex |
+    SyntaxTokenType::KeywordOrder | SyntaxTokenType::KeywordBy | SyntaxTokenType::KeywordAsc |
+    SyntaxTokenType::KeywordDesc | SyntaxTokenType::KeywordSkip | SyntaxTokenType::KeywordLimit => true,
+    _ => false
+  }
+}
+
+/// Text of an item as written, used as its column name
+fn expression_text ( tokens: &[SyntaxToken] ) -> String
+{
+  let atom = | t: &SyntaxToken | matches!( t.token_type, 
+    SyntaxTokenType::Value | SyntaxTokenType::StringValue | SyntaxTokenType::KeywordNull | SyntaxTokenType::Label | SyntaxTokenType::Parameter );
+  let mut text = String::new();
+  for ( i, token ) in tokens.iter().enumerate()
+  {
+    if i > 0 
+    {
+      let prev = &tokens[i - 1];
+      let joined = matches!( prev.token_type, 
+          SyntaxTokenType::OpenBracket | SyntaxTokenType::OpenIndex | SyntaxTokenType::Range | SyntaxTokenType::OpenNode ) || 
+        matches!( token.token_type, SyntaxTokenType::CloseBracket | SyntaxTokenType::OpenIndex | SyntaxTokenType::Range | 
+          SyntaxTokenType::OpenNode | SyntaxTokenType::CloseNode ) ||
+        ( prev.token_type == SyntaxTokenType::EdgeDirection && 
+          ( prev.val == "-" || token.token_type == SyntaxTokenType::Equals || ( prev.val == "<" && token.val == ">" )));
+      let listed = ( atom( prev ) || prev.token_type == SyntaxTokenType::CloseBracket ) && 
+        ( atom( token ) || token.token_type == SyntaxTokenType::OpenBracket );
+      if listed { text.push_str( ", " ); }
+      else if joined == false { text.push( ' ' ); }
+    }
+    if token.token_type == SyntaxTokenType::StringValue { text.push_str( &format!( "'{}'", token.val )); }
+    else if token.token_type == SyntaxTokenType::Parameter { text.push_str( &format!( "${}", token.val )); }
+    else { text.push_str( &token.val ); }
+  }
+  text
+}
+
+fn is_shortest_path ( token: &SyntaxToken ) -> bool
+{
+  token.token_type == SyntaxTokenType::Label &&
+    ( token.val.eq_ignore_ascii_case( "shortestPath" ) || token.val.eq_ignore_ascii_case( "allShortestPaths" ))
+}
+
+/// `n` or `n.key` of a SET or REMOVE item
+fn split_target ( target: &SyntaxToken, part: &str ) -> Result<( String, Option<String> ), QueryError>
+{
+  match Operand::from_label( &target.val )
+  {
+    Some( Operand::Variable( variable )) => Ok(( variable, None )),
+    Some( Operand::Property( variable, key )) => Ok(( variable, Some( key ))),
+    _ => Err( QueryError::at( format!( "Syntax Error: {} `{}`", part, target.val ), target.span, Some( format!( "`{}`", target.val )))),
+  }
+}
+
+/// Literal of a property value, or the variable or property it refers to
+fn value_operand ( word: &str ) -> Result<Operand, String>
+{
+  let literal = parse_literal( word );
+  if literal.is_some() { return Ok( Operand::Literal( literal.unwrap()? )); }
+
+  // {name: row.name} refers to a variable, 3x is still an invalid value
+  let is_name = word.starts_with( | c: char | c.is_alphabetic() || c == '_' );
+  let operand = Operand::from_label( word );
+  if is_name && operand.is_some() { return Ok( operand.unwrap() ); }
+  Err( format!( "Syntax Error: Property Value `{}`", word ))
+}
+
+// ---------------------------------------------------------------------------------------------------------------------
+struct Parser
+{
+  tokens: Vec<SyntaxToken>,
+  pos: usize,
+}
+
+impl Parser
+{
+  fn peek ( &self ) -> Option<&SyntaxToken> { self.tokens.get( self.pos ) }
+
+  fn peek_at ( &self, offset: usize ) -> Option<&SyntaxToken> { self.tokens.get( self.pos + offset ) }
+
+  fn is_next ( &self, token_type: SyntaxTokenType ) -> bool
+  {
+    self.peek().map( | t | t.token_type == token_type ).unwrap_or( false )
+  }
+
+  fn is_next_val ( &self, token_type: SyntaxTokenType, val: &str ) -> bool
+  {
+    self.peek().map( | t | t.token_type == token_type && t.val == val ).unwrap_or( false )
+  }
+
+  /// Step over the next token when it has the type
+  fn accept ( &mut self, token_type: SyntaxTokenType ) -> bool
+  {
+    if self.is_next( token_type ) == false { return false; }
+    self.pos += 1;
+    true
+  }
+
+  fn accept_val ( &mut self, token_type: SyntaxTokenType, val: &str ) -> bool
+  {
+    if self.is_next_val( token_type, val ) == false { return false; }
+    self.pos += 1;
+    true
+  }
+
+  fn next ( &mut self ) -> Option<SyntaxToken>
+  {
+    let token = self.tokens.get( self.pos ).cloned();
+    if token.is_some() { self.pos += 1; }
+    token
+  }
+
+  fn starts_clause ( &self ) -> bool { self.peek().map( is_clause_start ).unwrap_or( false ) }
+
+  /// Span from the token at start to the last token taken
+  fn span_from ( &self, start: usize ) -> Span
+  {
+    self.tokens[start].span.to( &self.tokens[self.pos.max( start + 1 ) - 1].span )
+  }
+
+  /// Error at the next token, or at the end of the query
+  fn error ( &self, message: String, expected: Option<&str> ) -> QueryError
+  {
+    let mut err = QueryError::at_token( message, &self.tokens, self.pos );
+    err.expected = expected.map( | e | e.to_string() );
+    err
+  }
+
+  /// Error at a token that was already taken
+  fn error_taken ( &self, message: String, token: &SyntaxToken ) -> QueryError
+  {
+    QueryError::at( message, token.span, Some( format!( "`{}`", token.val )))
+  }
+
+  /// Syntax Error: part `token` for the next token, or for the end of the query
+  fn unexpected ( &self, part: &str ) -> QueryError
+  {
+    let message = match self.peek()
+    {
+      Some( token ) => format!( "Syntax Error: {} `{}`", part, token.val ),
+      None => format!( "Syntax Error: {}", part ),
+    };
+    self.error( message, None )
+  }
+
+  /// Error for the next token, it should have been expected
+  fn expected ( &self, expected: &str, part: &str ) -> QueryError
+  {
+    let mut err = self.unexpected( part );
+    err.expected = Some( expected.to_string() );
+    err
+  }
+
+  fn parse_clause ( &mut self ) -> Result<Clause, QueryError>
+  {
+    let token = self.next().unwrap();
+    match token.token_type
+    {
+      SyntaxTokenType::KeywordMatch => self.parse_match( false ),
+      SyntaxTokenType::KeywordOptional =>
+      {
+        if self.accept( SyntaxTokenType::KeywordMatch ) { return self.parse_match( true ); }
+        if self.peek().is_none() || self.is_next( SyntaxTokenType::KeywordOptional )
+        {
+          return Err( self.error( String::from( "Syntax Error: Optional" ), Some( "`MATCH`" )));
+        }
+        Err( self.expected( "`MATCH`", "Optional" ))
+      }
+      SyntaxTokenType::KeywordMerge => self.parse_merge(),
+      SyntaxTokenType::KeywordCreate => self.parse_create(),
+      SyntaxTokenType::KeywordWith => self.parse_with(),
+      SyntaxTokenType::KeywordUnwind => self.parse_unwind(),
+      SyntaxTokenType::KeywordSet => Ok( Clause::Set( self.parse_set_items()? )),
+      SyntaxTokenType::KeywordRemove => self.parse_remove(),
+      SyntaxTokenType::KeywordDetach =>
+      {
+        if self.accept( SyntaxTokenType::KeywordDelete ) == false 
+        { 
+          return Err( self.error( String::from( "Syntax Error: Detach" ), Some( "`DELETE`" ))); 
+        }
+        self.parse_delete( true )
+      }
+      SyntaxTokenType::KeywordDelete => self.parse_delete( false ),
+      SyntaxTokenType::KeywordReturn => Ok( Clause::Return( self.parse_projection( "Return" )? )),
+
+      // parts of a RETURN without one
+      SyntaxTokenType::KeywordDistinct | SyntaxTokenType::KeywordOrder | SyntaxTokenType::KeywordBy |
+      SyntaxTokenType::KeywordAsc | SyntaxTokenType::KeywordDesc | SyntaxTokenType::KeywordSkip |
+      SyntaxTokenType::KeywordLimit => Err( self.error_taken( format!( "Syntax Error: Return `{}`", token.val ), &token )),
+
+      // the statements of a script are parsed one at a time
+      SyntaxTokenType::EndStatement =>
+      {
+        let mut err = self.error_taken( String::from( "Syntax Error: `;`" ), &token );
+        err.expected = Some( String::from( "end of query" ));
+        Err( err )
+      }
+      SyntaxTokenType::KeywordWhere => Err( self.error_taken( String::from( "Syntax Error: Where" ), &token )),
+      SyntaxTokenType::KeywordOn => Err( self.error_taken( String::from( "Syntax Error: On" ), &token )),
+      SyntaxTokenType::KeywordAs => Err( self.error_taken( String::from( "Syntax Error: As" ), &token )),
+      _ => Err( self.error_taken( format!( "Syntax Error: `{}`", token.val ), &token )),
+    }
+  }
+
+  fn parse_match ( &mut self, optional: bool ) -> Result<Clause, QueryError>
+  {
+    let patterns = self.parse_patterns()?;
+    if patterns.len() == 0 { return Err( self.expected( "a pattern", "Match" )); }
+    let predicate = self.parse_where()?;
+    Ok( Clause::Match { optional: optional, patterns: patterns, predicate: predicate })
+  }
+
+  /// MERGE pattern followed by ON CREATE SET and ON MATCH SET items
+  fn parse_merge ( &mut self ) -> Result<Clause, QueryError>
+  {
+    let patterns = self.parse_patterns()?;
+    if patterns.len() == 0 { return Err( self.expected( "a pattern", "Merge" )); }
+
+    let mut actions: Vec<( MergeAction, Vec<SetItem> )> = Vec::new();
+    while self.accept( SyntaxTokenType::KeywordOn )
+    {
+      let action = match self.peek().map( | t | t.token_type.clone() )
+      {
+        Some( SyntaxTokenType::KeywordCreate ) => MergeAction::OnCreate,
+        Some( SyntaxTokenType::KeywordMatch ) => MergeAction::OnMatch,
+        _ => { return Err( self.error( String::from( "Syntax Error: On" ), Some( "`CREATE` or `MATCH`" ))); }
+      };
+      self.pos += 1;
+      if self.accept( SyntaxTokenType::KeywordSet ) == false { return Err( self.error( String::from( "Syntax Error: On" ), Some( "`SET`" ))); }
+      actions.push(( action, self.parse_set_items()? ));
+    }
+    Ok( Clause::Merge { patterns: patterns, actions: actions })
+  }
+
+  /// CREATE GRAPH name may leave out the patterns
+  fn parse_create ( &mut self ) -> Result<Clause, QueryError>
+  {
+    let mut graph: Option<String> = None;
+    if self.accept( SyntaxTokenType::KeywordGraph )
+    {
+      if self.is_next( SyntaxTokenType::Label ) == false { return Err( self.expected( "a graph name", "Create" )); }
+      graph = Some( self.next().unwrap().val );
+    }
+
+    let patterns = self.parse_patterns()?;
+    if patterns.len() == 0 && graph.is_none() { return Err( self.expected( "a pattern", "Create" )); }
+    Ok( Clause::Create { graph: graph, patterns: patterns })
+  }
+
+  fn parse_with ( &mut self ) -> Result<Clause, QueryError>
+  {
+    let projection = self.parse_projection( "With" )?;
+    let predicate = self.parse_where()?;
+    Ok( Clause::With { projection: projection, predicate: predicate })
+  }
+
+  /// UNWIND list AS variable
+  fn parse_unwind ( &mut self ) -> Result<Clause, QueryError>
+  {
+    let operand = Operand::parse_next( &self.tokens, &mut self.pos )
+      .map_err( | err | QueryError { message: String::from( "Syntax Error: Unwind" ), ..err })?;
+    if self.accept( SyntaxTokenType::KeywordAs ) == false 
+    { 
+      return Err( self.error( String::from( "Syntax Error: Unwind" ), Some( "`AS`" ))); 
+    }
+    if self.is_next( SyntaxTokenType::Label ) == false || self.peek().unwrap().val.contains( '.' )
+    {
+      return Err( self.expected( "a variable", "Unwind" ));
+    }
+    Ok( Clause::Unwind { operand: operand, variable: self.next().unwrap().val })
+  }
+
+  /// Items of SET or ON CREATE SET / ON MATCH SET, every item starts with `n` or `n.key`
+  fn parse_set_items ( &mut self ) -> Result<Vec<SetItem>, QueryError>
+  {
+    let mut items: Vec<SetItem> = Vec::new();
+    while self.is_next( SyntaxTokenType::Label ) { items.push( self.parse_set_item()? ); }
+    if items.len() == 0 { return Err( self.expected( "a variable or a property", "Set" )); }
+    Ok( items )
+  }
+
+  fn parse_set_item ( &mut self ) -> Result<SetItem, QueryError>
+  {
+    let target = self.next().unwrap();
+    let ( variable, key ) = split_target( &target, "Set" )?;
+    if self.accept( SyntaxTokenType::Equals )
+    {
+      if key.is_none() 
+      { 
+        let mut err = self.error_taken( String::from( "Syntax Error: Set requires a property key" ), &target );
+        err.expected = Some( String::from( "a property" ));
+        return Err( err ); 
+      }
+      if self.is_next( SyntaxTokenType::Label ) { return Err( self.expected( "a value", "Set" )); }
+      if self.peek().is_none() || self.starts_clause() 
+      { 
+        return Err( self.error( format!( "Syntax Error: Set `{}`", variable ), Some( "a value" ))); 
+      }
+      let start = self.pos;
+      let value = self.parse_value()?;
+      return Ok( SetItem::Property { variable: variable, key: key.unwrap(), value: value, span: self.span_from( start ) });
+    }
+
+    if self.accept( SyntaxTokenType::PlusEquals )
+    {
+      if key.is_some() 
+      { 
+        let mut err = self.error_taken( String::from( "Syntax Error: Set += requires a map" ), &target );
+        err.expected = Some( String::from( "a variable" ));
+        return Err( err ); 
+      }
+      if self.is_next( SyntaxTokenType::Parameter ) 
+      { 
+        return Ok( SetItem::Merge { variable: variable, properties: Vec::new(), properties_param: Some( self.parse_map_param() ) }); 
+      }
+      if self.is_next( SyntaxTokenType::OpenBrace ) == false 
+      { 
+        return Err( self.error( format!( "Syntax Error: Set `{}`", variable ), Some( "`{` or a parameter" ))); 
+      }
+      let properties = self.parse_properties()?;
+      return Ok( SetItem::Merge { variable: variable, properties: properties, properties_param: None });
+    }
+
+    if key.is_none() && self.is_next( SyntaxTokenType::PrimaryLabel )
+    {
+      let mut labels: Vec<String> = Vec::new();
+      while self.is_next( SyntaxTokenType::PrimaryLabel ) { labels.push( self.next().unwrap().val ); }
+      return Ok( SetItem::Labels { variable: variable, labels: labels });
+    }
+    Err( self.error( format!( "Syntax Error: Set `{}`", target.val ), Some( "`=`, `+=` or a label" )))
+  }
+
+  fn parse_remove ( &mut self ) -> Result<Clause, QueryError>
+  {
+    let mut items: Vec<RemoveItem> = Vec::new();
+    while self.is_next( SyntaxTokenType::Label )
+    {
+      let target = self.next().unwrap();
+      let ( variable, key ) = split_target( &target, "Remove" )?;
+      if key.is_some()
+      {
+        if self.is_next( SyntaxTokenType::PrimaryLabel ) { return Err( self.unexpected( "Remove" )); }
+        items.push( RemoveItem::Property { variable: variable, key: key.unwrap() });
+        continue;
+      }
+
+      let mut labels: Vec<String> = Vec::new();
+      while self.is_next( SyntaxTokenType::PrimaryLabel )
+      {
+        let label = self.next().unwrap().val;
+        if labels.contains( &label ) == false { labels.push( label ); }
+      }
+      if labels.len() == 0 { return Err( self.error( format!( "Syntax Error: Remove `{}`", target.val ), Some( "a label" ))); }
+      items.push( RemoveItem::Labels { variable: variable, labels: labels });
+    }
+    if items.len() == 0 { return Err( self.expected( "a variable or a property", "Remove" )); }
+    Ok( Clause::Remove( items ))
+  }
+
+  /// DELETE GRAPH name, or the variables to delete
+  fn parse_delete ( &mut self, detach: bool ) -> Result<Clause, QueryError>
+  {
+    if detach == false && self.accept( SyntaxTokenType::KeywordGraph )
+    {
+      if self.is_next( SyntaxTokenType::Label ) == false { return Err( self.expected( "a graph name", "Delete" )); }
+      let graph = self.next().unwrap().val;
+      return Ok( Clause::Delete { detach: detach, graph: Some( graph ), variables: Vec::new() });
+    }
+
+    let mut variables: Vec<String> = Vec::new();
+    while self.is_next( SyntaxTokenType::Label )
+    {
+      let token = self.next().unwrap();
+      if token.val.contains( '.' ) 
+      { 
+        let mut err = self.error_taken( format!( "Syntax Error: Delete `{}`", token.val ), &token );
+        err.expected = Some( String::from( "a variable" ));
+        return Err( err ); 
+      }
+      if variables.contains( &token.val ) == false { variables.push( token.val ); }
+    }
+    if variables.len() == 0 { return Err( self.expected( "a variable", "Delete" )); }
+    Ok( Clause::Delete { detach: detach, graph: None, variables: variables })
+  }
+
+  /// [DISTINCT] items [ORDER BY keys] [SKIP count] [LIMIT count] of RETURN or WITH
+  fn parse_projection ( &mut self, clause: &str ) -> Result<Projection, QueryError>
+  {
+    let distinct = self.accept( SyntaxTokenType::KeywordDistinct );
+    let mut items: Vec<ProjectionItem> = Vec::new();
+    while self.peek().is_some() && starts_item( self.peek().unwrap() ) { items.push( self.parse_item( clause )? ); }
+    if items.len() == 0 { return Err( self.expected( "an item", clause )); }
+
+    let mut order_by: Vec<SortKey> = Vec::new();
+    if self.accept( SyntaxTokenType::KeywordOrder )
+    {
+      if self.accept( SyntaxTokenType::KeywordBy ) == false { return Err( self.expected( "`BY`", clause )); }
+      order_by = self.parse_sort_keys( clause )?;
+    }
+    let mut skip = None;
+    if self.accept( SyntaxTokenType::KeywordSkip ) { skip = Some( self.parse_count( clause, "Skip" )? ); }
+    let mut limit = None;
+    if self.accept( SyntaxTokenType::KeywordLimit ) { limit = Some( self.parse_count( clause, "Limit" )? ); }
+
+    // RETURN n AS a AS b, size(n) or LIMIT 1 SKIP 1
+    if self.peek().is_some() && is_projection_token( self.peek().unwrap() ) { return Err( self.unexpected( clause )); }
+    Ok( Projection { distinct: distinct, items: items, order_by: order_by, skip: skip, limit: limit })
+  }
+
+  /// Aggregate or operand with its alias
+  fn parse_item ( &mut self, clause: &str ) -> Result<ProjectionItem, QueryError>
+  {
+    let start = self.pos;
+    let mut aggregate: Option<Aggregate> = None;
+    let operand;
+    let expression;
+    if self.is_aggregate()
+    {
+      let ( function, argument ) = self.parse_aggregate( clause )?;
+
+      // count(n)[0], the value of an aggregate cannot be indexed
+      if self.is_next( SyntaxTokenType::OpenIndex )
+      {
+        let index_start = self.pos;
+        Operand::parse_postfix( argument, &self.tokens, &mut self.pos )
+          .map_err( | err | QueryError { message: format!( "Syntax Error: {}", clause ), ..err })?;
+        let index = expression_text( &self.tokens[index_start..self.pos] );
+        return Err( QueryError::at( format!( "Syntax Error: {} `{}`", clause, index ), self.span_from( index_start ), 
+          Some( format!( "`{}`", index ))));
+      }
+      expression = function.expression();
+      operand = argument;
+      aggregate = Some( function );
+    }
+    else
+    {
+      operand = Operand::parse_next( &self.tokens, &mut self.pos )
+        .map_err( | err | QueryError { message: format!( "Syntax Error: {}", clause ), ..err })?;
+      expression = expression_text( &self.tokens[start..self.pos] );
+    }
+
+    let mut alias = None;
+    if self.accept( SyntaxTokenType::KeywordAs )
+    {
+      if self.is_next( SyntaxTokenType::Label ) == false { return Err( self.expected( "an alias", clause )); }
+      alias = Some( self.next().unwrap().val );
+    }
+    Ok( ProjectionItem { expression: expression, operand: operand, aggregate: aggregate, alias: alias, span: self.span_from( start ) })
+  }
+
+  /// Name of an aggregate function followed by its (
+  fn is_aggregate ( &self ) -> bool
+  {
+    self.is_next( SyntaxTokenType::Label ) && Aggregate::new( &self.peek().unwrap().val ).is_some() &&
+      self.tokens.get( self.pos + 1 ).map( | t | t.token_type == SyntaxTokenType::OpenNode ).unwrap_or( false )
+  }
+
+  /// count( [DISTINCT] n.key ), percentileCont( n.key, 0.5 ) or count( * ), the operand is the aggregated value
+  fn parse_aggregate ( &mut self, clause: &str ) -> Result<( Aggregate, Operand ), QueryError>
+  {
+    let mut aggregate = Aggregate::new( &self.next().unwrap().val ).unwrap();
+    self.next();
+    aggregate.distinct = self.accept( SyntaxTokenType::KeywordDistinct );
+
+    let argument = self.peek().filter( | t | t.token_type == SyntaxTokenType::Label ).map( | t | t.val.clone() );
+    let operand = match argument.as_deref()
+    {
+      // every row counts, * is never null
+      Some( "*" ) => Some( Operand::Literal( Value::Boolean( true ))),
+      Some( label ) => Operand::from_label( label ),
+      None => None,
+    };
+    if operand.is_none() { return Err( self.expected( "a variable or a property", clause )); }
+    self.next();
+    aggregate.argument = argument;
+
+    if self.is_next( SyntaxTokenType::Value )
+    {
+      let token = self.next().unwrap();
+      let percentile = token.val.parse::<f64>();
+      if percentile.is_err() { return Err( self.error_taken( format!( "Syntax Error: {} `{}`", clause, token.val ), &token )); }
+      aggregate.percentile = Some( percentile.unwrap() );
+    }
+    if self.is_next( SyntaxTokenType::CloseNode ) == false { return Err( self.expected( "`)`", clause )); }
+
+    // count(DISTINCT *) or a percentile for a function without one
+    let close = self.next().unwrap();
+    if aggregate.is_complete() == false { return Err( self.error_taken( format!( "Syntax Error: {} `)`", clause ), &close )); }
+    Ok(( aggregate, operand.unwrap() ))
+  }
+
+  /// Operands after ORDER BY, each may be followed by ASC or DESC
+  fn parse_sort_keys ( &mut self, clause: &str ) -> Result<Vec<SortKey>, QueryError>
+  {
+    let mut keys: Vec<SortKey> = Vec::new();
+    while self.is_next( SyntaxTokenType::Label )
+    {
+      let start = self.pos;
+      let operand = Operand::parse_next( &self.tokens, &mut self.pos )
+        .map_err( | err | QueryError { message: format!( "Syntax Error: {}", clause ), ..err })?;
+      let expression = expression_text( &self.tokens[start..self.pos] );
+      let descending = self.accept( SyntaxTokenType::KeywordDesc );
+      if descending == false { self.accept( SyntaxTokenType::KeywordAsc ); }
+      keys.push( SortKey { expression: expression, operand: operand, descending: descending, span: self.span_from( start ) });
+    }
+    if keys.len() == 0 { return Err( self.expected( "a sort key", clause )); }
+    Ok( keys )
+  }
+
+  /// Non-negative integer or parameter after SKIP or LIMIT
+  fn parse_count ( &mut self, clause: &str, part: &str ) -> Result<( Count, Span ), QueryError>
+  {
+    if self.is_next( SyntaxTokenType::Parameter )
+    {
+      let token = self.next().unwrap();
+      return Ok(( Count::Parameter( token.val ), token.span ));
+    }
+    if self.is_next( SyntaxTokenType::Value ) == false { return Err( self.expected( "a count", clause )); }
+
+    let token = self.next().unwrap();
+    let count = token.val.parse::<usize>();
+    if count.is_err() 
+    { 
+      let mut err = self.error_taken( format!( "Syntax Error: {} `{}`", part, token.val ), &token );
+      err.expected = Some( String::from( "a non-negative integer" ));
+      return Err( err ); 
+    }
+    Ok(( Count::Value( count.unwrap() ), token.span ))
+  }
+
+  /// WHERE after the patterns of a MATCH or the items of a WITH, the next clause follows it
+  fn parse_where ( &mut self ) -> Result<Option<Predicate>, QueryError>
+  {
+    if self.accept( SyntaxTokenType::KeywordWhere ) == false { return Ok( None ); }
+    let predicate = self.parse_predicate()?;
+    if self.peek().is_some() && self.starts_clause() == false { return Err( self.expected( "a clause", "Where" )); }
+    Ok( Some( predicate ))
+  }
+
+  /// WHERE inside a node or relationship, closed by its ) or ]
+  fn parse_inline_where ( &mut self, close: SyntaxTokenType ) -> Result<Predicate, QueryError>
+  {
+    let predicate = self.parse_predicate()?;
+    if self.is_next( close.clone() ) == false 
+    { 
+      let expected = if close == SyntaxTokenType::CloseNode { "`)`" } else { "`]`" };
+      return Err( self.expected( expected, "Where" )); 
+    }
+    Ok( predicate )
+  }
+
+  fn parse_predicate ( &mut self ) -> Result<Predicate, QueryError>
+  {
+    if self.peek().is_none() || self.starts_clause() { return Err( self.error( String::from( "Syntax Error: Where" ), Some( "a predicate" ))); }
+    Predicate::parse_next( &self.tokens, &mut self.pos )
+  }
+
+  /// Patterns while the next token starts one: (, p = or shortestPath(
+  fn parse_patterns ( &mut self ) -> Result<Vec<Pattern>, QueryError>
+  {
+    let mut patterns: Vec<Pattern> = Vec::new();
+    while self.starts_pattern() { patterns.push( self.parse_pattern()? ); }
+    if self.is_next( SyntaxTokenType::Equals ) { return Err( self.unexpected( "Path" )); }
+    Ok( patterns )
+  }
+
+  fn starts_pattern ( &self ) -> bool
+  {
+    if self.is_next( SyntaxTokenType::OpenNode ) { return true; }
+    if self.is_next( SyntaxTokenType::Label ) == false { return false; }
+    let after = self.peek_at( 1 ).map( | t | t.token_type.clone() );
+    after == Some( SyntaxTokenType::Equals ) ||
+      ( is_shortest_path( self.peek().unwrap() ) && after == Some( SyntaxTokenType::OpenNode ))
+  }
+
+  fn parse_pattern ( &mut self ) -> Result<Pattern, QueryError>
+  {
+    let start = self.pos;
+    let mut pattern = Pattern { variable: None, shortest: None, nodes: Vec::new(), relationships: Vec::new(), span: Span::default() };
+    if self.is_next( SyntaxTokenType::Label ) && self.peek_at( 1 ).unwrap().token_type == SyntaxTokenType::Equals
+    {
+      let token = self.next().unwrap();
+      if token.val.contains( '.' ) { return Err( self.unexpected( "Path" )); }
+      self.pos += 1;
+      pattern.variable = Some( token.val );
+    }
+
+    let shortest = self.peek().map( is_shortest_path ).unwrap_or( false );
+    if shortest && self.peek_at( 1 ).map( | t | t.token_type == SyntaxTokenType::OpenNode ).unwrap_or( false )
+    {
+      pattern.shortest = Some( self.next().unwrap().val.eq_ignore_ascii_case( "allShortestPaths" ));
+      self.pos += 1;
+    }
+
+    if self.is_next( SyntaxTokenType::OpenNode ) == false { return Err( self.expected( "`(`", "Path" )); }
+    pattern.nodes.push( self.parse_node()? );
+    while self.is_next( SyntaxTokenType::EdgeDirection )
+    {
+      pattern.relationships.push( self.parse_relationship()? );
+      // shortestPath( ... ) wraps a whole pattern
+      if self.peek().map( is_shortest_path ).unwrap_or( false ) 
+      { 
+        return Err( self.error( format!( "Syntax Error: `{}`", self.peek().unwrap().val ), Some( "`(`" ))); 
+      }
+      if self.is_next( SyntaxTokenType::OpenNode ) == false { return Err( self.expected( "`(`", "Relationship" )); }
+      pattern.nodes.push( self.parse_node()? );
+    }
+
+    // ) of shortestPath( ... )
+    if pattern.shortest.is_some() && self.accept( SyntaxTokenType::CloseNode ) == false
+    {
+      return Err( self.error( String::from( "Syntax Error: shortestPath" ), Some( "`)`" )));
+    }
+    pattern.span = self.span_from( start );
+    Ok( pattern )
+  }
+
+  /// ( [variable] [:Label ...] [{ ... }] [WHERE ...] )
+  fn parse_node ( &mut self ) -> Result<NodePattern, QueryError>
+  {
+    let start = self.pos;
+    self.pos += 1;
+    let mut node = NodePattern
+    {
+      variable: None, labels: Vec::new(), label_expression: None, properties: Vec::new(), properties_param: None, predicate: None, 
+      span: Span::default()
+    };
+    if self.is_next( SyntaxTokenType::Label )
+    {
+      if self.peek().unwrap().val.contains( '.' ) { return Err( self.unexpected( "Node" )); }
+      node.variable = Some( self.next().unwrap().val );
+    }
+
+    while self.is_next( SyntaxTokenType::PrimaryLabel )
+    {
+      let label = self.next().unwrap().val;
+      if node.labels.contains( &label ) == false { node.labels.push( label ); }
+    }
+    if node.labels.len() == 0 && self.is_next( SyntaxTokenType::LabelExpression )
+    {
+      let token = self.next().unwrap();
+      node.label_expression = Some( LabelExpression::parse( &token.val ).map_err( | e | self.error_taken( e, &token ))? );
+    }
+
+    if self.is_next( SyntaxTokenType::OpenBrace ) { node.properties = self.parse_properties()?; }
+    else if self.is_next( SyntaxTokenType::Parameter ) { node.properties_param = Some( self.parse_map_param() ); }
+    if self.accept( SyntaxTokenType::KeywordWhere ) { node.predicate = Some( self.parse_inline_where( SyntaxTokenType::CloseNode )? ); }
+    if self.accept( SyntaxTokenType::CloseNode ) == false { return Err( self.expected( "`)`", "Node" )); }
+    node.span = self.span_from( start );
+    Ok( node )
+  }
+
+  /// -[ ... ]->, <-[ ... ]-, -[ ... ]- or <-[ ... ]->, the brackets may be left out
+  fn parse_relationship ( &mut self ) -> Result<RelationshipPattern, QueryError>
+  {
+    let start = self.pos;
+    let left = self.accept_val( SyntaxTokenType::EdgeDirection, "<" );
+    if self.accept_val( SyntaxTokenType::EdgeDirection, "-" ) == false { return Err( self.expected( "`-`", "Relationship" )); }
+
+    let mut rel = RelationshipPattern
+    {
+      variable: None, rel_type: None, direction: DirectionType::Undirected, hops: None, properties: Vec::new(), 
+      properties_param: None, predicate: None, span: Span::default()
+    };
+    if self.accept( SyntaxTokenType::OpenEdge )
+    {
+      if self.is_next( SyntaxTokenType::Label ) && self.is_next_val( SyntaxTokenType::Label, "*" ) == false
+      {
+        if self.peek().unwrap().val.contains( '.' ) { return Err( self.unexpected( "Relationship" )); }
+        rel.variable = Some( self.next().unwrap().val );
+      }
+      if self.is_next( SyntaxTokenType::PrimaryLabel ) { rel.rel_type = Some( self.next().unwrap().val ); }
+
+      // [*] is an unbounded hop range
+      if self.is_next( SyntaxTokenType::HopRange ) || self.is_next_val( SyntaxTokenType::Label, "*" )
+      {
+        let token = self.next().unwrap();
+        rel.hops = Some( HopRange::parse( &token.val ).map_err( | e | self.error_taken( e, &token ))? );
+      }
+      if self.is_next( SyntaxTokenType::OpenBrace ) { rel.properties = self.parse_properties()?; }
+      else if self.is_next( SyntaxTokenType::Parameter ) { rel.properties_param = Some( self.parse_map_param() ); }
+      if self.accept( SyntaxTokenType::KeywordWhere ) { rel.predicate = Some( self.parse_inline_where( SyntaxTokenType::CloseEdge )? ); }
+      if self.accept( SyntaxTokenType::CloseEdge ) == false { return Err( self.expected( "`]`", "Relationship" )); }
+    }
+
+    if self.accept_val( SyntaxTokenType::EdgeDirection, "-" ) == false { return Err( self.expected( "`-`", "Relationship" )); }
+    let right = self.accept_val( SyntaxTokenType::EdgeDirection, ">" );
+    rel.direction = match ( left, right )
+    {
+      ( true, true ) => DirectionType::Bidirectional,
+      ( true, false ) => DirectionType::Left,
+      ( false, true ) => DirectionType::Right,
+      ( false, false ) => DirectionType::Undirected,
+    };
+    rel.span = self.span_from( start );
+    Ok( rel )
+  }
+
+  /// { key: value ... }, keys are unique
+  fn parse_properties ( &mut self ) -> Result<Vec<( String, Operand, Span )>, QueryError>
+  {
+    self.pos += 1;
+    let mut properties: Vec<( String, Operand, Span )> = Vec::new();
+    while self.is_next( SyntaxTokenType::Key )
+    {
+      let token = self.next().unwrap();
+      if properties.iter().any( | ( k, _, _ ) | *k == token.val ) 
+      { 
+        return Err( self.error_taken( format!( "Syntax Error: Duplicate Property `{}`", token.val ), &token )); 
+      }
+      let start = self.pos;
+      let value = self.parse_value()?;
+      properties.push(( token.val, value, self.span_from( start )));
+    }
+    if self.accept( SyntaxTokenType::CloseBrace ) == false { return Err( self.expected( "`}`", "Property" )); }
+    Ok( properties )
+  }
+
+  /// $props in place of { key: value ... }, the map is resolved when the query is planned
+  fn parse_map_param ( &mut self ) -> ( String, Span )
+  {
+    let token = self.next().unwrap();
+    ( token.val, token.span )
+  }
+
+  /// Value of a property or a SET item: a literal, a list of literals, a parameter or a variable
+  fn parse_value ( &mut self ) -> Result<Operand, QueryError>
+  {
+    if self.peek().is_none() { return Err( self.error( String::from( "Syntax Error: Property Value" ), Some( "a value" ))); }
+
+    let token = self.next().unwrap();
+    match token.token_type
+    {
+      SyntaxTokenType::StringValue => Ok( Operand::Literal( Value::from( token.val.as_str() ))),
+      SyntaxTokenType::Parameter => Ok( Operand::Parameter( token.val )),
+      SyntaxTokenType::KeywordNull => Ok( Operand::Literal( Value::Null )),
+      SyntaxTokenType::OpenBracket => self.parse_list(),
+      SyntaxTokenType::Value => value_operand( &token.val ).map_err( | e | self.error_taken( e, &token )),
+      // -1 outside of { } is a - followed by the number
+      SyntaxTokenType::EdgeDirection if token.val == "-" && self.is_next( SyntaxTokenType::Value ) =>
+      {
+        let number = SyntaxToken::negated( &token, &self.next().unwrap() );
+        value_operand( &number.val ).map_err( | e | self.error_taken( e, &number ))
+      }
+      _ => Err( self.error_taken( format!( "Syntax Error: Property Value `{}`", token.val ), &token )),
+    }
+  }
+
+  /// [ literal ... ] after its [, lists hold literals of a single type
+  fn parse_list ( &mut self ) -> Result<Operand, QueryError>
+  {
+    let mut vals: Vec<Value> = Vec::new();
+    loop
+    {
+      if self.peek().is_none() { return Err( self.error( String::from( "Syntax Error: Property Value" ), Some( "`]`" ))); }
+
+      let token = self.next().unwrap();
+      let ( word, literal_token ) = match token.token_type
+      {
+        SyntaxTokenType::CloseBracket => { return Ok( Operand::List( vals )); }
+        SyntaxTokenType::OpenBracket => 
+        { 
+          return Err( self.error_taken( String::from( "Syntax Error: Nested list properties are not supported" ), &token )); 
+        }
+        SyntaxTokenType::StringValue =>
+        {
+          vals.push( Value::from( token.val.as_str() ));
+          continue;
+        }
+        SyntaxTokenType::Value => ( token.val.clone(), token ),
+        SyntaxTokenType::EdgeDirection if token.val == "-" && self.is_next( SyntaxTokenType::Value ) =>
+        {
+          let number = SyntaxToken::negated( &token, &self.next().unwrap() );
+          ( number.val.clone(), number )
+        }
+        _ => { return Err( self.error_taken( format!( "Syntax Error: Property Value `{}`", token.val ), &token )); }
+      };
+
+      let literal = parse_literal( &word );
+      if literal.is_none() { return Err( self.error_taken( format!( "Syntax Error: Property Value `{}`", word ), &literal_token )); }
+      vals.push( literal.unwrap().map_err( | e | self.error_taken( e, &literal_token ))? );
+    }
+  }
+}
+
+// ---------------------------------------------------------------------------------------------------------------------
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+  use crate::parser::parse_syntax;
+
+  fn query ( text: &str ) -> Query { parse_query( &parse_syntax( text )).unwrap() }
+
+  #[test]
+  fn test_parse_clauses ()
+  {
+    let q = query( "MATCH (n:Person) FROM devs WHERE n.age > 30 WITH n ORDER BY n.age UNWIND [1, 2] AS x SET n.x = $x RETURN n" );
+    assert_eq!( q.graph, Some( String::from( "devs" )));
+    assert_eq!( q.clauses.len(), 5 );
+    assert_eq!( matches!( &q.clauses[0], Clause::Match { optional: false, predicate: Some( _ ), .. } ), true );
+    assert_eq!( matches!( &q.clauses[1], Clause::With { predicate: None, .. } ), true );
+    assert_eq!( matches!( &q.clauses[2], Clause::Unwind { variable, .. } if variable == "x" ), true );
+    match &q.clauses[3]
+    {
+      Clause::Set( items ) =>
+      {
+        assert_eq!( matches!( &items[0], SetItem::Property { value: Operand::Parameter( _ ), .. } ), true );
+      }
+      clause => { panic!( "unexpected clause {:?}", clause ); }
+    }
+    assert_eq!( matches!( &q.clauses[4], Clause::Return( projection ) if projection.items.len() == 1 ), true );
+
+    let q1 = query( "MERGE (n:Person {name: 'Alice'}) ON CREATE SET n.seen = -1 ON MATCH SET n:Known n += {age: $age}" );
+    match &q1.clauses[0]
+    {
+      Clause::Merge { patterns, actions } =>
+      {
+        assert_eq!( patterns.len(), 1 );
+        assert_eq!( actions.len(), 2 );
+        assert_eq!( actions[0].0, MergeAction::OnCreate );
+        assert_eq!( matches!( &actions[0].1[0], SetItem::Property { value: Operand::Literal( Value::I64( -1 )), .. } ), true );
+        assert_eq!( actions[1].1.len(), 2 );
+      }
+      clause => { panic!( "unexpected clause {:?}", clause ); }
+    }
+
+    let q2 = query( "MATCH (a)-[r]->(b) OPTIONAL MATCH (b)--(c) DETACH DELETE a, r, a REMOVE b:X:Y, c.age DELETE GRAPH devs" );
+    assert_eq!( matches!( &q2.clauses[1], Clause::Match { optional: true, .. } ), true );
+    assert_eq!( matches!( &q2.clauses[2], Clause::Delete { detach: true, graph: None, variables } if variables.len() == 2 ), true );
+    assert_eq!( matches!( &q2.clauses[3], Clause::Remove( items ) if items.len() == 2 ), true );
+    assert_eq!( matches!( &q2.clauses[4], Clause::Delete { graph: Some( _ ), .. } ), true );
+
+    let q3 = query( "CREATE GRAPH devs" );
+    assert_eq!( matches!( &q3.clauses[0], Clause::Create { graph: Some( _ ), patterns } if patterns.len() == 0 ), true );
+  }
+
+  #[test]
+  fn test_parse_patterns ()
+  {
+    let q = query( "MATCH (a:A:B:A {x: -3, tags: ['a', 'b'], name: row.name}), p = allShortestPaths((b)<-[r:KNOWS*1..3 WHERE r.since > 1]->(c))" );
+    let patterns = match &q.clauses[0]
+    {
+      Clause::Match { patterns, .. } => patterns.clone(),
+      clause => { panic!( "unexpected clause {:?}", clause ); }
+    };
+    assert_eq!( patterns.len(), 2 );
+
+    let a = &patterns[0].nodes[0];
+    assert_eq!( a.variable, Some( String::from( "a" )));
+    assert_eq!( a.labels, vec![ String::from( "A" ), String::from( "B" ) ] );
+    assert_eq!( a.properties.iter().map( | ( k, v, _ ) | ( k.clone(), v.clone() )).collect::<Vec<( String, Operand )>>(), vec![
+      ( String::from( "x" ), Operand::Literal( Value::I64( -3 ))),
+      ( String::from( "tags" ), Operand::List( vec![ Value::from( "a" ), Value::from( "b" ) ] )),
+      ( String::from( "name" ), Operand::Property( String::from( "row" ), String::from( "name" ))),
+    ]);
+    assert_eq!( a.properties[1].2, Span { start: 29, end: 39, line: 1, column: 30 });
+    assert_eq!(( a.span.start, a.span.end ), ( 6, 57 ));
+
+    let path = &patterns[1];
+    assert_eq!( path.variable, Some( String::from( "p" )));
+    assert_eq!( path.span.column, 60 );
+    assert_eq!( path.shortest, Some( true ));
+    assert_eq!( path.nodes.len(), 2 );
+    let r = &path.relationships[0];
+    assert_eq!( r.variable, Some( String::from( "r" )));
+    assert_eq!( r.rel_type, Some( String::from( "KNOWS" )));
+    assert_eq!( r.direction, DirectionType::Bidirectional );
+    assert_eq!( r.hops, Some( HopRange { min: 1, max: Some( 3 ) }));
+    assert_eq!( r.predicate.is_some(), true );
+    assert_eq!( &q.keywords[0].val, "MATCH" );
+
+    let q1 = query( "CREATE (a)-->(b) (a)<--(b) (a)-[*]-(b) (n:(A & B) | C)" );
+    match &q1.clauses[0]
+    {
+      Clause::Create { patterns, .. } =>
+      {
+        assert_eq!( patterns.len(), 4 );
+        assert_eq!( patterns[0].relationships[0].direction, DirectionType::Right );
+        assert_eq!( patterns[1].relationships[0].direction, DirectionType::Left );
+        assert_eq!( patterns[2].relationships[0].direction, DirectionType::Undirected );
+        assert_eq!( patterns[2].relationships[0].hops, Some( HopRange { min: 1, max: None }));
+        assert_eq!( patterns[3].nodes[0].label_expression.is_some(), true );
+      }
+      clause => { panic!( "unexpected clause {:?}", clause ); }
+    }
+  }
+
+  #[test]
+  fn test_parse_projection ()
+  {
+    let text = "MATCH p = (n)-->(m) RETURN DISTINCT n.name AS name, count(DISTINCT m), length(p), n.tags[0] ORDER BY name DESC, n.age SKIP 1 LIMIT $rows";
+    let q = query( text );
+    match &q.clauses[1]
+    {
+      Clause::Return( projection ) =>
+      {
+        assert_eq!( projection.distinct, true );
+        let expressions: Vec<&str> = projection.items.iter().map( | item | item.expression.as_str() ).collect();
+        assert_eq!( expressions, vec![ "n.name", "count(DISTINCT m)", "length(p)", "n.tags[0]" ] );
+        assert_eq!( projection.items[0].alias, Some( String::from( "name" )));
+        assert_eq!( &text[projection.items[0].span.start..projection.items[0].span.end], "n.name AS name" );
+        assert_eq!( projection.items[1].aggregate.as_ref().unwrap().distinct, true );
+        assert_eq!( projection.items[1].operand, Operand::Variable( String::from( "m" )));
+        assert_eq!( matches!( projection.items[2].operand, Operand::Function( _, _ )), true );
+        assert_eq!( matches!( projection.items[3].operand, Operand::Index( _, _ )), true );
+
+        assert_eq!( projection.order_by.len(), 2 );
+        assert_eq!(( projection.order_by[0].expression.as_str(), projection.order_by[0].descending ), ( "name", true ));
+        assert_eq!(( projection.order_by[1].expression.as_str(), projection.order_by[1].descending ), ( "n.age", false ));
+        assert_eq!( projection.skip.as_ref().unwrap().0, Count::Value( 1 ));
+        let ( limit, limit_span ) = projection.limit.as_ref().unwrap();
+        assert_eq!( limit, &Count::Parameter( String::from( "rows" )));
+        assert_eq!( &text[limit_span.start..limit_span.end], "$rows" );
+      }
+      clause => { panic!( "unexpected clause {:?}", clause ); }
+    }
+
+    let err = parse_query( &parse_syntax( "MATCH (n) RETURN count(n)[0] AS c" )).unwrap_err();
+    assert_eq!(( err.span.unwrap().column, err.found ), ( 26, Some( String::from( "`[0]`" ))));
+  }
+
+  #[test]
+  fn test_parse_errors ()
+  {
+    let errors = vec![
+      ( "MATCH (n) FROM a FROM b", "Syntax Error: From" ),
+      ( "MATCH (n) FROM", "Syntax Error: From" ),
+      ( "MATCH RETURN n", "Syntax Error: Match `RETURN`" ),
+      ( "MATCH (n) OPTIONAL RETURN n", "Syntax Error: Optional `RETURN`" ),
+      ( "MATCH (n) WHERE", "Syntax Error: Where" ),
+      ( "MATCH (n) WHERE n.x = 1 (m)", "Syntax Error: Where `(`" ),
+      ( "MATCH (n WHERE n.x = 1 RETURN n", "Syntax Error: Where `RETURN`" ),
+      ( "MATCH (n {a: 1, a: 2})", "Syntax Error: Duplicate Property `a`" ),
+      ( "MATCH (n {a: 3x})", "Syntax Error: Property Value `3x`" ),
+      ( "MATCH (n {a: [[1]]})", "Syntax Error: Nested list properties are not supported" ),
+      ( "MATCH (a)-[r]-", "Syntax Error: Relationship" ),
+      ( "MATCH (a)-[r:A:B]->(b)", "Syntax Error: Relationship `B`" ),
+      ( "MATCH (a)-[r]->shortestPath((b)--(c))", "Syntax Error: `shortestPath`" ),
+      ( "MATCH p = shortestPath((a)--(b) RETURN p", "Syntax Error: shortestPath" ),
+      ( "MATCH (a) = (b)", "Syntax Error: Path `=`" ),
+      ( "MERGE (n) ON DELETE", "Syntax Error: On" ),
+      ( "MERGE (n) ON CREATE", "Syntax Error: On" ),
+      ( "CREATE", "Syntax Error: Create" ),
+      ( "UNWIND [1] x", "Syntax Error: Unwind" ),
+      ( "UNWIND [1] AS n.x", "Syntax Error: Unwind `n.x`" ),
+      ( "MATCH (n) SET n.x = y", "Syntax Error: Set `y`" ),
+      ( "MATCH (n) SET n.x = RETURN n", "Syntax Error: Set `n`" ),
+      ( "MATCH (n) SET n = 1", "Syntax Error: Set requires a property key" ),
+      ( "MATCH (n) SET n.x += {a: 1}", "Syntax Error: Set += requires a map" ),
+      ( "MATCH (n) REMOVE n", "Syntax Error: Remove `n`" ),
+      ( "MATCH (n) DETACH RETURN n", "Syntax Error: Detach" ),
+      ( "MATCH (n) DELETE n.x", "Syntax Error: Delete `n.x`" ),
+      ( "MATCH (n) RETURN n AS a AS b", "Syntax Error: Return `AS`" ),
+      ( "MATCH (n) WITH count(n", "Syntax Error: With" ),
+      ( "MATCH (n) LIMIT 1", "Syntax Error: Return `LIMIT`" ),
+      ( "MATCH (n) RETURN count(n)[0]", "Syntax Error: Return `[0]`" ),
+      ( "MATCH (n) RETURN n ORDER n", "Syntax Error: Return `n`" ),
+      ( "MATCH (n) RETURN n LIMIT 1 SKIP 1", "Syntax Error: Return `SKIP`" ),
+      ( "MATCH (n) RETURN n SKIP 1.5", "Syntax Error: Skip `1.5`" ),
+      ( "MATCH (n) WITH n AS RETURN n", "Syntax Error: With `RETURN`" ),
+      ( "MATCH (n) AS m", "Syntax Error: As" ),
+    ];
+    for ( text, err ) in errors.into_iter()
+    {
+      let res = parse_query( &parse_syntax( text ));
+      assert_eq!( res.err().map( | e | e.message ), Some( String::from( err )), "{}", text );
+    }
+  }
+
+  #[test]
+  fn test_statements ()
+  {
+    let tokens = parse_syntax( "CREATE GRAPH devs (a:Dev); MATCH (n) FROM devs RETURN n;" );
+    let statements = split_statements( &tokens );
+    assert_eq!( statements.len(), 2 );
+    assert_eq!( split_statements( &parse_syntax( ";MATCH (n);; ;" )).len(), 1 );
+    assert_eq!( statements[0].len(), 7 );
+    assert_eq!( parse_query( &statements[1] ).unwrap().graph, Some( String::from( "devs" )));
+
+    // one trailing ; ends a query, a statement after it needs a script
+    assert_eq!( parse_query( &parse_syntax( "MATCH () FROM devs;" )).unwrap().clauses.len(), 1 );
+    let err = parse_query( &tokens ).unwrap_err();
+    assert_eq!( err.message, String::from( "Syntax Error: `;`" ));
+    assert_eq!( err.expected, Some( String::from( "end of query" )));
+    assert_eq!( err.span.unwrap().column, 26 );
+  }
+
+  #[test]
+  fn test_error_spans ()
+  {
+    let err = parse_query( &parse_syntax( "MATCH (n:Person\nRETURN n" )).unwrap_err();
+    assert_eq!( err.message, String::from( "Syntax Error: Node `RETURN`" ));
+    assert_eq!( err.expected, Some( String::from( "`)`" )));
+    assert_eq!( err.found, Some( String::from( "`RETURN`" )));
+    assert_eq!(( err.span.unwrap().line, err.span.unwrap().column ), ( 2, 1 ));
+
+    // the token named by the message was already taken
+    let err1 = parse_query( &parse_syntax( "MATCH (n) LIMIT 1" )).unwrap_err();
+    assert_eq!( err1.span.unwrap().column, 11 );
+    assert_eq!( err1.found, Some( String::from( "`LIMIT`" )));
+
+    let err2 = parse_query( &parse_syntax( "MATCH (a)-[r:KNOWS" )).unwrap_err();
+    assert_eq!( err2.render( "MATCH (a)-[r:KNOWS" ).ends_with( "1 | MATCH (a)-[r:KNOWS\n  |                   ^ expected `]`, found end of query" ), true );
+
+    let err3 = parse_query( &parse_syntax( "FROM devs MATCH (n) FROM ops" )).unwrap_err();
+    assert_eq!( err3.span.unwrap().column, 21 );
+
+    let err4 = parse_query( &parse_syntax( "MATCH (n)\n/* RETURN n" )).unwrap_err();
+    assert_eq!( err4.message, String::from( "Syntax Error: Comment `/*`" ));
+    assert_eq!( err4.render( "MATCH (n)\n/* RETURN n" ).ends_with( "2 | /* RETURN n\n  | ^^ expected `*/`, found end of query" ), true );
+
+    // errors say what should have been there
+    let err5 = parse_query( &parse_syntax( "MERGE (n) ON DELETE SET n.x = 1" )).unwrap_err();
+    assert_eq!( err5.note(), Some( String::from( "expected `CREATE` or `MATCH`, found `DELETE`" )));
+    assert_eq!( err5.span.unwrap().column, 14 );
+    let err6 = parse_query( &parse_syntax( "MATCH (n) SET n = 1" )).unwrap_err();
+    assert_eq!(( err6.span.unwrap().column, err6.note() ), ( 15, Some( String::from( "expected a property, found `n`" ))));
+    let err7 = parse_query( &parse_syntax( "MATCH (n) WHERE n.zone IN 2 RETURN n" )).unwrap_err();
+    assert_eq!(( err7.span.unwrap().column, err7.expected ), ( 27, Some( String::from( "a list" ))));
+
+    let q = query( "MATCH (n)\n  WHERE n.age > 3 RETURN n" );
+    assert_eq!( q.spans[0], Span { start: 0, end: 27, line: 1, column: 1 });
+    assert_eq!( q.spans[1].column, 19 );
+  }
+}
@@ -0,0 +1,222 @@
+use std::cmp::Ordering;
+use crate::cmd::predicate::sort_order;
+use crate::result_set::Value;
+
+/* @version 0.3.0 */
+
+/*
+  RETURN n.team, count(*), count(DISTINCT n.age), avg(n.age), percentileCont(n.age, 0.5)
+
+  Values are collected per group, nulls are skipped by every function (count(*) counts rows).
+
+  Aggregate
+    :: new()
+    :: evaluate()
+        :: sum()
+        :: numbers()
+*/
+
+#[derive( Debug, Clone, PartialEq )]
+pub enum AggregateFunction { Count, Sum, Avg, Min, Max, Collect, PercentileCont, StDev }
+impl AggregateFunction
+{
+  pub fn from_name ( name: &str ) -> Option<AggregateFunction>
+  {
+    match name.to_lowercase().as_str()
+    {
+      "count" => Some( AggregateFunction::Count ),
+      "sum" => Some( AggregateFunction::Sum ),
+      "avg" => Some( AggregateFunction::Avg ),
+      "min" => Some( AggregateFunction::Min ),
+      "max" => Some( AggregateFunction::Max ),
+      "collect" => Some( AggregateFunction::Collect ),
+      "percentilecont" => Some( AggregateFunction::PercentileCont ),
+      "stdev" => Some( AggregateFunction::StDev ),
+      _ => None,
+    }
+  }
+}
+
+/// Aggregate function call of a RETURN item
+#[derive( Debug, Clone, PartialEq )]
+pub struct Aggregate
+{
+  pub name: String, // as written
+  pub function: AggregateFunction,
+  pub distinct: bool,
+  pub argument: Option<String>, // * or n.key
+  pub percentile: Option<f64>, // percentileCont( n.age, 0.5 )
+}
+impl Aggregate
+{
+  pub fn new ( name: &str ) -> Option<Aggregate>
+  {
+    let function = AggregateFunction::from_name( name )?;
+    Some( Aggregate { name: name.to_string(), function: function, distinct: false, argument: None, percentile: None })
+  }
+
+  /// Arguments are complete and allowed for the function
+  pub fn is_complete ( &self ) -> bool
+  {
+    if self.argument.is_none() { return false; }
+    if self.argument.as_deref() == Some( "*" ) && ( self.function != AggregateFunction::Count || self.distinct )
+    {
+      return false;
+    }
+    ( self.function == AggregateFunction::PercentileCont ) == self.percentile.is_some()
+  }
+
+  /// Expression text used as the column name, e.g. count(DISTINCT n.age)
+  pub fn expression ( &self ) -> String
+  {
+    let distinct = if self.distinct { "DISTINCT " } else { "" };
+    let percentile = self.percentile.map( | p | format!( ", {}", p )).unwrap_or( String::new() );
+    format!( "{}({}{}{})", self.name, distinct, self.argument.as_deref().unwrap_or( "" ), percentile )
+  }
+
+  /// Aggregate the values of a single group
+  pub fn evaluate ( &self, values: &Vec<Value> ) -> Result<Value, String>
+  {
+    let mut vals: Vec<&Value> = values.iter().filter( | v | v.is_null() == false ).collect();
+    if self.distinct
+    {
+      let mut unique: Vec<&Value> = Vec::new();
+      for val in vals.into_iter()
+      {
+        if unique.iter().any( | u | sort_order( u, val ) == Ordering::Equal ) == false { unique.push( val ); }
+      }
+      vals = unique;
+    }
+
+    match self.function
+    {
+      AggregateFunction::Count => Ok( Value::I64( vals.len() as i64 )),
+      AggregateFunction::Sum => self.sum( &vals ),
+      AggregateFunction::Avg =>
+      {
+        let nums = self.numbers( &vals )?;
+        if nums.len() == 0 { return Ok( Value::Null ); }
+        Ok( Value::F64( nums.iter().sum::<f64>() / nums.len() as f64 ))
+      }
+      AggregateFunction::Min => Ok( vals.into_iter().min_by( | a, b | sort_order( a, b )).cloned().unwrap_or( Value::Null )),
+      AggregateFunction::Max => Ok( vals.into_iter().max_by( | a, b | sort_order( a, b )).cloned().unwrap_or( Value::Null )),
+      AggregateFunction::Collect => Ok( Value::List( vals.into_iter().cloned().collect() )),
+      AggregateFunction::PercentileCont =>
+      {
+        let percentile = self.percentile.unwrap();
+        if percentile < 0.0 || percentile > 1.0
+        {
+          return Err( format!( "Error: Percentile `{}` must be between 0.0 and 1.0.", percentile ));
+        }
+        let mut nums = self.numbers( &vals )?;
+        if nums.len() == 0 { return Ok( Value::Null ); }
+        nums.sort_by( | a, b | a.partial_cmp( b ).unwrap_or( Ordering::Equal ));
+
+        // linear interpolation between the closest ranks
+        let rank = percentile * ( nums.len() - 1 ) as f64;
+        let ( lower, upper ) = ( rank.floor() as usize, rank.ceil() as usize );
+        Ok( Value::F64( nums[lower] + ( nums[upper] - nums[lower] ) * ( rank - lower as f64 )))
+      }
+      AggregateFunction::StDev =>
+      {
+        // sample standard deviation
+        let nums = self.numbers( &vals )?;
+        if nums.len() < 2 { return Ok( Value::F64( 0.0 )); }
+        let mean = nums.iter().sum::<f64>() / nums.len() as f64;
+        let variance = nums.iter().map( | n | ( n - mean ) * ( n - mean )).sum::<f64>() / ( nums.len() - 1 ) as f64;
+        Ok( Value::F64( variance.sqrt() ))
+      }
+    }
+  }
+
+  /// Integers of any width sum exactly (I64, or I128 when larger), any float makes the sum an F64
+  fn sum ( &self, vals: &Vec<&Value> ) -> Result<Value, String>
+  {
+    let nums = self.numbers( vals )?;
+    if vals.iter().all( | v | v.as_i128().is_some() )
+    {
+      let mut total: i128 = 0;
+      for val in vals.iter()
+      {
+        let next = total.checked_add( val.as_i128().unwrap() );
+        if next.is_none() { return Err( format!( "Error: {}() overflow.", self.name )); }
+        total = next.unwrap();
+      }
+      return Ok( i64::try_from( total ).map( Value::I64 ).unwrap_or( Value::I128( total )));
+    }
+    Ok( Value::F64( nums.iter().sum() ))
+  }
+
+  fn numbers ( &self, vals: &Vec<&Value> ) -> Result<Vec<f64>, String>
+  {
+    let mut ret: Vec<f64> = Vec::new();
+    for val in vals.iter()
+    {
+      if val.is_numeric() == false { return Err( format!( "Error: {}() requires numeric values.", self.name )); }
+      ret.push( val.as_f64().unwrap() );
+    }
+    Ok( ret )
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  fn aggregate ( name: &str, distinct: bool, percentile: Option<f64> ) -> Aggregate
+  {
+    let mut agg = Aggregate::new( name ).unwrap();
+    agg.distinct = distinct;
+    agg.argument = Some( String::from( "n.age" ));
+    agg.percentile = percentile;
+    agg
+  }
+
+  fn ages () -> Vec<Value>
+  {
+    vec![ Value::from( 38u8 ), Value::from( 27i16 ), Value::Null, Value::from( 45i64 ), Value::from( 38u32 ) ]
+  }
+
+  #[test]
+  fn test_aggregate_expression ()
+  {
+    assert_eq!( Aggregate::new( "size" ), None );
+    assert_eq!( aggregate( "count", true, None ).expression(), String::from( "count(DISTINCT n.age)" ));
+    assert_eq!( aggregate( "percentileCont", false, Some( 0.5 )).expression(), String::from( "percentileCont(n.age, 0.5)" ));
+    assert_eq!( aggregate( "percentileCont", false, None ).is_complete(), false );
+    assert_eq!( aggregate( "sum", false, Some( 0.5 )).is_complete(), false );
+
+    let mut all = Aggregate::new( "count" ).unwrap();
+    all.argument = Some( String::from( "*" ));
+    assert_eq!( all.is_complete(), true );
+    all.distinct = true;
+    assert_eq!( all.is_complete(), false );
+  }
+
+  #[test]
+  fn test_evaluate_aggregate ()
+  {
+    assert_eq!( aggregate( "count", false, None ).evaluate( &ages() ), Ok( Value::I64( 4 )));
+    assert_eq!( aggregate( "count", true, None ).evaluate( &ages() ), Ok( Value::I64( 3 )));
+    assert_eq!( aggregate( "sum", false, None ).evaluate( &ages() ), Ok( Value::I64( 148 )));
+    assert_eq!( aggregate( "sum", false, None ).evaluate( &vec![ Value::from( 1 ), Value::from( 0.5f32 ) ] ), Ok( Value::F64( 1.5 )));
+    assert_eq!( aggregate( "sum", false, None ).evaluate( &vec![ Value::from( i64::MAX ), Value::from( 1u8 ) ] ),
+      Ok( Value::I128( i64::MAX as i128 + 1 )));
+    assert_eq!( aggregate( "sum", false, None ).evaluate( &Vec::new() ), Ok( Value::I64( 0 )));
+    assert_eq!( aggregate( "avg", false, None ).evaluate( &ages() ), Ok( Value::F64( 37.0 )));
+    assert_eq!( aggregate( "avg", false, None ).evaluate( &vec![ Value::Null ] ), Ok( Value::Null ));
+    assert_eq!( aggregate( "min", false, None ).evaluate( &ages() ), Ok( Value::from( 27i16 )));
+    assert_eq!( aggregate( "max", false, None ).evaluate( &ages() ), Ok( Value::from( 45i64 )));
+    assert_eq!( aggregate( "collect", true, None ).evaluate( &ages() ),
+      Ok( Value::List( vec![ Value::from( 38u8 ), Value::from( 27i16 ), Value::from( 45i64 ) ] )));
+    assert_eq!( aggregate( "percentileCont", false, Some( 0.5 )).evaluate( &ages() ), Ok( Value::F64( 38.0 )));
+    assert_eq!( aggregate( "percentileCont", false, Some( 0.25 )).evaluate( &ages() ), Ok( Value::F64( 35.25 )));
+    assert_eq!( aggregate( "stDev", false, None ).evaluate( &vec![ Value::from( 2 ), Value::from( 4 ), Value::from( 6 ) ] ),
+      Ok( Value::F64( 2.0 )));
+
+    assert_eq!( aggregate( "sum", false, None ).evaluate( &vec![ Value::from( "a" ) ] ),
+      Err( String::from( "Error: sum() requires numeric values." )));
+    assert_eq!( aggregate( "percentileCont", false, Some( 1.5 )).evaluate( &ages() ).is_err(), true );
+  }
+}
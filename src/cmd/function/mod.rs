@@ -0,0 +1,66 @@
+use crate::result_set::Value;
+
+/* @version 0.3.0 */
+
+/*
+  MATCH p = (a)-[:KNOWS*1..3]->(b) RETURN nodes(p), relationships(p), length(p)
+
+  Scalar functions are evaluated per row, anything that is not a path is null.
+
+  ScalarFunction
+    :: from_name()
+    :: evaluate()
+*/
+
+#[derive( Debug, Clone, PartialEq )]
+pub enum ScalarFunction { Nodes, Relationships, Length }
+impl ScalarFunction
+{
+  pub fn from_name ( name: &str ) -> Option<ScalarFunction>
+  {
+    match name.to_lowercase().as_str()
+    {
+      "nodes" => Some( ScalarFunction::Nodes ),
+      "relationships" => Some( ScalarFunction::Relationships ),
+      "length" => Some( ScalarFunction::Length ),
+      _ => None,
+    }
+  }
+
+  pub fn evaluate ( &self, val: &Value ) -> Value
+  {
+    let path_opt = val.as_path();
+    if path_opt.is_none() { return Value::Null; }
+    let path = path_opt.unwrap();
+
+    match self
+    {
+      ScalarFunction::Nodes => Value::List( path.nodes.iter().map( | n | Value::Node( n.clone() )).collect() ),
+      ScalarFunction::Relationships => Value::List( path.edges.iter().map( | e | Value::Edge( e.clone() )).collect() ),
+      ScalarFunction::Length => Value::I64( path.len() as i64 ),
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+  use crate::common::{ DirectionType, Edge, Node };
+  use crate::result_set::Path;
+
+  #[test]
+  fn test_evaluate_function ()
+  {
+    let node = | id: &str | Node::new( String::from( id ), Some( String::from( "Developer" )), Vec::new() );
+    let edge = Edge::new( String::from( "e" ), None, String::from( "a" ), String::from( "b" ), DirectionType::Right, Vec::new() );
+    let path = Value::Path( Path::new( vec![ node( "a" ), node( "b" ) ], vec![ edge.clone() ] ));
+
+    assert_eq!( ScalarFunction::from_name( "Length" ), Some( ScalarFunction::Length ));
+    assert_eq!( ScalarFunction::from_name( "size" ), None );
+    assert_eq!( ScalarFunction::Length.evaluate( &path ), Value::I64( 1 ));
+    assert_eq!( ScalarFunction::Nodes.evaluate( &path ), Value::List( vec![ Value::Node( node( "a" )), Value::Node( node( "b" )) ] ));
+    assert_eq!( ScalarFunction::Relationships.evaluate( &path ), Value::List( vec![ Value::Edge( edge ) ] ));
+    assert_eq!( ScalarFunction::Length.evaluate( &Value::from( "ab" )), Value::Null );
+  }
+}
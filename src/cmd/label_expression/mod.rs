@@ -0,0 +1,172 @@
+/* @version 0.3.0 */
+
+/*
+  MATCH (n:(TrainStation & BusStation) | StationGroup)
+  MATCH (n:!Developer)
+  MATCH (n:%)
+
+  Precedence (loosest first): | then & (or :) then !.
+
+  LabelExpression
+    :: parse()
+        :: parse_or()
+            :: parse_and()
+                :: parse_not()
+    :: matches()
+*/
+
+/// Label expression of a node pattern
+#[derive( Debug, Clone, PartialEq )]
+pub enum LabelExpression
+{
+  Label( String ),
+  Any, // % matches any node with at least one label
+  Not( Box<LabelExpression> ),
+  And( Box<LabelExpression>, Box<LabelExpression> ),
+  Or( Box<LabelExpression>, Box<LabelExpression> ),
+}
+
+impl LabelExpression
+{
+  /// Build an expression from the text following the colon, e.g. (A & B) | C
+  pub fn parse ( expr: &str ) -> Result<LabelExpression, String>
+  {
+    let tokens = split_label_expression( expr );
+    let mut pos = 0;
+    let ret = LabelExpression::parse_or( &tokens, &mut pos );
+    if ret.is_err() || pos < tokens.len() { return Err( format!( "Syntax Error: Label Expression `{}`", expr )); }
+    ret
+  }
+
+  fn parse_or ( tokens: &Vec<String>, pos: &mut usize ) -> Result<LabelExpression, String>
+  {
+    let mut left = LabelExpression::parse_and( tokens, pos )?;
+    while is_op( tokens, *pos, "|" )
+    {
+      *pos += 1;
+      left = LabelExpression::Or( Box::new( left ), Box::new( LabelExpression::parse_and( tokens, pos )? ));
+    }
+    Ok( left )
+  }
+
+  fn parse_and ( tokens: &Vec<String>, pos: &mut usize ) -> Result<LabelExpression, String>
+  {
+    let mut left = LabelExpression::parse_not( tokens, pos )?;
+    while is_op( tokens, *pos, "&" ) || is_op( tokens, *pos, ":" )
+    {
+      *pos += 1;
+      left = LabelExpression::And( Box::new( left ), Box::new( LabelExpression::parse_not( tokens, pos )? ));
+    }
+    Ok( left )
+  }
+
+  fn parse_not ( tokens: &Vec<String>, pos: &mut usize ) -> Result<LabelExpression, String>
+  {
+    let token_opt = tokens.get( *pos );
+    if token_opt.is_none() { return Err( String::from( "Syntax Error: Label Expression" )); }
+    *pos += 1;
+
+    match token_opt.unwrap().as_str()
+    {
+      "!" => Ok( LabelExpression::Not( Box::new( LabelExpression::parse_not( tokens, pos )? ))),
+      "%" => Ok( LabelExpression::Any ),
+      "(" =>
+      {
+        let inner = LabelExpression::parse_or( tokens, pos )?;
+        if is_op( tokens, *pos, ")" ) == false { return Err( String::from( "Syntax Error: Label Expression" )); }
+        *pos += 1;
+        Ok( inner )
+      }
+      label =>
+      {
+        if label.chars().all( | c | is_label_char( &c )) == false 
+        { 
+          return Err( String::from( "Syntax Error: Label Expression" )); 
+        }
+        Ok( LabelExpression::Label( label.to_string() ))
+      }
+    }
+  }
+
+  /// Whether a node with the given labels satisfies the expression
+  pub fn matches ( &self, labels: &Vec<&str> ) -> bool
+  {
+    match self
+    {
+      LabelExpression::Label( label ) => labels.contains( &label.as_str() ),
+      LabelExpression::Any => labels.len() > 0,
+      LabelExpression::Not( inner ) => inner.matches( labels ) == false,
+      LabelExpression::And( a, b ) => a.matches( labels ) && b.matches( labels ),
+      LabelExpression::Or( a, b ) => a.matches( labels ) || b.matches( labels ),
+    }
+  }
+}
+
+/// Operators are single tokens, whitespace separates labels
+fn split_label_expression ( expr: &str ) -> Vec<String>
+{
+  let mut tokens: Vec<String> = Vec::new();
+  let mut label = String::new();
+  for c in expr.chars()
+  {
+    if is_label_char( &c )
+    {
+      label.push( c );
+      continue;
+    }
+    if label.len() > 0 { tokens.push( std::mem::take( &mut label )); }
+    if c.is_whitespace() == false { tokens.push( c.to_string() ); }
+  }
+  if label.len() > 0 { tokens.push( label ); }
+  tokens
+}
+
+fn is_op ( tokens: &Vec<String>, pos: usize, op: &str ) -> bool { tokens.get( pos ).map( | t | t == op ).unwrap_or( false ) }
+
+/// Characters of a single label inside an expression
+pub fn is_label_char ( c: &char ) -> bool { "&|!%():".contains( *c ) == false && c.is_whitespace() == false }
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  fn label ( l: &str ) -> Box<LabelExpression> { Box::new( LabelExpression::Label( String::from( l ))) }
+
+  #[test]
+  fn test_parse_label_expression ()
+  {
+    assert_eq!( LabelExpression::parse( "(TrainStation & BusStation) | StationGroup" ), Ok( LabelExpression::Or(
+      Box::new( LabelExpression::And( label( "TrainStation" ), label( "BusStation" ))),
+      label( "StationGroup" ))));
+    assert_eq!( LabelExpression::parse( "A|B&!C" ), Ok( LabelExpression::Or(
+      label( "A" ),
+      Box::new( LabelExpression::And( label( "B" ), Box::new( LabelExpression::Not( label( "C" ))))))));
+    assert_eq!( LabelExpression::parse( "A:B" ), Ok( LabelExpression::And( label( "A" ), label( "B" ))));
+    assert_eq!( LabelExpression::parse( "%" ), Ok( LabelExpression::Any ));
+
+    assert_eq!( LabelExpression::parse( "(A | B" ).is_err(), true );
+    assert_eq!( LabelExpression::parse( "A |" ).is_err(), true );
+    assert_eq!( LabelExpression::parse( "A B" ).is_err(), true );
+    assert_eq!( LabelExpression::parse( "" ).is_err(), true );
+  }
+
+  #[test]
+  fn test_match_label_expression ()
+  {
+    let train = vec![ "TrainStation" ];
+    let both = vec![ "TrainStation", "BusStation" ];
+    let none: Vec<&str> = Vec::new();
+
+    let expr = LabelExpression::parse( "(TrainStation & BusStation) | StationGroup" ).unwrap();
+    assert_eq!( expr.matches( &both ), true );
+    assert_eq!( expr.matches( &train ), false );
+    assert_eq!( expr.matches( &vec![ "StationGroup" ] ), true );
+
+    assert_eq!( LabelExpression::parse( "!BusStation" ).unwrap().matches( &train ), true );
+    assert_eq!( LabelExpression::parse( "!BusStation" ).unwrap().matches( &both ), false );
+    assert_eq!( LabelExpression::parse( "%" ).unwrap().matches( &train ), true );
+    assert_eq!( LabelExpression::parse( "%" ).unwrap().matches( &none ), false );
+    assert_eq!( LabelExpression::parse( "!%" ).unwrap().matches( &none ), true );
+  }
+}
@@ -1,8 +1,14 @@
+pub mod aggregate;
+pub mod function;
+pub mod label_expression;
+pub mod predicate;
 pub mod transaction;
 
-use crate::common::{ DirectionType };
+use crate::cmd::aggregate::Aggregate;
+use crate::cmd::label_expression::LabelExpression;
+use crate::cmd::predicate::{ Operand, Predicate };
+use crate::common::{ DirectionType, NEProperty };
 use crate::common::kvps::{ KeyValString };
-use crate::tokenize::{ SyntaxToken, SyntaxTokenType };
 use crate::utils::cons_uuid;
 
 /* @version 0.3.0 */
@@ -13,19 +19,123 @@ pub struct ReadClause
 { 
   pub order: u16, // must be query order
   pub match_statements: Vec<MatchStatement>,
+  pub bracket_statements: Vec<BracketStatement>, // relationships between match statements (by order)
+  pub predicates: Vec<Predicate>, // inline and trailing WHERE, every one must hold
+  pub path_variables: Vec<( String, u16 )>, // p = (a)-[]->(b), the variable and order of the first node
+  pub shortest_paths: Vec<( u16, bool )>, // shortestPath( ... ) by order of the first node, true for allShortestPaths
+  pub clauses: Vec<( u16, bool )>, // order every MATCH starts at, true for OPTIONAL MATCH
+  pub optional_predicates: Vec<( u16, Predicate )>, // WHERE of an OPTIONAL MATCH, by the order the clause starts at
 }
 impl ReadClause 
 {
   pub fn new ( order: u16 ) -> ReadClause 
   { 
-    ReadClause { order: order, match_statements: Vec::new() }
+    ReadClause { 
+      order: order, 
+      match_statements: Vec::new(), 
+      bracket_statements: Vec::new(), 
+      predicates: Vec::new(), 
+      path_variables: Vec::new(),
+      shortest_paths: Vec::new(),
+      clauses: Vec::new(),
+      optional_predicates: Vec::new(),
+    }
   }
 
   pub fn add_match_statement ( &mut self, stmt: MatchStatement ) { self.match_statements.push( stmt ); }
+  pub fn add_bracket_statement ( &mut self, stmt: BracketStatement ) { self.bracket_statements.push( stmt ); }
+
+  /// MatchStatements directly before and after a relationship
+  pub fn bracket_neighbors ( &self, stmt: &BracketStatement ) -> ( Option<&MatchStatement>, Option<&MatchStatement> ) 
+  {
+    let left = self.match_statements.iter().filter( | m | m.order < stmt.order ).max_by_key( | m | m.order );
+    let right = self.match_statements.iter().filter( | m | m.order > stmt.order ).min_by_key( | m | m.order );
+    ( left, right )
+  }
+
+  /// Relationships of the pattern starting at a MatchStatement, up to the next comma or MATCH
+  pub fn path_brackets ( &self, start: u16 ) -> Vec<&BracketStatement> 
+  {
+    let mut brackets: Vec<&BracketStatement> = self.bracket_statements.iter().filter( | b | b.order > start ).collect();
+    brackets.sort_by_key( | b | b.order );
+
+    let mut ret: Vec<&BracketStatement> = Vec::new();
+    let mut node_order = start;
+    for bracket in brackets.into_iter() 
+    {
+      let ( left, _ ) = self.bracket_neighbors( bracket );
+      if left.is_none() || left.unwrap().order != node_order { break; }
+      let ( _, right ) = self.bracket_neighbors( bracket );
+      if right.is_none() { break; }
+      node_order = right.unwrap().order;
+      ret.push( bracket );
+    }
+    ret
+  }
+
+  /// ( first order, first order of the next clause, optional ) of every MATCH, a single clause when there are none
+  pub fn clause_ranges ( &self ) -> Vec<( u16, Option<u16>, bool )> 
+  {
+    if self.clauses.len() == 0 { return vec![ ( 0, None, false ) ]; }
+    let mut ret = Vec::new();
+    for ( i, ( start, optional )) in self.clauses.iter().enumerate() 
+    {
+      ret.push(( *start, self.clauses.get( i + 1 ).map( | ( next, _ ) | *next ), *optional ));
+    }
+    ret
+  }
+
+  /// Variables bound by the pattern: nodes, relationships and paths
+  pub fn variables ( &self ) -> Vec<String> 
+  {
+    let mut ret: Vec<String> = Vec::new();
+    for stmt in self.match_statements.iter() 
+    {
+      if stmt.transaction_label.is_some() { ret.push( stmt.transaction_label.clone().unwrap() ); }
+    }
+    for stmt in self.bracket_statements.iter() 
+    {
+      if stmt.transaction_label.is_some() { ret.push( stmt.transaction_label.clone().unwrap() ); }
+    }
+    for ( variable, _ ) in self.path_variables.iter() { ret.push( variable.clone() ); }
+    ret
+  }
 }
 
+/// WITH [DISTINCT] n, count(r) AS c ORDER BY c SKIP 1 LIMIT 5 WHERE c > 2, projects the bindings of the stages before it
+#[derive( Debug, Clone )]
+pub struct WithClause 
+{
+  pub order: u16, // must be query order
+  pub projection: ReturnClause,
+  pub predicates: Vec<Predicate>, // WHERE on the projected columns
+}
+impl WithClause 
+{
+  pub fn new ( order: u16 ) -> WithClause 
+  { 
+    WithClause { order: order, projection: ReturnClause::new(), predicates: Vec::new() } 
+  }
+
+  /// Variables in scope after the WITH
+  pub fn columns ( &self ) -> Vec<String> { self.projection.items.iter().map( | item | item.column().clone() ).collect() }
+}
 
+/// UNWIND [1, 2, 3] AS x, one row per element of the list for every row before it
+#[derive( Debug, Clone )]
+pub struct UnwindClause 
+{
+  pub order: u16, // must be query order
+  pub operand: Operand,
+  pub variable: String,
+}
+
+/// Stage of a query pipeline: MATCH clauses up to the next WITH or UNWIND, a WITH, or an UNWIND
 #[derive( Debug )]
+pub enum QueryStage { Read( ReadClause ), With( WithClause ), Unwind( UnwindClause ) }
+
+
+#[derive( Debug, Clone )]
 pub struct MatchStatement 
 {
   pub order: u16, // must be query order
@@ -34,7 +144,11 @@ pub struct MatchStatement
   pub kvps_complete: bool, // whether or not key-value pairs can still be added
   pub transaction_label: Option<String>,
   pub primary_label: Option<String>,
+  pub label_expression: Option<LabelExpression>, // replaces primary_label for :(A & B) | C patterns
+  pub labels: Vec<String>, // secondary labels of :A:B, every one must be present
   pub kv_str: Vec<KeyValString>,
+  pub properties: Vec<NEProperty>, // typed key-value pairs (strings included)
+  pub property_refs: Vec<( String, Operand )>, // { key: $param } until parameters are bound
 }
 impl MatchStatement 
 {
@@ -48,119 +162,12 @@ impl MatchStatement
       kvps_complete: false,
       transaction_label: transaction_label, 
       primary_label: primary_label, 
-      kv_str: Vec::new()
-    }
-  }
-
-  pub fn from ( old_stmt: MatchStatement, token: &SyntaxToken ) -> Result<MatchStatement, String> 
-  {
-    match token.token_type 
-    {
-      SyntaxTokenType::Label => 
-      {
-        if old_stmt.transaction_label.is_none() == true && old_stmt.labels_complete == false 
-        {
-          return Ok( MatchStatement::new(
-            old_stmt.order, 
-            old_stmt.is_open, 
-            Some( token.val.clone() ), 
-            old_stmt.primary_label ));
-        }
-      }
-
-      SyntaxTokenType::PrimaryLabel => 
-      {
-        if old_stmt.primary_label.is_none() == true && old_stmt.labels_complete == false 
-        {
-          return Ok( MatchStatement::new(
-            old_stmt.order, 
-            old_stmt.is_open, 
-            old_stmt.transaction_label, 
-            Some( token.val.clone() )));
-        }
-      }
-
-      SyntaxTokenType::OpenNode => 
-      {
-        let stmt = MatchStatement::new( 
-          old_stmt.order, 
-          old_stmt.is_open, 
-          old_stmt.transaction_label, 
-          old_stmt.primary_label );
-        return Ok( stmt );
-      }
-
-      SyntaxTokenType::CloseNode => 
-      {
-        let mut stmt = MatchStatement::new( 
-          old_stmt.order, 
-          old_stmt.is_open, 
-          old_stmt.transaction_label, 
-          old_stmt.primary_label );
-        stmt.is_open = false;
-        stmt.kv_str = old_stmt.kv_str;
-        stmt.kvps_complete = old_stmt.kvps_complete;
-        stmt.labels_complete = old_stmt.labels_complete;
-        return Ok( stmt );
-      }
-
-      SyntaxTokenType::OpenBrace => 
-      {
-        let mut stmt = MatchStatement::new( 
-          old_stmt.order, 
-          old_stmt.is_open, 
-          old_stmt.transaction_label, 
-          old_stmt.primary_label );
-        stmt.labels_complete = true;
-        return Ok( stmt );
-      }
-
-      SyntaxTokenType::CloseBrace => 
-      {
-        let mut stmt = MatchStatement::new( 
-          old_stmt.order, 
-          old_stmt.is_open, 
-          old_stmt.transaction_label, 
-          old_stmt.primary_label );
-        stmt.kvps_complete = true;
-        stmt.labels_complete = old_stmt.labels_complete;
-        stmt.kv_str = old_stmt.kv_str;
-        return Ok( stmt );
-      }
-
-      SyntaxTokenType::Key => 
-      {
-        let mut stmt = MatchStatement::new( 
-          old_stmt.order, 
-          old_stmt.is_open, 
-          old_stmt.transaction_label, 
-          old_stmt.primary_label );
-        stmt.labels_complete = old_stmt.labels_complete;
-        stmt.kv_str = old_stmt.kv_str;
-        stmt.kv_str.push( KeyValString::new( token.val.clone(), None ));
-        return Ok( stmt );
-      }
-
-      SyntaxTokenType::StringValue => 
-      {
-        if old_stmt.kv_str.len() > 0 
-        {
-          let mut stmt = MatchStatement::new( 
-            old_stmt.order, 
-            old_stmt.is_open, 
-            old_stmt.transaction_label, 
-            old_stmt.primary_label );
-          stmt.labels_complete = old_stmt.labels_complete;
-          stmt.kv_str = old_stmt.kv_str;
-          let kvp_opt = stmt.kv_str.pop();
-          stmt.kv_str.push( KeyValString::from( &kvp_opt.unwrap(), token.val.clone() ));
-          return Ok( stmt );
-        }
-      }
-
-      _ => {}
+      label_expression: None,
+      labels: Vec::new(),
+      kv_str: Vec::new(),
+      properties: Vec::new(),
+      property_refs: Vec::new(),
     }
-    Err( String::from( "Syntax Error: Match Statement" ))
   }
 }
 
@@ -177,21 +184,6 @@ impl CreateStatement
   {
     CreateStatement { order: order, is_open: true, graph_name: name }
   }
-
-  pub fn from ( stmt: &CreateStatement, token: SyntaxToken ) -> Result<CreateStatement, String> 
-  {
-    if token.token_type == SyntaxTokenType::Label 
-    {
-      return Ok(
-          CreateStatement { 
-            order: stmt.order, 
-            is_open: stmt.is_open, 
-            graph_name: Some( token.val )
-          }
-        )
-    }
-    Err( String::from( "Syntax Error: Create Statement" ))
-  }
 }
 
 #[derive( Debug, Clone )]
@@ -201,6 +193,9 @@ pub struct ParenStatement
   pub is_open: bool,
   pub transaction_label: Option<String>,
   pub primary_label: Option<String>,
+  pub labels: Vec<String>, // secondary labels, stored as LabelRows
+  pub properties: Vec<NEProperty>,
+  pub property_refs: Vec<( String, Operand )>, // { key: row.name } resolved per row
 }
 impl ParenStatement 
 {
@@ -212,42 +207,13 @@ impl ParenStatement
       order: order, 
       is_open: is_open, 
       transaction_label: transaction_label, 
-      primary_label: primary_label 
+      primary_label: primary_label,
+      labels: Vec::new(),
+      properties: Vec::new(),
+      property_refs: Vec::new(),
     }
   }
   
-  pub fn from ( stmt: ParenStatement, token: &SyntaxToken ) -> Result<ParenStatement, String> 
-  {
-    match token.token_type 
-    {
-      SyntaxTokenType::Label => 
-      {
-        return Ok(
-          ParenStatement { 
-            order: stmt.order, 
-            is_open: stmt.is_open, 
-            transaction_label: Some( token.val.clone() ), 
-            primary_label: stmt.primary_label, 
-          }
-        )
-      },
-      SyntaxTokenType::PrimaryLabel => 
-      {
-        return Ok(
-          ParenStatement { 
-            order: stmt.order, 
-            is_open: stmt.is_open, 
-            transaction_label: stmt.transaction_label, 
-            primary_label: Some( token.val.clone() ), 
-          }
-        )
-      },
-      
-      _ => {}
-    }
-    Err( String::from( "Syntax Error: Paren Statement" ))
-  }
-
   pub fn is_ref ( &self ) -> bool { self.primary_label.is_none() }
 
   pub fn is_empty ( &self ) -> bool 
@@ -260,21 +226,31 @@ impl ParenStatement
   {
     if self.is_ref() == false 
     {
-      return Ok( 
-        NodeStatement::new( 
-          cons_uuid(), 
-          self.order, 
-          self.transaction_label.clone(), 
-          self.primary_label.clone().unwrap() ));
+      let mut stmt = NodeStatement::new( 
+        cons_uuid(), 
+        self.order, 
+        self.transaction_label.clone(), 
+        self.primary_label.clone().unwrap() );
+      stmt.properties = self.properties.clone();
+      stmt.property_refs = self.property_refs.clone();
+      for label in self.labels.iter() { stmt.properties.push( NEProperty::Tag( label.clone() )); }
+      return Ok( stmt );
     }
     Err( String::from( "Syntax Error: Paren Statement" ))
   }
 
   pub fn to_node_ref_statement ( &self, id_ref: Option<String> ) -> Result<NodeRefStatement, String>
   {
+    // a reference is found by its stored properties, which have to be known up front
+    if self.property_refs.len() > 0 
+    {
+      return Err( format!( "Syntax Error: Property Value `{}`", self.property_refs[0].0 ));
+    }
     if self.is_ref() 
     {
-      return Ok( NodeRefStatement::new( self.order, id_ref, self.transaction_label.clone().unwrap() ));
+      let mut stmt = NodeRefStatement::new( self.order, id_ref, self.transaction_label.clone().unwrap() );
+      stmt.properties = self.properties.clone();
+      return Ok( stmt );
     }
     Err( String::from( "Syntax Error: Paren Ref Statement" ))
   }
@@ -288,6 +264,9 @@ pub struct BracketStatement
   pub transaction_label: Option<String>,
   pub primary_label: Option<String>,
   pub edge_dir: DirectionType,
+  pub hops: Option<HopRange>, // variable-length relationship
+  pub properties: Vec<NEProperty>,
+  pub property_refs: Vec<( String, Operand )>, // { key: row.name } resolved per row
 }
 impl BracketStatement 
 {
@@ -301,53 +280,63 @@ impl BracketStatement
       is_open: is_open, 
       transaction_label: transaction_label, 
       primary_label: primary_label,
-      edge_dir: edge_dir
+      edge_dir: edge_dir,
+      hops: None,
+      properties: Vec::new(),
+      property_refs: Vec::new(),
     }
   }
-  
-  pub fn from ( stmt: BracketStatement, token: &SyntaxToken ) -> Result<BracketStatement, String> 
+}
+
+/// Number of relationships a variable-length pattern spans: *, *2, *1..3, *..3 or *2..
+#[derive( Debug, Clone, PartialEq )]
+pub struct HopRange 
+{
+  pub min: usize,
+  pub max: Option<usize>, // None is unbounded
+}
+impl HopRange 
+{
+  pub fn parse ( text: &str ) -> Result<HopRange, String> 
   {
-    match token.token_type 
-    {
-      SyntaxTokenType::Label => 
-      {
-        return Ok(
-          BracketStatement { 
-            order: stmt.order, 
-            is_open: stmt.is_open, 
-            transaction_label: Some( token.val.clone() ), 
-            primary_label: stmt.primary_label, 
-            edge_dir: stmt.edge_dir,
-          }
-        )
-      }
+    let err = format!( "Syntax Error: Hop Range `{}`", text );
+    let range = text.strip_prefix( '*' ).ok_or( err.clone() )?;
+    if range.len() == 0 { return Ok( HopRange { min: 1, max: None }); }
 
-      SyntaxTokenType::PrimaryLabel => 
-      {
-        return Ok(
-          BracketStatement { 
-            order: stmt.order, 
-            is_open: stmt.is_open, 
-            transaction_label: stmt.transaction_label, 
-            primary_label: Some( token.val.clone() ),
-            edge_dir: stmt.edge_dir,
-          }
-        )
-      }
-      
-      SyntaxTokenType::EdgeDirection => 
+    let bound = | s: &str | -> Result<Option<usize>, String> 
+    {
+      if s.len() == 0 { return Ok( None ); }
+      s.parse::<usize>().map( Some ).map_err( | _ | err.clone() )
+    };
+    let hops = match range.split_once( ".." ) 
+    {
+      Some(( min, max )) => HopRange { min: bound( min )?.unwrap_or( 1 ), max: bound( max )? },
+      None => 
       {
-        if token.val == "-" { return Ok( stmt ); }
+        let exact = bound( range )?;
+        HopRange { min: exact.unwrap(), max: exact }
       }
-
-      _ => {}
+    };
+    if hops.max.is_some() && hops.max.unwrap() < hops.min { return Err( err ); }
+    Ok( hops )
+  }
+}
+impl std::fmt::Display for HopRange 
+{
+  /// Shortest text of the range, *1.. and *.. are written as *
+  fn fmt ( &self, f: &mut std::fmt::Formatter ) -> std::fmt::Result 
+  {
+    match self.max 
+    {
+      None if self.min == 1 => write!( f, "*" ),
+      None => write!( f, "*{}..", self.min ),
+      Some( max ) if max == self.min => write!( f, "*{}", max ),
+      Some( max ) => write!( f, "*{}..{}", self.min, max ),
     }
-    Err( String::from( "Syntax Error: Bracket Statement" ))
   }
 }
 
-
-#[derive( Debug )]
+#[derive( Debug, Clone )]
 pub struct FromClause { pub graph_name: String }
 impl FromClause
 {
@@ -358,16 +347,122 @@ impl FromClause
 }
 
 
-#[derive( Debug )]
-pub struct ReturnClause { pub transaction_label: String, pub output_label: Option<String> }
+#[derive( Debug, Clone, PartialEq )]
+pub enum SetType { Property, Merge, Label }
+
+/// MERGE outcome a SET clause is limited to (ON CREATE SET / ON MATCH SET)
+#[derive( Debug, Clone, PartialEq )]
+pub enum MergeAction { OnCreate, OnMatch }
+
+/// A single SET item: n.key = value, n += { key: value } or n:Label
+#[derive( Debug, Clone )]
+pub struct SetStatement 
+{
+  pub order: u16, // must be query order
+  pub is_open: bool,
+  pub transaction_label: String,
+  pub set_type: Option<SetType>,
+  pub properties: Vec<NEProperty>,
+  pub property_refs: Vec<( String, Operand )>, // n.key = $param until parameters are bound
+  pub labels: Vec<String>,
+  pub merge_action: Option<MergeAction>, // applies regardless of the MERGE outcome when None
+}
+impl SetStatement 
+{
+  pub fn new ( order: u16, transaction_label: String ) -> SetStatement 
+  {
+    SetStatement 
+    {
+      order: order,
+      is_open: true,
+      transaction_label: transaction_label,
+      set_type: None,
+      properties: Vec::new(),
+      property_refs: Vec::new(),
+      labels: Vec::new(),
+      merge_action: None,
+    }
+  }
+}
+
+
+/// A single REMOVE item: n.key or n:Label
+#[derive( Debug, Clone )]
+pub struct RemoveStatement
+{
+  pub order: u16, // must be query order
+  pub transaction_label: String,
+  pub key: Option<String>,
+  pub labels: Vec<String>,
+}
+
+
+/// DELETE a, b / DETACH DELETE a / DELETE GRAPH name
+#[derive( Debug, Clone )]
+pub struct DeleteStatement
+{
+  pub order: u16, // must be query order
+  pub detach: bool, // also delete the relationships of deleted nodes
+  pub graph_name: Option<String>,
+  pub transaction_labels: Vec<String>,
+}
+impl DeleteStatement
+{
+  pub fn new ( order: u16, detach: bool ) -> DeleteStatement
+  {
+    DeleteStatement
+    {
+      order: order,
+      detach: detach,
+      graph_name: None,
+      transaction_labels: Vec::new()
+    }
+  }
+}
+
+
+/// RETURN [DISTINCT] n.name AS name, count(n) ORDER BY name DESC SKIP 1 LIMIT 5, lowered from ast::Projection
+#[derive( Debug, Clone )]
+pub struct ReturnClause 
+{ 
+  pub items: Vec<ReturnItem>,
+  pub distinct: bool,
+  pub order_by: Vec<SortItem>,
+  pub skip: Option<usize>,
+  pub limit: Option<usize>,
+}
 impl ReturnClause
 {
-  pub fn new ( transaction_label: String, output_label: Option<String> ) -> ReturnClause
+  pub fn new () -> ReturnClause
   {
-    ReturnClause { transaction_label: transaction_label, output_label: output_label }
+    ReturnClause { items: Vec::new(), distinct: false, order_by: Vec::new(), skip: None, limit: None }
   }
 }
 
+/// Single RETURN item, an operand or an aggregate
+#[derive( Debug, Clone )]
+pub struct ReturnItem
+{
+  pub expression: String,
+  pub operand: Operand, // aggregated value for an aggregate
+  pub aggregate: Option<Aggregate>,
+  pub alias: Option<String>,
+}
+impl ReturnItem
+{
+  /// Output column name, the alias when there is one
+  pub fn column ( &self ) -> &String { self.alias.as_ref().unwrap_or( &self.expression ) }
+}
+
+/// Single ORDER BY item, a returned column or an operand of the rows before it
+#[derive( Debug, Clone )]
+pub struct SortItem
+{
+  pub expression: String,
+  pub operand: Operand,
+  pub descending: bool,
+}
+
 
 #[derive( Debug )]
 pub struct NodeStatement 
@@ -376,12 +471,21 @@ pub struct NodeStatement
   pub query_order: u16,
   pub transaction_label: Option<String>,
   pub primary_label: String,
+  pub properties: Vec<NEProperty>,
+  pub property_refs: Vec<( String, Operand )>, // properties resolved per row
 }
 impl NodeStatement
 {
   pub fn new ( id: String, query_order: u16, transaction_label: Option<String>, primary_label: String ) -> NodeStatement
   {
-    NodeStatement { id: id, query_order: query_order, transaction_label: transaction_label, primary_label: primary_label }
+    NodeStatement { 
+      id: id, 
+      query_order: query_order, 
+      transaction_label: transaction_label, 
+      primary_label: primary_label, 
+      properties: Vec::new(),
+      property_refs: Vec::new(),
+    }
   }
 }
 
@@ -390,13 +494,20 @@ pub struct NodeRefStatement
 {
   pub query_order: u16,
   pub id_ref: Option<String>,
-  pub transaction_label: String
+  pub transaction_label: String,
+  pub properties: Vec<NEProperty>, // identify a stored node the reference resolves to
 }
 impl NodeRefStatement
 {
   pub fn new ( order: u16, id_ref: Option<String>, transaction_label: String ) -> NodeRefStatement
   {
-    NodeRefStatement { query_order: order, id_ref: id_ref, transaction_label: transaction_label }
+    NodeRefStatement 
+    { 
+      query_order: order, 
+      id_ref: id_ref, 
+      transaction_label: transaction_label, 
+      properties: Vec::new() 
+    }
   }
 }
 
@@ -407,12 +518,23 @@ pub struct EdgeStatement
   pub query_order: u16,
   pub transaction_label: Option<String>,
   pub primary_label: String,
+  pub direction: DirectionType,
+  pub properties: Vec<NEProperty>,
+  pub property_refs: Vec<( String, Operand )>, // properties resolved per row
 }
 impl EdgeStatement
 {
   pub fn new ( id: String, order: u16, transaction_label: Option<String>, primary_label: String ) -> EdgeStatement
   {
-    EdgeStatement { id: id, query_order: order, transaction_label: transaction_label, primary_label: primary_label }
+    EdgeStatement { 
+      id: id, 
+      query_order: order, 
+      transaction_label: transaction_label, 
+      primary_label: primary_label, 
+      direction: DirectionType::Undirected,
+      properties: Vec::new(),
+      property_refs: Vec::new(),
+    }
   }
 }
 
@@ -435,51 +557,17 @@ mod tests
     assert_eq!( ms.is_open, true );
     assert_eq!( ms.transaction_label, Some( String::from( "transaction label" )));
     assert_eq!( ms.primary_label, Some( String::from( "primary label" )));
+  }
 
-    // -- labels
-    let ms1 = MatchStatement::new( 5, true, None, None );
-    let res1 = MatchStatement::from( ms1, &SyntaxToken::new( SyntaxTokenType::Label, String::from( "transaction label" )));
-    assert_eq!( res1.is_ok(), true );
-    assert_eq!( res1.as_ref().unwrap().transaction_label.is_some(), true );
-    assert_eq!( res1.as_ref().unwrap().transaction_label, Some( String::from( "transaction label" )));
-
-    let res2 = MatchStatement::from( res1.unwrap(), &SyntaxToken::new( SyntaxTokenType::PrimaryLabel, String::from( "primary label" )));
-    assert_eq!( res2.is_ok(), true );
-    assert_eq!( res2.as_ref().unwrap().primary_label.is_some(), true );
-    assert_eq!( res2.as_ref().unwrap().primary_label, Some( String::from( "primary label" )));
-
-    // -- open / close nodes
-    let ms2 = MatchStatement::new( 5, true, None, None );
-    let res3 = MatchStatement::from( ms2, &SyntaxToken::new( SyntaxTokenType::OpenNode, String::from( "(" )));
-    assert_eq!( res3.is_ok(), true );
-    assert_eq!( res3.as_ref().unwrap().labels_complete, true );
-
-    let ms4 = MatchStatement::new( 5, true, None, None );
-    let res4 = MatchStatement::from( ms4, &SyntaxToken::new( SyntaxTokenType::CloseNode, String::from( ")" )));
-    assert_eq!( res4.is_ok(), true );
-    assert_eq!( res4.as_ref().unwrap().is_open, false );
-
-    // -- braces
-    let ms5 = MatchStatement::new( 5, true, None, None );
-    let res5 = MatchStatement::from( ms5, &SyntaxToken::new( SyntaxTokenType::OpenBrace, String::from( "{" )));
-    assert_eq!( res5.is_ok(), true );
-    assert_eq!( res5.as_ref().unwrap().labels_complete, true );
-
-    let ms6 = MatchStatement::new( 5, true, None, None );
-    let res6 = MatchStatement::from( ms6, &SyntaxToken::new( SyntaxTokenType::CloseBrace, String::from( "}" )));
-    assert_eq!( res6.is_ok(), true );
-    assert_eq!( res6.as_ref().unwrap().kvps_complete, true );
-
-    // -- kv str
-    let ms7 = MatchStatement::new( 5, true, None, None );
-    let res7 = MatchStatement::from( ms7, &SyntaxToken::new( SyntaxTokenType::Key, String::from( "key" )));
-    assert_eq!( res7.is_ok(), true );
-    assert_eq!( res7.as_ref().unwrap().kv_str.len(), 1 );
-
-    let res7_1 = MatchStatement::from( res7.unwrap(), &SyntaxToken::new( SyntaxTokenType::StringValue, String::from( "val" )));
-    assert_eq!( res7_1.is_ok(), true );
-    assert_eq!( res7_1.as_ref().unwrap().kv_str.len(), 1 );
-    assert_eq!( res7_1.as_ref().unwrap().kv_str.get( 0 ).unwrap().key, String::from( "key" ));
-    assert_eq!( res7_1.as_ref().unwrap().kv_str.get( 0 ).unwrap().val, Some( String::from( "val" )));
+  #[test]
+  fn test_hop_range () 
+  {
+    assert_eq!( HopRange::parse( "*" ), Ok( HopRange { min: 1, max: None }));
+    assert_eq!( HopRange::parse( "*2" ), Ok( HopRange { min: 2, max: Some( 2 ) }));
+    assert_eq!( HopRange::parse( "*1..3" ), Ok( HopRange { min: 1, max: Some( 3 ) }));
+    assert_eq!( HopRange::parse( "*..3" ), Ok( HopRange { min: 1, max: Some( 3 ) }));
+    assert_eq!( HopRange::parse( "*0.." ), Ok( HopRange { min: 0, max: None }));
+    assert_eq!( HopRange::parse( "*3..1" ), Err( String::from( "Syntax Error: Hop Range `*3..1`" )));
+    assert_eq!( HopRange::parse( "*a" ).is_err(), true );
   }
 }
\ No newline at end of file
@@ -0,0 +1,851 @@
+use std::cmp::Ordering;
+use crate::ast::error::QueryError;
+use crate::parser::literal::parse_literal;
+use crate::cmd::function::ScalarFunction;
+use crate::result_set::Value;
+use crate::tokenize::{ SyntaxToken, SyntaxTokenType };
+
+/* @version 0.3.0 */
+
+/*
+  WHERE n.name STARTS WITH 'Pre' AND NOT ( n.age < 30 OR n.age IS NULL )
+
+  Precedence (loosest first): OR, XOR, AND, NOT, comparison.
+  Evaluation is three-valued, None is unknown (null) and only Some( true ) keeps a row.
+
+  Operands may index and slice lists (n.tags[0], n.tags[1..3], n.tags[-1]) and build them with a list comprehension
+  [x IN n.tags WHERE x STARTS WITH 'a' | x], out of range indexes are null.
+
+  $name parameters are replaced by their values with bind_params() once the query is planned.
+
+  Predicate
+    :: parse()
+    :: parse_next()
+        :: parse_or()
+            :: parse_xor()
+                :: parse_and()
+                    :: parse_not()
+                        :: parse_comparison()
+                            :: parse_operand()
+                                :: parse_primary()
+                                    :: parse_list()
+                                :: parse_index()
+    :: bind_params()
+    :: evaluate()
+        :: compare()
+    :: variables()
+
+  sort_order() orders values of any type for ORDER BY, DISTINCT and min() / max()
+*/
+
+/// Side of a comparison
+#[derive( Debug, Clone, PartialEq )]
+pub enum Operand
+{
+  Variable( String ),
+  Property( String, String ), // ( variable, key )
+  Literal( Value ),
+  List( Vec<Value> ),
+  Function( ScalarFunction, Box<Operand> ), // length(p)
+  Index( Box<Operand>, Box<Operand> ), // list[0], negative indexes count from the end
+  Slice( Box<Operand>, Option<Box<Operand>>, Option<Box<Operand>> ), // list[1..3], either bound may be left out
+  Comprehension( String, Box<Operand>, Option<Box<Predicate>>, Option<Box<Operand>> ), // [x IN list WHERE ... | x.key]
+  Parameter( String ), // $name or $name.key, null until bound
+}
+impl Operand
+{
+  /// Operand made of every token
+  pub fn parse ( tokens: &Vec<SyntaxToken> ) -> Result<Operand, QueryError>
+  {
+    let mut pos = 0;
+    let operand = parse_operand( tokens, &mut pos )?;
+    if pos < tokens.len() { return Err( where_err( tokens, pos )); }
+    Ok( operand )
+  }
+
+  /// Operand starting at pos, pos is left on the first token after it
+  pub fn parse_next ( tokens: &Vec<SyntaxToken>, pos: &mut usize ) -> Result<Operand, QueryError>
+  {
+    parse_operand( tokens, pos )
+  }
+
+  /// Apply the [ index ] and [ slice ] tokens at pos to an operand that has already been parsed
+  pub fn parse_postfix ( base: Operand, tokens: &Vec<SyntaxToken>, pos: &mut usize ) -> Result<Operand, QueryError>
+  {
+    let mut operand = base;
+    while is_token( tokens, *pos, SyntaxTokenType::OpenIndex ) { operand = parse_index( operand, tokens, pos )?; }
+    Ok( operand )
+  }
+
+  /// Value of the operand for a binding, missing variables and properties are null
+  pub fn resolve ( &self, binding: &Vec<( String, Value )> ) -> Value
+  {
+    match self
+    {
+      Operand::Variable( var ) =>
+      {
+        binding.iter().find( | ( v, _ ) | v == var ).map( | ( _, val ) | val.clone() ).unwrap_or( Value::Null )
+      }
+      Operand::Property( var, key ) =>
+      {
+        let bound = binding.iter().find( | ( v, _ ) | v == var ).map( | ( _, val ) | val );
+        match bound
+        {
+          Some( Value::Node( node )) => node.property( key ).map( Value::from ).unwrap_or( Value::Null ),
+          Some( Value::Edge( edge )) => edge.property( key ).map( Value::from ).unwrap_or( Value::Null ),
+          Some( map ) => map.get( key ),
+          _ => Value::Null,
+        }
+      }
+      Operand::Literal( val ) => val.clone(),
+      Operand::List( vals ) => Value::List( vals.clone() ),
+      Operand::Parameter( _ ) => Value::Null,
+      Operand::Function( function, argument ) => function.evaluate( &argument.resolve( binding )),
+      Operand::Index( list, index ) =>
+      {
+        let ( list_val, index_val ) = ( list.resolve( binding ), index.resolve( binding ));
+        if list_val.as_list().is_none() || index_val.as_i64().is_none() { return Value::Null; }
+        let vals = list_val.as_list().unwrap();
+        let pos = list_position( index_val.as_i64().unwrap(), vals.len() );
+        if pos < 0 || pos >= vals.len() as i64 { return Value::Null; }
+        vals[pos as usize].clone()
+      }
+      Operand::Slice( list, start, end ) =>
+      {
+        let list_val = list.resolve( binding );
+        if list_val.as_list().is_none() { return Value::Null; }
+        let vals = list_val.as_list().unwrap();
+
+        // a null bound makes the slice null, a missing one spans to that end of the list
+        let bound = | operand: &Option<Box<Operand>>, default: i64 | -> Option<i64>
+        {
+          if operand.is_none() { return Some( default ); }
+          let pos = operand.as_ref().unwrap().resolve( binding ).as_i64()?;
+          Some( list_position( pos, vals.len() ).clamp( 0, vals.len() as i64 ))
+        };
+        let ( from, to ) = ( bound( start, 0 ), bound( end, vals.len() as i64 ));
+        if from.is_none() || to.is_none() { return Value::Null; }
+        if from.unwrap() >= to.unwrap() { return Value::List( Vec::new() ); }
+        Value::List( vals[from.unwrap() as usize..to.unwrap() as usize].to_vec() )
+      }
+      Operand::Comprehension( variable, list, predicate, projection ) =>
+      {
+        let list_val = list.resolve( binding );
+        if list_val.as_list().is_none() { return Value::Null; }
+
+        let mut ret = Vec::new();
+        for val in list_val.as_list().unwrap().iter()
+        {
+          // the comprehension variable hides a bound variable of the same name
+          let mut inner: Vec<( String, Value )> = binding.iter().filter( | ( v, _ ) | v != variable ).cloned().collect();
+          inner.push(( variable.clone(), val.clone() ));
+          if predicate.is_some() && predicate.as_ref().unwrap().evaluate( &inner ) != Some( true ) { continue; }
+          if projection.is_some() { ret.push( projection.as_ref().unwrap().resolve( &inner )); }
+          else { ret.push( val.clone() ); }
+        }
+        Value::List( ret )
+      }
+    }
+  }
+
+  /// n or n.key
+  pub fn from_label ( label: &str ) -> Option<Operand>
+  {
+    let mut split = label.splitn( 2, '.' );
+    let variable = split.next().unwrap_or( "" ).to_string();
+    let key = split.next();
+    if variable.len() == 0 || key == Some( "" ) { return None; }
+    if key.is_some() { return Some( Operand::Property( variable, key.unwrap().to_string() )); }
+    Some( Operand::Variable( variable ))
+  }
+
+  pub fn variable ( &self ) -> Option<&String>
+  {
+    match self
+    {
+      Operand::Variable( var ) | Operand::Property( var, _ ) => Some( var ),
+      Operand::Function( _, argument ) => argument.variable(),
+      Operand::Index( list, _ ) | Operand::Slice( list, _, _ ) | Operand::Comprehension( _, list, _, _ ) => list.variable(),
+      _ => None,
+    }
+  }
+
+  /// Every variable the operand refers to, a comprehension variable is local to it
+  pub fn variables ( &self ) -> Vec<String>
+  {
+    let mut ret = Vec::new();
+    match self
+    {
+      Operand::Variable( var ) | Operand::Property( var, _ ) => { ret.push( var.clone() ); }
+      Operand::Function( _, argument ) => { ret.append( &mut argument.variables() ); }
+      Operand::Index( list, index ) =>
+      {
+        ret.append( &mut list.variables() );
+        ret.append( &mut index.variables() );
+      }
+      Operand::Slice( list, start, end ) =>
+      {
+        ret.append( &mut list.variables() );
+        for bound in [ start, end ].into_iter().flatten() { ret.append( &mut bound.variables() ); }
+      }
+      Operand::Comprehension( variable, list, predicate, projection ) =>
+      {
+        let mut inner = Vec::new();
+        if predicate.is_some() { inner.append( &mut predicate.as_ref().unwrap().variables() ); }
+        if projection.is_some() { inner.append( &mut projection.as_ref().unwrap().variables() ); }
+        ret.append( &mut list.variables() );
+        ret.extend( inner.into_iter().filter( | v | v != variable ));
+      }
+      Operand::Literal( _ ) | Operand::List( _ ) | Operand::Parameter( _ ) => {}
+    }
+    ret
+  }
+
+  /// Replace every parameter with its value
+  pub fn bind_params ( &mut self, params: &Vec<( String, Value )> ) -> Result<bool, String>
+  {
+    match self
+    {
+      Operand::Parameter( name ) => { *self = Operand::Literal( param_value( name, params )? ); }
+      Operand::Function( _, argument ) => { argument.bind_params( params )?; }
+      Operand::Index( list, index ) =>
+      {
+        list.bind_params( params )?;
+        index.bind_params( params )?;
+      }
+      Operand::Slice( list, start, end ) =>
+      {
+        list.bind_params( params )?;
+        for bound in [ start, end ].into_iter().flatten() { bound.bind_params( params )?; }
+      }
+      Operand::Comprehension( _, list, predicate, projection ) =>
+      {
+        list.bind_params( params )?;
+        if predicate.is_some() { predicate.as_mut().unwrap().bind_params( params )?; }
+        if projection.is_some() { projection.as_mut().unwrap().bind_params( params )?; }
+      }
+      _ => {}
+    }
+    Ok( true )
+  }
+}
+
+/// Value of $name or $name.key, the key of a map parameter
+pub fn param_value ( name: &str, params: &Vec<( String, Value )> ) -> Result<Value, String>
+{
+  let mut split = name.splitn( 2, '.' );
+  let param = split.next().unwrap_or( "" );
+  let found = params.iter().find( | ( k, _ ) | k == param );
+  if found.is_none() { return Err( format!( "Error: Parameter `${}` not defined.", param )); }
+
+  let value = &found.unwrap().1;
+  let key = split.next();
+  if key.is_some() { return Ok( value.get( key.unwrap() )); }
+  Ok( value.clone() )
+}
+
+/// Position of a list index, negative indexes count from the end
+fn list_position ( index: i64, len: usize ) -> i64
+{
+  if index < 0 { return len as i64 + index; }
+  index
+}
+
+#[derive( Debug, Clone, PartialEq )]
+pub enum CompareOp { Eq, Ne, Lt, Le, Gt, Ge, StartsWith, EndsWith, Contains, In }
+
+/// WHERE expression tree
+#[derive( Debug, Clone, PartialEq )]
+pub enum Predicate
+{
+  Compare( Operand, CompareOp, Operand ),
+  IsNull( Operand ),
+  IsNotNull( Operand ),
+  Test( Operand ), // WHERE n.admin
+  Not( Box<Predicate> ),
+  And( Box<Predicate>, Box<Predicate> ),
+  Or( Box<Predicate>, Box<Predicate> ),
+  Xor( Box<Predicate>, Box<Predicate> ),
+}
+
+impl Predicate
+{
+  /// Build a predicate from the tokens following WHERE
+  pub fn parse ( tokens: &Vec<SyntaxToken> ) -> Result<Predicate, QueryError>
+  {
+    if tokens.len() == 0 { return Err( where_err( tokens, 0 )); }
+    let mut pos = 0;
+    let predicate = Predicate::parse_or( tokens, &mut pos )?;
+    if pos < tokens.len() { return Err( where_err( tokens, pos )); }
+    Ok( predicate )
+  }
+
+  /// Predicate starting at pos, pos is left on the first token after it
+  pub fn parse_next ( tokens: &Vec<SyntaxToken>, pos: &mut usize ) -> Result<Predicate, QueryError>
+  {
+    if *pos >= tokens.len() { return Err( where_err( tokens, *pos )); }
+    Predicate::parse_or( tokens, pos )
+  }
+
+  fn parse_or ( tokens: &Vec<SyntaxToken>, pos: &mut usize ) -> Result<Predicate, QueryError>
+  {
+    let mut left = Predicate::parse_xor( tokens, pos )?;
+    // | ends the WHERE of a list comprehension
+    while is_token( tokens, *pos, SyntaxTokenType::KeywordOr ) && is_val( tokens, *pos, SyntaxTokenType::KeywordOr, "|" ) == false
+    {
+      *pos += 1;
+      left = Predicate::Or( Box::new( left ), Box::new( Predicate::parse_xor( tokens, pos )? ));
+    }
+    Ok( left )
+  }
+
+  fn parse_xor ( tokens: &Vec<SyntaxToken>, pos: &mut usize ) -> Result<Predicate, QueryError>
+  {
+    let mut left = Predicate::parse_and( tokens, pos )?;
+    while is_token( tokens, *pos, SyntaxTokenType::KeywordXor )
+    {
+      *pos += 1;
+      left = Predicate::Xor( Box::new( left ), Box::new( Predicate::parse_and( tokens, pos )? ));
+    }
+    Ok( left )
+  }
+
+  fn parse_and ( tokens: &Vec<SyntaxToken>, pos: &mut usize ) -> Result<Predicate, QueryError>
+  {
+    let mut left = Predicate::parse_not( tokens, pos )?;
+    while is_token( tokens, *pos, SyntaxTokenType::KeywordAnd )
+    {
+      *pos += 1;
+      left = Predicate::And( Box::new( left ), Box::new( Predicate::parse_not( tokens, pos )? ));
+    }
+    Ok( left )
+  }
+
+  fn parse_not ( tokens: &Vec<SyntaxToken>, pos: &mut usize ) -> Result<Predicate, QueryError>
+  {
+    if is_token( tokens, *pos, SyntaxTokenType::KeywordNot )
+    {
+      *pos += 1;
+      return Ok( Predicate::Not( Box::new( Predicate::parse_not( tokens, pos )? )));
+    }
+
+    if is_token( tokens, *pos, SyntaxTokenType::OpenNode )
+    {
+      *pos += 1;
+      let inner = Predicate::parse_or( tokens, pos )?;
+      if is_token( tokens, *pos, SyntaxTokenType::CloseNode ) == false { return Err( where_err( tokens, *pos )); }
+      *pos += 1;
+      return Ok( inner );
+    }
+    Predicate::parse_comparison( tokens, pos )
+  }
+
+  fn parse_comparison ( tokens: &Vec<SyntaxToken>, pos: &mut usize ) -> Result<Predicate, QueryError>
+  {
+    let left = parse_operand( tokens, pos )?;
+    let token_opt = tokens.get( *pos );
+    if token_opt.is_none() { return Ok( Predicate::Test( left )); }
+
+    let token = token_opt.unwrap();
+    let op = match ( &token.token_type, token.val.as_str() )
+    {
+      ( SyntaxTokenType::Equals, _ ) => CompareOp::Eq,
+      ( SyntaxTokenType::EdgeDirection, "<" ) =>
+      {
+        if is_token( tokens, *pos + 1, SyntaxTokenType::Equals ) { *pos += 1; CompareOp::Le }
+        else if is_val( tokens, *pos + 1, SyntaxTokenType::EdgeDirection, ">" ) { *pos += 1; CompareOp::Ne }
+        else { CompareOp::Lt }
+      }
+      ( SyntaxTokenType::EdgeDirection, ">" ) =>
+      {
+        if is_token( tokens, *pos + 1, SyntaxTokenType::Equals ) { *pos += 1; CompareOp::Ge }
+        else { CompareOp::Gt }
+      }
+      ( SyntaxTokenType::KeywordStarts, _ ) | ( SyntaxTokenType::KeywordEnds, _ ) =>
+      {
+        if is_token( tokens, *pos + 1, SyntaxTokenType::KeywordWith ) == false { return Err( where_err( tokens, *pos + 1 )); }
+        *pos += 1;
+        if token.token_type == SyntaxTokenType::KeywordStarts { CompareOp::StartsWith } else { CompareOp::EndsWith }
+      }
+      ( SyntaxTokenType::KeywordContains, _ ) => CompareOp::Contains,
+      ( SyntaxTokenType::KeywordIn, _ ) => CompareOp::In,
+      ( SyntaxTokenType::KeywordIs, _ ) =>
+      {
+        let negated = is_token( tokens, *pos + 1, SyntaxTokenType::KeywordNot );
+        let null_pos = if negated { *pos + 2 } else { *pos + 1 };
+        if is_token( tokens, null_pos, SyntaxTokenType::KeywordNull ) == false { return Err( where_err( tokens, null_pos )); }
+        *pos = null_pos + 1;
+        if negated { return Ok( Predicate::IsNotNull( left )); }
+        return Ok( Predicate::IsNull( left ));
+      }
+      _ => { return Ok( Predicate::Test( left )); }
+    };
+    *pos += 1;
+
+    let right_pos = *pos;
+    let right = parse_operand( tokens, pos )?;
+    if op == CompareOp::In && matches!( right, Operand::Literal( _ )) 
+    {
+      let mut err = QueryError::at_token( String::from( "Syntax Error: In requires a list" ), tokens, right_pos );
+      err.expected = Some( String::from( "a list" ));
+      return Err( err );
+    }
+    Ok( Predicate::Compare( left, op, right ))
+  }
+
+  /// Some( true ) when the binding satisfies the predicate, None when the outcome is unknown (null)
+  pub fn evaluate ( &self, binding: &Vec<( String, Value )> ) -> Option<bool>
+  {
+    match self
+    {
+      Predicate::Compare( left, op, right ) => compare( &left.resolve( binding ), op, &right.resolve( binding )),
+      Predicate::IsNull( operand ) => Some( operand.resolve( binding ).is_null() ),
+      Predicate::IsNotNull( operand ) => Some( operand.resolve( binding ).is_null() == false ),
+      Predicate::Test( operand ) => operand.resolve( binding ).as_bool(),
+      Predicate::Not( inner ) => inner.evaluate( binding ).map( | b | b == false ),
+      Predicate::And( a, b ) =>
+      {
+        match ( a.evaluate( binding ), b.evaluate( binding ))
+        {
+          ( Some( false ), _ ) | ( _, Some( false )) => Some( false ),
+          ( Some( true ), Some( true )) => Some( true ),
+          _ => None,
+        }
+      }
+      Predicate::Or( a, b ) =>
+      {
+        match ( a.evaluate( binding ), b.evaluate( binding ))
+        {
+          ( Some( true ), _ ) | ( _, Some( true )) => Some( true ),
+          ( Some( false ), Some( false )) => Some( false ),
+          _ => None,
+        }
+      }
+      Predicate::Xor( a, b ) =>
+      {
+        let ( a_res, b_res ) = ( a.evaluate( binding ), b.evaluate( binding ));
+        if a_res.is_none() || b_res.is_none() { return None; }
+        Some( a_res.unwrap() != b_res.unwrap() )
+      }
+    }
+  }
+
+  /// Every variable the predicate refers to
+  pub fn variables ( &self ) -> Vec<String>
+  {
+    let mut ret = Vec::new();
+    match self
+    {
+      Predicate::Compare( left, _, right ) =>
+      {
+        ret.append( &mut left.variables() );
+        ret.append( &mut right.variables() );
+      }
+      Predicate::IsNull( operand ) | Predicate::IsNotNull( operand ) | Predicate::Test( operand ) =>
+      {
+        ret.append( &mut operand.variables() );
+      }
+      Predicate::Not( inner ) => { ret.append( &mut inner.variables() ); }
+      Predicate::And( a, b ) | Predicate::Or( a, b ) | Predicate::Xor( a, b ) =>
+      {
+        ret.append( &mut a.variables() );
+        ret.append( &mut b.variables() );
+      }
+    }
+    ret.dedup();
+    ret
+  }
+
+  /// Replace every parameter of the predicate with its value
+  pub fn bind_params ( &mut self, params: &Vec<( String, Value )> ) -> Result<bool, String>
+  {
+    match self
+    {
+      Predicate::Compare( left, _, right ) =>
+      {
+        left.bind_params( params )?;
+        right.bind_params( params )?;
+      }
+      Predicate::IsNull( operand ) | Predicate::IsNotNull( operand ) | Predicate::Test( operand ) =>
+      {
+        operand.bind_params( params )?;
+      }
+      Predicate::Not( inner ) => { inner.bind_params( params )?; }
+      Predicate::And( a, b ) | Predicate::Or( a, b ) | Predicate::Xor( a, b ) =>
+      {
+        a.bind_params( params )?;
+        b.bind_params( params )?;
+      }
+    }
+    Ok( true )
+  }
+}
+
+/// Compare two values, None when either side is null or the types cannot be compared
+pub fn compare ( left: &Value, op: &CompareOp, right: &Value ) -> Option<bool>
+{
+  if left.is_null() || right.is_null() { return None; }
+  match op
+  {
+    CompareOp::Eq => left.equals( right ),
+    CompareOp::Ne => left.equals( right ).map( | b | b == false ),
+    CompareOp::Lt => order( left, right ).map( | o | o == Ordering::Less ),
+    CompareOp::Le => order( left, right ).map( | o | o != Ordering::Greater ),
+    CompareOp::Gt => order( left, right ).map( | o | o == Ordering::Greater ),
+    CompareOp::Ge => order( left, right ).map( | o | o != Ordering::Less ),
+    CompareOp::StartsWith => Some( left.as_str()?.starts_with( right.as_str()? )),
+    CompareOp::EndsWith => Some( left.as_str()?.ends_with( right.as_str()? )),
+    CompareOp::Contains => Some( left.as_str()?.contains( right.as_str()? )),
+    CompareOp::In =>
+    {
+      let list = right.as_list()?;
+      let mut unknown = false;
+      for val in list.iter()
+      {
+        match left.equals( val )
+        {
+          Some( true ) => { return Some( true ); }
+          None => { unknown = true; }
+          _ => {}
+        }
+      }
+      if unknown { return None; }
+      Some( false )
+    }
+  }
+}
+
+/// Ordering of numbers, strings and booleans, None across types
+pub fn order ( left: &Value, right: &Value ) -> Option<Ordering>
+{
+  let ( int_a, int_b ) = ( left.as_i128(), right.as_i128() );
+  if int_a.is_some() && int_b.is_some() { return Some( int_a.unwrap().cmp( &int_b.unwrap() )); }
+  if left.is_numeric() && right.is_numeric() { return left.as_f64().unwrap().partial_cmp( &right.as_f64().unwrap() ); }
+  if left.as_str().is_some() && right.as_str().is_some() { return Some( left.as_str().unwrap().cmp( right.as_str().unwrap() )); }
+  if left.as_bool().is_some() && right.as_bool().is_some() { return Some( left.as_bool().unwrap().cmp( &right.as_bool().unwrap() )); }
+  None
+}
+
+/// Total order of values: maps, nodes, relationships, lists, paths, strings, booleans, numbers, then null
+pub fn sort_order ( left: &Value, right: &Value ) -> Ordering
+{
+  let ( rank_a, rank_b ) = ( type_rank( left ), type_rank( right ));
+  if rank_a != rank_b { return rank_a.cmp( &rank_b ); }
+
+  match ( left, right )
+  {
+    ( Value::Node( a ), Value::Node( b )) => a.id.cmp( &b.id ),
+    ( Value::Edge( a ), Value::Edge( b )) => a.id.cmp( &b.id ),
+    ( Value::Path( a ), Value::Path( b )) => a.len().cmp( &b.len() ),
+    ( Value::List( a ), Value::List( b )) =>
+    {
+      for ( x, y ) in a.iter().zip( b.iter() )
+      {
+        let ord = sort_order( x, y );
+        if ord != Ordering::Equal { return ord; }
+      }
+      a.len().cmp( &b.len() )
+    }
+    // NaN is not ordered against other numbers
+    _ => order( left, right ).unwrap_or( Ordering::Equal ),
+  }
+}
+
+fn type_rank ( val: &Value ) -> u8
+{
+  match val
+  {
+    Value::Map( _ ) => 0,
+    Value::Node( _ ) => 1,
+    Value::Edge( _ ) => 2,
+    Value::List( _ ) => 3,
+    Value::Path( _ ) => 4,
+    Value::String( _ ) => 5,
+    Value::Boolean( _ ) => 6,
+    Value::Null => 8,
+    _ => 7,
+  }
+}
+
+/// Operand followed by any number of [ index ] or [ slice ]
+fn parse_operand ( tokens: &Vec<SyntaxToken>, pos: &mut usize ) -> Result<Operand, QueryError>
+{
+  let mut operand = parse_primary( tokens, pos )?;
+  while is_token( tokens, *pos, SyntaxTokenType::OpenIndex ) { operand = parse_index( operand, tokens, pos )?; }
+  Ok( operand )
+}
+
+/// [ index ] or [ start..end ] of a list, starting at the [
+fn parse_index ( list: Operand, tokens: &Vec<SyntaxToken>, pos: &mut usize ) -> Result<Operand, QueryError>
+{
+  *pos += 1;
+  let mut start = None;
+  if is_token( tokens, *pos, SyntaxTokenType::Range ) == false { start = Some( Box::new( parse_operand( tokens, pos )? )); }
+
+  if is_token( tokens, *pos, SyntaxTokenType::Range )
+  {
+    *pos += 1;
+    let mut end = None;
+    if is_token( tokens, *pos, SyntaxTokenType::CloseBracket ) == false { end = Some( Box::new( parse_operand( tokens, pos )? )); }
+    if is_token( tokens, *pos, SyntaxTokenType::CloseBracket ) == false { return Err( where_err( tokens, *pos )); }
+    *pos += 1;
+    return Ok( Operand::Slice( Box::new( list ), start, end ));
+  }
+
+  if is_token( tokens, *pos, SyntaxTokenType::CloseBracket ) == false { return Err( where_err( tokens, *pos )); }
+  *pos += 1;
+  Ok( Operand::Index( Box::new( list ), start.unwrap() ))
+}
+
+/// [ literal, ... ] or [ x IN list WHERE predicate | projection ], starting after the [
+fn parse_list ( tokens: &Vec<SyntaxToken>, pos: &mut usize ) -> Result<Operand, QueryError>
+{
+  if is_token( tokens, *pos, SyntaxTokenType::Label ) && is_token( tokens, *pos + 1, SyntaxTokenType::KeywordIn )
+  {
+    let variable = tokens[*pos].val.clone();
+    if matches!( Operand::from_label( &variable ), Some( Operand::Variable( _ ))) == false { return Err( where_err( tokens, *pos )); }
+    *pos += 2;
+    let list = parse_operand( tokens, pos )?;
+
+    let mut predicate = None;
+    if is_token( tokens, *pos, SyntaxTokenType::KeywordWhere )
+    {
+      *pos += 1;
+      predicate = Some( Box::new( Predicate::parse_or( tokens, pos )? ));
+    }
+    let mut projection = None;
+    if is_val( tokens, *pos, SyntaxTokenType::KeywordOr, "|" )
+    {
+      *pos += 1;
+      projection = Some( Box::new( parse_operand( tokens, pos )? ));
+    }
+    if is_token( tokens, *pos, SyntaxTokenType::CloseBracket ) == false { return Err( where_err( tokens, *pos )); }
+    *pos += 1;
+    return Ok( Operand::Comprehension( variable, Box::new( list ), predicate, projection ));
+  }
+
+  let mut vals = Vec::new();
+  while is_token( tokens, *pos, SyntaxTokenType::CloseBracket ) == false
+  {
+    let start = *pos;
+    match parse_operand( tokens, pos )?
+    {
+      Operand::Literal( val ) => { vals.push( val ); }
+      Operand::List( list ) => { vals.push( Value::List( list )); }
+      _ => 
+      { 
+        let mut err = QueryError::at_token( String::from( "Syntax Error: List values must be literals" ), tokens, start );
+        err.expected = Some( String::from( "a literal" ));
+        return Err( err ); 
+      }
+    }
+  }
+  *pos += 1;
+  Ok( Operand::List( vals ))
+}
+
+/// n, n.key, a literal, -number, null, a [ list ] or a function of an operand
+fn parse_primary ( tokens: &Vec<SyntaxToken>, pos: &mut usize ) -> Result<Operand, QueryError>
+{
+  let token_opt = tokens.get( *pos );
+  if token_opt.is_none() { return Err( where_err( tokens, *pos )); }
+  let token = token_opt.unwrap();
+  *pos += 1;
+
+  match token.token_type
+  {
+    SyntaxTokenType::Label =>
+    {
+      // length(p)
+      let function = ScalarFunction::from_name( &token.val );
+      if function.is_some() && is_token( tokens, *pos, SyntaxTokenType::OpenNode )
+      {
+        *pos += 1;
+        let argument = parse_operand( tokens, pos )?;
+        if is_token( tokens, *pos, SyntaxTokenType::CloseNode ) == false { return Err( where_err( tokens, *pos )); }
+        *pos += 1;
+        return Ok( Operand::Function( function.unwrap(), Box::new( argument )));
+      }
+
+      let operand = Operand::from_label( &token.val );
+      if operand.is_none() { return Err( where_err( tokens, *pos - 1 )); }
+      Ok( operand.unwrap() )
+    }
+    SyntaxTokenType::KeywordNull => Ok( Operand::Literal( Value::Null )),
+    SyntaxTokenType::Parameter => Ok( Operand::Parameter( token.val.clone() )),
+    SyntaxTokenType::StringValue | SyntaxTokenType::Value => Ok( Operand::Literal( literal_value( token )? )),
+    SyntaxTokenType::EdgeDirection =>
+    {
+      // negative number
+      if token.val == "-" && is_token( tokens, *pos, SyntaxTokenType::Value )
+      {
+        let negated = SyntaxToken::negated( token, &tokens[*pos] );
+        *pos += 1;
+        return Ok( Operand::Literal( literal_value( &negated )? ));
+      }
+      Err( where_err( tokens, *pos - 1 ))
+    }
+    SyntaxTokenType::OpenBracket => parse_list( tokens, pos ),
+    _ => Err( where_err( tokens, *pos - 1 ))
+  }
+}
+
+/// Value of a string or number token, errors point at the token
+fn literal_value ( token: &SyntaxToken ) -> Result<Value, QueryError>
+{
+  if token.token_type == SyntaxTokenType::StringValue { return Ok( Value::from( token.val.clone() )); }
+  parse_literal( &token.val ).unwrap_or( Err( format!( "Syntax Error: Where `{}`", token.val )))
+    .map_err( | message | QueryError::at( message, token.span, Some( format!( "`{}`", token.val ))))
+}
+
+fn is_token ( tokens: &Vec<SyntaxToken>, pos: usize, token_type: SyntaxTokenType ) -> bool
+{
+  tokens.get( pos ).map( | t | t.token_type == token_type ).unwrap_or( false )
+}
+
+fn is_val ( tokens: &Vec<SyntaxToken>, pos: usize, token_type: SyntaxTokenType, val: &str ) -> bool
+{
+  tokens.get( pos ).map( | t | t.token_type == token_type && t.val == val ).unwrap_or( false )
+}
+
+/// Syntax Error: Where `token` at the token, or at the end of the query
+fn where_err ( tokens: &Vec<SyntaxToken>, pos: usize ) -> QueryError
+{
+  let message = match tokens.get( pos )
+  {
+    Some( token ) => format!( "Syntax Error: Where `{}`", token.val ),
+    None => String::from( "Syntax Error: Where" ),
+  };
+  QueryError::at_token( message, tokens, pos )
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+  use crate::common::{ NEProperty, Node };
+  use crate::parser::parse_syntax;
+
+  fn parse ( query: &str ) -> Result<Predicate, QueryError> { Predicate::parse( &parse_syntax( query )) }
+
+  fn binding () -> Vec<( String, Value )>
+  {
+    let node = Node::new( String::from( "1" ), Some( String::from( "Station" )), vec![
+      NEProperty::KvpString(( String::from( "name" ), String::from( "Preston Park" ))),
+      NEProperty::KvpI64(( String::from( "zone" ), 2 )),
+      NEProperty::KvpBoolean(( String::from( "staffed" ), true )),
+    ]);
+    vec![ ( String::from( "n" ), Value::Node( node )) ]
+  }
+
+  fn eval ( query: &str ) -> Option<bool> { parse( query ).unwrap().evaluate( &binding() ) }
+
+  #[test]
+  fn test_sort_order ()
+  {
+    assert_eq!( sort_order( &Value::from( 1 ), &Value::from( 1.0 )), Ordering::Equal );
+    assert_eq!( sort_order( &Value::from( 2u8 ), &Value::from( 10i64 )), Ordering::Less );
+    assert_eq!( sort_order( &Value::from( "z" ), &Value::from( 1 )), Ordering::Less );
+    assert_eq!( sort_order( &Value::from( 1 ), &Value::Null ), Ordering::Less );
+    assert_eq!( sort_order( &Value::Null, &Value::Null ), Ordering::Equal );
+    assert_eq!( sort_order( &Value::from( vec![ Value::from( 1 ) ] ), &Value::from( vec![ Value::from( 1 ), Value::from( 0 ) ] )),
+      Ordering::Less );
+  }
+
+  #[test]
+  fn test_parse_predicate ()
+  {
+    assert_eq!( parse( "n.zone >= 2" ), Ok( Predicate::Compare(
+      Operand::Property( String::from( "n" ), String::from( "zone" )), CompareOp::Ge, Operand::Literal( Value::I64( 2 )))));
+    assert_eq!( parse( "n.zone <> -1" ), Ok( Predicate::Compare(
+      Operand::Property( String::from( "n" ), String::from( "zone" )), CompareOp::Ne, Operand::Literal( Value::I64( -1 )))));
+    assert_eq!( parse( "n.name IS NOT NULL" ),
+      Ok( Predicate::IsNotNull( Operand::Property( String::from( "n" ), String::from( "name" )))));
+
+    // AND binds tighter than OR
+    let res = parse( "n.a = 1 OR n.b = 2 AND n.c = 3" ).unwrap();
+    assert_eq!( matches!( res, Predicate::Or( _, _ )), true );
+    assert_eq!( res.variables(), vec![ String::from( "n" ) ] );
+
+    assert_eq!( parse( "" ).is_err(), true );
+    assert_eq!( parse( "n.name STARTS 'P'" ).is_err(), true );
+    assert_eq!( parse( "n.zone IN 2" ).is_err(), true );
+    assert_eq!( parse( "(n.zone = 2" ).is_err(), true );
+    assert_eq!( parse( "n.zone = 2 n.name" ).is_err(), true );
+  }
+
+  #[test]
+  fn test_evaluate_predicate ()
+  {
+    assert_eq!( eval( "n.name STARTS WITH 'Preston'" ), Some( true ));
+    assert_eq!( eval( "n.name ENDS WITH 'Preston'" ), Some( false ));
+    assert_eq!( eval( "n.name CONTAINS 'ton P'" ), Some( true ));
+    assert_eq!( eval( "n.zone IN [1, 2, 3]" ), Some( true ));
+    assert_eq!( eval( "n.zone IN ['2']" ), Some( false ));
+    assert_eq!( eval( "n.zone < 2.5 AND n.zone>1" ), Some( true ));
+    assert_eq!( eval( "n.zone <= 1 XOR n.staffed" ), Some( true ));
+    assert_eq!( eval( "NOT (n.zone = 2 OR n.zone = 3)" ), Some( false ));
+    assert_eq!( eval( "n.name < 'Q'" ), Some( true ));
+    assert_eq!( eval( "n.name > 2" ), None );
+
+    // nulls are unknown, IS NULL is not
+    assert_eq!( eval( "n.missing = 1" ), None );
+    assert_eq!( eval( "NOT n.missing = 1" ), None );
+    assert_eq!( eval( "n.missing = 1 OR n.zone = 2" ), Some( true ));
+    assert_eq!( eval( "n.missing = 1 AND n.zone = 3" ), Some( false ));
+    assert_eq!( eval( "n.missing IS NULL" ), Some( true ));
+    assert_eq!( eval( "m IS NULL" ), Some( true ));
+  }
+
+  #[test]
+  fn test_resolve_list_operand ()
+  {
+    let list = | values: Vec<i64> | Value::List( values.into_iter().map( Value::from ).collect() );
+    let mut row = binding();
+    row.push(( String::from( "xs" ), list( vec![ 1, 2, 3, 4 ] )));
+    let resolve = | query: &str | Operand::parse( &parse_syntax( query )).unwrap().resolve( &row );
+
+    assert_eq!( resolve( "[1, 'a', [2]]" ), Value::List( vec![ Value::I64( 1 ), Value::from( "a" ), list( vec![ 2 ] ) ] ));
+    assert_eq!( resolve( "xs[0]" ), Value::I64( 1 ));
+    assert_eq!( resolve( "xs[-1]" ), Value::I64( 4 ));
+    assert_eq!( resolve( "xs[9]" ), Value::Null );
+    assert_eq!( resolve( "xs[1..3]" ), list( vec![ 2, 3 ] ));
+    assert_eq!( resolve( "xs[..2]" ), list( vec![ 1, 2 ] ));
+    assert_eq!( resolve( "xs[-2..]" ), list( vec![ 3, 4 ] ));
+    assert_eq!( resolve( "xs[3..1]" ), list( vec![] ));
+    assert_eq!( resolve( "[x IN xs WHERE x > 2]" ), list( vec![ 3, 4 ] ));
+    assert_eq!( resolve( "[x IN xs | n.zone]" ), list( vec![ 2, 2, 2, 2 ] ));
+    assert_eq!( resolve( "[x IN xs WHERE x <> 2 | x][1]" ), Value::I64( 3 ));
+
+    // the comprehension variable is not a variable of the query
+    assert_eq!( Operand::parse( &parse_syntax( "[x IN xs WHERE x > n.zone]" )).unwrap().variables(), 
+      vec![ String::from( "xs" ), String::from( "n" ) ] );
+    assert_eq!( Operand::parse( &parse_syntax( "[1, 2" )).is_err(), true );
+    assert_eq!( Operand::parse( &parse_syntax( "[x IN xs WHERE]" )).is_err(), true );
+  }
+
+  #[test]
+  fn test_bind_params ()
+  {
+    let params = vec![ 
+      ( String::from( "zone" ), Value::U8( 2 )),
+      ( String::from( "names" ), Value::List( vec![ Value::from( "Preston Park" ), Value::from( "Hove" ) ] )),
+      ( String::from( "filter" ), Value::Map( vec![ ( String::from( "staffed" ), Value::Boolean( true )) ] )),
+    ];
+    let bound = | query: &str | -> Result<Option<bool>, String> 
+    {
+      let mut predicate = parse( query ).unwrap();
+      predicate.bind_params( &params )?;
+      Ok( predicate.evaluate( &binding() ))
+    };
+    assert_eq!( bound( "n.zone = $zone" ), Ok( Some( true )));
+    assert_eq!( bound( "n.name IN $names AND n.staffed = $filter.staffed" ), Ok( Some( true )));
+    assert_eq!( bound( "n.zone = $filter.zone" ), Ok( None ));
+    assert_eq!( bound( "n.zone = $missing" ), Err( String::from( "Error: Parameter `$missing` not defined." )));
+
+    // unbound parameters resolve to null
+    assert_eq!( parse( "n.zone = $zone" ).unwrap().evaluate( &binding() ), None );
+    assert_eq!( param_value( "filter", &params ).unwrap().get( "staffed" ), Value::Boolean( true ));
+    assert_eq!( param_value( "zone.x", &params ), Ok( Value::Null ));
+  }
+}
@@ -1,7 +1,10 @@
 use std::fmt::{ Display, Formatter, Result };
+use crate::ast::error::QueryError;
 use crate::datagramv2::internal_grams::{ Label, UUID };
 use crate::utils::{ parse_padded_str };
-use crate::cmd::{ CreateStatement, EdgeStatement, FromClause, NodeRefStatement, NodeStatement, ReadClause, ReturnClause };
+use crate::cmd::{ 
+  CreateStatement, DeleteStatement, EdgeStatement, FromClause, NodeRefStatement, NodeStatement, QueryStage, ReadClause, RemoveStatement, 
+  ReturnClause, SetStatement };
 
 #[derive( Debug )]
 pub struct Transaction
@@ -10,14 +13,20 @@ pub struct Transaction
   pub db_nickname: Label,
   pub query_order: u16,
   pub err_state: Option<String>,
+  pub error: Option<QueryError>, // err_state with where it is in the query, render() shows it
 
   pub create_statement: Option<CreateStatement>,
 
   pub node_statements: Vec<NodeStatement>,
   pub node_ref_statements: Vec<NodeRefStatement>,
   pub edge_statements: Vec<EdgeStatement>,
+  pub set_statements: Vec<SetStatement>,
+  pub remove_statements: Vec<RemoveStatement>,
+  pub delete_statement: Option<DeleteStatement>,
+  pub merge: bool, // read_clause is a MERGE pattern, created where it does not match
 
-  pub read_clause: Option<ReadClause>,
+  pub read_clause: Option<ReadClause>, // first stage of the query
+  pub stages: Vec<QueryStage>, // WITH and the stages after it, in query order
   pub from_clause: Option<FromClause>,
   pub return_clause: Option<ReturnClause>,
 }
@@ -32,21 +41,57 @@ impl Transaction
       db_nickname: nickname,
       query_order: query_order,
       err_state: None,
+      error: None,
 
       create_statement: None,
 
       node_statements: Vec::new(),
       node_ref_statements: Vec::new(),
       edge_statements: Vec::new(),
+      set_statements: Vec::new(),
+      remove_statements: Vec::new(),
+      delete_statement: None,
+      merge: false,
 
       read_clause: None,
+      stages: Vec::new(),
       from_clause: None,
       return_clause: None,
     }
   }
 
   
-  pub fn has_writes ( &self ) -> bool { self.create_statement.is_some() }
+  pub fn has_writes ( &self ) -> bool 
+  { 
+    self.create_statement.is_some() || self.set_statements.len() > 0 || self.remove_statements.len() > 0 || 
+      self.delete_statement.is_some() || self.merge 
+  }
+
+  /// Variables in scope after the last stage, WITH keeps only its columns
+  pub fn variables ( &self ) -> Vec<String> 
+  {
+    let mut variables: Vec<String> = Vec::new();
+    if self.read_clause.is_some() { variables = self.read_clause.as_ref().unwrap().variables(); }
+    for stage in self.stages.iter() 
+    {
+      match stage 
+      {
+        QueryStage::Read( read_clause ) => 
+        { 
+          for variable in read_clause.variables().into_iter() 
+          {
+            if variables.contains( &variable ) == false { variables.push( variable ); }
+          }
+        }
+        QueryStage::With( with_clause ) => { variables = with_clause.columns(); }
+        QueryStage::Unwind( unwind_clause ) => 
+        { 
+          if variables.contains( &unwind_clause.variable ) == false { variables.push( unwind_clause.variable.clone() ); }
+        }
+      }
+    }
+    variables
+  }
 
   
   pub fn next_node_statement ( &self, query_order: u16 ) -> Option<&NodeStatement> 
@@ -138,6 +183,8 @@ impl Display for Transaction
       let _ = write!( f, "Read Clause {:?} \n", self.read_clause );
     }
     else { let _ = write!( f, "Read Clause {:?} \n", self.read_clause ); }
+    let _ = write!( f, "Stages ({:?}): \n", self.stages.len() );
+    for stage in self.stages.iter() { let _ = write!( f, "  {:?} \n", stage ); }
 
     /*
     if self.match_clause.is_some() 
@@ -171,9 +218,10 @@ impl Display for Transaction
 
     if self.return_clause.is_some() 
     {
-      let _ = write!( f, "Return Clause {:?} AS {:?} \n",
-        &self.return_clause.as_ref().unwrap().transaction_label, 
-        &self.return_clause.as_ref().unwrap().output_label );
+      let return_clause = self.return_clause.as_ref().unwrap();
+      let columns: Vec<&String> = return_clause.items.iter().map( | item | item.column() ).collect();
+      let _ = write!( f, "Return Clause {:?} distinct({:?}) skip({:?}) limit({:?}) \n",
+        columns, return_clause.distinct, return_clause.skip, return_clause.limit );
     }
     else { let _ = write!( f, "Return Clause {:?} \n", self.return_clause ); }
     
@@ -28,7 +28,7 @@
   14. KeyValF64 - For f64 values (64-bit float)
 */
 
-#[derive( Debug )]
+#[derive( Debug, Clone )]
 pub struct KeyValString { pub key: String, pub val: Option<String> }
 impl KeyValString 
 {
@@ -43,7 +43,7 @@ impl KeyValString
   }
 }
 
-#[derive( Debug )]
+#[derive( Debug, Clone )]
 pub struct KeyValBoolean { pub key: String, pub val: Option<bool> }
 impl KeyValBoolean
 {
@@ -58,7 +58,7 @@ impl KeyValBoolean
   }
 }
 
-#[derive( Debug )]
+#[derive( Debug, Clone )]
 pub struct KeyValI8 { pub key: String, pub val: Option<i8> }
 impl KeyValI8 
 {
@@ -73,7 +73,7 @@ impl KeyValI8
   }
 }
 
-#[derive( Debug )]
+#[derive( Debug, Clone )]
 pub struct KeyValU8 { pub key: String, pub val: Option<u8> }
 impl KeyValU8 
 {
@@ -88,7 +88,7 @@ impl KeyValU8
   }
 }
 
-#[derive( Debug )]
+#[derive( Debug, Clone )]
 pub struct KeyValI16 { pub key: String, pub val: Option<i16> }
 impl KeyValI16 
 {
@@ -103,7 +103,7 @@ impl KeyValI16
   }
 }
 
-#[derive( Debug )]
+#[derive( Debug, Clone )]
 pub struct KeyValU16 { pub key: String, pub val: Option<u16> }
 impl KeyValU16 
 {
@@ -118,7 +118,7 @@ impl KeyValU16
   }
 }
 
-#[derive( Debug )]
+#[derive( Debug, Clone )]
 pub struct KeyValI32 { pub key: String, pub val: Option<i32> }
 impl KeyValI32 
 {
@@ -133,7 +133,7 @@ impl KeyValI32
   }
 }
 
-#[derive( Debug )]
+#[derive( Debug, Clone )]
 pub struct KeyValU32 { pub key: String, pub val: Option<u32> }
 impl KeyValU32 
 {
@@ -148,7 +148,7 @@ impl KeyValU32
   }
 }
 
-#[derive( Debug )]
+#[derive( Debug, Clone )]
 pub struct KeyValI64 { pub key: String, pub val: Option<i64> }
 impl KeyValI64 
 {
@@ -163,7 +163,7 @@ impl KeyValI64
   }
 }
 
-#[derive( Debug )]
+#[derive( Debug, Clone )]
 pub struct KeyValU64 { pub key: String, pub val: Option<u64> }
 impl KeyValU64 
 {
@@ -178,7 +178,7 @@ impl KeyValU64
   }
 }
 
-#[derive( Debug )]
+#[derive( Debug, Clone )]
 pub struct KeyValI128 { pub key: String, pub val: Option<i128> }
 impl KeyValI128 
 {
@@ -193,7 +193,7 @@ impl KeyValI128
   }
 }
 
-#[derive( Debug )]
+#[derive( Debug, Clone )]
 pub struct KeyValU128 { pub key: String, pub val: Option<u128> }
 impl KeyValU128 
 {
@@ -208,7 +208,7 @@ impl KeyValU128
   }
 }
 
-#[derive( Debug )]
+#[derive( Debug, Clone )]
 pub struct KeyValF32 { pub key: String, pub val: Option<f32> }
 impl KeyValF32 
 {
@@ -223,7 +223,7 @@ impl KeyValF32
   }
 }
 
-#[derive( Debug )]
+#[derive( Debug, Clone )]
 pub struct KeyValF64 { pub key: String, pub val: Option<f64> }
 impl KeyValF64 
 {
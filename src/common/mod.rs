@@ -47,6 +47,9 @@ pub static DIR_LEFT: &'static str = "[::DIRL]";
 pub static DIR_RIGHT: &'static str = "[::DIRR]";
 pub static DIR_BI: &'static str = "[:DIRBI]";
 
+pub static DEFAULT_GRAPH: &'static str = "DEFAULT_GRAPH";
+pub static DEFAULT_GRAPH_UUID: &'static str = "1b622a2c-68dc-4848-a018-e71b604b5597";
+
 pub static PLACEHOLDER: &'static str = "[::PLCH]";
 pub static END_DB: &'static str = "[:::END]";
 
@@ -65,7 +68,14 @@ pub fn direction_to_str ( dt: &DirectionType ) -> &str
   }
 }
 
-pub fn direction_from_str () {}
+pub fn direction_from_str ( dir: &str ) -> Option<DirectionType> 
+{
+  if dir == DIR_UNDIRECTED { return Some( DirectionType::Undirected ); }
+  if dir == DIR_LEFT { return Some( DirectionType::Left ); }
+  if dir == DIR_RIGHT { return Some( DirectionType::Right ); }
+  if dir == DIR_BI { return Some( DirectionType::Bidirectional ); }
+  None
+}
 
 pub fn bool_to_affix ( b: bool ) -> &'static str 
 {
@@ -74,26 +84,84 @@ pub fn bool_to_affix ( b: bool ) -> &'static str
 }
 
 /// Node / Edge Property
-#[derive( Debug, PartialEq )]
+#[derive( Debug, Clone, PartialEq )]
 pub enum NEProperty
 {
   // Default Types
   Tag( String ),
   
-  //KvpString(( String, Option<String> )),
-  //KvpFloat(( String, Option<f64> )),
-  //KvpBool(( String, Option<bool> )),
+  // Key-Value Types (one per KVP gram)
+  KvpString(( String, String )),
+  KvpBoolean(( String, bool )),
+  KvpI8(( String, i8 )),
+  KvpI16(( String, i16 )),
+  KvpI32(( String, i32 )),
+  KvpI64(( String, i64 )),
+  KvpI128(( String, i128 )),
+  KvpU8(( String, u8 )),
+  KvpU16(( String, u16 )),
+  KvpU32(( String, u32 )),
+  KvpU64(( String, u64 )),
+  KvpU128(( String, u128 )),
+  KvpF32(( String, f32 )),
+  KvpF64(( String, f64 )),
+
+  // List Types (one per list KVP row, elements share a type)
+  ListString(( String, Vec<String> )),
+  ListBool(( String, Vec<bool> )),
+  ListI64(( String, Vec<i64> )),
+  ListFloat(( String, Vec<f64> )),
+}
+impl NEProperty 
+{
+  /// Property key (Tags have none)
+  pub fn key ( &self ) -> Option<&str> 
+  {
+    match self 
+    {
+      NEProperty::Tag( _ ) => None,
+      NEProperty::KvpString(( k, _ )) => Some( k ),
+      NEProperty::KvpBoolean(( k, _ )) => Some( k ),
+      NEProperty::KvpI8(( k, _ )) => Some( k ),
+      NEProperty::KvpI16(( k, _ )) => Some( k ),
+      NEProperty::KvpI32(( k, _ )) => Some( k ),
+      NEProperty::KvpI64(( k, _ )) => Some( k ),
+      NEProperty::KvpI128(( k, _ )) => Some( k ),
+      NEProperty::KvpU8(( k, _ )) => Some( k ),
+      NEProperty::KvpU16(( k, _ )) => Some( k ),
+      NEProperty::KvpU32(( k, _ )) => Some( k ),
+      NEProperty::KvpU64(( k, _ )) => Some( k ),
+      NEProperty::KvpU128(( k, _ )) => Some( k ),
+      NEProperty::KvpF32(( k, _ )) => Some( k ),
+      NEProperty::KvpF64(( k, _ )) => Some( k ),
+      NEProperty::ListString(( k, _ )) => Some( k ),
+      NEProperty::ListBool(( k, _ )) => Some( k ),
+      NEProperty::ListI64(( k, _ )) => Some( k ),
+      NEProperty::ListFloat(( k, _ )) => Some( k ),
+    }
+  }
+}
 
-  //ListString(( String, Option<Vec<String>> )),
-  //ListFloat(( String, Option<Vec<f64>> )),
-  //ListBool(( String, Option<Vec<bool>> )),
-  
-  // Other Schema Types
-  //KvpInteger(( String, Option<u64> )),
+/// Find key-value property by key
+pub fn find_property<'a> ( properties: &'a Vec<NEProperty>, key: &str ) -> Option<&'a NEProperty> 
+{
+  for prop in properties.iter() 
+  {
+    if prop.key() == Some( key ) { return Some( prop ); }
+  }
+  None
+}
+
+/// Replace the key-value property with the same key, or add it
+pub fn set_property ( properties: &mut Vec<NEProperty>, prop: NEProperty ) 
+{
+  let pos = properties.iter().position( | p | p.key().is_some() && p.key() == prop.key() );
+  if pos.is_some() { properties[pos.unwrap()] = prop; }
+  else { properties.push( prop ); }
 }
 
 /// Graph Node
-#[derive( Debug )]
+#[derive( Debug, Clone, PartialEq )]
 pub struct Node
 {
   pub id: String,
@@ -114,12 +182,43 @@ impl Node
   /// Add NEProperty to Node
   pub fn io_add_property ( &mut self, prop: NEProperty ) { self.properties.push( prop ); }
 
+  /// Replace or add a key-value property
+  pub fn io_set_property ( &mut self, prop: NEProperty ) { set_property( &mut self.properties, prop ); }
+
+  /// Remove a stored property or Tag
+  pub fn io_remove_property ( &mut self, prop: &NEProperty ) { self.properties.retain( | p | p != prop ); }
+
+  /// Primary label or secondary Tag matches label
+  pub fn has_label ( &self, label: &str ) -> bool 
+  {
+    if self.primary_tag.as_deref() == Some( label ) { return true; }
+    self.properties.iter().any( | p | *p == NEProperty::Tag( label.to_string() ))
+  }
+
+  /// Primary label followed by every secondary Tag
+  pub fn labels ( &self ) -> Vec<&str> 
+  {
+    let mut ret: Vec<&str> = self.primary_tag.iter().map( | t | t.as_str() ).collect();
+    for prop in self.properties.iter() 
+    {
+      match prop 
+      {
+        NEProperty::Tag( label ) => { ret.push( label ); }
+        _ => {}
+      }
+    }
+    ret
+  }
+
   /// Node has any Properties
   pub fn has_props ( &self ) -> bool { self.properties.len() > 0 }
+
+  /// Key-value property by key
+  pub fn property ( &self, key: &str ) -> Option<&NEProperty> { find_property( &self.properties, key ) }
 }
 
 /// Graph Edge
-#[derive( Debug )]
+#[derive( Debug, Clone, PartialEq )]
 pub struct Edge
 {
   pub id: String,
@@ -162,8 +261,17 @@ impl Edge
   /// Add NEProperty to Edge
   pub fn io_add_property ( &mut self, prop: NEProperty ) { self.properties.push( prop ); }
 
+  /// Replace or add a key-value property
+  pub fn io_set_property ( &mut self, prop: NEProperty ) { set_property( &mut self.properties, prop ); }
+
+  /// Remove a stored property or Tag
+  pub fn io_remove_property ( &mut self, prop: &NEProperty ) { self.properties.retain( | p | p != prop ); }
+
   /// Edge has any Properties
   pub fn has_props ( &self ) -> bool { self.properties.len() > 0 }
+
+  /// Key-value property by key
+  pub fn property ( &self, key: &str ) -> Option<&NEProperty> { find_property( &self.properties, key ) }
 }
 
 // ---------------------------------------------------------------------------------------------------------------------
@@ -272,6 +380,37 @@ mod tests
     assert_eq!( node.has_props(), true );
   }
 
+  #[test]
+  fn test_direction_from_str () 
+  {
+    for dir in [ DirectionType::Undirected, DirectionType::Left, DirectionType::Right, DirectionType::Bidirectional ] 
+    {
+      assert_eq!( direction_from_str( direction_to_str( &dir )), Some( dir ));
+    }
+    assert_eq!( direction_from_str( "[::::EG]" ), None );
+  }
+
+  #[test]
+  fn test_node_property () 
+  {
+    let mut node: Node = Node::new_empty();
+    node.io_add_property( cons_tag_property( String::from( "tag" )));
+    node.io_add_property( NEProperty::KvpString(( String::from( "name" ), String::from( "Alice" ))));
+    node.io_add_property( NEProperty::KvpI64(( String::from( "age" ), 38 )));
+
+    assert_eq!( node.property( "name" ), Some( &NEProperty::KvpString(( String::from( "name" ), String::from( "Alice" )))));
+    assert_eq!( node.property( "age" ), Some( &NEProperty::KvpI64(( String::from( "age" ), 38 ))));
+    assert_eq!( node.property( "tag" ), None );
+    assert_eq!( node.property( "missing" ), None );
+
+    node.io_set_property( NEProperty::KvpI64(( String::from( "age" ), 39 )));
+    node.io_set_property( NEProperty::KvpBoolean(( String::from( "admin" ), true )));
+    assert_eq!( node.properties.len(), 4 );
+    assert_eq!( node.property( "age" ), Some( &NEProperty::KvpI64(( String::from( "age" ), 39 ))));
+    assert_eq!( node.has_label( "tag" ), true );
+    assert_eq!( node.has_label( "missing" ), false );
+  }
+
   #[test]
   fn test_edge_has_props () 
   {
@@ -30,11 +30,37 @@ impl KVP for KVPf32Gram
   }
 }
 
+#[derive( Debug, Clone )]
+pub struct KVPf64Gram { pub key: String, pub val: f64 }
+impl KVPf64Gram 
+{
+  pub fn new ( key: String, val: f64 ) -> Result<KVPf64Gram, String> 
+  {
+    if validate_label( &key ) 
+    {
+      return Ok( KVPf64Gram { key: key, val: val } );
+    }
+    Err( String::from( "KVPf64 invalid" ))
+  }
+}
+impl KVP for KVPf64Gram
+{
+  fn unwrap ( &self ) -> Vec<u8> 
+  {
+    let key_padding = gen_pad_str( LABEL_BYTES - &self.key.bytes().len() );
+    let mut ret = Vec::new();
+    ret.append( &mut self.key.clone().into_bytes() );
+    ret.append( &mut key_padding.into_bytes() );
+    ret.append( &mut self.val.to_le_bytes().to_vec() );
+    ret
+  }
+}
+
 #[cfg(test)]
 mod tests 
 {
   use super::*;
-  use crate::common::F32_BYTES;
+  use crate::common::{ F32_BYTES, F64_BYTES };
 
   // KVPf32Gram Tests
   #[test]
@@ -214,4 +240,26 @@ mod tests
       assert_eq!(kvp_unwrapped.val, val);
     }
   }
-}
\ No newline at end of file
+
+  // KVPf64Gram Tests
+  #[test]
+  fn test_kvp_f64_gram_new () 
+  {
+    let kvp = KVPf64Gram::new( String::from( "rating" ), 4.75 );
+    assert!( kvp.is_ok() );
+    assert_eq!( kvp.as_ref().unwrap().val, 4.75 );
+
+    let too_long_key = "a".repeat( LABEL_BYTES + 1 );
+    assert_eq!( KVPf64Gram::new( too_long_key, 1.0 ).unwrap_err(), "KVPf64 invalid" );
+  }
+
+  #[test]
+  fn test_kvp_f64_gram_unwrap () 
+  {
+    let unwrapped = KVPf64Gram::new( String::from( "test" ), f64::MAX ).unwrap().unwrap();
+    assert_eq!( unwrapped.len(), LABEL_BYTES + F64_BYTES );
+
+    let val_array: [u8; 8] = unwrapped[LABEL_BYTES..].try_into().unwrap();
+    assert_eq!( f64::from_le_bytes( val_array ), f64::MAX );
+  }
+}
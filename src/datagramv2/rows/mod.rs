@@ -4,8 +4,11 @@ use crate::datagramv2::external_grams::traits::KVP;
 use crate::datagramv2::external_grams::basic::{ KVPBooleanGram, KVPStringGram };
 use crate::datagramv2::external_grams::signed::{ KVPi128Gram, KVPi16Gram, KVPi32Gram, KVPi64Gram, KVPi8Gram };
 use crate::datagramv2::external_grams::unsigned::{ KVPu128Gram, KVPu16Gram, KVPu32Gram, KVPu64Gram, KVPu8Gram };
-use crate::utils::{ parse_padded_str, str_from_bytes };
-use crate::common::{ END_DB, KVSTR_BYTES, LABEL_BYTES, PLACEHOLDER, ROW_AFFIX_BYTES, UUID_BYTES };
+use crate::datagramv2::external_grams::float::{ KVPf32Gram, KVPf64Gram };
+use crate::utils::{ parse_padded_str, parse_uuid_str, str_from_bytes };
+use crate::common::{ 
+  BOOL_BYTES, END_DB, FALSE_AFFIX, I128_BYTES, I16_BYTES, I32_BYTES, I64_BYTES, I8_BYTES, LABEL_BYTES, PLACEHOLDER, 
+  ROW_AFFIX_BYTES, TRUE_AFFIX, U64_BYTES, UUID_BYTES, direction_from_str, Edge, NEProperty };
 use crate::datagramv2::dg_utils::next_u64;
 use crate::datagramv2::internal_grams::{ DGu64, Label, UUID };
 
@@ -173,6 +176,41 @@ impl NodeRow
     if affix == NodeRow::AFFIX { return true; }
     false
   }
+
+  /// Assumes first affix has been read
+  /// returns (graph order, node id, primary label)
+  pub fn read ( f: &mut File ) -> Result<( u64, String, String ), String> 
+  {
+    let mut order_buffer = [ 0; U64_BYTES ];
+    let order_res = f.read_exact( &mut order_buffer );
+    if order_res.is_err() { return Err( String::from( "Read Node Row Error: Graph Order" )); }
+
+    let mut uuid_buffer = [ 0; UUID_BYTES ];
+    let _ = f.read_exact( &mut uuid_buffer );
+    let uuid_res = str_from_bytes( &uuid_buffer.to_vec() );
+    if uuid_res.is_err() { return Err( String::from( "Read Node Row Error: UUID" )); }
+
+    let mut label_buffer = [ 0; LABEL_BYTES ];
+    let _ = f.read_exact( &mut label_buffer );
+    let label_res = str_from_bytes( &label_buffer.to_vec() );
+    if label_res.is_err() { return Err( String::from( "Read Node Row Error: Label" )); }
+
+    let _ = f.seek( SeekFrom::Current(( ROW_AFFIX_BYTES ) as i64 ));
+
+    Ok(( 
+      u64::from_be_bytes( order_buffer ), 
+      parse_uuid_str( &uuid_res.unwrap() ).to_string(), 
+      parse_padded_str( &label_res.unwrap() ).to_string() ))
+  }
+
+  /// Assumes first affix has been read
+  pub fn skip ( f: &mut File ) -> Result<u64, Error>
+  {
+    return f.seek( SeekFrom::Current(( NodeRow::size() - ROW_AFFIX_BYTES ) as i64 ));
+  }
+
+  pub fn size () -> usize { (ROW_AFFIX_BYTES * 2) + U64_BYTES + UUID_BYTES + LABEL_BYTES }
+  pub fn cell_count () -> usize { NodeRow::size() / 8 }
 }
 
 pub struct EdgeRow {}
@@ -205,6 +243,107 @@ impl EdgeRow
     if affix == EdgeRow::AFFIX { return true; }
     false
   }
+
+  /// Assumes first affix has been read -> ( graph order, edge without properties )
+  pub fn read ( f: &mut File ) -> Result<( u64, Edge ), String> 
+  {
+    let mut buffer = vec![ 0; EdgeRow::size() - ROW_AFFIX_BYTES ];
+    if f.read_exact( &mut buffer ).is_err() { return Err( String::from( "Read Edge Row Error" )); }
+
+    let mut pos: usize = 0;
+    let order = u64::from_be_bytes( buffer[pos..pos + U64_BYTES].try_into().unwrap() );
+    pos += U64_BYTES;
+
+    let uuid_res = str_from_bytes( &buffer[pos..pos + UUID_BYTES] );
+    if uuid_res.is_err() { return Err( String::from( "Read Edge Row Error: UUID" )); }
+    pos += UUID_BYTES;
+
+    let label_res = str_from_bytes( &buffer[pos..pos + LABEL_BYTES] );
+    if label_res.is_err() { return Err( String::from( "Read Edge Row Error: Label" )); }
+    pos += LABEL_BYTES;
+
+    let dir_res = str_from_bytes( &buffer[pos..pos + ROW_AFFIX_BYTES] );
+    let dir_opt = if dir_res.is_ok() { direction_from_str( &dir_res.unwrap() ) } else { None };
+    if dir_opt.is_none() { return Err( String::from( "Read Edge Row Error: Direction" )); }
+    pos += ROW_AFFIX_BYTES;
+
+    let left_res = str_from_bytes( &buffer[pos..pos + UUID_BYTES] );
+    if left_res.is_err() { return Err( String::from( "Read Edge Row Error: Left UUID" )); }
+    pos += UUID_BYTES;
+
+    let right_res = str_from_bytes( &buffer[pos..pos + UUID_BYTES] );
+    if right_res.is_err() { return Err( String::from( "Read Edge Row Error: Right UUID" )); }
+
+    Ok(( order, Edge::new(
+      parse_uuid_str( &uuid_res.unwrap() ).to_string(),
+      Some( parse_padded_str( &label_res.unwrap() ).to_string() ),
+      parse_uuid_str( &left_res.unwrap() ).to_string(),
+      parse_uuid_str( &right_res.unwrap() ).to_string(),
+      dir_opt.unwrap(),
+      Vec::new() )))
+  }
+
+  /// Assumes first affix has been read
+  pub fn skip ( f: &mut File ) -> Result<u64, Error>
+  {
+    return f.seek( SeekFrom::Current(( EdgeRow::size() - ROW_AFFIX_BYTES ) as i64 ));
+  }
+
+  pub fn size () -> usize { (ROW_AFFIX_BYTES * 3) + U64_BYTES + (UUID_BYTES * 3) + LABEL_BYTES }
+  pub fn cell_count () -> usize { EdgeRow::size() / 8 }
+}
+
+/*
+  Label rows hold a secondary label of a node.
+
+  [AFFIX][OWNER UUID][LABEL][AFFIX]
+*/
+pub struct LabelRow {}
+impl LabelRow 
+{
+  const AFFIX: &'static str = "[::::LB]";
+
+  pub fn new ( owner: &UUID, label: &Label ) -> Vec<u8> 
+  {
+    let mut ret = Vec::new();
+    ret.append( &mut String::from( LabelRow::AFFIX ).into_bytes() );   // [::::LB]
+    ret.append( &mut owner.unwrap().into_bytes() );                    // [UUID]
+    ret.append( &mut label.unwrap().into_bytes() );                    // [Label]
+    ret.append( &mut String::from( LabelRow::AFFIX ).into_bytes() );   // [::::LB]
+    ret
+  }
+
+  pub fn is_affix ( affix: &str ) -> bool 
+  {
+    if affix == LabelRow::AFFIX { return true; }
+    false
+  }
+
+  /// Assumes first affix has been read -> ( owner id, label )
+  pub fn read ( f: &mut File ) -> Result<( String, String ), String> 
+  {
+    let mut buffer = vec![ 0; LabelRow::size() - ROW_AFFIX_BYTES ];
+    if f.read_exact( &mut buffer ).is_err() { return Err( String::from( "Read Label Row Error" )); }
+
+    let owner_res = str_from_bytes( &buffer[0..UUID_BYTES] );
+    if owner_res.is_err() { return Err( String::from( "Read Label Row Error: UUID" )); }
+
+    let label_res = str_from_bytes( &buffer[UUID_BYTES..UUID_BYTES + LABEL_BYTES] );
+    if label_res.is_err() { return Err( String::from( "Read Label Row Error: Label" )); }
+
+    Ok(( 
+      parse_uuid_str( &owner_res.unwrap() ).to_string(), 
+      parse_padded_str( &label_res.unwrap() ).to_string() ))
+  }
+
+  /// Assumes first affix has been read
+  pub fn skip ( f: &mut File ) -> Result<u64, Error>
+  {
+    return f.seek( SeekFrom::Current(( LabelRow::size() - ROW_AFFIX_BYTES ) as i64 ));
+  }
+
+  pub fn size () -> usize { (ROW_AFFIX_BYTES * 2) + UUID_BYTES + LABEL_BYTES }
+  pub fn cell_count () -> usize { LabelRow::size() / 8 }
 }
 
 #[derive( Debug, Clone, PartialEq )]
@@ -306,6 +445,15 @@ impl PageRow
 }
 
 
+/*
+  Key-Value Pair rows hold a single property of a node or edge.
+
+  [AFFIX][OWNER UUID][KEY][VALUE + ZERO PADDING TO CELL][AFFIX]
+
+  List rows hold every element of a list property, elements share a type and each one is padded to the cell size.
+
+  [AFFIX][OWNER UUID][KEY][COUNT][VALUE + ZERO PADDING TO CELL]...[AFFIX]
+*/
 pub struct KVPRow {}
 impl KVPRow 
 {
@@ -323,113 +471,109 @@ impl KVPRow
   const KVU128_AFFIX: &'static str = "[KVU128]";
   const KVF32_AFFIX: &'static str = "[:KVF32]";
   const KVF64_AFFIX: &'static str = "[:KVF64]";
+  const LKVSTR_AFFIX: &'static str = "[LKVSTR]";
+  const LKVBOOL_AFFIX: &'static str = "[LKVBOL]";
+  const LKVI64_AFFIX: &'static str = "[LKVI64]";
+  const LKVF64_AFFIX: &'static str = "[LKVF64]";
 
-  pub fn new_kvstr ( kv: &KVPStringGram ) -> Vec<u8> 
+  fn cons_row ( affix: &str, owner: &UUID, kv: &impl KVP ) -> Vec<u8> 
   {
     let mut ret = Vec::new();
-    ret.append( &mut String::from( KVPRow::KVSTR_AFFIX ).into_bytes() );
+    ret.append( &mut String::from( affix ).into_bytes() );
+    ret.append( &mut owner.unwrap().into_bytes() );
     ret.append( &mut kv.unwrap() );
-    ret.append( &mut String::from( KVPRow::KVSTR_AFFIX ).into_bytes() );
+    while ret.len() % ROW_AFFIX_BYTES != 0 { ret.push( 0 ); }
+    ret.append( &mut String::from( affix ).into_bytes() );
     ret
   }
 
-  pub fn new_kvbool ( kv: &KVPBooleanGram ) -> Vec<u8> 
+  pub fn new_kvstr ( owner: &UUID, kv: &KVPStringGram ) -> Vec<u8> { KVPRow::cons_row( KVPRow::KVSTR_AFFIX, owner, kv ) }
+  pub fn new_kvbool ( owner: &UUID, kv: &KVPBooleanGram ) -> Vec<u8> { KVPRow::cons_row( KVPRow::KVBOOL_AFFIX, owner, kv ) }
+  pub fn new_kvi8 ( owner: &UUID, kv: &KVPi8Gram ) -> Vec<u8> { KVPRow::cons_row( KVPRow::KVI8_AFFIX, owner, kv ) }
+  pub fn new_kvu8 ( owner: &UUID, kv: &KVPu8Gram ) -> Vec<u8> { KVPRow::cons_row( KVPRow::KVU8_AFFIX, owner, kv ) }
+  pub fn new_kvi16 ( owner: &UUID, kv: &KVPi16Gram ) -> Vec<u8> { KVPRow::cons_row( KVPRow::KVI16_AFFIX, owner, kv ) }
+  pub fn new_kvu16 ( owner: &UUID, kv: &KVPu16Gram ) -> Vec<u8> { KVPRow::cons_row( KVPRow::KVU16_AFFIX, owner, kv ) }
+  pub fn new_kvi32 ( owner: &UUID, kv: &KVPi32Gram ) -> Vec<u8> { KVPRow::cons_row( KVPRow::KVI32_AFFIX, owner, kv ) }
+  pub fn new_kvu32 ( owner: &UUID, kv: &KVPu32Gram ) -> Vec<u8> { KVPRow::cons_row( KVPRow::KVU32_AFFIX, owner, kv ) }
+  pub fn new_kvi64 ( owner: &UUID, kv: &KVPi64Gram ) -> Vec<u8> { KVPRow::cons_row( KVPRow::KVI64_AFFIX, owner, kv ) }
+  pub fn new_kvu64 ( owner: &UUID, kv: &KVPu64Gram ) -> Vec<u8> { KVPRow::cons_row( KVPRow::KVU64_AFFIX, owner, kv ) }
+  pub fn new_kvi128 ( owner: &UUID, kv: &KVPi128Gram ) -> Vec<u8> { KVPRow::cons_row( KVPRow::KVI128_AFFIX, owner, kv ) }
+  pub fn new_kvu128 ( owner: &UUID, kv: &KVPu128Gram ) -> Vec<u8> { KVPRow::cons_row( KVPRow::KVU128_AFFIX, owner, kv ) }
+  pub fn new_kvf32 ( owner: &UUID, kv: &KVPf32Gram ) -> Vec<u8> { KVPRow::cons_row( KVPRow::KVF32_AFFIX, owner, kv ) }
+  pub fn new_kvf64 ( owner: &UUID, kv: &KVPf64Gram ) -> Vec<u8> { KVPRow::cons_row( KVPRow::KVF64_AFFIX, owner, kv ) }
+
+  fn cons_list_row ( affix: &str, owner: &UUID, key: &str, elements: Vec<Vec<u8>> ) -> Result<Vec<u8>, String> 
   {
-    let mut ret = Vec::new();
-    ret.append( &mut String::from( KVPRow::KVBOOL_AFFIX ).into_bytes() );
-    ret.append( &mut kv.unwrap() );
-    ret.append( &mut String::from( KVPRow::KVBOOL_AFFIX ).into_bytes() );
-    ret
-  }
-
-  pub fn new_kvi8 ( kv: &KVPi8Gram ) -> Vec<u8> 
-  {
-    let mut ret = Vec::new();
-    ret.append( &mut String::from( KVPRow::KVI8_AFFIX ).into_bytes() );
-    ret.append( &mut kv.unwrap() );
-    ret.append( &mut String::from( KVPRow::KVI8_AFFIX ).into_bytes() );
-    ret
-  }
-
-  pub fn new_kvu8 ( kv: &KVPu8Gram ) -> Vec<u8> 
-  {
-    let mut ret = Vec::new();
-    ret.append( &mut String::from( KVPRow::KVU8_AFFIX ).into_bytes() );
-    ret.append( &mut kv.unwrap() );
-    ret.append( &mut String::from( KVPRow::KVU8_AFFIX ).into_bytes() );
-    ret
-  }
+    let key_res = Label::new( key.to_string() );
+    if key_res.is_err() { return Err( String::from( "KVPList invalid" )); }
 
-  pub fn new_kvi16 ( kv: &KVPi16Gram ) -> Vec<u8> 
-  {
     let mut ret = Vec::new();
-    ret.append( &mut String::from( KVPRow::KVI16_AFFIX ).into_bytes() );
-    ret.append( &mut kv.unwrap() );
-    ret.append( &mut String::from( KVPRow::KVI16_AFFIX ).into_bytes() );
-    ret
+    ret.append( &mut String::from( affix ).into_bytes() );
+    ret.append( &mut owner.unwrap().into_bytes() );
+    ret.append( &mut key_res.unwrap().unwrap().into_bytes() );
+    ret.append( &mut ( elements.len() as u64 ).to_le_bytes().to_vec() );
+    for mut element in elements.into_iter() 
+    {
+      ret.append( &mut element );
+      while ret.len() % ROW_AFFIX_BYTES != 0 { ret.push( 0 ); }
+    }
+    ret.append( &mut String::from( affix ).into_bytes() );
+    Ok( ret )
   }
 
-  pub fn new_kvu16 ( kv: &KVPu16Gram ) -> Vec<u8> 
+  pub fn new_list_str ( owner: &UUID, key: &str, vals: &Vec<String> ) -> Result<Vec<u8>, String> 
   {
-    let mut ret = Vec::new();
-    ret.append( &mut String::from( KVPRow::KVU16_AFFIX ).into_bytes() );
-    ret.append( &mut kv.unwrap() );
-    ret.append( &mut String::from( KVPRow::KVU16_AFFIX ).into_bytes() );
-    ret
+    let mut elements = Vec::new();
+    for val in vals.iter() 
+    {
+      let val_res = Label::new( val.clone() );
+      if val_res.is_err() { return Err( String::from( "KVPList invalid" )); }
+      elements.push( val_res.unwrap().unwrap().into_bytes() );
+    }
+    KVPRow::cons_list_row( KVPRow::LKVSTR_AFFIX, owner, key, elements )
   }
 
-  pub fn new_kvi32 ( kv: &KVPi32Gram ) -> Vec<u8> 
+  pub fn new_list_bool ( owner: &UUID, key: &str, vals: &Vec<bool> ) -> Result<Vec<u8>, String> 
   {
-    let mut ret = Vec::new();
-    ret.append( &mut String::from( KVPRow::KVI32_AFFIX ).into_bytes() );
-    ret.append( &mut kv.unwrap() );
-    ret.append( &mut String::from( KVPRow::KVI32_AFFIX ).into_bytes() );
-    ret
+    let elements = vals.iter().map( | v | String::from( if *v { TRUE_AFFIX } else { FALSE_AFFIX } ).into_bytes() ).collect();
+    KVPRow::cons_list_row( KVPRow::LKVBOOL_AFFIX, owner, key, elements )
   }
 
-  pub fn new_kvu32 ( kv: &KVPu32Gram ) -> Vec<u8> 
+  pub fn new_list_i64 ( owner: &UUID, key: &str, vals: &Vec<i64> ) -> Result<Vec<u8>, String> 
   {
-    let mut ret = Vec::new();
-    ret.append( &mut String::from( KVPRow::KVU32_AFFIX ).into_bytes() );
-    ret.append( &mut kv.unwrap() );
-    ret.append( &mut String::from( KVPRow::KVU32_AFFIX ).into_bytes() );
-    ret
+    KVPRow::cons_list_row( KVPRow::LKVI64_AFFIX, owner, key, vals.iter().map( | v | v.to_le_bytes().to_vec() ).collect() )
   }
 
-  pub fn new_kvi64 ( kv: &KVPi64Gram ) -> Vec<u8> 
+  pub fn new_list_f64 ( owner: &UUID, key: &str, vals: &Vec<f64> ) -> Result<Vec<u8>, String> 
   {
-    let mut ret = Vec::new();
-    ret.append( &mut String::from( KVPRow::KVI64_AFFIX ).into_bytes() );
-    ret.append( &mut kv.unwrap() );
-    ret.append( &mut String::from( KVPRow::KVI64_AFFIX ).into_bytes() );
-    ret
+    KVPRow::cons_list_row( KVPRow::LKVF64_AFFIX, owner, key, vals.iter().map( | v | v.to_le_bytes().to_vec() ).collect() )
   }
 
-  pub fn new_kvu64 ( kv: &KVPu64Gram ) -> Vec<u8> 
+  /// Row for a key-value property, Tags are not key-value pairs
+  pub fn from_property ( owner: &UUID, prop: &NEProperty ) -> Result<Vec<u8>, String> 
   {
-    let mut ret = Vec::new();
-    ret.append( &mut String::from( KVPRow::KVU64_AFFIX ).into_bytes() );
-    ret.append( &mut kv.unwrap() );
-    ret.append( &mut String::from( KVPRow::KVU64_AFFIX ).into_bytes() );
-    ret
-  }
-
-  pub fn new_kvi128 ( kv: &KVPi128Gram ) -> Vec<u8> 
-  {
-    let mut ret = Vec::new();
-    ret.append( &mut String::from( KVPRow::KVI128_AFFIX ).into_bytes() );
-    ret.append( &mut kv.unwrap() );
-    ret.append( &mut String::from( KVPRow::KVI128_AFFIX ).into_bytes() );
-    ret
-  }
-
-  pub fn new_kvu128 ( kv: &KVPu128Gram ) -> Vec<u8> 
-  {
-    let mut ret = Vec::new();
-    ret.append( &mut String::from( KVPRow::KVU128_AFFIX ).into_bytes() );
-    ret.append( &mut kv.unwrap() );
-    ret.append( &mut String::from( KVPRow::KVU128_AFFIX ).into_bytes() );
-    ret
+    match prop 
+    {
+      NEProperty::Tag( _ ) => Err( String::from( "Error: Tag is not a key-value property." )),
+      NEProperty::KvpString(( k, v )) => Ok( KVPRow::new_kvstr( owner, &KVPStringGram::new( k.clone(), v.clone() )? )),
+      NEProperty::KvpBoolean(( k, v )) => Ok( KVPRow::new_kvbool( owner, &KVPBooleanGram::new( k.clone(), *v )? )),
+      NEProperty::KvpI8(( k, v )) => Ok( KVPRow::new_kvi8( owner, &KVPi8Gram::new( k.clone(), *v )? )),
+      NEProperty::KvpU8(( k, v )) => Ok( KVPRow::new_kvu8( owner, &KVPu8Gram::new( k.clone(), *v )? )),
+      NEProperty::KvpI16(( k, v )) => Ok( KVPRow::new_kvi16( owner, &KVPi16Gram::new( k.clone(), *v )? )),
+      NEProperty::KvpU16(( k, v )) => Ok( KVPRow::new_kvu16( owner, &KVPu16Gram::new( k.clone(), *v )? )),
+      NEProperty::KvpI32(( k, v )) => Ok( KVPRow::new_kvi32( owner, &KVPi32Gram::new( k.clone(), *v )? )),
+      NEProperty::KvpU32(( k, v )) => Ok( KVPRow::new_kvu32( owner, &KVPu32Gram::new( k.clone(), *v )? )),
+      NEProperty::KvpI64(( k, v )) => Ok( KVPRow::new_kvi64( owner, &KVPi64Gram::new( k.clone(), *v )? )),
+      NEProperty::KvpU64(( k, v )) => Ok( KVPRow::new_kvu64( owner, &KVPu64Gram::new( k.clone(), *v )? )),
+      NEProperty::KvpI128(( k, v )) => Ok( KVPRow::new_kvi128( owner, &KVPi128Gram::new( k.clone(), *v )? )),
+      NEProperty::KvpU128(( k, v )) => Ok( KVPRow::new_kvu128( owner, &KVPu128Gram::new( k.clone(), *v )? )),
+      NEProperty::KvpF32(( k, v )) => Ok( KVPRow::new_kvf32( owner, &KVPf32Gram::new( k.clone(), *v )? )),
+      NEProperty::KvpF64(( k, v )) => Ok( KVPRow::new_kvf64( owner, &KVPf64Gram::new( k.clone(), *v )? )),
+      NEProperty::ListString(( k, v )) => KVPRow::new_list_str( owner, k, v ),
+      NEProperty::ListBool(( k, v )) => KVPRow::new_list_bool( owner, k, v ),
+      NEProperty::ListI64(( k, v )) => KVPRow::new_list_i64( owner, k, v ),
+      NEProperty::ListFloat(( k, v )) => KVPRow::new_list_f64( owner, k, v ),
+    }
   }
 
   pub fn new_kvstr_affix () -> Vec<u8> { String::from( KVPRow::KVSTR_AFFIX ).into_bytes() }
@@ -531,40 +675,159 @@ impl KVPRow
     false
   }
 
-  /*
-    pub fn new_kvstr_affix () -> Vec<u8> { String::from( KVPRow::KVSTR_AFFIX ).into_bytes() }
-    pub fn new_kvbool_affix () -> Vec<u8> { String::from( KVPRow::KVBOOL_AFFIX ).into_bytes() }
-    pub fn new_kvi8_affix () -> Vec<u8> { String::from( KVPRow::KVI8_AFFIX ).into_bytes() }
-    pub fn new_kvu8_affix () -> Vec<u8> { String::from( KVPRow::KVU8_AFFIX ).into_bytes() }
-    pub fn new_kvi16_affix () -> Vec<u8> { String::from( KVPRow::KVI16_AFFIX ).into_bytes() }
-    pub fn new_kvu16_affix () -> Vec<u8> { String::from( KVPRow::KVU16_AFFIX ).into_bytes() }
-    pub fn new_kvi32_affix () -> Vec<u8> { String::from( KVPRow::KVI32_AFFIX ).into_bytes() }
-    pub fn new_kvu32_affix () -> Vec<u8> { String::from( KVPRow::KVU32_AFFIX ).into_bytes() }
-    pub fn new_kvi64_affix () -> Vec<u8> { String::from( KVPRow::KVI64_AFFIX ).into_bytes() }
-    pub fn new_kvu64_affix () -> Vec<u8> { String::from( KVPRow::KVU64_AFFIX ).into_bytes() }
-    pub fn new_kvi128_affix () -> Vec<u8> { String::from( KVPRow::KVI128_AFFIX ).into_bytes() }
-    pub fn new_kvu128_affix () -> Vec<u8> { String::from( KVPRow::KVU128_AFFIX ).into_bytes() }
-    pub fn new_kvf32_affix () -> Vec<u8> { String::from( KVPRow::KVF32_AFFIX ).into_bytes() }
-    pub fn new_kvf64_affix () -> Vec<u8> { String::from( KVPRow::KVF64_AFFIX ).into_bytes() }
-  */
-
-  // Assumes first affix has been read
-  pub fn read_kvstr ( f: &mut File ) -> Result<KVPStringGram, String> 
-  {
-    let mut buffer = [ 0; KVSTR_BYTES ];
-    let _ = f.read_exact( &mut buffer );
-    
-    let key_res = str_from_bytes( &buffer[0..LABEL_BYTES].to_vec() );
+  /// Any key-value affix, single value or list
+  pub fn is_affix ( affix: &str ) -> bool { KVPRow::value_bytes( affix ).is_some() || KVPRow::is_list_affix( affix ) }
+
+  pub fn is_list_affix ( affix: &str ) -> bool { KVPRow::element_bytes( affix ).is_some() }
+
+  /// Padded element length of a list row by affix
+  fn element_bytes ( affix: &str ) -> Option<usize> 
+  {
+    match affix 
+    {
+      KVPRow::LKVSTR_AFFIX => Some( LABEL_BYTES ),
+      KVPRow::LKVBOOL_AFFIX => Some( BOOL_BYTES ),
+      KVPRow::LKVI64_AFFIX | KVPRow::LKVF64_AFFIX => Some( I64_BYTES ),
+      _ => None
+    }
+  }
+
+  /// Unpadded value length by affix
+  fn value_bytes ( affix: &str ) -> Option<usize> 
+  {
+    match affix 
+    {
+      KVPRow::KVSTR_AFFIX => Some( LABEL_BYTES ),
+      KVPRow::KVBOOL_AFFIX => Some( BOOL_BYTES ),
+      KVPRow::KVI8_AFFIX | KVPRow::KVU8_AFFIX => Some( I8_BYTES ),
+      KVPRow::KVI16_AFFIX | KVPRow::KVU16_AFFIX => Some( I16_BYTES ),
+      KVPRow::KVI32_AFFIX | KVPRow::KVU32_AFFIX | KVPRow::KVF32_AFFIX => Some( I32_BYTES ),
+      KVPRow::KVI64_AFFIX | KVPRow::KVU64_AFFIX | KVPRow::KVF64_AFFIX => Some( I64_BYTES ),
+      KVPRow::KVI128_AFFIX | KVPRow::KVU128_AFFIX => Some( I128_BYTES ),
+      _ => None
+    }
+  }
+
+  /// Value length padded to the cell size
+  fn padded_value_bytes ( affix: &str ) -> Option<usize> 
+  {
+    let val = KVPRow::value_bytes( affix )?;
+    Some((( val + ROW_AFFIX_BYTES - 1 ) / ROW_AFFIX_BYTES ) * ROW_AFFIX_BYTES )
+  }
+
+  /// Full row size by affix, lists vary with their element count and have none
+  pub fn size ( affix: &str ) -> Option<usize> 
+  {
+    Some(( ROW_AFFIX_BYTES * 2 ) + UUID_BYTES + LABEL_BYTES + KVPRow::padded_value_bytes( affix )? )
+  }
+
+  pub fn cell_count ( affix: &str ) -> Option<usize> { Some( KVPRow::size( affix )? / ROW_AFFIX_BYTES ) }
+
+  /// Full row size of a list by affix and element count
+  pub fn list_size ( affix: &str, count: usize ) -> Option<usize> 
+  {
+    Some(( ROW_AFFIX_BYTES * 2 ) + UUID_BYTES + LABEL_BYTES + U64_BYTES + ( KVPRow::element_bytes( affix )? * count ))
+  }
+
+  /// Assumes first affix has been read
+  pub fn skip ( affix: &str, f: &mut File ) -> Result<u64, Error> 
+  {
+    if KVPRow::is_list_affix( affix ) 
+    {
+      f.seek( SeekFrom::Current(( UUID_BYTES + LABEL_BYTES ) as i64 ))?;
+      let count = next_u64( f ) as usize;
+      let remaining = KVPRow::list_size( affix, count ).unwrap() - ROW_AFFIX_BYTES - UUID_BYTES - LABEL_BYTES - U64_BYTES;
+      return f.seek( SeekFrom::Current( remaining as i64 ));
+    }
+
+    let size = KVPRow::size( affix );
+    if size.is_none() { return Err( Error::new( std::io::ErrorKind::Other, String::from( "Error: Not a KVP row" ))); }
+    f.seek( SeekFrom::Current(( size.unwrap() - ROW_AFFIX_BYTES ) as i64 ))
+  }
+
+  /// Assumes first affix has been read -> ( owner uuid, property )
+  pub fn read ( affix: &str, f: &mut File ) -> Result<( String, NEProperty ), String> 
+  {
+    if KVPRow::is_list_affix( affix ) { return KVPRow::read_list( affix, f ); }
+
+    let padded = KVPRow::padded_value_bytes( affix );
+    if padded.is_none() { return Err( String::from( "Read Error: Not a KVP row" )); }
+
+    let mut buffer = vec![ 0; UUID_BYTES + LABEL_BYTES + padded.unwrap() + ROW_AFFIX_BYTES ];
+    if f.read_exact( &mut buffer ).is_err() { return Err( String::from( "Read Error: KVP row" )); }
+
+    let owner_res = str_from_bytes( &buffer[0..UUID_BYTES] );
+    if owner_res.is_err() { return Err( String::from( "Read Error: Owner" )); }
+
+    let key_res = str_from_bytes( &buffer[UUID_BYTES..UUID_BYTES + LABEL_BYTES] );
     if key_res.is_err() { return Err( String::from( "Read Error: Key" )); }
+    let key = parse_padded_str( &key_res.unwrap() ).to_string();
 
-    let val_res = str_from_bytes( &buffer[LABEL_BYTES + 1 ..].to_vec() );
-    if val_res.is_err() { return Err( String::from( "Read Error: Value" )); }
+    let val: &[u8] = &buffer[UUID_BYTES + LABEL_BYTES..];
+    let prop = match affix 
+    {
+      KVPRow::KVSTR_AFFIX => 
+      {
+        let val_res = str_from_bytes( &val[0..LABEL_BYTES] );
+        if val_res.is_err() { return Err( String::from( "Read Error: Value" )); }
+        NEProperty::KvpString(( key, parse_padded_str( &val_res.unwrap() ).to_string() ))
+      },
+      KVPRow::KVBOOL_AFFIX => NEProperty::KvpBoolean(( key, &val[0..BOOL_BYTES] == TRUE_AFFIX.as_bytes() )),
+      KVPRow::KVI8_AFFIX => NEProperty::KvpI8(( key, i8::from_le_bytes( val[0..1].try_into().unwrap() ))),
+      KVPRow::KVU8_AFFIX => NEProperty::KvpU8(( key, u8::from_le_bytes( val[0..1].try_into().unwrap() ))),
+      KVPRow::KVI16_AFFIX => NEProperty::KvpI16(( key, i16::from_le_bytes( val[0..2].try_into().unwrap() ))),
+      KVPRow::KVU16_AFFIX => NEProperty::KvpU16(( key, u16::from_le_bytes( val[0..2].try_into().unwrap() ))),
+      KVPRow::KVI32_AFFIX => NEProperty::KvpI32(( key, i32::from_le_bytes( val[0..4].try_into().unwrap() ))),
+      KVPRow::KVU32_AFFIX => NEProperty::KvpU32(( key, u32::from_le_bytes( val[0..4].try_into().unwrap() ))),
+      KVPRow::KVF32_AFFIX => NEProperty::KvpF32(( key, f32::from_le_bytes( val[0..4].try_into().unwrap() ))),
+      KVPRow::KVI64_AFFIX => NEProperty::KvpI64(( key, i64::from_le_bytes( val[0..8].try_into().unwrap() ))),
+      KVPRow::KVU64_AFFIX => NEProperty::KvpU64(( key, u64::from_le_bytes( val[0..8].try_into().unwrap() ))),
+      KVPRow::KVF64_AFFIX => NEProperty::KvpF64(( key, f64::from_le_bytes( val[0..8].try_into().unwrap() ))),
+      KVPRow::KVI128_AFFIX => NEProperty::KvpI128(( key, i128::from_le_bytes( val[0..16].try_into().unwrap() ))),
+      _ => NEProperty::KvpU128(( key, u128::from_le_bytes( val[0..16].try_into().unwrap() ))),
+    };
+
+    Ok(( parse_uuid_str( &owner_res.unwrap() ).to_string(), prop ))
+  }
 
-    let _ = f.seek( SeekFrom::Current(( ROW_AFFIX_BYTES ) as i64 ));
-    
-    KVPStringGram::new( 
-      parse_padded_str( &key_res.unwrap() ).to_string(), 
-      parse_padded_str( &val_res.unwrap() ).to_string() )
+  /// Assumes first affix of a list row has been read -> ( owner uuid, property )
+  fn read_list ( affix: &str, f: &mut File ) -> Result<( String, NEProperty ), String> 
+  {
+    let mut header = vec![ 0; UUID_BYTES + LABEL_BYTES + U64_BYTES ];
+    if f.read_exact( &mut header ).is_err() { return Err( String::from( "Read Error: KVP row" )); }
+
+    let owner_res = str_from_bytes( &header[0..UUID_BYTES] );
+    if owner_res.is_err() { return Err( String::from( "Read Error: Owner" )); }
+
+    let key_res = str_from_bytes( &header[UUID_BYTES..UUID_BYTES + LABEL_BYTES] );
+    if key_res.is_err() { return Err( String::from( "Read Error: Key" )); }
+    let key = parse_padded_str( &key_res.unwrap() ).to_string();
+
+    let count = u64::from_le_bytes( header[UUID_BYTES + LABEL_BYTES..].try_into().unwrap() ) as usize;
+    let element_bytes = KVPRow::element_bytes( affix ).unwrap();
+    let mut buffer = vec![ 0; ( element_bytes * count ) + ROW_AFFIX_BYTES ];
+    if f.read_exact( &mut buffer ).is_err() { return Err( String::from( "Read Error: KVP row" )); }
+
+    let elements: Vec<&[u8]> = buffer[..element_bytes * count].chunks( element_bytes ).collect();
+    let prop = match affix 
+    {
+      KVPRow::LKVSTR_AFFIX => 
+      {
+        let mut vals = Vec::new();
+        for element in elements.iter() 
+        {
+          let val_res = str_from_bytes( element );
+          if val_res.is_err() { return Err( String::from( "Read Error: Value" )); }
+          vals.push( parse_padded_str( &val_res.unwrap() ).to_string() );
+        }
+        NEProperty::ListString(( key, vals ))
+      },
+      KVPRow::LKVBOOL_AFFIX => NEProperty::ListBool(( key, elements.iter().map( | e | *e == TRUE_AFFIX.as_bytes() ).collect() )),
+      KVPRow::LKVI64_AFFIX => NEProperty::ListI64(( key, elements.iter().map( | e | i64::from_le_bytes( e[0..8].try_into().unwrap() )).collect() )),
+      _ => NEProperty::ListFloat(( key, elements.iter().map( | e | f64::from_le_bytes( e[0..8].try_into().unwrap() )).collect() )),
+    };
+
+    Ok(( parse_uuid_str( &owner_res.unwrap() ).to_string(), prop ))
   }
 }
 
@@ -576,6 +839,7 @@ pub enum AffixType
   DBPage, DataPage, AJMPage,
   BuildId, DBNickname,
   Graph, Empty, StartEmpty,
+  Node, Edge, KVP, Label,
   Placeholder, End
 }
 
@@ -589,6 +853,10 @@ pub fn affix_to_type ( affix: &str ) -> Option<AffixType>
   if BuildIDRow::is_affix( affix ) { return Some( AffixType::BuildId ); }
   if DBNicknameRow::is_affix( affix ) { return Some( AffixType::DBNickname ); }
   if GraphRow::is_affix( affix ) { return Some( AffixType::Graph ); }
+  if NodeRow::is_affix( affix ) { return Some( AffixType::Node ); }
+  if EdgeRow::is_affix( affix ) { return Some( AffixType::Edge ); }
+  if KVPRow::is_affix( affix ) { return Some( AffixType::KVP ); }
+  if LabelRow::is_affix( affix ) { return Some( AffixType::Label ); }
   if is_end_affix( affix ) { return Some( AffixType::End ); }
   if is_placeholder_affix( affix ) { return Some( AffixType::Placeholder ); }
   None
@@ -733,6 +1001,182 @@ mod tests
     //assert_eq!( row.len(), 168 );
   }
 
+  #[test]
+  fn test_node_row_size () 
+  {
+    let row = NodeRow::new( 
+      &DGu64::new( 0 ), 
+      &UUID::new( String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" )).unwrap(), 
+      &Label::new( String::from( "node1" )).unwrap() );
+    assert_eq!( row.len(), NodeRow::size() );
+    assert_eq!( NodeRow::cell_count(), 16 );
+  }
+
+  #[test]
+  fn test_edge_row_size () 
+  {
+    let row = EdgeRow::new( 
+      &DGu64::new( 0 ), 
+      &UUID::new( String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" )).unwrap(), 
+      &Label::new( String::from( "edge1" )).unwrap(),
+      crate::common::DIR_RIGHT,
+      &UUID::new( String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" )).unwrap(), 
+      &UUID::new( String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" )).unwrap() );
+    assert_eq!( row.len(), EdgeRow::size() );
+    assert_eq!( EdgeRow::cell_count(), 27 );
+  }
+
+  #[test]
+  fn test_edge_row_read () 
+  {
+    use std::io::Write;
+
+    let path = "../test_data/EdgeRow_test_edge_row_read.sdb";
+    let mut f = File::create( path ).unwrap();
+    let _ = f.write_all( &EdgeRow::new( 
+      &DGu64::new( 7 ), 
+      &UUID::new( String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" )).unwrap(), 
+      &Label::new( String::from( "KNOWS" )).unwrap(),
+      crate::common::DIR_LEFT,
+      &UUID::new( String::from( "11111111-10b1-426f-9247-bb680e5fe0c8" )).unwrap(), 
+      &UUID::new( String::from( "22222222-10b1-426f-9247-bb680e5fe0c8" )).unwrap() ));
+    drop( f );
+
+    let mut f = File::open( path ).unwrap();
+    let affix = crate::datagramv2::dg_utils::next_row_affix( &mut f ).unwrap();
+    assert_eq!( affix_to_type( &affix ), Some( AffixType::Edge ));
+
+    let ( order, edge ) = EdgeRow::read( &mut f ).unwrap();
+    assert_eq!( order, 7 );
+    assert_eq!( edge.id, String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" ));
+    assert_eq!( edge.primary_tag, Some( String::from( "KNOWS" )));
+    assert_eq!( edge.direction, crate::common::DirectionType::Left );
+    assert_eq!( edge.left_id, String::from( "11111111-10b1-426f-9247-bb680e5fe0c8" ));
+    assert_eq!( edge.right_id, String::from( "22222222-10b1-426f-9247-bb680e5fe0c8" ));
+
+    let _ = std::fs::remove_file( path );
+  }
+
+  #[test]
+  fn test_label_row_read () 
+  {
+    use std::io::Write;
+
+    let path = "../test_data/LabelRow_test_label_row_read.sdb";
+    let mut f = File::create( path ).unwrap();
+    let _ = f.write_all( &LabelRow::new( 
+      &UUID::new( String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" )).unwrap(), 
+      &Label::new( String::from( "Lead" )).unwrap() ));
+    drop( f );
+
+    assert_eq!( LabelRow::cell_count(), 15 );
+
+    let mut f = File::open( path ).unwrap();
+    let affix = crate::datagramv2::dg_utils::next_row_affix( &mut f ).unwrap();
+    assert_eq!( affix_to_type( &affix ), Some( AffixType::Label ));
+
+    let ( owner, label ) = LabelRow::read( &mut f ).unwrap();
+    assert_eq!( owner, String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" ));
+    assert_eq!( label, String::from( "Lead" ));
+
+    let _ = std::fs::remove_file( path );
+  }
+
+  #[test]
+  fn test_kvp_row_size () 
+  {
+    let owner = UUID::new( String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" )).unwrap();
+    let kv_str = KVPRow::new_kvstr( &owner, &KVPStringGram::new( String::from( "name" ), String::from( "Alice" )).unwrap() );
+    let kv_i8 = KVPRow::new_kvi8( &owner, &KVPi8Gram::new( String::from( "age" ), 38 ).unwrap() );
+    let kv_u128 = KVPRow::new_kvu128( &owner, &KVPu128Gram::new( String::from( "big" ), u128::MAX ).unwrap() );
+
+    assert_eq!( kv_str.len(), KVPRow::size( KVPRow::KVSTR_AFFIX ).unwrap() );
+    assert_eq!( kv_i8.len(), KVPRow::size( KVPRow::KVI8_AFFIX ).unwrap() );
+    assert_eq!( kv_u128.len(), KVPRow::size( KVPRow::KVU128_AFFIX ).unwrap() );
+    assert_eq!( kv_i8.len() % ROW_AFFIX_BYTES, 0 );
+    assert_eq!( KVPRow::cell_count( KVPRow::KVSTR_AFFIX ), Some( 23 ));
+    assert_eq!( KVPRow::cell_count( KVPRow::KVBOOL_AFFIX ), Some( 16 ));
+    assert_eq!( KVPRow::size( "[::::ND]" ), None );
+  }
+
+  #[test]
+  fn test_kvp_row_read () 
+  {
+    use std::io::Write;
+
+    let path = "../test_data/KVPRow_test_kvp_row_read.sdb";
+    let owner = UUID::new( String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" )).unwrap();
+    let props = vec![
+      NEProperty::KvpString(( String::from( "name" ), String::from( "Alice" ))),
+      NEProperty::KvpBoolean(( String::from( "admin" ), true )),
+      NEProperty::KvpI16(( String::from( "delta" ), -300 )),
+      NEProperty::KvpU64(( String::from( "count" ), u64::MAX )),
+      NEProperty::KvpI128(( String::from( "wide" ), i128::MIN )),
+      NEProperty::KvpF64(( String::from( "rating" ), 4.5 )),
+    ];
+
+    let mut f = File::create( path ).unwrap();
+    for prop in props.iter() { let _ = f.write_all( &KVPRow::from_property( &owner, prop ).unwrap() ); }
+    drop( f );
+
+    let mut f = File::open( path ).unwrap();
+    for prop in props.iter() 
+    {
+      let affix = crate::datagramv2::dg_utils::next_row_affix( &mut f ).unwrap();
+      assert_eq!( affix_to_type( &affix ), Some( AffixType::KVP ));
+
+      let ( read_owner, read_prop ) = KVPRow::read( &affix, &mut f ).unwrap();
+      assert_eq!( read_owner, owner.val );
+      assert_eq!( &read_prop, prop );
+    }
+
+    assert_eq!( KVPRow::from_property( &owner, &NEProperty::Tag( String::from( "tag" ))).is_err(), true );
+    let _ = std::fs::remove_file( path );
+  }
+
+  #[test]
+  fn test_kvp_list_row_read () 
+  {
+    use std::io::Write;
+
+    let path = "../test_data/KVPRow_test_kvp_list_row_read.sdb";
+    let owner = UUID::new( String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" )).unwrap();
+    let colors = NEProperty::ListString(( String::from( "likedColors" ), vec![ String::from( "Pink" ), String::from( "Yellow" ) ] ));
+    let props = vec![
+      colors.clone(),
+      NEProperty::ListBool(( String::from( "flags" ), vec![ true, false, true ] )),
+      NEProperty::ListI64(( String::from( "empty" ), Vec::new() )),
+      NEProperty::ListFloat(( String::from( "ratings" ), vec![ 1.0, 4.5 ] )),
+      NEProperty::KvpString(( String::from( "name" ), String::from( "Eskil" ))),
+    ];
+
+    let row = KVPRow::from_property( &owner, &colors ).unwrap();
+    assert_eq!( row.len(), KVPRow::list_size( KVPRow::LKVSTR_AFFIX, 2 ).unwrap() );
+    assert_eq!( row.len() % ROW_AFFIX_BYTES, 0 );
+    assert_eq!( KVPRow::size( KVPRow::LKVSTR_AFFIX ), None );
+
+    let mut f = File::create( path ).unwrap();
+    for prop in props.iter() { let _ = f.write_all( &KVPRow::from_property( &owner, prop ).unwrap() ); }
+    drop( f );
+
+    let mut f = File::open( path ).unwrap();
+    for prop in props.iter() 
+    {
+      let affix = crate::datagramv2::dg_utils::next_row_affix( &mut f ).unwrap();
+      assert_eq!( affix_to_type( &affix ), Some( AffixType::KVP ));
+      assert_eq!( &KVPRow::read( &affix, &mut f ).unwrap().1, prop );
+    }
+
+    // skipping a list row lands on the next row
+    let mut f = File::open( path ).unwrap();
+    let affix = crate::datagramv2::dg_utils::next_row_affix( &mut f ).unwrap();
+    assert_eq!( KVPRow::skip( &affix, &mut f ).unwrap(), row.len() as u64 );
+
+    let long = NEProperty::ListString(( String::from( "k" ), vec![ "a".repeat( LABEL_BYTES + 1 ) ] ));
+    assert_eq!( KVPRow::from_property( &owner, &long ).is_err(), true );
+    let _ = std::fs::remove_file( path );
+  }
+
   #[test]
   fn test_cons_edge_row () 
   {
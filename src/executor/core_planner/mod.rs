@@ -1,7 +1,8 @@
 use std::fs::{ File };
 use std::path::PathBuf;
 use crate::datagramv2::internal_grams::{ Label };
-use crate::datagramv2::rows::{ affix_to_type, AffixType, PageType };
+use crate::common::ROW_AFFIX_BYTES;
+use crate::datagramv2::rows::{ affix_to_type, AffixType, EdgeRow, GraphRow, KVPRow, LabelRow, NodeRow, PageType };
 use crate::utils::open_file;
 use crate::executor::core::CoreExecutor;
 
@@ -19,6 +20,7 @@ pub struct PlannerPage
   pub end_pos: Option<u64>,
   pub empty_cell_count: Option<u64>,
   pub empty_cell_start_pos: Option<u64>,
+  pub empty_regions: Vec<( u64, u64 )>, // ( position, empty cell count ) of every empty cell region, freed rows included
   pub graph_name: Option<String>,
   pub graph_uuid: Option<String>,
 }
@@ -34,6 +36,7 @@ impl PlannerPage
       end_pos: None, 
       empty_cell_count: None, 
       empty_cell_start_pos: None,
+      empty_regions: Vec::new(),
       graph_name: None,
       graph_uuid: None,
     }
@@ -49,6 +52,10 @@ pub struct WriteNewGraphPlanner<'a>
   pub db_page: Option<PlannerPage>,
   pub pages: Vec<PlannerPage>,
   pub end_pos: Option<u64>, // end of file affix position 
+  pub existing: bool, // plan writes into an existing graph instead of a new one
+  pub graph_uuid: Option<String>, // uuid of an existing graph
+  pub graph_row_pos: Option<u64>, // DBPage GraphRow position of an existing graph
+  pub graph_uuids: Vec<String>, // uuids of every graph with a GraphRow on the DBPage
   pub err_state: Option<String>,
 }
 
@@ -64,14 +71,31 @@ impl WriteNewGraphPlanner<'_>
       db_page: None,
       pages: Vec::new(),
       end_pos: None,
+      existing: false,
+      graph_uuid: None,
+      graph_row_pos: None,
+      graph_uuids: Vec::new(),
       err_state: None,
     }
   }
 
+  /// Planner for writes into the existing graph graph_name
+  pub fn for_existing ( path: String, graph_name: &Label ) -> WriteNewGraphPlanner
+  {
+    let mut planner = WriteNewGraphPlanner::new( path, graph_name );
+    planner.existing = true;
+    planner
+  }
+
   pub fn plan ( &mut self ) 
   {
     let mut open_res = open_file( &PathBuf::from( &self.path ));
     while self.next( open_res.as_mut().unwrap() ) == true { continue; }
+
+    if self.existing && self.err_state.is_none() && self.graph_uuid.is_none() 
+    {
+      self.err_state = Some( String::from( "Error: Graph not found." ));
+    }
   }
 
   pub fn next ( &mut self, f: &mut File ) -> bool 
@@ -80,7 +104,8 @@ impl WriteNewGraphPlanner<'_>
     if affix.is_some() 
     {
       //println!( "{:?}", affix );
-      let affix_type: Option<AffixType> = affix_to_type( &affix.unwrap() );
+      let affix_str = affix.unwrap();
+      let affix_type: Option<AffixType> = affix_to_type( &affix_str );
       if affix_type.is_some() 
       {
         match affix_type.unwrap() 
@@ -115,11 +140,37 @@ impl WriteNewGraphPlanner<'_>
             return true;
           }
 
+          AffixType::Node => 
+          {
+            let _ = NodeRow::skip( f );
+            return true;
+          }
+
+          AffixType::Edge => 
+          {
+            let _ = EdgeRow::skip( f );
+            return true;
+          }
+
+          AffixType::KVP => 
+          {
+            let _ = KVPRow::skip( &affix_str, f );
+            return true;
+          }
+
+          AffixType::Label => 
+          {
+            let _ = LabelRow::skip( f );
+            return true;
+          }
+
           AffixType::StartEmpty =>
           {
             self.process_start_empty( f );
             return true;
           }
+
+          AffixType::Empty => { return true; }
           
           AffixType::Placeholder => 
           {
@@ -239,7 +290,9 @@ impl WriteNewGraphPlanner<'_>
       let skip_res = CoreExecutor::skip_empty_cells( f );
       if skip_res.is_ok() 
       {
-        self.pages.last_mut().unwrap().empty_cell_count = Some( skip_res.unwrap().1 );
+        let page = self.pages.last_mut().unwrap();
+        page.empty_cell_count = Some( skip_res.unwrap().1 );
+        page.empty_regions.push(( page.empty_cell_start_pos.unwrap(), page.empty_cell_count.unwrap() ));
         return;
       }
     }
@@ -263,12 +316,20 @@ impl WriteNewGraphPlanner<'_>
 
   pub fn process_db_page_graph_row ( &mut self, f: &mut File ) 
   {
+    let pos_res = CoreExecutor::file_position( f );
     let graph_row_res = CoreExecutor::read_graph_row( f );
     if graph_row_res.is_ok() 
     {
-      if ( graph_row_res.unwrap().1 == self.graph_name.unwrap() ) == true 
+      let ( graph_uuid, graph_name ) = graph_row_res.unwrap();
+      self.graph_uuids.push( graph_uuid.clone() );
+      if graph_name == self.graph_name.unwrap() 
       {
-        self.err_state = Some( String::from( "Error: Graph exists." ));
+        if self.existing 
+        { 
+          self.graph_uuid = Some( graph_uuid );
+          self.graph_row_pos = pos_res.ok().map( | pos | pos - 8 );
+        }
+        else { self.err_state = Some( String::from( "Error: Graph exists." )); }
       }
       return;
     }
@@ -354,6 +415,8 @@ impl WriteNewGraphPlanner<'_>
     let mut ret: Vec<&PlannerPage> = Vec::new();
     for page in self.pages.iter() 
     {
+      // pages of a deleted graph keep its name, the uuid tells them apart
+      if self.graph_uuid.is_some() && page.graph_uuid != self.graph_uuid { continue; }
       if page.graph_name.is_some() 
       {
         if page.graph_name.as_ref().unwrap() == name { ret.push( page ); }
@@ -361,6 +424,33 @@ impl WriteNewGraphPlanner<'_>
     }
     ret
   }
+
+  /// ( start position, body cell count ) of the DataPages of deleted graphs, any graph can claim them
+  pub fn free_data_pages ( &self ) -> Vec<( u64, usize )>
+  {
+    let mut ret: Vec<( u64, usize )> = Vec::new();
+    for page in self.pages.iter() 
+    {
+      if page.graph_uuid.is_none() || page.end_pos.is_none() { continue; }
+      if self.graph_uuids.contains( page.graph_uuid.as_ref().unwrap() ) { continue; }
+
+      let body_start = page.start_pos + ( ROW_AFFIX_BYTES + GraphRow::size() ) as u64;
+      ret.push(( page.start_pos, (( page.end_pos.unwrap() - 8 - body_start ) / 8 ) as usize ));
+    }
+    ret
+  }
+
+  /// ( position, cell count ) of the empty cell regions on the DataPages of a graph
+  pub fn empty_regions_by_graph ( &self, name: &str ) -> Vec<( u64, usize )>
+  {
+    let mut ret: Vec<( u64, usize )> = Vec::new();
+    for page in self.fetch_data_pages_by_graph( name ).iter() 
+    {
+      // the count excludes the [STEMTY] cell itself
+      for ( position, count ) in page.empty_regions.iter() { ret.push(( *position, *count as usize + 1 )); }
+    }
+    ret
+  }
 }
 
 mod tests 
@@ -407,6 +497,7 @@ mod tests
     assert_eq!( planner.pages.get(0).unwrap().end_pos, Some( 8184 ) );
     assert_eq!( planner.pages.get(0).unwrap().empty_cell_count, Some( 493 ));
     assert_eq!( planner.pages.get(0).unwrap().empty_cell_start_pos, Some( 4224 ));
+    assert_eq!( planner.pages.get(0).unwrap().empty_regions, vec![ ( 4224, 493 ) ] );
     assert_eq!( planner.pages.get(0).unwrap().graph_name.is_some(), true );
     assert_eq!( planner.pages.get(0).unwrap().graph_uuid.is_some(), true );
 
@@ -2,6 +2,7 @@ pub mod core;
 pub mod reader;
 pub mod writer;
 pub mod core_planner;
+pub mod script;
 
 /* @version 0.3.0 */
 
@@ -1 +1,3 @@
-mod simple_match;
\ No newline at end of file
+pub mod projection;
+pub mod shortest_path;
+pub mod simple_match;
\ No newline at end of file
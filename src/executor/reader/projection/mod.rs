@@ -0,0 +1,271 @@
+use std::cmp::Ordering;
+use crate::cmd::{ ReturnClause, SortItem };
+use crate::cmd::predicate::sort_order;
+use crate::result_set::{ ResultSet, Value };
+
+/* @version 0.3.0 */
+
+/*
+  RETURN DISTINCT n.name AS name ORDER BY name DESC SKIP 1 LIMIT 5
+  RETURN n.team AS team, count(*) AS members ORDER BY members DESC
+
+  Bindings are projected one at a time. Without ORDER BY projection stops as soon as SKIP + LIMIT rows are kept,
+  with ORDER BY every row is sorted before SKIP and LIMIT apply.
+  Aggregates group bindings by the values of the other RETURN items, every group is a single row.
+
+  project()
+      :: check_variables()
+      :: aggregate_rows()
+      :: add_row()
+          :: sort_value()
+*/
+
+/// Project bindings onto the RETURN items
+pub fn project (
+  return_clause: &ReturnClause, bindings: &Vec<Vec<( String, Value )>>, variables: &Vec<String> ) -> Result<ResultSet, String>
+{
+  check_variables( return_clause, variables )?;
+
+  let columns: Vec<String> = return_clause.items.iter().map( | item | item.column().clone() ).collect();
+  let skip = return_clause.skip.unwrap_or( 0 );
+  let limit = return_clause.limit.unwrap_or( usize::MAX );
+  let aggregating = return_clause.items.iter().any( | item | item.aggregate.is_some() );
+  let streaming = return_clause.order_by.len() == 0 && aggregating == false;
+
+  // ( values, sort keys )
+  let mut rows: Vec<( Vec<Value>, Vec<Value> )> = Vec::new();
+  if aggregating
+  {
+    for values in aggregate_rows( return_clause, bindings )?.into_iter() { add_row( &mut rows, return_clause, values, None ); }
+  }
+  else
+  {
+    for binding in bindings.iter()
+    {
+      if streaming && rows.len() >= skip.saturating_add( limit ) { break; }
+
+      let values: Vec<Value> = return_clause.items.iter().map( | item | item.operand.resolve( binding )).collect();
+      add_row( &mut rows, return_clause, values, Some( binding ));
+    }
+  }
+
+  if streaming == false
+  {
+    // stable, rows with equal keys keep match order
+    rows.sort_by( | a, b |
+    {
+      for ( i, sort ) in return_clause.order_by.iter().enumerate()
+      {
+        let ord = sort_order( &a.1[i], &b.1[i] );
+        if ord != Ordering::Equal { return if sort.descending { ord.reverse() } else { ord }; }
+      }
+      Ordering::Equal
+    });
+  }
+
+  let mut rs = ResultSet::new( columns );
+  for ( values, _ ) in rows.into_iter().skip( skip ).take( limit ) { rs.push( values )?; }
+  Ok( rs )
+}
+
+/// RETURN items use match variables, ORDER BY items a returned column or a match variable
+fn check_variables ( return_clause: &ReturnClause, variables: &Vec<String> ) -> Result<bool, String>
+{
+  for item in return_clause.items.iter()
+  {
+    // count(*) has no variable
+    for var in item.operand.variables().iter()
+    {
+      if variables.contains( var ) == false { return Err( format!( "Error: Variable `{}` not defined.", var )); }
+    }
+  }
+
+  for sort in return_clause.order_by.iter()
+  {
+    if returned_index( sort, return_clause ).is_some() { continue; }
+    if return_clause.distinct
+    {
+      return Err( format!( "Error: ORDER BY `{}` must be returned with DISTINCT.", sort.expression ));
+    }
+    if return_clause.items.iter().any( | item | item.aggregate.is_some() )
+    {
+      return Err( format!( "Error: ORDER BY `{}` must be returned with aggregation.", sort.expression ));
+    }
+    let var = sort.operand.variable().unwrap();
+    if variables.contains( var ) == false { return Err( format!( "Error: Variable `{}` not defined.", var )); }
+  }
+  Ok( true )
+}
+
+/// Index of the RETURN item a sort item refers to, by alias or by expression
+fn returned_index ( sort: &SortItem, return_clause: &ReturnClause ) -> Option<usize>
+{
+  let alias = return_clause.items.iter().position( | item | item.alias.as_ref() == Some( &sort.expression ));
+  if alias.is_some() { return alias; }
+  return_clause.items.iter().position( | item | item.expression == sort.expression )
+}
+
+/// One row per group, values in RETURN order. Without grouping items there is always a row (count is 0).
+fn aggregate_rows ( return_clause: &ReturnClause, bindings: &Vec<Vec<( String, Value )>> ) -> Result<Vec<Vec<Value>>, String>
+{
+  let aggregate_count = return_clause.items.iter().filter( | item | item.aggregate.is_some() ).count();
+
+  // ( grouping values, values collected per aggregate )
+  let mut groups: Vec<( Vec<Value>, Vec<Vec<Value>> )> = Vec::new();
+  for binding in bindings.iter()
+  {
+    let keys: Vec<Value> = return_clause.items.iter()
+      .filter( | item | item.aggregate.is_none() )
+      .map( | item | item.operand.resolve( binding ))
+      .collect();
+
+    let found = groups.iter().position( | ( k, _ ) | same_values( k, &keys ));
+    let index = match found
+    {
+      Some( index ) => index,
+      None =>
+      {
+        groups.push(( keys, vec![ Vec::new(); aggregate_count ] ));
+        groups.len() - 1
+      }
+    };
+
+    let aggregated = return_clause.items.iter().filter( | item | item.aggregate.is_some() );
+    for ( i, item ) in aggregated.enumerate() { groups[index].1[i].push( item.operand.resolve( binding )); }
+  }
+  if groups.len() == 0 && aggregate_count == return_clause.items.len() 
+  { 
+    groups.push(( Vec::new(), vec![ Vec::new(); aggregate_count ] )); 
+  }
+
+  let mut rows: Vec<Vec<Value>> = Vec::new();
+  for ( keys, collected ) in groups.into_iter()
+  {
+    let ( mut keys_iter, mut collected_iter ) = ( keys.into_iter(), collected.iter() );
+    let mut values: Vec<Value> = Vec::new();
+    for item in return_clause.items.iter()
+    {
+      match item.aggregate.as_ref()
+      {
+        Some( aggregate ) => { values.push( aggregate.evaluate( collected_iter.next().unwrap() )? ); }
+        None => { values.push( keys_iter.next().unwrap() ); }
+      }
+    }
+    rows.push( values );
+  }
+  Ok( rows )
+}
+
+/// Keep a projected row unless DISTINCT has already seen it, sort keys come from the row or the binding
+fn add_row ( 
+  rows: &mut Vec<( Vec<Value>, Vec<Value> )>, return_clause: &ReturnClause, values: Vec<Value>, 
+  binding: Option<&Vec<( String, Value )>> )
+{
+  if return_clause.distinct && rows.iter().any( | ( v, _ ) | same_values( v, &values )) { return; }
+
+  let keys: Vec<Value> = return_clause.order_by.iter()
+    .map( | sort | sort_value( sort, return_clause, &values, binding ))
+    .collect();
+  rows.push(( values, keys ));
+}
+
+fn sort_value ( 
+  sort: &SortItem, return_clause: &ReturnClause, values: &Vec<Value>, binding: Option<&Vec<( String, Value )>> ) -> Value
+{
+  let index = returned_index( sort, return_clause );
+  if index.is_some() { return values[index.unwrap()].clone(); }
+  binding.map( | b | sort.operand.resolve( b )).unwrap_or( Value::Null )
+}
+
+/// DISTINCT rows, null equals null and 1 equals 1.0
+fn same_values ( a: &Vec<Value>, b: &Vec<Value> ) -> bool
+{
+  a.iter().zip( b.iter() ).all( | ( x, y ) | sort_order( x, y ) == Ordering::Equal )
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+  use crate::ast::{ parse_query, Clause };
+  use crate::parser::parse_syntax;
+  use crate::planner::lower_projection;
+
+  /// ReturnClause of `RETURN items`
+  fn return_clause ( items: &str ) -> ReturnClause
+  {
+    let query = parse_query( &parse_syntax( &format!( "RETURN {}", items ))).unwrap();
+    match &query.clauses[0]
+    {
+      Clause::Return( projection ) => lower_projection( projection, &Vec::new() ).unwrap(),
+      clause => { panic!( "unexpected clause {:?}", clause ); }
+    }
+  }
+
+  fn bindings () -> Vec<Vec<( String, Value )>>
+  {
+    vec![
+      vec![ ( String::from( "n" ), Value::from( "b" )) ],
+      vec![ ( String::from( "n" ), Value::Null ) ],
+      vec![ ( String::from( "n" ), Value::from( 2 )) ],
+      vec![ ( String::from( "n" ), Value::from( "a" )) ],
+      vec![ ( String::from( "n" ), Value::from( 1.5 )) ],
+      vec![ ( String::from( "n" ), Value::from( "b" )) ],
+    ]
+  }
+
+  #[test]
+  fn test_project ()
+  {
+    let variables = vec![ String::from( "n" ) ];
+    let clause = return_clause( "DISTINCT n AS v ORDER BY v" );
+    let rs = project( &clause, &bindings(), &variables ).unwrap();
+    assert_eq!( rs.columns, vec![ String::from( "v" ) ] );
+    assert_eq!( rs.column( "v" ), vec![ &Value::from( "a" ), &Value::from( "b" ), &Value::from( 1.5 ), &Value::from( 2 ), &Value::Null ] );
+
+    let desc = return_clause( "n ORDER BY n DESC SKIP 1 LIMIT 2" );
+    let rs1 = project( &desc, &bindings(), &variables ).unwrap();
+    assert_eq!( rs1.column( "n" ), vec![ &Value::from( 2 ), &Value::from( 1.5 ) ] );
+
+    // without ORDER BY rows keep match order
+    let limit = return_clause( "n LIMIT 2" );
+    let rs2 = project( &limit, &bindings(), &variables ).unwrap();
+    assert_eq!( rs2.column( "n" ), vec![ &Value::from( "b" ), &Value::Null ] );
+
+    let undefined = return_clause( "m.name" );
+    assert_eq!( project( &undefined, &bindings(), &variables ).unwrap_err(), String::from( "Error: Variable `m` not defined." ));
+
+    let distinct = return_clause( "DISTINCT n ORDER BY n.age" );
+    assert_eq!( project( &distinct, &bindings(), &variables ).unwrap_err(),
+      String::from( "Error: ORDER BY `n.age` must be returned with DISTINCT." ));
+  }
+
+  #[test]
+  fn test_project_aggregates ()
+  {
+    let variables = vec![ String::from( "n" ), String::from( "m" ) ];
+    let team_bindings = vec![
+      vec![ ( String::from( "n" ), Value::from( "core" )), ( String::from( "m" ), Value::from( 38 )) ],
+      vec![ ( String::from( "n" ), Value::from( "web" )), ( String::from( "m" ), Value::from( 27 )) ],
+      vec![ ( String::from( "n" ), Value::from( "core" )), ( String::from( "m" ), Value::from( 45 )) ],
+      vec![ ( String::from( "n" ), Value::from( "web" )), ( String::from( "m" ), Value::Null ) ],
+    ];
+
+    let grouped = return_clause( "n, count(*), sum(m) AS total ORDER BY total" );
+    let rs = project( &grouped, &team_bindings, &variables ).unwrap();
+    assert_eq!( rs.columns, vec![ String::from( "n" ), String::from( "count(*)" ), String::from( "total" ) ] );
+    assert_eq!( rs.column( "n" ), vec![ &Value::from( "web" ), &Value::from( "core" ) ] );
+    assert_eq!( rs.column( "count(*)" ), vec![ &Value::I64( 2 ), &Value::I64( 2 ) ] );
+    assert_eq!( rs.column( "total" ), vec![ &Value::I64( 27 ), &Value::I64( 83 ) ] );
+
+    // no grouping items, a single row even without bindings
+    let total = return_clause( "count(DISTINCT n)" );
+    assert_eq!( project( &total, &team_bindings, &variables ).unwrap().column( "count(DISTINCT n)" ), vec![ &Value::I64( 2 ) ] );
+    assert_eq!( project( &total, &Vec::new(), &variables ).unwrap().column( "count(DISTINCT n)" ), vec![ &Value::I64( 0 ) ] );
+    assert_eq!( project( &grouped, &Vec::new(), &variables ).unwrap().len(), 0 );
+
+    let unsorted = return_clause( "count(n) ORDER BY m" );
+    assert_eq!( project( &unsorted, &team_bindings, &variables ).unwrap_err(),
+      String::from( "Error: ORDER BY `m` must be returned with aggregation." ));
+  }
+}
@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+use crate::common::{ DirectionType, Edge };
+use crate::executor::reader::simple_match::edge_orientations;
+
+/* @version 0.3.0 */
+
+/*
+  MATCH p = shortestPath((a {city: 'Copenhagen'})-[:TRAVEL_ROUTE*]->(b {city: 'Malmö'})) FROM routes RETURN p
+
+  Bidirectional breadth first search: the smaller frontier grows one level at a time until both sides meet.
+  Every node of the meeting level lies on a shortest walk, walks are rebuilt from the parents of both sides.
+
+  shortest_walks()
+    :: SearchSide::expand()
+    :: SearchSide::chains()
+*/
+
+/// Breadth first search from one end of the pattern
+struct SearchSide
+{
+  root: String,
+  level: usize,
+  frontier: Vec<String>,
+  distances: HashMap<String, usize>,
+  parents: HashMap<String, Vec<( String, String )>>, // node -> ( edge id, node one level closer to the root )
+}
+impl SearchSide
+{
+  fn new ( root: &String ) -> SearchSide
+  {
+    let mut distances = HashMap::new();
+    distances.insert( root.clone(), 0 );
+    SearchSide { root: root.clone(), level: 0, frontier: vec![ root.clone() ], distances: distances, parents: HashMap::new() }
+  }
+
+  /// Visit the next level, every edge reaching a node of that level is kept as a parent
+  fn expand ( &mut self, adjacent: &HashMap<String, Vec<( String, String )>> )
+  {
+    self.level += 1;
+    let mut next: Vec<String> = Vec::new();
+    for node in self.frontier.iter()
+    {
+      let neighbours = adjacent.get( node );
+      if neighbours.is_none() { continue; }
+      for ( edge_id, neighbour ) in neighbours.unwrap().iter()
+      {
+        match self.distances.get( neighbour )
+        {
+          None =>
+          {
+            self.distances.insert( neighbour.clone(), self.level );
+            self.parents.insert( neighbour.clone(), vec![ ( edge_id.clone(), node.clone() ) ] );
+            next.push( neighbour.clone() );
+          }
+          Some( level ) =>
+          {
+            if *level == self.level { self.parents.get_mut( neighbour ).unwrap().push(( edge_id.clone(), node.clone() )); }
+          }
+        }
+      }
+    }
+    self.frontier = next;
+  }
+
+  /// Every shortest chain from node back to the root as ( edge id, next node ) hops
+  fn chains ( &self, node: &String ) -> Vec<Vec<( String, String )>>
+  {
+    if node == &self.root { return vec![ Vec::new() ]; }
+    let mut ret = Vec::new();
+    for ( edge_id, parent ) in self.parents.get( node ).unwrap().iter()
+    {
+      for chain in self.chains( parent ).into_iter()
+      {
+        let mut hops = vec![ ( edge_id.clone(), parent.clone() ) ];
+        hops.extend( chain );
+        ret.push( hops );
+      }
+    }
+    ret
+  }
+}
+
+/// Shortest walks from left to right as ( edge id, node id reached ) hops, only the first one unless all are requested
+pub fn shortest_walks (
+  edges: &Vec<&Edge>, pattern_dir: &DirectionType, left: &String, right: &String, min: usize, max: Option<usize>,
+  all: bool ) -> Vec<Vec<( String, String )>>
+{
+  if left == right
+  {
+    if min == 0 { return vec![ Vec::new() ]; }
+    return Vec::new();
+  }
+
+  // forward follows the pattern direction, backward walks against it from the right node
+  let mut forward: HashMap<String, Vec<( String, String )>> = HashMap::new();
+  let mut backward: HashMap<String, Vec<( String, String )>> = HashMap::new();
+  for edge in edges.iter()
+  {
+    for ( from_id, to_id ) in edge_orientations( edge, pattern_dir ).into_iter()
+    {
+      forward.entry( from_id.clone() ).or_default().push(( edge.id.clone(), to_id.clone() ));
+      backward.entry( to_id ).or_default().push(( edge.id.clone(), from_id ));
+    }
+  }
+
+  let mut left_side = SearchSide::new( left );
+  let mut right_side = SearchSide::new( right );
+  let mut meeting: Vec<String> = Vec::new();
+  while meeting.len() == 0
+  {
+    if left_side.frontier.len() == 0 || right_side.frontier.len() == 0 { return Vec::new(); }
+    if max.is_some() && left_side.level + right_side.level >= max.unwrap() { return Vec::new(); }
+
+    if left_side.frontier.len() <= right_side.frontier.len()
+    {
+      left_side.expand( &forward );
+      meeting = left_side.frontier.iter().filter( | node | right_side.distances.contains_key( *node )).cloned().collect();
+    }
+    else
+    {
+      right_side.expand( &backward );
+      meeting = right_side.frontier.iter().filter( | node | left_side.distances.contains_key( *node )).cloned().collect();
+    }
+  }
+
+  let mut ret: Vec<Vec<( String, String )>> = Vec::new();
+  for node in meeting.iter()
+  {
+    for chain in left_side.chains( node ).into_iter()
+    {
+      // left chain runs node -> left, reverse it into left -> node
+      let mut head: Vec<( String, String )> = Vec::new();
+      let mut reached = node.clone();
+      for ( edge_id, parent ) in chain.into_iter()
+      {
+        head.push(( edge_id, reached ));
+        reached = parent;
+      }
+      head.reverse();
+
+      for tail in right_side.chains( node ).into_iter()
+      {
+        let mut walk = head.clone();
+        walk.extend( tail );
+        ret.push( walk );
+        if all == false { return ret; }
+      }
+    }
+  }
+  ret
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  fn edge ( id: &str, left: &str, right: &str, dir: DirectionType ) -> Edge
+  {
+    Edge::new( String::from( id ), Some( String::from( "ROUTE" )), String::from( left ), String::from( right ), dir, Vec::new() )
+  }
+
+  fn hop ( edge_id: &str, node_id: &str ) -> ( String, String ) { ( String::from( edge_id ), String::from( node_id )) }
+
+  #[test]
+  fn test_shortest_walks ()
+  {
+    /*
+      a -> b -> d -> e
+      a -> c -> d
+      a -> x -> y -> z -> e
+    */
+    let stored = vec![
+      edge( "ab", "a", "b", DirectionType::Right ),
+      edge( "bd", "b", "d", DirectionType::Right ),
+      edge( "ac", "a", "c", DirectionType::Right ),
+      edge( "cd", "c", "d", DirectionType::Right ),
+      edge( "de", "d", "e", DirectionType::Right ),
+      edge( "ax", "a", "x", DirectionType::Right ),
+      edge( "xy", "x", "y", DirectionType::Right ),
+      edge( "yz", "y", "z", DirectionType::Right ),
+      edge( "ze", "z", "e", DirectionType::Right ),
+    ];
+    let edges: Vec<&Edge> = stored.iter().collect();
+    let id = | s: &str | String::from( s );
+
+    assert_eq!( shortest_walks( &edges, &DirectionType::Right, &id( "a" ), &id( "e" ), 1, None, false ),
+      vec![ vec![ hop( "ab", "b" ), hop( "bd", "d" ), hop( "de", "e" ) ] ] );
+    assert_eq!( shortest_walks( &edges, &DirectionType::Right, &id( "a" ), &id( "e" ), 1, None, true ),
+      vec![
+        vec![ hop( "ab", "b" ), hop( "bd", "d" ), hop( "de", "e" ) ],
+        vec![ hop( "ac", "c" ), hop( "cd", "d" ), hop( "de", "e" ) ] ] );
+
+    // against the stored direction, and out of range
+    assert_eq!( shortest_walks( &edges, &DirectionType::Left, &id( "e" ), &id( "a" ), 1, None, true ).len(), 2 );
+    assert_eq!( shortest_walks( &edges, &DirectionType::Right, &id( "e" ), &id( "a" ), 1, None, true ).len(), 0 );
+    assert_eq!( shortest_walks( &edges, &DirectionType::Right, &id( "a" ), &id( "e" ), 1, Some( 2 ), true ).len(), 0 );
+    assert_eq!( shortest_walks( &edges, &DirectionType::Right, &id( "a" ), &id( "a" ), 0, None, false ), vec![ Vec::new() ] );
+    assert_eq!( shortest_walks( &edges, &DirectionType::Right, &id( "a" ), &id( "a" ), 1, None, false ).len(), 0 );
+  }
+}
@@ -1,4 +1,16 @@
+use std::fs::{ File };
+use std::path::PathBuf;
+use crate::cmd::{ BracketStatement, HopRange, MatchStatement, QueryStage, ReadClause, UnwindClause, WithClause };
+use crate::cmd::predicate::Predicate;
 use crate::cmd::transaction::Transaction;
+use crate::common::{ DEFAULT_GRAPH, DirectionType, Edge, find_property, NEProperty, Node };
+use crate::datagramv2::internal_grams::{ Label };
+use crate::datagramv2::rows::{ affix_to_type, AffixType, EdgeRow, KVPRow, LabelRow, NodeRow, PageType };
+use crate::executor::core::CoreExecutor;
+use crate::executor::reader::projection::project;
+use crate::executor::reader::shortest_path::shortest_walks;
+use crate::result_set::{ Path, ResultSet, Value };
+use crate::utils::{ open_file, parse_padded_str };
 
 /*
 MATCH () FROM devs;
@@ -12,33 +24,919 @@ MATCH (n:Stop)
 MATCH (n:Developer)
 FROM devs
 RETURN n AS Developer
+
+MATCH (n {name: 'Alice'}) FROM devs RETURN n.age
+
+MATCH (a:Developer)-[r:KNOWS]->(b) FROM devs RETURN b.name
+
+MATCH p = (a {name: 'Alice'})-[:KNOWS*1..3]->(b) FROM devs RETURN nodes(p), length(p)
+
+MATCH (a)-[r:KNOWS]->() FROM devs WITH a, count(r) AS c WHERE c > 1 MATCH (a)<-[:KNOWS]-(b) RETURN a.name, c, b.name
 */
 
-pub struct SimpleMatchExecutor<'a> 
+/*
+  SimpleMatchExecutor
+    :: new()
+    :: execute()
+        :: read_graph()
+            :: set_graph_name()
+            :: next()
+                :: process_graph_row()
+                :: process_node_row()
+                :: process_edge_row()
+                :: process_kvp_row()
+                :: process_label_row()
+            :: attach_properties()
+        :: read_stage()
+          :: match_pattern()
+            :: expand_clause()
+                :: expand_relationship()
+                    :: walk()
+                        :: can_traverse()
+                    :: edge_orientations()
+                    :: bind_walk()
+                :: expand_shortest()
+                    :: shortest_walks()
+                    :: bind_walk()
+                :: expand_node()
+                    :: bind_node()
+                        :: matches_node()
+                :: bind_path()
+          :: filter_bindings()
+        :: with_stage()
+        :: unwind_stage()
+    :: result_set()
+*/
+/// Partial match of a pattern
+#[derive( Debug, Clone )]
+struct PatternRow
+{
+  binding: Vec<( String, Value )>,
+  slots: Vec<( u16, String )>, // ( MatchStatement order, node id ) including anonymous nodes
+  steps: Vec<( u16, Vec<( String, String )> )>, // ( BracketStatement order, ( edge id, node id reached ) per hop )
+}
+impl PatternRow
+{
+  fn new ( binding: Vec<( String, Value )> ) -> PatternRow { PatternRow { binding: binding, slots: Vec::new(), steps: Vec::new() } }
+
+  fn uses_edge ( &self, edge_id: &String ) -> bool 
+  { 
+    self.steps.iter().any( | ( _, hops ) | hops.iter().any( | ( id, _ ) | id == edge_id )) 
+  }
+}
+
+/// (left)-[bracket]-(right) of a pattern
+struct PatternHop<'p>
+{
+  bracket: &'p BracketStatement,
+  left: &'p MatchStatement,
+  right: &'p MatchStatement,
+}
+
+pub struct SimpleMatchExecutor<'a>
 {
   pub transaction: &'a Transaction,
   pub path: &'a str,
   pub page_size: usize,
+  pub graph_name: Option<Label>,
+  pub graph_uuid: Option<String>,
+  pub current_page_type: Option<PageType>,
+  pub in_graph: bool, // current DataPage belongs to the matched graph
+  pub nodes: Vec<Node>, // matched nodes for node-only patterns, every graph node otherwise
+  pub edges: Vec<Edge>,
+  pub properties: Vec<( String, NEProperty )>, // ( owner id, property ) of every KVPRow and LabelRow in the graph
+  pub property_rows: Vec<( u64, usize, String, NEProperty )>, // ( position, cell count, owner id, property ) per row
+  pub row_positions: Vec<( u64, usize, String )>, // ( position, cell count, node or edge id ) per NodeRow and EdgeRow
+  pub bindings: Vec<Vec<( String, Value )>>, // ( variable, value ) per matched row, projected by WITH
+  pub next_graph_order: u64, // graph order following the last stored row
+  pub err_state: Option<String>,
 }
 
 impl SimpleMatchExecutor<'_>
 {
   pub fn new<'a> ( t: &'a Transaction, path: &'a str, page_size: usize ) -> SimpleMatchExecutor<'a>
   {
-    SimpleMatchExecutor 
+    SimpleMatchExecutor
     {
-      transaction: t, 
-      path: path, 
+      transaction: t,
+      path: path,
       page_size: page_size,
-    } 
+      graph_name: None,
+      graph_uuid: None,
+      current_page_type: None,
+      in_graph: false,
+      nodes: Vec::new(),
+      edges: Vec::new(),
+      properties: Vec::new(),
+      property_rows: Vec::new(),
+      row_positions: Vec::new(),
+      bindings: Vec::new(),
+      next_graph_order: 0,
+      err_state: None,
+    }
   }
 
-  pub fn execute ( &mut self ) {}
+  pub fn execute ( &mut self )
+  {
+    self.read_graph();
+    let transaction = self.transaction;
+    if self.err_state.is_some() || ( transaction.read_clause.is_none() && transaction.stages.len() == 0 ) { return; }
+
+    // stages run in query order, each one starts from the bindings of the last
+    let mut scope: Vec<String> = Vec::new();
+    self.bindings = vec![ Vec::new() ];
+    let mut stage_res: Result<bool, String> = Ok( true );
+    if transaction.read_clause.is_some() { stage_res = self.read_stage( transaction.read_clause.as_ref().unwrap(), &mut scope ); }
+    for stage in transaction.stages.iter()
+    {
+      if stage_res.is_err() { break; }
+      stage_res = match stage
+      {
+        QueryStage::Read( next_clause ) => self.read_stage( next_clause, &mut scope ),
+        QueryStage::With( with_clause ) => self.with_stage( with_clause, &mut scope ),
+        QueryStage::Unwind( unwind_clause ) => self.unwind_stage( unwind_clause, &mut scope ),
+      };
+    }
+    if stage_res.is_err()
+    {
+      self.err_state = Some( stage_res.unwrap_err() );
+      return;
+    }
+
+    // node-only patterns keep only the bound nodes
+    if transaction.stages.len() == 0 && transaction.read_clause.as_ref().unwrap().bracket_statements.len() == 0
+    {
+      let bindings = &self.bindings;
+      self.nodes.retain( | node | bindings.iter().any( | binding | 
+        binding.iter().any( | ( _, val ) | val.as_node().map( | n | n.id == node.id ) == Some( true ))));
+    }
+  }
+
+  /// Read every node and edge of the graph with their properties, without matching
+  pub fn read_graph ( &mut self )
+  {
+    let name_res = self.set_graph_name();
+    if name_res.is_err()
+    {
+      self.err_state = Some( name_res.unwrap_err() );
+      return;
+    }
+
+    let open_res = open_file( &PathBuf::from( &self.path ));
+    if open_res.is_err()
+    {
+      self.err_state = Some( String::from( "Error opening database file." ));
+      return;
+    }
+
+    let mut f = open_res.unwrap();
+    while self.next( &mut f ) == true { continue; }
+
+    if self.err_state.is_none() && self.graph_uuid.is_none()
+    {
+      self.err_state = Some( String::from( "Error: Graph not found." ));
+      return;
+    }
+
+    // KVPRows may live anywhere in the graph, so rows are only matched once every row has been read
+    self.attach_properties();
+  }
+
+  pub fn next ( &mut self, f: &mut File ) -> bool
+  {
+    let affix = CoreExecutor::next_affix( f );
+    if affix.is_some()
+    {
+      let affix_str = affix.unwrap();
+      let affix_type: Option<AffixType> = affix_to_type( &affix_str );
+      if affix_type.is_some()
+      {
+        match affix_type.unwrap()
+        {
+          AffixType::DBPage =>
+          {
+            self.toggle_current_page( PageType::DBPage );
+            return true;
+          }
+
+          AffixType::DataPage =>
+          {
+            self.toggle_current_page( PageType::DataPage );
+            return true;
+          }
+
+          AffixType::BuildId =>
+          {
+            CoreExecutor::skip_build_id_row( f );
+            return true;
+          }
+
+          AffixType::DBNickname =>
+          {
+            CoreExecutor::skip_db_nickname_row( f );
+            return true;
+          }
+
+          AffixType::Graph => { return self.process_graph_row( f ); }
+          AffixType::Node => { return self.process_node_row( f ); }
+          AffixType::KVP => { return self.process_kvp_row( &affix_str, f ); }
+          AffixType::Label => { return self.process_label_row( f ); }
+
+          AffixType::Edge => { return self.process_edge_row( f ); }
+
+          AffixType::StartEmpty =>
+          {
+            let skip_res = CoreExecutor::skip_empty_cells( f );
+            if skip_res.is_ok() { return true; }
+            self.err_state = Some( skip_res.unwrap_err() );
+            return false;
+          }
+
+          AffixType::Empty => { return true; }
+          AffixType::Placeholder => { return true; }
+          AffixType::End => { return false; }
+          AffixType::AJMPage => {}
+        }
+      }
+    }
+    self.err_state = Some( String::from( "Error reading row affix." ));
+    false
+  }
+}
+
+impl SimpleMatchExecutor<'_>
+{
+  /// Resolve graph name from FromClause, falls back to the default graph
+  pub fn set_graph_name ( &mut self ) -> Result<bool, String>
+  {
+    let mut name = String::from( DEFAULT_GRAPH );
+    if self.transaction.from_clause.is_some()
+    {
+      name = self.transaction.from_clause.as_ref().unwrap().graph_name.clone();
+    }
+
+    let label_res = Label::new( name );
+    if label_res.is_ok()
+    {
+      self.graph_name = Some( label_res.unwrap() );
+      return Ok( true );
+    }
+    Err( String::from( "Error finding graph name." ))
+  }
+
+  /// GraphRows on the DBPage resolve the graph uuid, GraphRows on a DataPage mark page ownership
+  pub fn process_graph_row ( &mut self, f: &mut File ) -> bool
+  {
+    let graph_row_res = CoreExecutor::read_graph_row( f );
+    if graph_row_res.is_err()
+    {
+      self.err_state = Some( graph_row_res.unwrap_err() );
+      return false;
+    }
+
+    let ( graph_uuid, graph_name ) = graph_row_res.unwrap();
+    match self.current_page_type
+    {
+      Some( PageType::DBPage ) =>
+      {
+        if graph_name == self.graph_name.as_ref().unwrap().unwrap() { self.graph_uuid = Some( graph_uuid ); }
+      }
+
+      Some( PageType::DataPage ) =>
+      {
+        self.in_graph = self.graph_uuid.is_some() && self.graph_uuid.as_ref().unwrap() == &graph_uuid;
+      }
+
+      _ => {}
+    }
+    true
+  }
+
+  /// Decode NodeRow, filtering happens after properties are attached
+  pub fn process_node_row ( &mut self, f: &mut File ) -> bool
+  {
+    if self.in_graph == false
+    {
+      let _ = NodeRow::skip( f );
+      return true;
+    }
+
+    let position = CoreExecutor::file_position( f ).unwrap_or( 8 ) - 8;
+    let node_row_res = NodeRow::read( f );
+    if node_row_res.is_err()
+    {
+      self.err_state = Some( node_row_res.unwrap_err() );
+      return false;
+    }
+
+    let ( graph_order, node_id, primary_label ) = node_row_res.unwrap();
+    self.next_graph_order = self.next_graph_order.max( graph_order + 1 );
+    self.row_positions.push(( position, NodeRow::cell_count(), node_id.clone() ));
+    self.nodes.push( Node::new( node_id, Some( primary_label ), Vec::new() ));
+    true
+  }
+
+  /// Decode EdgeRow of the matched graph
+  pub fn process_edge_row ( &mut self, f: &mut File ) -> bool
+  {
+    if self.in_graph == false
+    {
+      let _ = EdgeRow::skip( f );
+      return true;
+    }
+
+    let position = CoreExecutor::file_position( f ).unwrap_or( 8 ) - 8;
+    let edge_row_res = EdgeRow::read( f );
+    if edge_row_res.is_err()
+    {
+      self.err_state = Some( edge_row_res.unwrap_err() );
+      return false;
+    }
+    let ( graph_order, edge ) = edge_row_res.unwrap();
+    self.next_graph_order = self.next_graph_order.max( graph_order + 1 );
+    self.row_positions.push(( position, EdgeRow::cell_count(), edge.id.clone() ));
+    self.edges.push( edge );
+    true
+  }
+
+  /// Decode KVPRow of the matched graph
+  pub fn process_kvp_row ( &mut self, affix: &str, f: &mut File ) -> bool
+  {
+    if self.in_graph == false
+    {
+      let _ = KVPRow::skip( affix, f );
+      return true;
+    }
+
+    let position = CoreExecutor::file_position( f ).unwrap_or( 8 ) - 8;
+    let kvp_row_res = KVPRow::read( affix, f );
+    if kvp_row_res.is_err()
+    {
+      self.err_state = Some( kvp_row_res.unwrap_err() );
+      return false;
+    }
+
+    // list rows vary in size, the row spans everything read
+    let end = CoreExecutor::file_position( f ).unwrap_or( position );
+    let ( owner, prop ) = kvp_row_res.unwrap();
+    self.property_rows.push(( position, (( end - position ) / 8 ) as usize, owner.clone(), prop.clone() ));
+    self.properties.push(( owner, prop ));
+    true
+  }
+
+  /// Decode LabelRow of the matched graph, labels are attached as Tags
+  pub fn process_label_row ( &mut self, f: &mut File ) -> bool
+  {
+    if self.in_graph == false
+    {
+      let _ = LabelRow::skip( f );
+      return true;
+    }
+
+    let position = CoreExecutor::file_position( f ).unwrap_or( 8 ) - 8;
+    let label_row_res = LabelRow::read( f );
+    if label_row_res.is_err()
+    {
+      self.err_state = Some( label_row_res.unwrap_err() );
+      return false;
+    }
+
+    let ( owner, label ) = label_row_res.unwrap();
+    self.property_rows.push(( position, LabelRow::cell_count(), owner.clone(), NEProperty::Tag( label.clone() )));
+    self.properties.push(( owner, NEProperty::Tag( label )));
+    true
+  }
+
+  /// Move properties onto the nodes and edges that own them
+  pub fn attach_properties ( &mut self )
+  {
+    let properties = std::mem::take( &mut self.properties );
+    for ( owner, prop ) in properties.into_iter()
+    {
+      let node_opt = self.nodes.iter_mut().find( | node | node.id == owner );
+      if node_opt.is_some() 
+      { 
+        node_opt.unwrap().io_add_property( prop ); 
+        continue;
+      }
+
+      let edge_opt = self.edges.iter_mut().find( | edge | edge.id == owner );
+      if edge_opt.is_some() { edge_opt.unwrap().properties.push( prop ); }
+    }
+  }
+
+  /// Match a ReadClause from the current bindings and keep the rows its WHERE holds for
+  pub fn read_stage ( &mut self, read_clause: &ReadClause, scope: &mut Vec<String> ) -> Result<bool, String>
+  {
+    for variable in read_clause.variables().into_iter()
+    {
+      if scope.contains( &variable ) == false { scope.push( variable ); }
+    }
+    check_predicate_variables( read_clause, scope )?;
+    self.match_pattern( read_clause )?;
+    self.filter_bindings( read_clause );
+    Ok( true )
+  }
+
+  /// Project the bindings onto the WITH items, only its columns stay in scope
+  pub fn with_stage ( &mut self, with_clause: &WithClause, scope: &mut Vec<String> ) -> Result<bool, String>
+  {
+    let rs = project( &with_clause.projection, &self.bindings, scope )?;
+    *scope = rs.columns.clone();
+    for predicate in with_clause.predicates.iter()
+    {
+      for var in predicate.variables().iter()
+      {
+        if scope.contains( var ) == false { return Err( format!( "Error: Variable `{}` not defined.", var )); }
+      }
+    }
+
+    self.bindings = rs.rows.into_iter()
+      .map( | row | row.columns.into_iter().zip( row.values.into_iter() ).collect() )
+      .filter( | binding | with_clause.predicates.iter().all( | p | p.evaluate( binding ) == Some( true )))
+      .collect();
+    Ok( true )
+  }
+
+  /// One row per list element for every binding, null unwinds to no rows and any other value to itself
+  pub fn unwind_stage ( &mut self, unwind_clause: &UnwindClause, scope: &mut Vec<String> ) -> Result<bool, String>
+  {
+    for var in unwind_clause.operand.variables().iter()
+    {
+      if scope.contains( var ) == false { return Err( format!( "Error: Variable `{}` not defined.", var )); }
+    }
+
+    let mut bindings: Vec<Vec<( String, Value )>> = Vec::new();
+    for binding in self.bindings.iter()
+    {
+      let values = match unwind_clause.operand.resolve( binding )
+      {
+        Value::List( values ) => values,
+        Value::Null => Vec::new(),
+        value => vec![ value ],
+      };
+      for value in values.into_iter()
+      {
+        let mut row = binding.clone();
+        row.retain( | ( var, _ ) | var != &unwind_clause.variable );
+        row.push(( unwind_clause.variable.clone(), value ));
+        bindings.push( row );
+      }
+    }
+    self.bindings = bindings;
+    if scope.contains( &unwind_clause.variable ) == false { scope.push( unwind_clause.variable.clone() ); }
+    Ok( true )
+  }
+
+  /// Bind every MatchStatement and relationship of the ReadClause clause by clause, a variable used more than once 
+  /// binds the same node or edge everywhere. Rows an OPTIONAL MATCH does not extend are kept with its variables null.
+  pub fn match_pattern ( &mut self, read_clause: &ReadClause ) -> Result<bool, String>
+  {
+    let bindings = std::mem::take( &mut self.bindings );
+    let mut rows: Vec<PatternRow> = bindings.into_iter().map( PatternRow::new ).collect();
+
+    for ( start, end, optional ) in read_clause.clause_ranges().into_iter()
+    {
+      let in_clause = | order: u16 | order >= start && ( end.is_none() || order < end.unwrap() );
+      if optional == false
+      {
+        rows = self.expand_clause( read_clause, rows, &in_clause )?;
+        continue;
+      }
+
+      let predicates: Vec<&Predicate> = read_clause.optional_predicates.iter()
+        .filter( | ( order, _ ) | *order == start )
+        .map( | ( _, predicate ) | predicate )
+        .collect();
+      let mut variables: Vec<String> = Vec::new();
+      for stmt in read_clause.match_statements.iter().filter( | stmt | in_clause( stmt.order ))
+      {
+        if stmt.transaction_label.is_some() { variables.push( stmt.transaction_label.clone().unwrap() ); }
+      }
+      for stmt in read_clause.bracket_statements.iter().filter( | stmt | in_clause( stmt.order ))
+      {
+        if stmt.transaction_label.is_some() { variables.push( stmt.transaction_label.clone().unwrap() ); }
+      }
+      for ( variable, _ ) in read_clause.path_variables.iter().filter( | ( _, order ) | in_clause( *order ))
+      {
+        variables.push( variable.clone() );
+      }
+
+      let mut extended: Vec<PatternRow> = Vec::new();
+      for row in rows.into_iter()
+      {
+        let mut matched = self.expand_clause( read_clause, vec![ row.clone() ], &in_clause )?;
+        matched.retain( | next | predicates.iter().all( | p | p.evaluate( &next.binding ) == Some( true )));
+        if matched.len() > 0
+        {
+          extended.append( &mut matched );
+          continue;
+        }
+
+        let mut padded = row;
+        for variable in variables.iter()
+        {
+          if padded.binding.iter().any( | ( var, _ ) | var == variable ) == false 
+          { 
+            padded.binding.push(( variable.clone(), Value::Null )); 
+          }
+        }
+        extended.push( padded );
+      }
+      rows = extended;
+    }
+
+    self.bindings = rows.into_iter().map( | row | row.binding ).collect();
+    Ok( true )
+  }
+
+  /// Bind the relationships, unconnected nodes and paths of a single MATCH clause
+  fn expand_clause ( 
+    &self, read_clause: &ReadClause, rows: Vec<PatternRow>, in_clause: &dyn Fn( u16 ) -> bool ) -> Result<Vec<PatternRow>, String>
+  {
+    let node_only = read_clause.bracket_statements.len() == 0;
+    let mut rows = rows;
+
+    let mut brackets: Vec<&BracketStatement> = read_clause.bracket_statements.iter()
+      .filter( | bracket | in_clause( bracket.order ))
+      .collect();
+    brackets.sort_by_key( | bracket | bracket.order );
+    let mut connected: Vec<u16> = Vec::new();
+    for bracket in brackets.into_iter()
+    {
+      let ( left_opt, right_opt ) = read_clause.bracket_neighbors( bracket );
+      if left_opt.is_none() || right_opt.is_none() { return Err( String::from( "Error: Relationship requires two nodes." )); }
+      connected.push( left_opt.unwrap().order );
+      connected.push( right_opt.unwrap().order );
+
+      let shortest = read_clause.shortest_paths.iter()
+        .find( | ( start, _ ) | read_clause.path_brackets( *start ).iter().any( | b | b.order == bracket.order ));
+      let hop = PatternHop { bracket: bracket, left: left_opt.unwrap(), right: right_opt.unwrap() };
+      match shortest
+      {
+        // the planner checked the pattern is a single variable-length relationship
+        Some(( _, all )) => { rows = self.expand_shortest( rows, &hop, *all ); }
+        None => { rows = self.expand_relationship( rows, &hop ); }
+      }
+    }
+
+    // (a:Developer), (b:Administrator) and nodes without relationships
+    for stmt in read_clause.match_statements.iter().filter( | stmt | in_clause( stmt.order ))
+    {
+      if connected.contains( &stmt.order ) { continue; }
+      rows = self.expand_node( rows, stmt, node_only );
+    }
+
+    for ( variable, start ) in read_clause.path_variables.iter().filter( | ( _, order ) | in_clause( *order ))
+    {
+      let brackets = read_clause.path_brackets( *start );
+      for row in rows.iter_mut()
+      {
+        let path = self.bind_path( row, *start, &brackets );
+        row.binding.push(( variable.clone(), Value::Path( path )));
+      }
+    }
+    Ok( rows )
+  }
+
+  /// Nodes and relationships a row bound from the first node of a pattern through its relationships
+  fn bind_path ( &self, row: &PatternRow, start: u16, brackets: &Vec<&BracketStatement> ) -> Path
+  {
+    let mut nodes: Vec<Node> = Vec::new();
+    let mut edges: Vec<Edge> = Vec::new();
+    let first = row.slots.iter().find( | ( order, _ ) | *order == start );
+    if first.is_some()
+    {
+      nodes.push( self.nodes.iter().find( | node | node.id == first.unwrap().1 ).unwrap().clone() );
+    }
+
+    for bracket in brackets.iter()
+    {
+      let steps = row.steps.iter().find( | ( order, _ ) | *order == bracket.order );
+      if steps.is_none() { continue; }
+      for ( edge_id, node_id ) in steps.unwrap().1.iter()
+      {
+        edges.push( self.edges.iter().find( | edge | &edge.id == edge_id ).unwrap().clone() );
+        nodes.push( self.nodes.iter().find( | node | &node.id == node_id ).unwrap().clone() );
+      }
+    }
+    Path::new( nodes, edges )
+  }
+
+  /// Extend every row with each walk that fits (left)-[bracket]-(right), a single relationship unless the bracket 
+  /// has a hop range
+  fn expand_relationship ( &self, rows: Vec<PatternRow>, hop: &PatternHop ) -> Vec<PatternRow>
+  {
+    let bracket = hop.bracket;
+    let hops = bracket.hops.clone().unwrap_or( HopRange { min: 1, max: Some( 1 ) });
+    let mut ret: Vec<PatternRow> = Vec::new();
+    for row in rows.iter()
+    {
+      // a repeated variable re-binds the same relationships
+      let bound = bracket.transaction_label.as_ref()
+        .and_then( | variable | row.binding.iter().find( | ( var, _ ) | var == variable ))
+        .map( | ( _, val ) | val );
+      let reuse: Option<Vec<String>> = match bound
+      {
+        Some( Value::Edge( edge )) => Some( vec![ edge.id.clone() ] ),
+        Some( Value::List( vals )) => 
+        {
+          let ids: Option<Vec<String>> = vals.iter().map( | val | val.as_edge().map( | edge | edge.id.clone() )).collect();
+          if ids.is_none() { continue; }
+          ids
+        }
+        Some( _ ) => { continue; }
+        None => None,
+      };
+
+      // ( left node id, walk )
+      let mut walks: Vec<( String, Vec<( String, String )> )> = Vec::new();
+      if hops.min == 0 && reuse.as_ref().map( | ids | ids.len() == 0 ).unwrap_or( true )
+      {
+        for node in self.nodes.iter() { walks.push(( node.id.clone(), Vec::new() )); }
+      }
+      for edge in self.edges.iter()
+      {
+        if self.can_traverse( row, bracket, &reuse, &Vec::new(), edge ) == false { continue; }
+        for ( left_id, right_id ) in edge_orientations( edge, &bracket.edge_dir ).into_iter()
+        {
+          let mut left_row = row.clone();
+          if self.bind_node( &mut left_row, hop.left, &left_id, false ) == false { continue; }
+          let mut found: Vec<Vec<( String, String )>> = Vec::new();
+          self.walk( row, bracket, &hops, &reuse, &mut vec![ ( edge.id.clone(), right_id ) ], &mut found );
+          for steps in found.into_iter() { walks.push(( left_id.clone(), steps )); }
+        }
+      }
+
+      for ( left_id, steps ) in walks.into_iter()
+      {
+        let next = self.bind_walk( row, hop, left_id, steps, reuse.is_some() );
+        if next.is_some() { ret.push( next.unwrap() ); }
+      }
+    }
+    ret
+  }
+
+  /// Extend every row with the shortest walks between each pair of nodes left and right accept
+  fn expand_shortest ( &self, rows: Vec<PatternRow>, hop: &PatternHop, all: bool ) -> Vec<PatternRow>
+  {
+    let bracket = hop.bracket;
+    let hops = bracket.hops.as_ref().unwrap();
+    let mut ret: Vec<PatternRow> = Vec::new();
+    for row in rows.iter()
+    {
+      let bound = bracket.transaction_label.as_ref().map( | variable | row.binding.iter().any( | ( var, _ ) | var == variable ));
+      if bound == Some( true ) { continue; }
+
+      let edges: Vec<&Edge> = self.edges.iter()
+        .filter( | edge | matches_edge( edge, bracket ) && row.uses_edge( &edge.id ) == false )
+        .collect();
+      let candidates = | stmt: &MatchStatement | -> Vec<String>
+      {
+        self.nodes.iter()
+          .filter( | node | self.bind_node( &mut row.clone(), stmt, &node.id, false ))
+          .map( | node | node.id.clone() )
+          .collect()
+      };
+      let rights = candidates( hop.right );
+      for left_id in candidates( hop.left ).into_iter()
+      {
+        for right_id in rights.iter()
+        {
+          for steps in shortest_walks( &edges, &bracket.edge_dir, &left_id, right_id, hops.min, hops.max, all ).into_iter()
+          {
+            let next = self.bind_walk( row, hop, left_id.clone(), steps, false );
+            if next.is_some() { ret.push( next.unwrap() ); }
+          }
+        }
+      }
+    }
+    ret
+  }
+
+  /// Bind left, the relationships of a walk (unless their variable is already bound) and the node it ends at
+  fn bind_walk ( 
+    &self, row: &PatternRow, hop: &PatternHop, left_id: String, steps: Vec<( String, String )>, reused: bool ) -> Option<PatternRow>
+  {
+    let bracket = hop.bracket;
+    let mut next = row.clone();
+    if self.bind_node( &mut next, hop.left, &left_id, false ) == false { return None; }
+    if bracket.transaction_label.is_some() && reused == false
+    {
+      let edges: Vec<Value> = steps.iter()
+        .map( | ( edge_id, _ ) | Value::Edge( self.edges.iter().find( | edge | &edge.id == edge_id ).unwrap().clone() ))
+        .collect();
+      let val = match bracket.hops
+      {
+        Some( _ ) => Value::List( edges ),
+        None => edges.into_iter().next().unwrap(),
+      };
+      next.binding.push(( bracket.transaction_label.clone().unwrap(), val ));
+    }
+    let right_id = steps.last().map( | ( _, node_id ) | node_id.clone() ).unwrap_or( left_id );
+    next.steps.push(( bracket.order, steps ));
+    if self.bind_node( &mut next, hop.right, &right_id, false ) == false { return None; }
+    Some( next )
+  }
+
+  /// Collect every walk within the hop range that continues steps, depth first
+  fn walk ( 
+    &self, row: &PatternRow, bracket: &BracketStatement, hops: &HopRange, reuse: &Option<Vec<String>>, 
+    steps: &mut Vec<( String, String )>, found: &mut Vec<Vec<( String, String )>> )
+  {
+    let complete = reuse.as_ref().map( | ids | ids.len() == steps.len() ).unwrap_or( true );
+    if steps.len() >= hops.min && complete { found.push( steps.clone() ); }
+    if hops.max.is_some() && steps.len() >= hops.max.unwrap() { return; }
+    if reuse.is_some() && steps.len() >= reuse.as_ref().unwrap().len() { return; }
+
+    let current = steps.last().unwrap().1.clone();
+    for edge in self.edges.iter()
+    {
+      if self.can_traverse( row, bracket, reuse, steps, edge ) == false { continue; }
+      for ( from_id, to_id ) in edge_orientations( edge, &bracket.edge_dir ).into_iter()
+      {
+        if from_id != current { continue; }
+        steps.push(( edge.id.clone(), to_id ));
+        self.walk( row, bracket, hops, reuse, steps, found );
+        steps.pop();
+      }
+    }
+  }
+
+  /// Edge fits the next hop, a relationship is traversed at most once per row unless its variable is repeated
+  fn can_traverse ( 
+    &self, row: &PatternRow, bracket: &BracketStatement, reuse: &Option<Vec<String>>, steps: &Vec<( String, String )>, 
+    edge: &Edge ) -> bool
+  {
+    if matches_edge( edge, bracket ) == false { return false; }
+    match reuse
+    {
+      Some( ids ) => ids.get( steps.len() ) == Some( &edge.id ),
+      None => row.uses_edge( &edge.id ) == false && steps.iter().any( | ( id, _ ) | id == &edge.id ) == false,
+    }
+  }
+
+  /// Extend every row with each node stmt accepts
+  fn expand_node ( &self, rows: Vec<PatternRow>, stmt: &MatchStatement, node_only: bool ) -> Vec<PatternRow>
+  {
+    let mut ret: Vec<PatternRow> = Vec::new();
+    for row in rows.iter()
+    {
+      for node in self.nodes.iter()
+      {
+        let mut next = row.clone();
+        if self.bind_node( &mut next, stmt, &node.id, node_only ) { ret.push( next ); }
+      }
+    }
+    ret
+  }
+
+  /// Bind stmt to a node, false when the node does not match or its variable is bound to another node
+  fn bind_node ( &self, row: &mut PatternRow, stmt: &MatchStatement, node_id: &String, node_only: bool ) -> bool
+  {
+    let slot = row.slots.iter().find( | ( order, _ ) | *order == stmt.order );
+    if slot.is_some() { return &slot.unwrap().1 == node_id; }
+
+    let node_opt = self.nodes.iter().find( | node | &node.id == node_id );
+    if node_opt.is_none() || matches_node( node_opt.unwrap(), stmt ) == false { return false; }
+
+    // anonymous nodes of node-only patterns are returned as `node`
+    let variable = match ( stmt.transaction_label.as_ref(), node_only )
+    {
+      ( Some( var ), _ ) => Some( var.clone() ),
+      ( None, true ) => Some( String::from( "node" )),
+      ( None, false ) => None,
+    };
+    if variable.is_some()
+    {
+      let bound = row.binding.iter().find( | ( var, _ ) | var == variable.as_ref().unwrap() ).map( | ( _, val ) | val );
+      match bound
+      {
+        Some( Value::Node( node )) => { if &node.id != node_id && stmt.transaction_label.is_some() { return false; } }
+        Some( _ ) => { return false; }
+        None => { row.binding.push(( variable.unwrap(), Value::Node( node_opt.unwrap().clone() ))); }
+      }
+    }
+    row.slots.push(( stmt.order, node_id.clone() ));
+    true
+  }
+
+  /// Keep bindings every WHERE predicate holds for
+  pub fn filter_bindings ( &mut self, read_clause: &ReadClause )
+  {
+    if read_clause.predicates.len() == 0 { return; }
+
+    self.bindings.retain( | binding | read_clause.predicates.iter().all( | p | p.evaluate( binding ) == Some( true )));
+  }
+
+  /// Project bindings onto the ReturnClause (or the first match variable when there is none)
+  pub fn result_set ( &self ) -> Result<ResultSet, String>
+  {
+    if self.err_state.is_some() { return Err( self.err_state.as_ref().unwrap().clone() ); }
+
+    let variables = self.transaction.variables();
+
+    // without RETURN the first match variable is returned
+    if self.transaction.return_clause.is_none()
+    {
+      let variable = variables.first().cloned().unwrap_or( String::from( "node" ));
+      let mut rs = ResultSet::new( vec![ variable.clone() ] );
+      for binding in self.bindings.iter()
+      {
+        let bound = binding.iter().find( | ( var, _ ) | var == &variable ).map( | ( _, val ) | val.clone() );
+        let _ = rs.push( vec![ bound.unwrap_or( Value::Null ) ] );
+      }
+      return Ok( rs );
+    }
+    project( self.transaction.return_clause.as_ref().unwrap(), &self.bindings, &variables )
+  }
+
+  pub fn toggle_current_page ( &mut self, page_type: PageType )
+  {
+    if self.current_page_type.is_none() { self.current_page_type = Some( page_type ); }
+    else
+    {
+      self.current_page_type = None;
+      self.in_graph = false;
+    }
+  }
+}
+
+/// Every variable a WHERE refers to is part of the pattern or was bound by an earlier stage
+pub fn check_predicate_variables ( read_clause: &ReadClause, variables: &Vec<String> ) -> Result<bool, String>
+{
+  let optional = read_clause.optional_predicates.iter().map( | ( _, predicate ) | predicate );
+  for predicate in read_clause.predicates.iter().chain( optional )
+  {
+    for var in predicate.variables().iter()
+    {
+      if variables.contains( var ) == false { return Err( format!( "Error: Variable `{}` not defined.", var )); }
+    }
+  }
+  Ok( true )
+}
+
+/// Node accepted by a MatchStatement (primary label or label expression, and properties)
+pub fn matches_node ( node: &Node, stmt: &MatchStatement ) -> bool
+{
+  if stmt.label_expression.is_some() && stmt.label_expression.as_ref().unwrap().matches( &node.labels() ) == false 
+  { 
+    return false; 
+  }
+  // any stored label satisfies the first pattern label, every further one must be present too
+  if stmt.primary_label.is_some() && node.has_label( parse_padded_str( stmt.primary_label.as_ref().unwrap() )) == false 
+  { 
+    return false; 
+  }
+  if stmt.labels.iter().any( | label | node.has_label( label ) == false ) { return false; }
+  matches_properties( &node.properties, &stmt.properties )
+}
+
+/// Edge accepted by a relationship pattern (type and properties), direction is checked separately
+pub fn matches_edge ( edge: &Edge, stmt: &BracketStatement ) -> bool
+{
+  if stmt.primary_label.is_some() && stmt.primary_label.as_deref() != edge.primary_tag.as_deref() { return false; }
+  matches_properties( &edge.properties, &stmt.properties )
+}
+
+/// Every pattern property exists with an equal value
+pub fn matches_properties ( properties: &Vec<NEProperty>, pattern: &Vec<NEProperty> ) -> bool
+{
+  pattern.iter().all( | prop | 
+  {
+    let found = find_property( properties, prop.key().unwrap() );
+    found.is_some() && Value::from( found.unwrap() ).equals( &Value::from( prop )) == Some( true )
+  })
+}
+
+/// ( pattern left id, pattern right id ) pairs an edge satisfies for a pattern direction
+pub fn edge_orientations ( edge: &Edge, pattern_dir: &DirectionType ) -> Vec<( String, String )>
+{
+  let forward = ( edge.left_id.clone(), edge.right_id.clone() );
+  let backward = ( edge.right_id.clone(), edge.left_id.clone() );
+
+  // stored arrows as ( from, to )
+  let arrows = match edge.direction
+  {
+    DirectionType::Right => vec![ forward.clone() ],
+    DirectionType::Left => vec![ backward.clone() ],
+    DirectionType::Bidirectional => vec![ forward.clone(), backward.clone() ],
+    DirectionType::Undirected => Vec::new(),
+  };
+
+  let mut ret = match pattern_dir
+  {
+    DirectionType::Right => arrows,
+    DirectionType::Left => arrows.into_iter().map( | ( from, to ) | ( to, from )).collect(),
+    _ => vec![ forward, backward ],
+  };
+  ret.dedup();
+  ret
 }
 
 #[cfg(test)]
-mod tests 
-{ 
+mod tests
+{
   use super::*;
   use std::fs::{ metadata, remove_file };
   use std::path::PathBuf;
@@ -47,6 +945,8 @@ mod tests
   use crate::planner::process_query;
   use crate::utils::create_file;
   use crate::executor::writer::new_db::WriteNewDBExecutor;
+  use crate::executor::writer::new_graph::WriteNewGraphExecutor;
+  use crate::executor::writer::set_properties::SetPropertiesExecutor;
 
   const PAGE_SIZE: usize = 4096;
 
@@ -59,42 +959,925 @@ mod tests
     let _ = WriteNewDBExecutor::execute_write_new( &build_id(), &db_nickname(), PAGE_SIZE, &mut stream );
   }
 
+  fn write_devs_graph ( path: &str )
+  {
+    let query_string = "
+      CREATE GRAPH devs
+        (alice:Developer)
+        (bob:Administrator)
+        (chris:Developer)
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+  }
+
   #[test]
-  fn test_testing () 
+  fn test_testing ()
   {
     let path_str = "../test_data/SimpleMatchExecutor_test_testing.sdb";
     write_new_db( path_str );
     assert_eq!( metadata( &PathBuf::from( path_str ) ).unwrap().len(), 4096 as u64 );
-    
-    // !!! come back with more complex graph representation
-    //let query_string = "CREATE GRAPH devs";
-    //let t = process_query( &query_string, build_id(), db_nickname() );
 
     let query_string = "MATCH ()";
     let t = process_query( &query_string, build_id(), db_nickname() );
-    println!( "{}", t );
 
     let mut read_executor = SimpleMatchExecutor::new( &t, path_str, 4096 );
-    
+    read_executor.execute();
+
+    assert_eq!( read_executor.err_state, None );
+    assert_eq!( read_executor.graph_uuid, Some( String::from( "1b622a2c-68dc-4848-a018-e71b604b5597::::" )));
+    assert_eq!( read_executor.nodes.len(), 0 );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_match_all_from_graph ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_match_all_from_graph.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    let t = process_query( "MATCH () FROM devs", build_id(), db_nickname() );
+    let mut read_executor = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+    read_executor.execute();
+
+    assert_eq!( read_executor.err_state, None );
+    assert_eq!( read_executor.nodes.len(), 3 );
+    assert_eq!( read_executor.nodes.get( 0 ).unwrap().primary_tag, Some( String::from( "Developer" )));
+    assert_eq!( read_executor.nodes.get( 1 ).unwrap().primary_tag, Some( String::from( "Administrator" )));
+    assert_eq!( read_executor.nodes.get( 0 ).unwrap().id.len(), 36 );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_match_overflow_pages ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_match_overflow_pages.sdb";
+    write_new_db( path_str );
+
+    let mut query_string = String::from( "CREATE GRAPH devs" );
+    for i in 0..100 { query_string.push_str( &format!( " (n{}:Developer {{name: 'dev{}', level: {}}})", i, i, i )); }
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+
+    assert_eq!( writer.err_state, None );
+    assert_eq!( writer.page.as_ref().unwrap().page_count > 1, true );
+
+    let t1 = process_query( "MATCH (n {level: 99}) FROM devs RETURN n.name", build_id(), db_nickname() );
+    let mut read_executor = SimpleMatchExecutor::new( &t1, path_str, PAGE_SIZE );
+    read_executor.execute();
+
+    assert_eq!( read_executor.err_state, None );
+    let rs = read_executor.result_set().unwrap();
+    assert_eq!( rs.rows.len(), 1 );
+    assert_eq!( rs.rows[0].get( "n.name" ), Some( &Value::String( String::from( "dev99" ))));
+
+    let t2 = process_query( "MATCH (n:Developer) FROM devs", build_id(), db_nickname() );
+    let mut read_executor1 = SimpleMatchExecutor::new( &t2, path_str, PAGE_SIZE );
+    read_executor1.execute();
+
+    assert_eq!( read_executor1.err_state, None );
+    assert_eq!( read_executor1.nodes.len(), 100 );
+    assert_eq!( read_executor1.nodes.iter().all( | n | n.properties.len() == 2 ), true );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_match_primary_label ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_match_primary_label.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    let t = process_query( "MATCH (n:Developer) FROM devs", build_id(), db_nickname() );
+    let mut read_executor = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+    read_executor.execute();
+
+    assert_eq!( read_executor.err_state, None );
+    assert_eq!( read_executor.nodes.len(), 2 );
+
+    let t1 = process_query( "MATCH (n:Stop) FROM devs", build_id(), db_nickname() );
+    let mut read_executor1 = SimpleMatchExecutor::new( &t1, path_str, PAGE_SIZE );
+    read_executor1.execute();
+
+    assert_eq!( read_executor1.err_state, None );
+    assert_eq!( read_executor1.nodes.len(), 0 );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_match_default_graph ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_match_default_graph.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    let t = process_query( "MATCH (n:Developer)", build_id(), db_nickname() );
+    let mut read_executor = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+    read_executor.execute();
+
+    assert_eq!( read_executor.err_state, None );
+    assert_eq!( read_executor.nodes.len(), 0 );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_result_set ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_result_set.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    let t = process_query( "MATCH (n:Developer) FROM devs RETURN n AS Developer", build_id(), db_nickname() );
+    let mut read_executor = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+    read_executor.execute();
+
+    let rs = read_executor.result_set().unwrap();
+    assert_eq!( rs.columns, vec![ String::from( "Developer" ) ] );
+    assert_eq!( rs.len(), 2 );
+    assert_eq!( 
+      rs.rows.get( 0 ).unwrap().get( "Developer" ).unwrap().as_node().unwrap().primary_tag, 
+      Some( String::from( "Developer" )));
+
+    let t1 = process_query( "MATCH (n) FROM devs", build_id(), db_nickname() );
+    let mut read_executor1 = SimpleMatchExecutor::new( &t1, path_str, PAGE_SIZE );
+    read_executor1.execute();
+
+    let rs1 = read_executor1.result_set().unwrap();
+    assert_eq!( rs1.columns, vec![ String::from( "n" ) ] );
+    assert_eq!( rs1.len(), 3 );
+
+    let t2 = process_query( "MATCH (n) FROM devs RETURN m", build_id(), db_nickname() );
+    let mut read_executor2 = SimpleMatchExecutor::new( &t2, path_str, PAGE_SIZE );
+    read_executor2.execute();
+
+    assert_eq!( read_executor2.result_set().is_err(), true );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_match_properties ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_match_properties.sdb";
+    write_new_db( path_str );
+
+    let query_string = "
+      CREATE GRAPH devs
+        (alice:Developer {name: 'Alice', age: 38, admin: true})
+        (bob:Administrator {name: 'Bob', age: 41})
+        (chris:Developer {name: 'Chris', rating: 4.5})
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+
+    let t1 = process_query( "MATCH (n:Developer) FROM devs RETURN n.name", build_id(), db_nickname() );
+    let mut read_executor1 = SimpleMatchExecutor::new( &t1, path_str, PAGE_SIZE );
+    read_executor1.execute();
+
+    let rs1 = read_executor1.result_set().unwrap();
+    assert_eq!( rs1.len(), 2 );
+    assert_eq!( rs1.rows.get( 0 ).unwrap().get( "n.name" ), Some( &Value::from( "Alice" )));
+    assert_eq!( rs1.rows.get( 1 ).unwrap().get( "n.name" ), Some( &Value::from( "Chris" )));
+    assert_eq!( read_executor1.nodes.get( 0 ).unwrap().properties.len(), 3 );
+    assert_eq!( read_executor1.nodes.get( 1 ).unwrap().property( "rating" ), 
+      Some( &NEProperty::KvpF64(( String::from( "rating" ), 4.5 ))));
+
+    let t2 = process_query( "MATCH (n {age: 41}) FROM devs RETURN n.name", build_id(), db_nickname() );
+    let mut read_executor2 = SimpleMatchExecutor::new( &t2, path_str, PAGE_SIZE );
+    read_executor2.execute();
+
+    let rs2 = read_executor2.result_set().unwrap();
+    assert_eq!( rs2.len(), 1 );
+    assert_eq!( rs2.rows.get( 0 ).unwrap().get( "n.name" ), Some( &Value::from( "Bob" )));
+
+    let t3 = process_query( "MATCH (n:Developer {name: 'Chris'}) FROM devs RETURN n.age", build_id(), db_nickname() );
+    let mut read_executor3 = SimpleMatchExecutor::new( &t3, path_str, PAGE_SIZE );
+    read_executor3.execute();
+
+    let rs3 = read_executor3.result_set().unwrap();
+    assert_eq!( rs3.len(), 1 );
+    assert_eq!( rs3.rows.get( 0 ).unwrap().get( "n.age" ), Some( &Value::Null ));
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_match_edge_direction ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_match_edge_direction.sdb";
+    write_new_db( path_str );
+
+    let query_string = "
+      CREATE GRAPH social
+        (alice:Person {name: 'Alice'})
+        (bob:Person {name: 'Bob'})
+        (chris:Person {name: 'Chris'})
+      CREATE (alice)-[:FOLLOWS]->(bob)
+        (chris)<-[:FOLLOWS {since: 2020}]-(bob)
+        (alice)-[:KNOWS]-(chris)
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+
+    let names = | query: &str, column: &str | -> Vec<Value>
+    {
+      let t = process_query( query, build_id(), db_nickname() );
+      let mut read_executor = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+      read_executor.execute();
+      let rs = read_executor.result_set().unwrap();
+      rs.column( column ).into_iter().cloned().collect()
+    };
+
+    // who alice follows / who follows bob
+    assert_eq!( names( "MATCH (a {name: 'Alice'})-[:FOLLOWS]->(b) FROM social RETURN b.name", "b.name" ), 
+      vec![ Value::from( "Bob" ) ] );
+    assert_eq!( names( "MATCH (b {name: 'Bob'})<-[:FOLLOWS]-(a) FROM social RETURN a.name", "a.name" ), 
+      vec![ Value::from( "Alice" ) ] );
+
+    // stored as (chris)<-(bob), so bob follows chris
+    assert_eq!( names( "MATCH (a {name: 'Bob'})-[r:FOLLOWS]->(b) FROM social RETURN b.name", "b.name" ), 
+      vec![ Value::from( "Chris" ) ] );
+    assert_eq!( names( "MATCH (a {name: 'Chris'})-[:FOLLOWS]->(b) FROM social RETURN b.name", "b.name" ).len(), 0 );
+    assert_eq!( names( "MATCH (a)-[r:FOLLOWS]->(b) FROM social RETURN r.since", "r.since" ), 
+      vec![ Value::Null, Value::I64( 2020 ) ] );
+
+    // undirected patterns match both ways, undirected edges never match arrows
+    assert_eq!( names( "MATCH (a {name: 'Bob'})-[:FOLLOWS]-(b) FROM social RETURN b.name", "b.name" ), 
+      vec![ Value::from( "Alice" ), Value::from( "Chris" ) ] );
+    assert_eq!( names( "MATCH (a {name: 'Chris'})-[:KNOWS]-(b) FROM social RETURN b.name", "b.name" ), 
+      vec![ Value::from( "Alice" ) ] );
+    assert_eq!( names( "MATCH (a)-[:KNOWS]->(b) FROM social RETURN b", "b" ).len(), 0 );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_match_where ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_match_where.sdb";
+    write_new_db( path_str );
+
+    let query_string = "
+      CREATE GRAPH rail
+        (a:Station {name: 'Preston', zone: 1})
+        (b:Station {name: 'Preston Park', zone: 2})
+        (c:Station {name: 'Brighton', zone: 1, staffed: true})
+        (d:Stop {name: 'Old Preston'})
+        (a)-[:NEXT {minutes: 4}]->(b)
+        (b)-[:NEXT {minutes: 9}]->(c)
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+
+    let names = | query: &str, column: &str | -> Result<Vec<Value>, String>
+    {
+      let t = process_query( query, build_id(), db_nickname() );
+      assert_eq!( t.err_state, None );
+      let mut read_executor = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+      read_executor.execute();
+      let rs = read_executor.result_set()?;
+      Ok( rs.column( column ).into_iter().cloned().collect() )
+    };
+
+    assert_eq!( names( "MATCH (n:Station WHERE n.name STARTS WITH 'Preston') FROM rail RETURN n.name", "n.name" ), 
+      Ok( vec![ Value::from( "Preston" ), Value::from( "Preston Park" ) ] ));
+    assert_eq!( names( "MATCH (n WHERE n.name ENDS WITH 'Preston') FROM rail RETURN n.name", "n.name" ), 
+      Ok( vec![ Value::from( "Preston" ), Value::from( "Old Preston" ) ] ));
+    assert_eq!( names( "MATCH (n) WHERE n.zone = 1 AND NOT n.staffed FROM rail RETURN n.name", "n.name" ), 
+      Ok( Vec::new() ));
+    assert_eq!( names( "MATCH (n) WHERE n.zone = 1 AND n.staffed IS NULL FROM rail RETURN n.name", "n.name" ), 
+      Ok( vec![ Value::from( "Preston" ) ] ));
+    assert_eq!( names( "MATCH (n) WHERE n.zone >= 2 OR n.name CONTAINS 'ght' FROM rail RETURN n.name", "n.name" ), 
+      Ok( vec![ Value::from( "Preston Park" ), Value::from( "Brighton" ) ] ));
+    assert_eq!( names( "MATCH (n) WHERE n.name IN ['Brighton', 'Old Preston'] XOR n.zone = 1 FROM rail RETURN n.name", "n.name" ), 
+      Ok( vec![ Value::from( "Preston" ) ] ));
+
+    // relationships, inline and trailing
+    assert_eq!( names( "MATCH (x)-[r:NEXT WHERE r.minutes > 5]->(y) FROM rail RETURN y.name", "y.name" ), 
+      Ok( vec![ Value::from( "Brighton" ) ] ));
+    assert_eq!( names( "MATCH (x)-[r:NEXT]->(y) WHERE x.zone < y.zone FROM rail RETURN y.name", "y.name" ), 
+      Ok( vec![ Value::from( "Preston Park" ) ] ));
+
+    assert_eq!( names( "MATCH (n) WHERE m.zone = 1 FROM rail RETURN n", "n" ), 
+      Err( String::from( "Error: Variable `m` not defined." )));
+
+    let t1 = process_query( "MATCH (n:Station) WHERE n.zone = 1 FROM rail", build_id(), db_nickname() );
+    let mut read_executor = SimpleMatchExecutor::new( &t1, path_str, PAGE_SIZE );
+    read_executor.execute();
+    assert_eq!( read_executor.nodes.len(), 2 );
+
     let _ = remove_file( PathBuf::from( path_str ));
-    /* 
-    let mut write_executor = WriteNewGraphExecutor::new( &t, path_str, 4096 );
-    
-    write_executor.graph_name = Some( Label::new( String::from( "devs" ), LABEL_BYTES ).unwrap() );
-    write_executor.graph_uuid = Some( UUID::new( String::from( "67e55044-10b1-426f-9247-bb680e5fe0cX" )).unwrap() );
+  }
 
-    let stmt = NodeStatement::new( 
-      String::from( "67e55044-10b1-426f-9247-bb680e5fe0cX" ), 
-      0, 
-      None, 
-      String::from( "primary" ));
+  #[test]
+  fn test_match_label_expression ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_match_label_expression.sdb";
+    write_new_db( path_str );
+
+    let query_string = "
+      CREATE GRAPH rail
+        (a:TrainStation {name: 'Central'})
+        (b:TrainStation {name: 'Park'})
+        (c:BusStation {name: 'Depot'})
+        (d:StationGroup {name: 'City'})
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
 
-    let open_res = open_file( &PathBuf::from( &path_str ));
-    let mut writer: BufWriter<File> = BufWriter::new( open_res.unwrap() );
+    // Central is also a bus station
+    let t1 = process_query( "MATCH (n {name: 'Central'}) SET n:BusStation FROM rail", build_id(), db_nickname() );
+    let mut set_executor = SetPropertiesExecutor::new( &t1, path_str, PAGE_SIZE );
+    set_executor.execute();
+    assert_eq!( set_executor.err_state, None );
 
-    let _ = write_executor.write_node( &stmt, 0, &mut writer );
+    let names = | query: &str | -> Vec<Value>
+    {
+      let t = process_query( query, build_id(), db_nickname() );
+      assert_eq!( t.err_state, None );
+      let mut read_executor = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+      read_executor.execute();
+      let rs = read_executor.result_set().unwrap();
+      rs.column( "n.name" ).into_iter().cloned().collect()
+    };
 
-    assert_eq!( write_executor.err_state, None );
-    */
+    assert_eq!( names( "MATCH (n:(TrainStation & BusStation)) FROM rail RETURN n.name" ), vec![ Value::from( "Central" ) ] );
+    assert_eq!( names( "MATCH (n:(TrainStation & BusStation) | StationGroup) FROM rail RETURN n.name" ), 
+      vec![ Value::from( "Central" ), Value::from( "City" ) ] );
+    assert_eq!( names( "MATCH (n:TrainStation|BusStation {name: 'Depot'}) FROM rail RETURN n.name" ), 
+      vec![ Value::from( "Depot" ) ] );
+    assert_eq!( names( "MATCH (n:!TrainStation) FROM rail RETURN n.name" ), 
+      vec![ Value::from( "Depot" ), Value::from( "City" ) ] );
+    assert_eq!( names( "MATCH (n:% WHERE n.name STARTS WITH 'P') FROM rail RETURN n.name" ), vec![ Value::from( "Park" ) ] );
+
+    let t2 = process_query( "MATCH (n:(TrainStation | )) FROM rail", build_id(), db_nickname() );
+    assert_eq!( t2.err_state.is_some(), true );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_match_multiple_labels ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_match_multiple_labels.sdb";
+    write_new_db( path_str );
+
+    let query_string = "
+      CREATE GRAPH devs
+        (alice:Person:Developer:Lead {name: 'Alice'})
+        (bob:Person:Developer {name: 'Bob'})
+        (carol:Person {name: 'Carol'})
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+
+    let names = | query: &str | -> Vec<Value>
+    {
+      let t = process_query( query, build_id(), db_nickname() );
+      assert_eq!( t.err_state, None );
+      let mut read_executor = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+      read_executor.execute();
+      let rs = read_executor.result_set().unwrap();
+      rs.column( "n.name" ).into_iter().cloned().collect()
+    };
+
+    assert_eq!( names( "MATCH (n:Developer) FROM devs RETURN n.name" ), vec![ Value::from( "Alice" ), Value::from( "Bob" ) ] );
+    assert_eq!( names( "MATCH (n:Lead) FROM devs RETURN n.name" ), vec![ Value::from( "Alice" ) ] );
+    assert_eq!( names( "MATCH (n:Developer:Person) FROM devs RETURN n.name" ), 
+      vec![ Value::from( "Alice" ), Value::from( "Bob" ) ] );
+    assert_eq!( names( "MATCH (n:Person:Lead) FROM devs RETURN n.name" ), vec![ Value::from( "Alice" ) ] );
+    assert_eq!( names( "MATCH (n:Person:Manager) FROM devs RETURN n.name" ), Vec::<Value>::new() );
+
+    let t1 = process_query( "MATCH (n {name: 'Alice'}) FROM devs", build_id(), db_nickname() );
+    let mut read_executor = SimpleMatchExecutor::new( &t1, path_str, PAGE_SIZE );
+    read_executor.execute();
+    assert_eq!( read_executor.nodes[0].labels(), vec![ "Person", "Developer", "Lead" ] );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_match_return_projection ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_match_return_projection.sdb";
+    write_new_db( path_str );
+
+    let query_string = "
+      CREATE GRAPH devs
+        (alice:Developer {name: 'Alice', age: 38, team: 'core'})
+        (bob:Developer {name: 'Bob', age: 27, team: 'web'})
+        (carol:Developer {name: 'Carol', age: 45, team: 'core'})
+        (dave:Developer {name: 'Dave', team: 'web'})
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+
+    let result = | query: &str | -> Result<ResultSet, String>
+    {
+      let t = process_query( query, build_id(), db_nickname() );
+      assert_eq!( t.err_state, None );
+      let mut read_executor = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+      read_executor.execute();
+      read_executor.result_set()
+    };
+
+    let rs = result( "MATCH (n:Developer) FROM devs RETURN n.name AS name, n.age ORDER BY n.age DESC" ).unwrap();
+    assert_eq!( rs.columns, vec![ String::from( "name" ), String::from( "n.age" ) ] );
+    assert_eq!( rs.column( "name" ), vec![ &Value::from( "Dave" ), &Value::from( "Carol" ), &Value::from( "Alice" ), &Value::from( "Bob" ) ] );
+    assert_eq!( rs.rows[0].get( "n.age" ), Some( &Value::Null ));
+
+    let rs1 = result( "MATCH (n:Developer) FROM devs RETURN DISTINCT n.team AS team ORDER BY team" ).unwrap();
+    assert_eq!( rs1.column( "team" ), vec![ &Value::from( "core" ), &Value::from( "web" ) ] );
+
+    let rs2 = result( "MATCH (n:Developer) FROM devs RETURN n.name ORDER BY n.team DESC, n.name SKIP 1 LIMIT 2" ).unwrap();
+    assert_eq!( rs2.column( "n.name" ), vec![ &Value::from( "Dave" ), &Value::from( "Alice" ) ] );
+
+    let rs3 = result( "MATCH (n:Developer) FROM devs RETURN n LIMIT 1" ).unwrap();
+    assert_eq!( rs3.len(), 1 );
+
+    assert_eq!( result( "MATCH (n:Developer) FROM devs RETURN n.name ORDER BY m.age" ).unwrap_err(), 
+      String::from( "Error: Variable `m` not defined." ));
+
+    let _ = remove_file( PathBuf::from( path_str ));
   }
-}
\ No newline at end of file
+
+  #[test]
+  fn test_match_aggregates ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_match_aggregates.sdb";
+    write_new_db( path_str );
+
+    let query_string = "
+      CREATE GRAPH devs
+        (alice:Developer {name: 'Alice', age: 38})
+        (bob:Developer {name: 'Bob', age: 27})
+        (carol:Developer {name: 'Carol', age: 45.5})
+        (alice)-[:KNOWS {since: 2019}]->(bob)
+        (alice)-[:KNOWS {since: 2021}]->(carol)
+        (bob)-[:KNOWS {since: 2020}]->(carol)
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+
+    let result = | query: &str | -> ResultSet
+    {
+      let t = process_query( query, build_id(), db_nickname() );
+      assert_eq!( t.err_state, None );
+      let mut read_executor = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+      read_executor.execute();
+      read_executor.result_set().unwrap()
+    };
+
+    let rs = result( "MATCH (a)-[r:KNOWS]->(b) FROM devs RETURN a.name, count(r) As numberOfFriends ORDER BY a.name" );
+    assert_eq!( rs.column( "a.name" ), vec![ &Value::from( "Alice" ), &Value::from( "Bob" ) ] );
+    assert_eq!( rs.column( "numberOfFriends" ), vec![ &Value::I64( 2 ), &Value::I64( 1 ) ] );
+
+    let rs1 = result( "MATCH (n:Developer) FROM devs RETURN count(*), sum(n.age), min(n.age), max(n.age), collect(n.name) AS names" );
+    assert_eq!( rs1.len(), 1 );
+    assert_eq!( rs1.rows[0].get( "count(*)" ), Some( &Value::I64( 3 )));
+    assert_eq!( rs1.rows[0].get( "sum(n.age)" ), Some( &Value::F64( 110.5 )));
+    assert_eq!( rs1.rows[0].get( "min(n.age)" ).unwrap().as_i64(), Some( 27 ));
+    assert_eq!( rs1.rows[0].get( "max(n.age)" ).unwrap().as_f64(), Some( 45.5 ));
+    assert_eq!( rs1.rows[0].get( "names" ).unwrap().as_list().unwrap().len(), 3 );
+
+    let rs2 = result( "MATCH (a)-[r:KNOWS]->(b) FROM devs RETURN count(DISTINCT b), percentileCont(r.since, 0.5) AS median" );
+    assert_eq!( rs2.rows[0].get( "count(DISTINCT b)" ), Some( &Value::I64( 2 )));
+    assert_eq!( rs2.rows[0].get( "median" ), Some( &Value::F64( 2020.0 )));
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_match_multi_hop ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_match_multi_hop.sdb";
+    write_new_db( path_str );
+
+    let query_string = "
+      CREATE GRAPH devs
+        (alice:Developer {name: 'Alice'})
+        (bob:Developer {name: 'Bob'})
+        (carol:Developer {name: 'Carol'})
+        (dave:Manager {name: 'Dave'})
+        (alice)-[:KNOWS]->(bob)
+        (bob)-[:KNOWS]->(carol)
+        (carol)-[:KNOWS]->(alice)
+        (dave)-[:MANAGES]->(bob)
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+
+    let rows = | query: &str | -> Vec<Vec<Value>>
+    {
+      let t = process_query( query, build_id(), db_nickname() );
+      assert_eq!( t.err_state, None );
+      let mut read_executor = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+      read_executor.execute();
+      assert_eq!( read_executor.err_state, None );
+      read_executor.result_set().unwrap().rows.into_iter().map( | row | row.values ).collect()
+    };
+
+    assert_eq!( rows( "MATCH (a {name: 'Alice'})-[:KNOWS]->(b)-[:KNOWS]->(c) FROM devs RETURN c.name" ), 
+      vec![ vec![ Value::from( "Carol" ) ] ] );
+    assert_eq!( rows( "MATCH (a)-[r1:KNOWS]->(b)<-[r2:MANAGES]-(c) FROM devs RETURN a.name, b.name, c.name" ), 
+      vec![ vec![ Value::from( "Alice" ), Value::from( "Bob" ), Value::from( "Dave" ) ] ] );
+
+    // variables are shared across MATCH clauses and comma separated patterns
+    assert_eq!( rows( "MATCH (a:Developer {name: 'Alice'})-[:KNOWS]->(b) MATCH (b)-[:KNOWS]->(c) FROM devs RETURN c.name" ), 
+      vec![ vec![ Value::from( "Carol" ) ] ] );
+    assert_eq!( rows( "MATCH (m)-[:MANAGES]->(b), (b)-[:KNOWS]->(c) FROM devs RETURN m.name, c.name" ), 
+      vec![ vec![ Value::from( "Dave" ), Value::from( "Carol" ) ] ] );
+    assert_eq!( rows( "MATCH (m {name: 'Dave'})-[:MANAGES]->()-[:KNOWS]->(c) FROM devs RETURN c.name" ), 
+      vec![ vec![ Value::from( "Carol" ) ] ] );
+    assert_eq!( rows( "MATCH (a {name: 'Alice'}), (m:Manager) FROM devs RETURN a.name, m.name" ), 
+      vec![ vec![ Value::from( "Alice" ), Value::from( "Dave" ) ] ] );
+
+    // the triangle closes after three hops, a fourth would reuse a relationship
+    assert_eq!( rows( "MATCH (a)-[:KNOWS]->(b)-[:KNOWS]->(c)-[:KNOWS]->(a) FROM devs RETURN count(*)" ), 
+      vec![ vec![ Value::I64( 3 ) ] ] );
+    assert_eq!( rows( "MATCH (a)-[:KNOWS]->()-[:KNOWS]->()-[:KNOWS]->()-[:KNOWS]->(e) FROM devs RETURN count(*)" ), 
+      vec![ vec![ Value::I64( 0 ) ] ] );
+    assert_eq!( rows( "MATCH (a)-[r:KNOWS]->(b) MATCH (a)-[r]->(b) FROM devs RETURN count(*)" ), 
+      vec![ vec![ Value::I64( 3 ) ] ] );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_match_variable_length ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_match_variable_length.sdb";
+    write_new_db( path_str );
+
+    let query_string = "
+      CREATE GRAPH devs
+        (alice:Developer {name: 'Alice'})
+        (bob:Developer {name: 'Bob'})
+        (carol:Developer {name: 'Carol'})
+        (dave:Developer {name: 'Dave'})
+        (alice)-[:KNOWS]->(bob)
+        (bob)-[:KNOWS]->(carol)
+        (carol)-[:KNOWS]->(dave)
+        (dave)-[:KNOWS]->(alice)
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+
+    let rows = | query: &str | -> Vec<Vec<Value>>
+    {
+      let t = process_query( query, build_id(), db_nickname() );
+      assert_eq!( t.err_state, None );
+      let mut read_executor = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+      read_executor.execute();
+      assert_eq!( read_executor.err_state, None );
+      read_executor.result_set().unwrap().rows.into_iter().map( | row | row.values ).collect()
+    };
+    let names = | vals: Vec<&str> | -> Vec<Vec<Value>> { vals.into_iter().map( | v | vec![ Value::from( v ) ] ).collect() };
+
+    assert_eq!( rows( "MATCH (a {name: 'Alice'})-[:KNOWS*1..2]->(b) FROM devs RETURN b.name ORDER BY b.name" ), 
+      names( vec![ "Bob", "Carol" ] ));
+    assert_eq!( rows( "MATCH (a {name: 'Alice'})-[:KNOWS*2]->(b) FROM devs RETURN b.name" ), names( vec![ "Carol" ] ));
+    assert_eq!( rows( "MATCH (a {name: 'Alice'})-[*0..1]->(b) FROM devs RETURN b.name ORDER BY b.name" ), 
+      names( vec![ "Alice", "Bob" ] ));
+    assert_eq!( rows( "MATCH (a {name: 'Alice'})<-[:KNOWS*..2]-(b) FROM devs RETURN b.name ORDER BY b.name" ), 
+      names( vec![ "Carol", "Dave" ] ));
+
+    // unbounded, every relationship is traversed at most once so the cycle ends back at Alice
+    assert_eq!( rows( "MATCH (a {name: 'Alice'})-[:KNOWS*]->(b) FROM devs RETURN b.name, count(*) ORDER BY b.name" ), 
+      vec![ 
+        vec![ Value::from( "Alice" ), Value::I64( 1 ) ], 
+        vec![ Value::from( "Bob" ), Value::I64( 1 ) ], 
+        vec![ Value::from( "Carol" ), Value::I64( 1 ) ], 
+        vec![ Value::from( "Dave" ), Value::I64( 1 ) ] ] );
+    assert_eq!( rows( "MATCH (a {name: 'Alice'})-[r:KNOWS*]-(b {name: 'Carol'}) FROM devs RETURN count(r)" ), 
+      vec![ vec![ Value::I64( 2 ) ] ] );
+
+    // path variables and functions
+    let t = process_query( 
+      "MATCH p = (a {name: 'Alice'})-[r:KNOWS*1..3]->(b) WHERE length(p) > 1 FROM devs RETURN p, r, length(p) AS hops ORDER BY hops", 
+      build_id(), db_nickname() );
+    let mut read_executor = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+    read_executor.execute();
+    let rs = read_executor.result_set().unwrap();
+    assert_eq!( rs.rows.len(), 2 );
+    let path = rs.rows[0].values[0].as_path().unwrap();
+    let path_names: Vec<Value> = path.nodes.iter().map( | n | Value::from( n.property( "name" ).unwrap() )).collect();
+    assert_eq!( path_names, vec![ Value::from( "Alice" ), Value::from( "Bob" ), Value::from( "Carol" ) ] );
+    assert_eq!( rs.rows[0].values[1], Value::List( path.edges.iter().map( | e | Value::Edge( e.clone() )).collect() ));
+    assert_eq!( rs.rows[0].values[2], Value::I64( 2 ));
+    assert_eq!( rs.rows[1].values[2], Value::I64( 3 ));
+
+    assert_eq!( rows( "MATCH p = (a {name: 'Bob'})-[:KNOWS]->()-[:KNOWS]->(c) FROM devs RETURN length(p), c.name" ), 
+      vec![ vec![ Value::I64( 2 ), Value::from( "Dave" ) ] ] );
+    assert_eq!( rows( "MATCH p = (a {name: 'Bob'}) FROM devs RETURN length(p)" ), vec![ vec![ Value::I64( 0 ) ] ] );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_match_shortest_path ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_match_shortest_path.sdb";
+    write_new_db( path_str );
+
+    let query_string = "
+      CREATE GRAPH routes
+        (copenhagen:TrainStation {city: 'Copenhagen'})
+        (kastrup:Airport {city: 'Kastrup'})
+        (helsingor:TrainStation {city: 'Helsingør'})
+        (helsingborg:TrainStation {city: 'Helsingborg'})
+        (malmo:Office {city: 'Malmö'})
+        (copenhagen)-[:TRAVEL_ROUTE]->(kastrup)
+        (kastrup)-[:TRAVEL_ROUTE]->(malmo)
+        (copenhagen)-[:TRAVEL_ROUTE]->(helsingor)
+        (helsingor)-[:FERRY]->(helsingborg)
+        (helsingborg)-[:TRAVEL_ROUTE]->(malmo)
+        (copenhagen)-[:TRAVEL_ROUTE]->(helsingborg)
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+
+    let rows = | query: &str | -> Result<Vec<Vec<Value>>, String>
+    {
+      let t = process_query( query, build_id(), db_nickname() );
+      assert_eq!( t.err_state, None );
+      let mut read_executor = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+      read_executor.execute();
+      Ok( read_executor.result_set()?.rows.into_iter().map( | row | row.values ).collect() )
+    };
+    let cities = | val: &Value | -> Vec<Value>
+    {
+      val.as_path().unwrap().nodes.iter().map( | n | Value::from( n.property( "city" ).unwrap() )).collect()
+    };
+
+    let rs = rows( "
+      MATCH p = shortestPath((a {city: 'Copenhagen'})-[:TRAVEL_ROUTE*]->(b {city: 'Malmö'})) FROM routes RETURN p" ).unwrap();
+    assert_eq!( rs.len(), 1 );
+    assert_eq!( cities( &rs[0][0] ), vec![ Value::from( "Copenhagen" ), Value::from( "Kastrup" ), Value::from( "Malmö" ) ] );
+
+    let rs = rows( "
+      MATCH p = allShortestPaths((a {city: 'Copenhagen'})-[:TRAVEL_ROUTE*]->(b:Office)) FROM routes RETURN p" ).unwrap();
+    assert_eq!( rs.len(), 2 );
+    assert_eq!( cities( &rs[1][0] ), vec![ Value::from( "Copenhagen" ), Value::from( "Helsingborg" ), Value::from( "Malmö" ) ] );
+
+    // any relationship type, against the stored direction and bounded
+    assert_eq!( rows( "
+      MATCH p = shortestPath((a {city: 'Helsingør'})<-[*]-(b {city: 'Copenhagen'})) FROM routes RETURN length(p)" ), 
+      Ok( vec![ vec![ Value::I64( 1 ) ] ] ));
+    assert_eq!( rows( "
+      MATCH (a:Office), (b {city: 'Helsingør'}) MATCH shortestPath((a)-[r*..3]-(b)) FROM routes RETURN count(r)" ), 
+      Ok( vec![ vec![ Value::I64( 1 ) ] ] ));
+    assert_eq!( rows( "
+      MATCH shortestPath((a {city: 'Kastrup'})-[*..1]->(b {city: 'Helsingborg'})) FROM routes RETURN a" ), Ok( Vec::new() ));
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_match_optional ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_match_optional.sdb";
+    write_new_db( path_str );
+
+    let query_string = "
+      CREATE GRAPH movies
+        (keanu:Person {name: 'Keanu'})
+        (carrie:Person {name: 'Carrie'})
+        (tom:Person {name: 'Tom'})
+        (matrix:Movie {title: 'The Matrix', year: 1999})
+        (wick:Movie {title: 'John Wick', year: 2014})
+        (keanu)-[:ACTED_IN]->(matrix)
+        (keanu)-[:ACTED_IN]->(wick)
+        (carrie)-[:ACTED_IN]->(matrix)
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+
+    let rows = | query: &str | -> Vec<Vec<Value>>
+    {
+      let t = process_query( query, build_id(), db_nickname() );
+      assert_eq!( t.err_state, None );
+      let mut read_executor = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+      read_executor.execute();
+      assert_eq!( read_executor.err_state, None );
+      read_executor.result_set().unwrap().rows.into_iter().map( | row | row.values ).collect()
+    };
+    let row = | name: &str, title: Value | vec![ Value::from( name ), title ];
+
+    assert_eq!( rows( "
+      MATCH (p:Person) OPTIONAL MATCH (p)-[:ACTED_IN]->(m) FROM movies RETURN p.name, m.title ORDER BY p.name, m.title" ), 
+      vec![ 
+        row( "Carrie", Value::from( "The Matrix" )), 
+        row( "Keanu", Value::from( "John Wick" )), 
+        row( "Keanu", Value::from( "The Matrix" )), 
+        row( "Tom", Value::Null ) ] );
+
+    // the optional WHERE decides the match, it does not drop rows
+    assert_eq!( rows( "
+      MATCH (p:Person) OPTIONAL MATCH (p)-[r:ACTED_IN]->(m) WHERE m.year > 2000 FROM movies 
+      RETURN p.name, m.title ORDER BY p.name" ), 
+      vec![ row( "Carrie", Value::Null ), row( "Keanu", Value::from( "John Wick" )), row( "Tom", Value::Null ) ] );
+    assert_eq!( rows( "
+      MATCH (p:Person) OPTIONAL MATCH (p)-[:ACTED_IN]->(m) FROM movies RETURN p.name, count(m) AS movies ORDER BY movies DESC, p.name" ), 
+      vec![ 
+        row( "Keanu", Value::I64( 2 )), 
+        row( "Carrie", Value::I64( 1 )), 
+        row( "Tom", Value::I64( 0 )) ] );
+
+    // a null variable matches nothing in later clauses
+    assert_eq!( rows( "
+      MATCH (p {name: 'Tom'}) OPTIONAL MATCH (p)-[:ACTED_IN]->(m) OPTIONAL MATCH (m)<-[:ACTED_IN]-(c) FROM movies 
+      RETURN p.name, c" ), 
+      vec![ row( "Tom", Value::Null ) ] );
+    assert_eq!( rows( "
+      MATCH (p {name: 'Tom'}) OPTIONAL MATCH (p)-[:ACTED_IN]->(m) MATCH (m)<-[:ACTED_IN]-(c) FROM movies RETURN c" ), 
+      Vec::<Vec<Value>>::new() );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_match_with ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_match_with.sdb";
+    write_new_db( path_str );
+
+    let query_string = "
+      CREATE GRAPH movies
+        (keanu:Person {name: 'Keanu'})
+        (carrie:Person {name: 'Carrie'})
+        (tom:Person {name: 'Tom'})
+        (matrix:Movie {title: 'The Matrix', year: 1999})
+        (wick:Movie {title: 'John Wick', year: 2014})
+        (keanu)-[:ACTED_IN]->(matrix)
+        (keanu)-[:ACTED_IN]->(wick)
+        (carrie)-[:ACTED_IN]->(matrix)
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+
+    let execute = | query: &str | -> Result<ResultSet, String>
+    {
+      let t = process_query( query, build_id(), db_nickname() );
+      assert_eq!( t.err_state, None );
+      let mut read_executor = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+      read_executor.execute();
+      read_executor.result_set()
+    };
+    let rows = | query: &str | -> Vec<Vec<Value>>
+    {
+      execute( query ).unwrap().rows.into_iter().map( | row | row.values ).collect()
+    };
+
+    assert_eq!( rows( "
+      MATCH (p:Person)-[r:ACTED_IN]->(m) FROM movies WITH p, count(r) AS movies WHERE movies > 1 
+      MATCH (p)-[:ACTED_IN]->(m) RETURN p.name, movies, m.title ORDER BY m.title" ), 
+      vec![ 
+        vec![ Value::from( "Keanu" ), Value::I64( 2 ), Value::from( "John Wick" ) ],
+        vec![ Value::from( "Keanu" ), Value::I64( 2 ), Value::from( "The Matrix" ) ] ] );
+
+    // ORDER BY and LIMIT apply before the next stage
+    assert_eq!( rows( "
+      MATCH (m:Movie) FROM movies WITH m, m.year AS year ORDER BY year DESC LIMIT 1 
+      MATCH (m)<-[:ACTED_IN]-(p) RETURN m.title, p.name" ), 
+      vec![ vec![ Value::from( "John Wick" ), Value::from( "Keanu" ) ] ] );
+    assert_eq!( rows( "
+      MATCH (p:Person) FROM movies WITH p.name AS name WITH name WHERE name STARTS WITH 'C' OR name = 'Tom' 
+      RETURN name ORDER BY name" ), 
+      vec![ vec![ Value::from( "Carrie" ) ], vec![ Value::from( "Tom" ) ] ] );
+    assert_eq!( rows( "
+      MATCH (p:Person)-[:ACTED_IN]->(m) FROM movies WITH DISTINCT m RETURN count(m) AS movies" ), 
+      vec![ vec![ Value::I64( 2 ) ] ] );
+
+    // only the projected variables stay in scope
+    assert_eq!( execute( "MATCH (p:Person)-[:ACTED_IN]->(m) FROM movies WITH p RETURN m.title" ).unwrap_err(),
+      String::from( "Error: Variable `m` not defined." ));
+    assert_eq!( execute( "MATCH (p:Person) FROM movies WITH p.name AS name WHERE p.name = 'Tom' RETURN name" ).unwrap_err(),
+      String::from( "Error: Variable `p` not defined." ));
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_match_unwind ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_match_unwind.sdb";
+    write_new_db( path_str );
+
+    let query_string = "
+      CREATE GRAPH books
+        (hobbit:Book {name: 'The Hobbit', ratings: [1.0, 4.5], colors: ['Pink', 'Yellow', 'Black']})
+        (dune:Book {name: 'Dune', ratings: [5.0], colors: []})
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+
+    let execute = | query: &str | -> Result<ResultSet, String>
+    {
+      let t = process_query( query, build_id(), db_nickname() );
+      assert_eq!( t.err_state, None );
+      let mut read_executor = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+      read_executor.execute();
+      read_executor.result_set()
+    };
+    let rows = | query: &str | -> Vec<Vec<Value>>
+    {
+      execute( query ).unwrap().rows.into_iter().map( | row | row.values ).collect()
+    };
+    let strings = | values: Vec<&str> | Value::List( values.into_iter().map( Value::from ).collect() );
+
+    // list properties are read back whole
+    assert_eq!( rows( "MATCH (b:Book {name: 'The Hobbit'}) FROM books RETURN b.ratings, b.colors" ), 
+      vec![ vec![ Value::List( vec![ Value::F64( 1.0 ), Value::F64( 4.5 ) ] ), strings( vec![ "Pink", "Yellow", "Black" ] ) ] ] );
+    assert_eq!( rows( "
+      MATCH (b:Book) FROM books RETURN b.name, b.colors[0], b.colors[1..], [c IN b.colors WHERE c <> 'Pink'] AS other
+      ORDER BY b.name" ), 
+      vec![ 
+        vec![ Value::from( "Dune" ), Value::Null, strings( vec![] ), strings( vec![] ) ],
+        vec![ Value::from( "The Hobbit" ), Value::from( "Pink" ), strings( vec![ "Yellow", "Black" ] ), 
+          strings( vec![ "Yellow", "Black" ] ) ] ] );
+    assert_eq!( rows( "MATCH (b:Book) FROM books WHERE 'Black' IN b.colors RETURN b.name" ), 
+      vec![ vec![ Value::from( "The Hobbit" ) ] ] );
+
+    // one row per element, an empty list removes the row
+    assert_eq!( rows( "MATCH (b:Book) FROM books UNWIND b.colors AS color RETURN b.name, color ORDER BY color" ), 
+      vec![ 
+        vec![ Value::from( "The Hobbit" ), Value::from( "Black" ) ],
+        vec![ Value::from( "The Hobbit" ), Value::from( "Pink" ) ],
+        vec![ Value::from( "The Hobbit" ), Value::from( "Yellow" ) ] ] );
+    assert_eq!( rows( "UNWIND [3, 1, 2] AS x UNWIND ['a'] AS y RETURN x, y ORDER BY x DESC LIMIT 2" ), 
+      vec![ vec![ Value::I64( 3 ), Value::from( "a" ) ], vec![ Value::I64( 2 ), Value::from( "a" ) ] ] );
+    assert_eq!( rows( "MATCH (b:Book) FROM books UNWIND b.ratings AS r WITH count(r) AS c RETURN c" ), 
+      vec![ vec![ Value::I64( 3 ) ] ] );
+
+    assert_eq!( execute( "UNWIND x AS y RETURN y" ).unwrap_err(), String::from( "Error: Variable `x` not defined." ));
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_edge_orientations ()
+  {
+    let edge = | dir: DirectionType | Edge::new( 
+      String::from( "e" ), None, String::from( "l" ), String::from( "r" ), dir, Vec::new() );
+    let lr = ( String::from( "l" ), String::from( "r" ));
+    let rl = ( String::from( "r" ), String::from( "l" ));
+
+    assert_eq!( edge_orientations( &edge( DirectionType::Right ), &DirectionType::Right ), vec![ lr.clone() ] );
+    assert_eq!( edge_orientations( &edge( DirectionType::Right ), &DirectionType::Left ), vec![ rl.clone() ] );
+    assert_eq!( edge_orientations( &edge( DirectionType::Left ), &DirectionType::Right ), vec![ rl.clone() ] );
+    assert_eq!( edge_orientations( &edge( DirectionType::Bidirectional ), &DirectionType::Right ).len(), 2 );
+    assert_eq!( edge_orientations( &edge( DirectionType::Undirected ), &DirectionType::Right ).len(), 0 );
+    assert_eq!( edge_orientations( &edge( DirectionType::Undirected ), &DirectionType::Undirected ), vec![ lr, rl ] );
+  }
+
+  #[test]
+  fn test_match_missing_graph ()
+  {
+    let path_str = "../test_data/SimpleMatchExecutor_test_match_missing_graph.sdb";
+    write_new_db( path_str );
+
+    let t = process_query( "MATCH () FROM movies", build_id(), db_nickname() );
+    let mut read_executor = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+    read_executor.execute();
+
+    assert_eq!( read_executor.err_state, Some( String::from( "Error: Graph not found." )));
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+}
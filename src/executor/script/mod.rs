@@ -0,0 +1,215 @@
+use crate::ast::error::QueryError;
+use crate::cmd::transaction::Transaction;
+use crate::datagramv2::internal_grams::{ Label, UUID };
+use crate::planner::process_script;
+use crate::result_set::{ ResultSet, Value };
+
+use crate::executor::reader::simple_match::SimpleMatchExecutor;
+use crate::executor::writer::delete::DeleteExecutor;
+use crate::executor::writer::merge::MergeExecutor;
+use crate::executor::writer::new_graph::WriteNewGraphExecutor;
+use crate::executor::writer::set_properties::SetPropertiesExecutor;
+use crate::executor::writer::update_graph::UpdateGraphExecutor;
+
+/* @version 0.3.0 */
+
+/*
+// seed data
+CREATE GRAPH devs (alice:Developer {name: 'Alice'}) (bob:Developer {name: 'Bob'});
+
+/* bob moved teams */
+MATCH (n:Developer {name: 'Bob'}) FROM devs SET n.team = 'ops';
+*/
+
+/*
+  Runs the statements of a script in order, each one sees what the statements before it wrote. Every statement is
+  planned before the first one runs, a syntax error anywhere runs nothing. The first statement that fails ends the
+  script, the statements before it stay written.
+
+  ScriptExecutor
+    :: new()
+    :: execute()
+        :: execute_statement()
+*/
+pub struct ScriptExecutor<'a>
+{
+  pub script: &'a str,
+  pub params: &'a Vec<( String, Value )>,
+  pub path: &'a str,
+  pub page_size: usize,
+  pub build_id: UUID,
+  pub nickname: Label,
+  pub result_sets: Vec<ResultSet>, // one for every statement that ran
+  pub error: Option<QueryError>, // points at the statement that failed, render() shows it
+  pub err_state: Option<String>,
+}
+
+impl ScriptExecutor<'_>
+{
+  pub fn new<'a> (
+    script: &'a str, params: &'a Vec<( String, Value )>, path: &'a str, page_size: usize, build_id: UUID, nickname: Label
+  ) -> ScriptExecutor<'a>
+  {
+    ScriptExecutor
+    {
+      script: script,
+      params: params,
+      path: path,
+      page_size: page_size,
+      build_id: build_id,
+      nickname: nickname,
+      result_sets: Vec::new(),
+      error: None,
+      err_state: None,
+    }
+  }
+
+  pub fn execute ( &mut self )
+  {
+    let statements = process_script( self.script, self.params, self.build_id.clone(), self.nickname.clone() );
+    let plan_err = statements.iter().find( | ( _, t ) | t.error.is_some() );
+    if plan_err.is_some()
+    {
+      self.err_state = plan_err.unwrap().1.err_state.clone();
+      self.error = plan_err.unwrap().1.error.clone();
+      return;
+    }
+
+    for ( span, transaction ) in statements.iter()
+    {
+      let rs_res = self.execute_statement( transaction );
+      if rs_res.is_err()
+      {
+        let err = QueryError::at( rs_res.unwrap_err(), *span, None );
+        self.err_state = Some( err.message.clone() );
+        self.error = Some( err );
+        return;
+      }
+      self.result_sets.push( rs_res.unwrap() );
+    }
+  }
+
+  /// Executor of what the statement writes, statements that write nothing are read
+  pub fn execute_statement ( &self, t: &Transaction ) -> Result<ResultSet, String>
+  {
+    if t.delete_statement.is_some()
+    {
+      let mut executor = DeleteExecutor::new( t, self.path, self.page_size );
+      executor.execute();
+      return executor.result_set();
+    }
+    if t.merge
+    {
+      let mut executor = MergeExecutor::new( t, self.path, self.page_size );
+      executor.execute();
+      return executor.result_set();
+    }
+    if t.set_statements.len() > 0 || t.remove_statements.len() > 0
+    {
+      let mut executor = SetPropertiesExecutor::new( t, self.path, self.page_size );
+      executor.execute();
+      return executor.result_set();
+    }
+
+    // CREATE GRAPH name writes a new graph, CREATE on its own adds to an existing one
+    if t.create_statement.is_some() && t.create_statement.as_ref().unwrap().graph_name.is_some()
+    {
+      let mut executor = WriteNewGraphExecutor::new( t, self.path, self.page_size );
+      executor.execute();
+      if executor.err_state.is_some() { return Err( executor.err_state.unwrap() ); }
+      return Ok( ResultSet::new( Vec::new() ));
+    }
+    if t.create_statement.is_some()
+    {
+      let mut executor = UpdateGraphExecutor::new( t, self.path, self.page_size );
+      executor.execute();
+      if executor.err_state.is_some() { return Err( executor.err_state.unwrap() ); }
+      return Ok( ResultSet::new( Vec::new() ));
+    }
+
+    let mut executor = SimpleMatchExecutor::new( t, self.path, self.page_size );
+    executor.execute();
+    executor.result_set()
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+  use std::fs::remove_file;
+  use std::io::BufWriter;
+  use std::path::PathBuf;
+  use crate::utils::create_file;
+  use crate::executor::writer::new_db::WriteNewDBExecutor;
+
+  const PAGE_SIZE: usize = 4096;
+
+  fn build_id () -> UUID { UUID::new( String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" )).unwrap() }
+  fn db_nickname () -> Label { Label::new( String::from( "devs" ) ).unwrap() }
+  fn write_new_db ( path: &str )
+  {
+    let open_res = create_file( &PathBuf::from( path ));
+    let mut stream = BufWriter::new( open_res.unwrap() );
+    let _ = WriteNewDBExecutor::execute_write_new( &build_id(), &db_nickname(), PAGE_SIZE, &mut stream );
+  }
+
+  /// ( err_state, error, result sets )
+  fn execute_script ( script: &str, path: &str ) -> ( Option<String>, Option<QueryError>, Vec<ResultSet> )
+  {
+    let params = vec![ ( String::from( "team" ), Value::from( "ops" )) ];
+    let mut executor = ScriptExecutor::new( script, &params, path, PAGE_SIZE, build_id(), db_nickname() );
+    executor.execute();
+    ( executor.err_state, executor.error, executor.result_sets )
+  }
+
+  #[test]
+  fn test_execute_script ()
+  {
+    let path_str = "../test_data/ScriptExecutor_test_execute_script.sdb";
+    write_new_db( path_str );
+
+    let script = "
+      // seed data
+      CREATE GRAPH devs (alice:Developer {name: 'Alice'}) (bob:Developer {name: 'Bob'});
+      CREATE (carol:Lead {name: 'Carol'}) FROM devs;
+
+      /* bob moved teams */
+      MATCH (n:Developer {name: 'Bob'}) FROM devs SET n.team = $team;
+      MATCH (n) FROM devs WHERE n.team = 'ops' RETURN n.name;
+      MATCH (n) FROM devs RETURN n.name;
+    ";
+    let ( err_state, _, result_sets ) = execute_script( script, path_str );
+    assert_eq!( err_state, None );
+    assert_eq!( result_sets.len(), 5 );
+    assert_eq!( result_sets[3].rows.len(), 1 );
+    assert_eq!( result_sets[3].rows[0].values[0], Value::from( "Bob" ));
+    assert_eq!( result_sets[4].rows.len(), 3 );
+
+    let _ = remove_file( path_str );
+  }
+
+  #[test]
+  fn test_script_errors ()
+  {
+    let path_str = "../test_data/ScriptExecutor_test_script_errors.sdb";
+    write_new_db( path_str );
+
+    // a syntax error in any statement runs nothing
+    let ( err_state, error, result_sets ) = execute_script( "CREATE GRAPH devs (a:Developer);\nMATCH (n RETURN n", path_str );
+    assert_eq!( err_state, Some( String::from( "Syntax Error: Node `RETURN`" )));
+    assert_eq!( error.unwrap().span.unwrap().line, 2 );
+    assert_eq!( result_sets.len(), 0 );
+    assert_eq!( execute_script( "MATCH (n) FROM devs RETURN n", path_str ).0, Some( String::from( "Error: Graph not found." )));
+
+    // the statement that fails ends the script and is pointed at
+    let script = "CREATE GRAPH devs (a:Developer);\nMATCH (n) FROM ops RETURN n;\nMATCH (n) FROM devs RETURN n";
+    let ( err_state1, error1, result_sets1 ) = execute_script( script, path_str );
+    assert_eq!( err_state1, Some( String::from( "Error: Graph not found." )));
+    assert_eq!( result_sets1.len(), 1 );
+    assert_eq!( error1.unwrap().render( script ), String::from(
+      "Error: Graph not found.\n --> line 2, column 1\n  |\n2 | MATCH (n) FROM ops RETURN n;\n  | ^^^^^^^^^^^^^^^^^^^^^^^^^^^" ));
+
+    let _ = remove_file( path_str );
+  }
+}
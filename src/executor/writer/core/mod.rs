@@ -1,8 +1,9 @@
+use std::collections::VecDeque;
 use std::fs::{ File };
 use std::io::{ BufWriter, Seek, SeekFrom, Write };
-use crate::common::{ END_DB, LABEL_BYTES, PLACEHOLDER };
+use crate::common::{ DEFAULT_GRAPH, DEFAULT_GRAPH_UUID, END_DB, LABEL_BYTES, NEProperty, PLACEHOLDER, ROW_AFFIX_BYTES };
 use crate::datagramv2::internal_grams::{ DGu64, Label, UUID };
-use crate::datagramv2::rows::{ BuildIDRow, DBNicknameRow, EdgeRow, GraphRow, NodeRow, PageRow };
+use crate::datagramv2::rows::{ BuildIDRow, DBNicknameRow, EdgeRow, GraphRow, KVPRow, LabelRow, NodeRow, PageRow };
 use crate::executor::core_planner::{ EmptySpace, WriteNewGraphPlanner };
 
 
@@ -25,8 +26,157 @@ impl PageWriteResult
       err_state: err_state 
     }
   }
+
+  /// Position of the END affix that follows the written DataPage
+  pub fn end_affix_pos ( &self ) -> u64 { self.position_start_empty + (( self.empty_cell_count as u64 + 1 ) * 8 ) }
+}
+
+
+/*
+  DataPageCursor
+    :: new()
+    :: from_regions()
+    :: write_row()
+        :: next_region()
+        :: next_page()
+            :: claim_page()
+    :: close()
+*/
+/// Tracks the empty cells of the DataPage being written, chains a new DataPage for the graph once it is full
+#[derive(Debug)]
+pub struct DataPageCursor 
+{
+  pub graph_uuid: UUID,
+  pub graph_name: Label,
+  pub page_size: usize,
+  pub empty_cell_count: usize,
+  pub position: u64, // position of the first empty cell
+  pub regions: VecDeque<( u64, usize )>, // ( position, cell count ) of empty cells to fill before new pages
+  pub end_affix_pos: u64, // new DataPages are written over the END affix
+  pub free_pages: VecDeque<( u64, usize )>, // ( start position, cell count ) of DataPages of deleted graphs, claimed before new ones
+  pub page_count: usize, // DataPages written through this cursor
+}
+
+impl DataPageCursor 
+{
+  pub fn new ( graph_uuid: &UUID, graph_name: &Label, page_size: usize, page: &PageWriteResult ) -> DataPageCursor 
+  {
+    DataPageCursor 
+    {
+      graph_uuid: graph_uuid.clone(),
+      graph_name: graph_name.clone(),
+      page_size: page_size,
+      empty_cell_count: page.empty_cell_count,
+      position: page.position_start_empty,
+      regions: VecDeque::new(),
+      end_affix_pos: page.end_affix_pos(),
+      free_pages: VecDeque::new(),
+      page_count: 1,
+    }
+  }
+
+  /// Cursor over the empty cell regions of existing DataPages
+  pub fn from_regions ( 
+    graph_uuid: &UUID, graph_name: &Label, page_size: usize, 
+    regions: Vec<( u64, usize )>, end_affix_pos: u64 ) -> DataPageCursor 
+  {
+    DataPageCursor 
+    {
+      graph_uuid: graph_uuid.clone(),
+      graph_name: graph_name.clone(),
+      page_size: page_size,
+      empty_cell_count: 0,
+      position: 0,
+      regions: VecDeque::from( regions ),
+      end_affix_pos: end_affix_pos,
+      free_pages: VecDeque::new(),
+      page_count: 0,
+    }
+  }
+
+  /// Write a row to the current DataPage, a new DataPage is appended when the row does not fit
+  pub fn write_row ( &mut self, row: &[u8], writer: &mut BufWriter<File> ) -> Result<usize, String> 
+  {
+    let cells = row.len() / 8;
+    if cells > PageRow::empty_cell_count( self.page_size - PageRow::data_page_size() ) 
+    {
+      return Err( String::from( "Error: Row does not fit in a DataPage." ));
+    }
+    if cells > self.empty_cell_count 
+    {
+      self.close( writer )?;
+      if self.next_region( cells ) == false { self.next_page( writer )?; }
+    }
+
+    if writer.seek( SeekFrom::Start( self.position )).is_err() 
+    { 
+      return Err( String::from( "Error seeking empty cells." )); 
+    }
+    if writer.write_all( row ).is_err() { return Err( String::from( "Error writing row." )); }
+
+    self.position += row.len() as u64;
+    self.empty_cell_count -= cells;
+    Ok( row.len() )
+  }
+
+  /// Move to the next empty cell region with room for cells, smaller regions are left untouched
+  pub fn next_region ( &mut self, cells: usize ) -> bool 
+  {
+    while self.regions.len() > 0 
+    {
+      let ( position, count ) = self.regions.pop_front().unwrap();
+      if count >= cells 
+      {
+        self.position = position;
+        self.empty_cell_count = count;
+        return true;
+      }
+    }
+    false
+  }
+
+  /// Append a new DataPage for the same graph, a DataPage of a deleted graph is claimed first
+  pub fn next_page ( &mut self, writer: &mut BufWriter<File> ) -> Result<bool, String> 
+  {
+    if self.free_pages.len() > 0 { return self.claim_page( writer ); }
+
+    let page = CoreWriteExecutor::write_data_page_at( 
+      &self.graph_uuid, &self.graph_name, self.page_size, self.end_affix_pos, writer )?;
+
+    self.empty_cell_count = page.empty_cell_count;
+    self.position = page.position_start_empty;
+    self.end_affix_pos = page.end_affix_pos();
+    self.page_count += 1;
+    Ok( true )
+  }
+
+  /// Take over the next DataPage of a deleted graph
+  pub fn claim_page ( &mut self, writer: &mut BufWriter<File> ) -> Result<bool, String> 
+  {
+    let ( start_pos, cell_count ) = self.free_pages.pop_front().unwrap();
+    let page = CoreWriteExecutor::claim_data_page( &self.graph_uuid, &self.graph_name, start_pos, cell_count, writer )?;
+
+    self.empty_cell_count = page.empty_cell_count;
+    self.position = page.position_start_empty;
+    self.page_count += 1;
+    Ok( true )
+  }
+
+  /// Mark the cells left behind the last row as empty
+  pub fn close ( &mut self, writer: &mut BufWriter<File> ) -> Result<bool, String> 
+  {
+    if self.empty_cell_count == 0 { return Ok( true ); }
+    if writer.seek( SeekFrom::Start( self.position )).is_err() 
+    { 
+      return Err( String::from( "Error seeking empty cells." )); 
+    }
+    let res = CoreWriteExecutor::write_empty_marker( self.empty_cell_count, writer );
+    self.empty_cell_count = 0;
+    res
+  }
 }
 
+
 pub struct CoreWriteExecutor {}
 impl CoreWriteExecutor
 {
@@ -40,8 +190,8 @@ impl CoreWriteExecutor
     let _ = stream.write( &BuildIDRow::new( build_id )).unwrap();
     let _ = stream.write( &DBNicknameRow::new( db_nickname )).unwrap();
 
-    let default_graph_uuid = UUID::new( String::from( "1b622a2c-68dc-4848-a018-e71b604b5597" ));
-    let default_graph_name = Label::new( String::from( "DEFAULT_GRAPH" ) );
+    let default_graph_uuid = UUID::new( String::from( DEFAULT_GRAPH_UUID ));
+    let default_graph_name = Label::new( String::from( DEFAULT_GRAPH ) );
     let _ = stream.write( &GraphRow::new( &default_graph_uuid.unwrap(), &default_graph_name.unwrap() )).unwrap();
     
     let _ = stream.write( &PageRow::gen_empty_cells( page_size - byte_size )).unwrap();
@@ -82,6 +232,19 @@ impl CoreWriteExecutor
   {
     let seek_end_res = CoreWriteExecutor::seek_end_affix( planner, writer );
     if seek_end_res.is_err() { return Err( seek_end_res.unwrap_err() ); }
+    CoreWriteExecutor::write_data_page_at( graph_uuid, graph_name, page_size, seek_end_res.unwrap(), writer )
+  }
+
+
+  /// write new data page over the END affix at end_affix_pos
+  pub fn write_data_page_at (
+    graph_uuid: &UUID, graph_name: &Label, page_size: usize,
+    end_affix_pos: u64, writer: &mut BufWriter<File> ) -> Result<PageWriteResult, String> 
+  {
+    if writer.seek( SeekFrom::Start( end_affix_pos )).is_err() 
+    { 
+      return Err( String::from( "Error seeking end of file." )); 
+    }
 
     let _ = writer.write( &PLACEHOLDER.as_bytes() ).unwrap();
     let _ = writer.write( &PageRow::new_data_affix() ).unwrap();
@@ -90,7 +253,7 @@ impl CoreWriteExecutor
     let _ = writer.write( &PageRow::new_data_affix() ).unwrap();
     let _ = writer.write( &END_DB.as_bytes() ).unwrap(); 
 
-    let start_empty_pos = seek_end_res.unwrap() + (PageRow::data_page_size() as u64) - 8;
+    let start_empty_pos = end_affix_pos + (PageRow::data_page_size() as u64) - 8;
     Ok( PageWriteResult::new( 
       PageRow::empty_cell_count( page_size - PageRow::data_page_size() ), 
       start_empty_pos, 
@@ -98,6 +261,32 @@ impl CoreWriteExecutor
   }
 
 
+  /// Write the GraphRow of a graph over the one of a freed DataPage at start_pos, its cell_count body cells are empty
+  pub fn claim_data_page (
+    graph_uuid: &UUID, graph_name: &Label, 
+    start_pos: u64, cell_count: usize, writer: &mut BufWriter<File> ) -> Result<PageWriteResult, String> 
+  {
+    let graph_row_pos = start_pos + ROW_AFFIX_BYTES as u64;
+    CoreWriteExecutor::write_at( graph_row_pos, &GraphRow::new( graph_uuid, graph_name ), writer )?;
+    Ok( PageWriteResult::new( cell_count, graph_row_pos + GraphRow::size() as u64, None ))
+  }
+
+
+  /// Mark empty_cell_count cells at the current position as empty
+  /// a [STEMTY] affix stays consistent with the cell counts already behind it
+  pub fn write_empty_marker ( empty_cell_count: usize, writer: &mut BufWriter<File> ) -> Result<bool, String> 
+  {
+    let marker = match empty_cell_count 
+    {
+      0 => { return Ok( true ); }
+      1 => PageRow::new_empty_affix(),
+      _ => PageRow::new_start_empty_affix()
+    };
+    if writer.write_all( &marker ).is_err() { return Err( String::from( "Error writing empty cells." )); }
+    Ok( true )
+  }
+
+
   /// Write new node to data page (!!! handle errors)
   pub fn write_node ( 
     graph_order: &DGu64, node_id: &UUID, primary_label: &Label, 
@@ -123,9 +312,95 @@ impl CoreWriteExecutor
   }
 
 
-  pub fn write_properties ( planner: &WriteNewGraphPlanner, writer: &mut BufWriter<File> ) -> Result<bool, String> 
+  /// Write a KVPRow per key-value property and a LabelRow per Tag, returns bytes written
+  pub fn write_properties ( 
+    owner: &UUID, properties: &Vec<NEProperty>, 
+    cursor: &mut DataPageCursor, writer: &mut BufWriter<File> ) -> Result<usize, String> 
+  {
+    let rows = CoreWriteExecutor::property_rows( owner, properties )?;
+    let mut written: usize = 0;
+    for row in rows.iter() { written += cursor.write_row( row, writer )?; }
+    Ok( written )
+  }
+
+
+  /// Build a KVPRow per key-value property and a LabelRow per Tag, fails on values that do not fit a row
+  pub fn property_rows ( owner: &UUID, properties: &Vec<NEProperty> ) -> Result<Vec<Vec<u8>>, String> 
+  {
+    let mut rows: Vec<Vec<u8>> = Vec::new();
+    for prop in properties.iter() 
+    {
+      match prop 
+      {
+        NEProperty::Tag( label ) => 
+        {
+          let label_res = Label::new( label.clone() );
+          if label_res.is_err() { return Err( format!( "Error: Invalid label `{}`.", label )); }
+          rows.push( LabelRow::new( owner, &label_res.unwrap() ));
+        }
+        _ => { rows.push( KVPRow::from_property( owner, prop )? ); }
+      }
+    }
+    Ok( rows )
+  }
+
+  
+  /// Overwrite a row of the same size in place
+  pub fn write_at ( position: u64, row: &[u8], writer: &mut BufWriter<File> ) -> Result<usize, String> 
+  {
+    if writer.seek( SeekFrom::Start( position )).is_err() { return Err( String::from( "Error seeking row." )); }
+    if writer.write_all( row ).is_err() { return Err( String::from( "Error writing row." )); }
+    Ok( row.len() )
+  }
+
+
+  /// Turn the cells of a row back into an empty cell region
+  pub fn free_cells ( position: u64, cell_count: usize, writer: &mut BufWriter<File> ) -> Result<usize, String> 
+  {
+    CoreWriteExecutor::write_at( position, &PageRow::gen_empty_cells( cell_count * 8 ), writer )
+  }
+
+
+  /// Free ( position, cell count ) spans, neighbouring spans become a single empty cell region
+  pub fn free_spans ( spans: &Vec<( u64, usize )>, writer: &mut BufWriter<File> ) -> Result<usize, String> 
+  {
+    let mut sorted = spans.clone();
+    sorted.sort();
+    sorted.dedup();
+
+    let mut merged: Vec<( u64, usize )> = Vec::new();
+    for ( position, cell_count ) in sorted.into_iter() 
+    {
+      let last = merged.last_mut();
+      if last.is_some() 
+      {
+        let ( last_pos, last_count ) = last.unwrap();
+        if *last_pos + ( *last_count as u64 * 8 ) == position 
+        {
+          *last_count += cell_count;
+          continue;
+        }
+      }
+      merged.push(( position, cell_count ));
+    }
+
+    let mut freed = 0;
+    for ( position, cell_count ) in merged.iter() 
+    {
+      CoreWriteExecutor::free_cells( *position, *cell_count, writer )?;
+      freed += cell_count;
+    }
+    Ok( freed )
+  }
+
+
+  /// Write a Node or Edge row followed by the rows of its properties, returns bytes written
+  pub fn write_rows ( 
+    owner: &UUID, row: &[u8], properties: &Vec<NEProperty>, 
+    cursor: &mut DataPageCursor, writer: &mut BufWriter<File> ) -> Result<usize, String> 
   {
-    Err( String::from( "Error writing properties" ))
+    let written = cursor.write_row( row, writer )?;
+    Ok( written + CoreWriteExecutor::write_properties( owner, properties, cursor, writer )? )
   }
 
   
@@ -150,7 +425,7 @@ impl CoreWriteExecutor
   {
     if planner.end_pos.is_some() 
     {
-      let seek_res = writer.seek( SeekFrom::Current(( planner.end_pos.as_ref().unwrap() - 8 ) as i64 ));
+      let seek_res = writer.seek( SeekFrom::Start( planner.end_pos.as_ref().unwrap() - 8 ));
       if seek_res.is_ok() { return Ok( seek_res.unwrap() ); }
       else { return Err( seek_res.unwrap_err().to_string() ); }
     }
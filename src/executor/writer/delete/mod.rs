@@ -0,0 +1,408 @@
+use std::fs::File;
+use std::io::{ BufWriter, Write };
+use std::path::PathBuf;
+
+use crate::cmd::transaction::Transaction;
+use crate::common::{ DEFAULT_GRAPH, ROW_AFFIX_BYTES };
+use crate::datagramv2::internal_grams::Label;
+use crate::datagramv2::rows::GraphRow;
+use crate::result_set::{ ResultSet, Value };
+use crate::utils::open_file;
+
+use crate::executor::core_planner::WriteNewGraphPlanner;
+use crate::executor::reader::simple_match::SimpleMatchExecutor;
+use crate::executor::writer::core::CoreWriteExecutor;
+
+/*
+MATCH (n:Developer {name: 'Alice'}) DELETE n FROM devs
+
+MATCH (a)-[r:KNOWS]->(b) DELETE r FROM devs
+
+MATCH (n {name: 'Alice'}) DETACH DELETE n FROM devs
+
+DELETE GRAPH devs
+*/
+
+/*
+  DeleteExecutor
+    :: new()
+    :: execute()
+        :: delete_graph()
+        :: collect_deletes()
+        :: write()
+    :: result_set()
+*/
+/// Deleted rows are turned back into empty cell regions that later writes reuse
+pub struct DeleteExecutor<'a>
+{
+  pub transaction: &'a Transaction,
+  pub path: &'a str,
+  pub page_size: usize,
+  pub reader: Option<SimpleMatchExecutor<'a>>, // MATCH the deleted variables refer to
+  pub deleted_nodes: Vec<String>, // node ids
+  pub deleted_edges: Vec<String>, // edge ids, relationships of DETACH deleted nodes included
+  pub freed_cell_count: usize,
+  pub err_state: Option<String>,
+}
+
+impl DeleteExecutor<'_>
+{
+  pub fn new<'a> ( t: &'a Transaction, path: &'a str, page_size: usize ) -> DeleteExecutor<'a>
+  {
+    DeleteExecutor
+    {
+      transaction: t,
+      path: path,
+      page_size: page_size,
+      reader: None,
+      deleted_nodes: Vec::new(),
+      deleted_edges: Vec::new(),
+      freed_cell_count: 0,
+      err_state: None,
+    }
+  }
+
+  pub fn execute ( &mut self )
+  {
+    if self.transaction.err_state.is_some()
+    {
+      self.err_state = self.transaction.err_state.clone();
+      return;
+    }
+    if self.transaction.delete_statement.is_none()
+    {
+      self.err_state = Some( String::from( "Error: Nothing to delete." ));
+      return;
+    }
+
+    let graph_name = self.transaction.delete_statement.as_ref().unwrap().graph_name.clone();
+    if graph_name.is_some()
+    {
+      let res = self.delete_graph( &graph_name.unwrap() );
+      if res.is_err() { self.err_state = Some( res.unwrap_err() ); }
+      return;
+    }
+
+    let mut reader = SimpleMatchExecutor::new( self.transaction, self.path, self.page_size );
+    reader.execute();
+    if reader.err_state.is_some()
+    {
+      self.err_state = reader.err_state;
+      return;
+    }
+    self.reader = Some( reader );
+
+    let res = self.collect_deletes();
+    if res.is_err()
+    {
+      self.err_state = Some( res.unwrap_err() );
+      return;
+    }
+
+    let write_res = self.write();
+    if write_res.is_err() { self.err_state = Some( write_res.unwrap_err() ); }
+  }
+
+  /// Result of the MATCH, rows still show the deleted values
+  pub fn result_set ( &self ) -> Result<ResultSet, String>
+  {
+    if self.err_state.is_some() { return Err( self.err_state.as_ref().unwrap().clone() ); }
+    if self.reader.is_none() { return Ok( ResultSet::new( Vec::new() )); }
+    self.reader.as_ref().unwrap().result_set()
+  }
+}
+
+impl DeleteExecutor<'_>
+{
+  /// Free the GraphRow on the DBPage and everything stored on the DataPages of the graph
+  pub fn delete_graph ( &mut self, name: &str ) -> Result<bool, String>
+  {
+    if name == DEFAULT_GRAPH { return Err( String::from( "Error: The default graph cannot be deleted." )); }
+
+    let label_res = Label::new( name.to_string() );
+    if label_res.is_err() { return Err( String::from( "Error finding graph name." )); }
+    let graph_name = label_res.unwrap();
+
+    let mut planner = WriteNewGraphPlanner::for_existing( self.path.to_string(), &graph_name );
+    planner.plan();
+    if planner.err_state.is_some() { return Err( planner.err_state.unwrap() ); }
+    if planner.graph_row_pos.is_none() { return Err( String::from( "Error: Graph not found." )); }
+
+    // DataPages keep their GraphRow, rows between it and the closing affix are freed
+    // without a GraphRow on the DBPage the planner hands the pages to the next graph that needs one
+    let mut spans: Vec<( u64, usize )> = vec![ ( planner.graph_row_pos.unwrap(), GraphRow::cell_count() ) ];
+    let body_offset = ( ROW_AFFIX_BYTES + GraphRow::size() ) as u64;
+    for page in planner.fetch_data_pages_by_graph( &graph_name.unwrap() ).iter()
+    {
+      if page.end_pos.is_none() { return Err( String::from( "Error reading DataPage." )); }
+      let body_start = page.start_pos + body_offset;
+      spans.push(( body_start, (( page.end_pos.unwrap() - 8 - body_start ) / 8 ) as usize ));
+    }
+
+    let open_res = open_file( &PathBuf::from( &self.path ));
+    if open_res.is_err() { return Err( String::from( "Error opening database file." )); }
+    let mut writer: BufWriter<File> = BufWriter::new( open_res.unwrap() );
+
+    self.freed_cell_count = CoreWriteExecutor::free_spans( &spans, &mut writer )?;
+    if writer.flush().is_err() { return Err( String::from( "Error writing database file." )); }
+    Ok( true )
+  }
+
+  /// Resolve the deleted variables, nodes with relationships require DETACH
+  pub fn collect_deletes ( &mut self ) -> Result<bool, String>
+  {
+    let reader = self.reader.as_ref().unwrap();
+    let delete_stmt = self.transaction.delete_statement.as_ref().unwrap();
+
+    let variables = self.transaction.variables();
+
+    let mut nodes: Vec<( String, String )> = Vec::new(); // ( variable, node id )
+    let mut edges: Vec<String> = Vec::new();
+    for variable in delete_stmt.transaction_labels.iter()
+    {
+      if variables.contains( variable ) == false
+      {
+        return Err( format!( "Error: Variable `{}` not defined.", variable ));
+      }
+
+      for binding in reader.bindings.iter()
+      {
+        match binding.iter().find( | ( var, _ ) | var == variable ).map( | ( _, val ) | val )
+        {
+          Some( Value::Node( node )) =>
+          {
+            if nodes.iter().any( | ( _, id ) | id == &node.id ) == false { nodes.push(( variable.clone(), node.id.clone() )); }
+          }
+          Some( Value::Edge( edge )) =>
+          {
+            if edges.contains( &edge.id ) == false { edges.push( edge.id.clone() ); }
+          }
+          // [r*1..3] binds a list of relationships
+          Some( Value::List( vals )) =>
+          {
+            for edge in vals.iter().filter_map( | val | val.as_edge() )
+            {
+              if edges.contains( &edge.id ) == false { edges.push( edge.id.clone() ); }
+            }
+          }
+          Some( Value::Path( path )) =>
+          {
+            for node in path.nodes.iter()
+            {
+              if nodes.iter().any( | ( _, id ) | id == &node.id ) == false { nodes.push(( variable.clone(), node.id.clone() )); }
+            }
+            for edge in path.edges.iter()
+            {
+              if edges.contains( &edge.id ) == false { edges.push( edge.id.clone() ); }
+            }
+          }
+          _ => {}
+        }
+      }
+    }
+
+    // reader.edges holds every relationship of the graph
+    for ( variable, node_id ) in nodes.iter()
+    {
+      for edge in reader.edges.iter()
+      {
+        if &edge.left_id != node_id && &edge.right_id != node_id { continue; }
+        if edges.contains( &edge.id ) { continue; }
+        if delete_stmt.detach == false
+        {
+          return Err( format!( "Error: Node `{}` still has relationships, use DETACH DELETE.", variable ));
+        }
+        edges.push( edge.id.clone() );
+      }
+    }
+
+    self.deleted_nodes = nodes.into_iter().map( | ( _, id ) | id ).collect();
+    self.deleted_edges = edges;
+    Ok( true )
+  }
+
+  /// Free NodeRows and EdgeRows together with the KVPRows and LabelRows they own
+  pub fn write ( &mut self ) -> Result<bool, String>
+  {
+    let reader = self.reader.as_ref().unwrap();
+    let is_deleted = | id: &String | self.deleted_nodes.contains( id ) || self.deleted_edges.contains( id );
+
+    let mut spans: Vec<( u64, usize )> = Vec::new();
+    for ( position, cell_count, id ) in reader.row_positions.iter()
+    {
+      if is_deleted( id ) { spans.push(( *position, *cell_count )); }
+    }
+    for ( position, cell_count, owner, _ ) in reader.property_rows.iter()
+    {
+      if is_deleted( owner ) { spans.push(( *position, *cell_count )); }
+    }
+    if spans.len() == 0 { return Ok( true ); }
+
+    let open_res = open_file( &PathBuf::from( &self.path ));
+    if open_res.is_err() { return Err( String::from( "Error opening database file." )); }
+    let mut writer: BufWriter<File> = BufWriter::new( open_res.unwrap() );
+
+    self.freed_cell_count = CoreWriteExecutor::free_spans( &spans, &mut writer )?;
+    if writer.flush().is_err() { return Err( String::from( "Error writing database file." )); }
+    Ok( true )
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+  use std::fs::{ metadata, remove_file };
+  use crate::datagramv2::internal_grams::UUID;
+  use crate::datagramv2::rows::{ KVPRow, NodeRow };
+  use crate::planner::process_query;
+  use crate::utils::create_file;
+  use crate::executor::writer::new_db::WriteNewDBExecutor;
+  use crate::executor::writer::new_graph::WriteNewGraphExecutor;
+  use crate::executor::writer::update_graph::UpdateGraphExecutor;
+
+  const PAGE_SIZE: usize = 4096;
+
+  fn build_id () -> UUID { UUID::new( String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" )).unwrap() }
+  fn db_nickname () -> Label { Label::new( String::from( "devs" ) ).unwrap() }
+  fn write_new_db ( path: &str )
+  {
+    let open_res = create_file( &PathBuf::from( path ));
+    let mut stream = BufWriter::new( open_res.unwrap() );
+    let _ = WriteNewDBExecutor::execute_write_new( &build_id(), &db_nickname(), PAGE_SIZE, &mut stream );
+  }
+
+  fn write_devs_graph ( path: &str )
+  {
+    let query_string = "
+      CREATE GRAPH devs
+        (alice:Developer {name: 'Alice'})
+        (bob:Administrator {name: 'Bob'})
+        (carol:Lead {name: 'Carol'})
+        (alice)-[:KNOWS]->(bob)
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+  }
+
+  /// ( err_state, deleted nodes, deleted edges, freed cells )
+  fn execute_delete ( query_string: &str, path: &str ) -> ( Option<String>, usize, usize, usize )
+  {
+    let t = process_query( query_string, build_id(), db_nickname() );
+    let mut executor = DeleteExecutor::new( &t, path, PAGE_SIZE );
+    executor.execute();
+    ( executor.err_state, executor.deleted_nodes.len(), executor.deleted_edges.len(), executor.freed_cell_count )
+  }
+
+  fn match_all ( path: &str ) -> ( usize, usize )
+  {
+    let t = process_query( "MATCH (n) FROM devs", build_id(), db_nickname() );
+    let mut reader = SimpleMatchExecutor::new( &t, path, PAGE_SIZE );
+    reader.execute();
+    assert_eq!( reader.err_state, None );
+    ( reader.nodes.len(), reader.edges.len() )
+  }
+
+  #[test]
+  fn test_delete_node ()
+  {
+    let path_str = "../test_data/DeleteExecutor_test_delete_node.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    let freed = NodeRow::cell_count() + KVPRow::cell_count( "[:KVSTR]" ).unwrap();
+    assert_eq!( execute_delete( "MATCH (n:Lead) DELETE n FROM devs", path_str ), ( None, 1, 0, freed ));
+    assert_eq!( match_all( path_str ), ( 2, 1 ));
+
+    // the NodeRow and its KVPRow become one region that new rows reuse
+    let name = db_nickname();
+    let mut planner = WriteNewGraphPlanner::for_existing( path_str.to_string(), &name );
+    planner.plan();
+    assert_eq!( planner.empty_regions_by_graph( &name.unwrap() ).iter().any( | ( _, count ) | *count == freed ), true );
+
+    let t = process_query( "CREATE (dave:Developer {name: 'Dave'}) FROM devs", build_id(), db_nickname() );
+    let mut update = UpdateGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    update.execute();
+    assert_eq!( update.err_state, None );
+    assert_eq!( match_all( path_str ), ( 3, 1 ));
+    assert_eq!( metadata( &PathBuf::from( path_str ) ).unwrap().len(), (PAGE_SIZE * 2) as u64 );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_delete_relationships ()
+  {
+    let path_str = "../test_data/DeleteExecutor_test_delete_relationships.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    let ( err_state, _, _, _ ) = execute_delete( "MATCH (n:Developer) DELETE n FROM devs", path_str );
+    assert_eq!( err_state, Some( String::from( "Error: Node `n` still has relationships, use DETACH DELETE." )));
+    assert_eq!( match_all( path_str ), ( 3, 1 ));
+
+    // deleting the relationship in the same statement is enough
+    let ( err_state1, nodes1, edges1, _ ) = execute_delete( "MATCH (a:Developer)-[r:KNOWS]->(b) DELETE a, r FROM devs", path_str );
+    assert_eq!( ( err_state1, nodes1, edges1 ), ( None, 1, 1 ));
+    assert_eq!( match_all( path_str ), ( 2, 0 ));
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_detach_delete ()
+  {
+    let path_str = "../test_data/DeleteExecutor_test_detach_delete.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    let ( err_state, nodes, edges, _ ) = execute_delete( "MATCH (n {name: 'Bob'}) DETACH DELETE n FROM devs", path_str );
+    assert_eq!( ( err_state, nodes, edges ), ( None, 1, 1 ));
+    assert_eq!( match_all( path_str ), ( 2, 0 ));
+
+    let ( err_state1, _, _, _ ) = execute_delete( "MATCH (n) DELETE m FROM devs", path_str );
+    assert_eq!( err_state1, Some( String::from( "Error: Variable `m` not defined." )));
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_delete_graph ()
+  {
+    let path_str = "../test_data/DeleteExecutor_test_delete_graph.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    assert_eq!( execute_delete( "DELETE GRAPH devs", path_str ).0, None );
+
+    let t = process_query( "MATCH (n) FROM devs", build_id(), db_nickname() );
+    let mut reader = SimpleMatchExecutor::new( &t, path_str, PAGE_SIZE );
+    reader.execute();
+    assert_eq!( reader.err_state, Some( String::from( "Error: Graph not found." )));
+
+    // a graph with the same name starts out empty
+    let t1 = process_query( "CREATE GRAPH devs (eve:Developer)", build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t1, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+    assert_eq!( match_all( path_str ), ( 1, 0 ));
+
+    // the freed DataPages are reused, the file does not grow on every delete and create
+    let len = metadata( &PathBuf::from( path_str )).unwrap().len();
+    for _ in 0..3
+    {
+      assert_eq!( execute_delete( "DELETE GRAPH devs", path_str ).0, None );
+      write_devs_graph( path_str );
+      assert_eq!( metadata( &PathBuf::from( path_str )).unwrap().len(), len );
+    }
+    assert_eq!( match_all( path_str ), ( 3, 1 ));
+
+    assert_eq!( execute_delete( "DELETE GRAPH qa", path_str ).0, Some( String::from( "Error: Graph not found." )));
+    assert_eq!( execute_delete( "DELETE GRAPH DEFAULT_GRAPH", path_str ).0,
+      Some( String::from( "Error: The default graph cannot be deleted." )));
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+}
@@ -0,0 +1,476 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{ BufWriter, Write };
+use std::path::PathBuf;
+
+use crate::cmd::{ transaction::Transaction, BracketStatement, MatchStatement, MergeAction, SetStatement, SetType };
+use crate::common::{ direction_to_str, Edge, NEProperty, Node };
+use crate::datagramv2::internal_grams::{ DGu64, Label, UUID };
+use crate::datagramv2::rows::{ EdgeRow, NodeRow };
+use crate::result_set::{ ResultSet, Value };
+use crate::utils::{ cons_uuid, open_file, parse_padded_str, parse_uuid_str };
+
+use crate::executor::core_planner::WriteNewGraphPlanner;
+use crate::executor::reader::simple_match::{ edge_orientations, matches_edge, matches_node, SimpleMatchExecutor };
+use crate::executor::writer::core::{ CoreWriteExecutor, DataPageCursor };
+use crate::executor::writer::set_properties::SetPropertiesExecutor;
+
+/*
+MERGE (n:Person {name: 'Alice'}) FROM devs
+
+MERGE (n:Person {name: 'Alice'}) ON CREATE SET n.created = true ON MATCH SET n.seen = 2 FROM devs RETURN n
+
+MERGE (a:Person {name: 'Alice'})-[r:KNOWS]->(b:Person {name: 'Bob'}) ON CREATE SET r.since = 2024 FROM devs
+*/
+
+/*
+  MergeExecutor
+    :: new()
+    :: execute()
+        :: merge_pattern()
+            :: merge_node()
+            :: merge_relationship()
+            :: apply_create_sets()
+        :: write_created()
+        :: apply_match_sets()
+    :: result_set()
+*/
+/// MERGE is MATCH or CREATE per pattern element: each node is merged on its own, then the relationship between them,
+/// so re-running a MERGE never duplicates nodes or edges
+pub struct MergeExecutor<'a>
+{
+  pub transaction: &'a Transaction,
+  pub path: &'a str,
+  pub page_size: usize,
+  pub reader: Option<SimpleMatchExecutor<'a>>, // stored graph, its bindings hold the merged pattern
+  pub graph_order: u64, // graph order of the next written row
+  pub created_nodes: Vec<Node>,
+  pub created_edges: Vec<Edge>,
+  pub err_state: Option<String>,
+}
+
+impl MergeExecutor<'_>
+{
+  pub fn new<'a> ( t: &'a Transaction, path: &'a str, page_size: usize ) -> MergeExecutor<'a>
+  {
+    MergeExecutor
+    {
+      transaction: t,
+      path: path,
+      page_size: page_size,
+      reader: None,
+      graph_order: 0,
+      created_nodes: Vec::new(),
+      created_edges: Vec::new(),
+      err_state: None,
+    }
+  }
+
+  pub fn execute ( &mut self )
+  {
+    if self.transaction.err_state.is_some()
+    {
+      self.err_state = self.transaction.err_state.clone();
+      return;
+    }
+    if self.transaction.merge == false || self.transaction.read_clause.is_none()
+    {
+      self.err_state = Some( String::from( "Error: Nothing to merge." ));
+      return;
+    }
+
+    let mut reader = SimpleMatchExecutor::new( self.transaction, self.path, self.page_size );
+    reader.read_graph();
+    if reader.err_state.is_some()
+    {
+      self.err_state = reader.err_state;
+      return;
+    }
+    self.graph_order = reader.next_graph_order;
+    self.reader = Some( reader );
+
+    // everything is resolved before the first write
+    let res = self.merge_pattern();
+    if res.is_err()
+    {
+      self.err_state = Some( res.unwrap_err() );
+      return;
+    }
+
+    if self.created_nodes.len() > 0 || self.created_edges.len() > 0
+    {
+      let write_res = self.write_created();
+      if write_res.is_err()
+      {
+        self.err_state = Some( write_res.unwrap_err() );
+        return;
+      }
+    }
+
+    let set_res = self.apply_match_sets();
+    if set_res.is_err() { self.err_state = Some( set_res.unwrap_err() ); }
+  }
+
+  /// Result of the merged pattern
+  pub fn result_set ( &self ) -> Result<ResultSet, String>
+  {
+    if self.err_state.is_some() { return Err( self.err_state.as_ref().unwrap().clone() ); }
+    if self.reader.is_none() { return Err( String::from( "Error: Query has not been executed." )); }
+    self.reader.as_ref().unwrap().result_set()
+  }
+}
+
+impl MergeExecutor<'_>
+{
+  /// Match or create every element of the pattern and bind it
+  pub fn merge_pattern ( &mut self ) -> Result<bool, String>
+  {
+    let read_clause = self.transaction.read_clause.as_ref().unwrap();
+    let variables = read_clause.variables();
+    for stmt in self.transaction.set_statements.iter()
+    {
+      if variables.contains( &stmt.transaction_label ) == false
+      {
+        return Err( format!( "Error: Variable `{}` not defined.", stmt.transaction_label ));
+      }
+    }
+
+    let bindings = match ( read_clause.match_statements.len(), read_clause.bracket_statements.len() )
+    {
+      ( 1, 0 ) =>
+      {
+        let stmt = read_clause.match_statements.first().unwrap();
+        let variable = stmt.transaction_label.clone().unwrap_or( String::from( "node" ));
+        self.merge_node( stmt, false )?.into_iter()
+          .map( | node | vec![ ( variable.clone(), Value::Node( node )) ] )
+          .collect()
+      }
+      ( _, 1 ) => 
+      { 
+        let bracket = read_clause.bracket_statements.first().unwrap();
+        if bracket.hops.is_some() { return Err( String::from( "Error: Merge does not support variable-length relationships." )); }
+        self.merge_relationship( bracket )? 
+      }
+      ( _, 0 ) => { return Err( String::from( "Error: Merge requires a single node or relationship pattern." )); }
+      _ => { return Err( String::from( "Error: Multi-hop patterns are not supported." )); }
+    };
+    self.reader.as_mut().unwrap().bindings = bindings;
+    Ok( true )
+  }
+
+  /// Stored nodes accepted by stmt, or the node to create when there are none
+  pub fn merge_node ( &mut self, stmt: &MatchStatement, single: bool ) -> Result<Vec<Node>, String>
+  {
+    let variable = stmt.transaction_label.clone().unwrap_or( String::from( "node" ));
+    let matched: Vec<Node> = self.reader.as_ref().unwrap().nodes.iter()
+      .filter( | node | matches_node( node, stmt ))
+      .cloned()
+      .collect();
+
+    if matched.len() > 1 && single { return Err( format!( "Error: Node `{}` matches more than one node.", variable )); }
+    if matched.len() > 0 { return Ok( matched ); }
+
+    let created_opt = self.created_nodes.iter().find( | node | matches_node( node, stmt ));
+    if created_opt.is_some() { return Ok( vec![ created_opt.unwrap().clone() ] ); }
+
+    if stmt.primary_label.is_none() { return Err( format!( "Error: Merge requires a label to create `{}`.", variable )); }
+    let mut node = Node::new(
+      cons_uuid(),
+      Some( parse_padded_str( stmt.primary_label.as_ref().unwrap() ).to_string() ),
+      stmt.properties.clone() );
+    for label in stmt.labels.iter() 
+    { 
+      if node.has_label( label ) == false { node.io_add_property( NEProperty::Tag( label.clone() )); } 
+    }
+    for prop in self.create_sets( stmt.transaction_label.as_ref(), true )?.into_iter()
+    {
+      match prop
+      {
+        NEProperty::Tag( ref label ) => { if node.has_label( label ) == false { node.io_add_property( prop ); } }
+        _ => { node.io_set_property( prop ); }
+      }
+    }
+    self.created_nodes.push( node.clone() );
+    Ok( vec![ node ] )
+  }
+
+  /// (left)-[bracket]-(right) where both nodes are merged first
+  pub fn merge_relationship ( &mut self, bracket: &BracketStatement ) -> Result<Vec<Vec<( String, Value )>>, String>
+  {
+    let ( left_opt, right_opt ) = self.transaction.read_clause.as_ref().unwrap().bracket_neighbors( bracket );
+    if left_opt.is_none() || right_opt.is_none() { return Err( String::from( "Error: Relationship requires two nodes." )); }
+    if bracket.primary_label.is_none() { return Err( String::from( "Error: Merge requires a relationship type." )); }
+    let ( left_stmt, right_stmt ) = ( left_opt.unwrap(), right_opt.unwrap() );
+
+    let left = self.merge_node( left_stmt, true )?.remove( 0 );
+    let right = self.merge_node( right_stmt, true )?.remove( 0 );
+
+    let ids = ( left.id.clone(), right.id.clone() );
+    let mut edges: Vec<Edge> = self.reader.as_ref().unwrap().edges.iter()
+      .filter( | edge | matches_edge( edge, bracket ) && edge_orientations( edge, &bracket.edge_dir ).contains( &ids ))
+      .cloned()
+      .collect();
+
+    if edges.len() == 0
+    {
+      let mut edge = Edge::new(
+        cons_uuid(),
+        bracket.primary_label.clone(),
+        left.id.clone(),
+        right.id.clone(),
+        bracket.edge_dir.clone(),
+        bracket.properties.clone() );
+      for prop in self.create_sets( bracket.transaction_label.as_ref(), false )?.into_iter() { edge.io_set_property( prop ); }
+      self.created_edges.push( edge.clone() );
+      edges.push( edge );
+    }
+
+    let mut bindings = Vec::new();
+    for edge in edges.into_iter()
+    {
+      let mut binding = Vec::new();
+      if left_stmt.transaction_label.is_some()
+      {
+        binding.push(( left_stmt.transaction_label.clone().unwrap(), Value::Node( left.clone() )));
+      }
+      if bracket.transaction_label.is_some()
+      {
+        binding.push(( bracket.transaction_label.clone().unwrap(), Value::Edge( edge )));
+      }
+      if right_stmt.transaction_label.is_some()
+      {
+        binding.push(( right_stmt.transaction_label.clone().unwrap(), Value::Node( right.clone() )));
+      }
+      bindings.push( binding );
+    }
+    Ok( bindings )
+  }
+
+  /// Properties and labels the SET items give a created element (SET and ON CREATE SET)
+  fn create_sets ( &self, variable: Option<&String>, is_node: bool ) -> Result<Vec<NEProperty>, String>
+  {
+    let mut props: Vec<NEProperty> = Vec::new();
+    if variable.is_none() { return Ok( props ); }
+
+    for stmt in self.transaction.set_statements.iter()
+    {
+      if &stmt.transaction_label != variable.unwrap() || stmt.merge_action == Some( MergeAction::OnMatch ) { continue; }
+      if stmt.set_type == Some( SetType::Label )
+      {
+        if is_node == false { return Err( String::from( "Error: Labels can only be set on nodes." )); }
+        for label in stmt.labels.iter() { props.push( NEProperty::Tag( label.clone() )); }
+        continue;
+      }
+      for prop in stmt.properties.iter() { props.push( prop.clone() ); }
+    }
+    Ok( props )
+  }
+
+  /// Write created nodes before the edges that refer to them
+  pub fn write_created ( &mut self ) -> Result<bool, String>
+  {
+    let reader = self.reader.as_ref().unwrap();
+    let graph_name = reader.graph_name.as_ref().unwrap();
+    let mut planner = WriteNewGraphPlanner::for_existing( self.path.to_string(), graph_name );
+    planner.plan();
+    if planner.err_state.is_some() { return Err( planner.err_state.unwrap() ); }
+
+    let uuid_res = UUID::new( parse_uuid_str( reader.graph_uuid.as_ref().unwrap() ).to_string() );
+    if uuid_res.is_err() { return Err( String::from( "Error reading graph uuid." )); }
+
+    let open_res = open_file( &PathBuf::from( &self.path ));
+    if open_res.is_err() { return Err( String::from( "Error opening database file." )); }
+    let mut writer: BufWriter<File> = BufWriter::new( open_res.unwrap() );
+
+    let mut page = DataPageCursor::from_regions(
+      &uuid_res.unwrap(),
+      graph_name,
+      self.page_size,
+      planner.empty_regions_by_graph( &graph_name.unwrap() ),
+      planner.end_pos.unwrap() - 8 );
+    page.free_pages = VecDeque::from( planner.free_data_pages() );
+
+    for node in self.created_nodes.iter()
+    {
+      let uuid = UUID::new( node.id.clone() )?;
+      let label_res = Label::new( node.primary_tag.clone().unwrap() );
+      if label_res.is_err() { return Err( String::from( "Error: Invalid label." )); }
+      let row = NodeRow::new( &DGu64::new( self.graph_order ), &uuid, &label_res.unwrap() );
+      CoreWriteExecutor::write_rows( &uuid, &row, &node.properties, &mut page, &mut writer )?;
+      self.graph_order += 1;
+    }
+
+    for edge in self.created_edges.iter()
+    {
+      let uuid = UUID::new( edge.id.clone() )?;
+      let label_res = Label::new( edge.primary_tag.clone().unwrap() );
+      if label_res.is_err() { return Err( String::from( "Error: Invalid label." )); }
+      let row = EdgeRow::new(
+        &DGu64::new( self.graph_order ),
+        &uuid,
+        &label_res.unwrap(),
+        direction_to_str( &edge.direction ),
+        &UUID::new( edge.left_id.clone() )?,
+        &UUID::new( edge.right_id.clone() )? );
+      CoreWriteExecutor::write_rows( &uuid, &row, &edge.properties, &mut page, &mut writer )?;
+      self.graph_order += 1;
+    }
+
+    page.close( &mut writer )?;
+    if writer.flush().is_err() { return Err( String::from( "Error writing database file." )); }
+    Ok( true )
+  }
+
+  /// SET and ON MATCH SET items on the matched elements, created elements already carry their values
+  pub fn apply_match_sets ( &mut self ) -> Result<bool, String>
+  {
+    let stmts: Vec<&SetStatement> = self.transaction.set_statements.iter()
+      .filter( | stmt | stmt.merge_action != Some( MergeAction::OnCreate ))
+      .collect();
+    if stmts.len() == 0 { return Ok( true ); }
+
+    let mut set_executor = SetPropertiesExecutor::with_reader(
+      self.transaction, self.path, self.page_size, self.reader.take().unwrap() );
+    set_executor.skip_owners = self.created_nodes.iter().map( | node | node.id.clone() )
+      .chain( self.created_edges.iter().map( | edge | edge.id.clone() ))
+      .collect();
+    set_executor.apply( &stmts );
+
+    self.reader = set_executor.reader.take();
+    if set_executor.err_state.is_some() { return Err( set_executor.err_state.unwrap() ); }
+    Ok( true )
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+  use std::fs::{ metadata, remove_file };
+  use crate::planner::process_query;
+  use crate::utils::create_file;
+  use crate::executor::writer::new_db::WriteNewDBExecutor;
+  use crate::executor::writer::new_graph::WriteNewGraphExecutor;
+
+  const PAGE_SIZE: usize = 4096;
+
+  fn build_id () -> UUID { UUID::new( String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" )).unwrap() }
+  fn db_nickname () -> Label { Label::new( String::from( "devs" ) ).unwrap() }
+  fn write_new_db ( path: &str )
+  {
+    let open_res = create_file( &PathBuf::from( path ));
+    let mut stream = BufWriter::new( open_res.unwrap() );
+    let _ = WriteNewDBExecutor::execute_write_new( &build_id(), &db_nickname(), PAGE_SIZE, &mut stream );
+  }
+
+  fn write_devs_graph ( path: &str )
+  {
+    let query_string = "
+      CREATE GRAPH devs
+        (alice:Developer {name: 'Alice'})
+        (bob:Administrator {name: 'Bob'})
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+  }
+
+  /// ( err_state, created nodes, created edges, first RETURN value )
+  fn execute_merge ( query_string: &str, path: &str ) -> ( Option<String>, usize, usize, Option<Value> )
+  {
+    let t = process_query( query_string, build_id(), db_nickname() );
+    let mut executor = MergeExecutor::new( &t, path, PAGE_SIZE );
+    executor.execute();
+    let value = executor.result_set().ok().and_then( | rs | rs.rows.first().map( | row | row.values[0].clone() ));
+    ( executor.err_state, executor.created_nodes.len(), executor.created_edges.len(), value )
+  }
+
+  fn match_all ( path: &str ) -> ( Vec<Node>, usize )
+  {
+    let t = process_query( "MATCH (n) FROM devs", build_id(), db_nickname() );
+    let mut reader = SimpleMatchExecutor::new( &t, path, PAGE_SIZE );
+    reader.execute();
+    assert_eq!( reader.err_state, None );
+    ( reader.nodes, reader.edges.len() )
+  }
+
+  #[test]
+  fn test_merge_node ()
+  {
+    let path_str = "../test_data/MergeExecutor_test_merge_node.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    let query_string = "
+      MERGE (n:Developer {name: 'Carol'})
+      ON CREATE SET n.runs = 1
+      ON MATCH SET n.runs = 2
+      FROM devs
+      RETURN n.runs";
+    assert_eq!( execute_merge( query_string, path_str ), ( None, 1, 0, Some( Value::I64( 1 ))));
+    assert_eq!( match_all( path_str ).0.len(), 3 );
+
+    // a re-run matches the node created by the first run
+    assert_eq!( execute_merge( query_string, path_str ), ( None, 0, 0, Some( Value::I64( 2 ))));
+    assert_eq!( execute_merge( query_string, path_str ), ( None, 0, 0, Some( Value::I64( 2 ))));
+
+    let ( nodes, _ ) = match_all( path_str );
+    assert_eq!( nodes.len(), 3 );
+    let carol = nodes.iter().find( | n | n.property( "name" ) == Some( &NEProperty::KvpString(( String::from( "name" ), String::from( "Carol" ))))).unwrap();
+    assert_eq!( carol.properties.len(), 2 );
+    assert_eq!( carol.property( "runs" ), Some( &NEProperty::KvpI64(( String::from( "runs" ), 2 ))));
+    assert_eq!( metadata( &PathBuf::from( path_str ) ).unwrap().len(), (PAGE_SIZE * 2) as u64 );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_merge_relationship ()
+  {
+    let path_str = "../test_data/MergeExecutor_test_merge_relationship.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    let query_string = "
+      MERGE (a:Developer {name: 'Alice'})-[r:KNOWS]->(b:Lead {name: 'Dave'})
+      ON CREATE SET r.since = 2024, b:Manager
+      FROM devs
+      RETURN r.since";
+    assert_eq!( execute_merge( query_string, path_str ), ( None, 1, 1, Some( Value::I64( 2024 ))));
+    assert_eq!( execute_merge( query_string, path_str ), ( None, 0, 0, Some( Value::I64( 2024 ))));
+
+    let ( nodes, edge_count ) = match_all( path_str );
+    assert_eq!( nodes.len(), 3 );
+    assert_eq!( edge_count, 1 );
+    let dave = nodes.iter().find( | n | n.primary_tag == Some( String::from( "Lead" ))).unwrap();
+    assert_eq!( dave.has_label( "Manager" ), true );
+
+    // the reverse direction is a different relationship
+    let reverse = "MERGE (b:Lead {name: 'Dave'})-[r:KNOWS]->(a:Developer {name: 'Alice'}) FROM devs";
+    let ( err, created_nodes, created_edges, _ ) = execute_merge( reverse, path_str );
+    assert_eq!( ( err, created_nodes, created_edges ), ( None, 0, 1 ));
+    assert_eq!( match_all( path_str ).1, 2 );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_merge_errors ()
+  {
+    let path_str = "../test_data/MergeExecutor_test_merge_errors.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    assert_eq!( execute_merge( "MERGE (n {name: 'Eve'}) FROM devs", path_str ).0,
+      Some( String::from( "Error: Merge requires a label to create `n`." )));
+    assert_eq!( execute_merge( "MERGE (n:Developer) ON CREATE SET m.x = 1 FROM devs", path_str ).0,
+      Some( String::from( "Error: Variable `m` not defined." )));
+    assert_eq!( execute_merge( "MERGE (a)-[:KNOWS]->(b:Developer) FROM devs", path_str ).0,
+      Some( String::from( "Error: Node `a` matches more than one node." )));
+    let ( nodes, edge_count ) = match_all( path_str );
+    assert_eq!( ( nodes.len(), edge_count ), ( 2, 0 ));
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+}
@@ -1,4 +1,7 @@
 pub mod core;
+pub mod delete;
+pub mod merge;
 pub mod new_graph;
 pub mod new_db;
-pub mod update_graph;
\ No newline at end of file
+pub mod set_properties;
+pub mod update_graph;
@@ -1,20 +1,22 @@
+use std::collections::VecDeque;
 use std::fs::File;
-use std::io::BufWriter;
+use std::io::{ BufWriter, Write };
 use std::path::PathBuf;
 
 use crate::cmd::{ transaction::Transaction, EdgeStatement, NodeStatement };
-use crate::common::{ direction_to_str, DirectionType, LABEL_BYTES };
+use crate::common::{ direction_to_str, DirectionType, NEProperty };
 use crate::datagramv2::internal_grams::{ DGu64, Label, UUID };
+use crate::datagramv2::rows::{ EdgeRow, NodeRow };
 use crate::utils::{ cons_uuid, open_file };
 
-use crate::executor::core::CoreExecutor;
 use crate::executor::core_planner::WriteNewGraphPlanner;
-use crate::executor::writer::core::{ CoreWriteExecutor, PageWriteResult };
+use crate::executor::writer::core::{ CoreWriteExecutor, DataPageCursor, PageWriteResult };
 
 /* 
   WriteNewGraphExecutor
     :: new()
     :: execute()
+        :: validate_rows()
         :: set_graph_name_uuid()
             :: find_graph_name()
         :: write()
@@ -22,7 +24,8 @@ use crate::executor::writer::core::{ CoreWriteExecutor, PageWriteResult };
             :: write_data_page()
             :: write_node()
             :: write_edge()
-            :: validate_edge_statement()
+                :: write_rows()
+            :: node_id_at()
 */
 pub struct WriteNewGraphExecutor<'a> 
 {
@@ -31,6 +34,7 @@ pub struct WriteNewGraphExecutor<'a>
   pub page_size: usize,
   pub graph_uuid: Option<UUID>,
   pub graph_name: Option<Label>,
+  pub page: Option<DataPageCursor>, // DataPage rows are written to
   pub err_state: Option<String>,
 }
 
@@ -45,19 +49,49 @@ impl WriteNewGraphExecutor<'_>
       page_size: page_size,
       graph_uuid: None, 
       graph_name: None, 
+      page: None,
       err_state: None 
     }
   }
 
   pub fn execute ( &mut self ) 
   {
+    if self.transaction.err_state.is_some() 
+    {
+      self.err_state = self.transaction.err_state.clone();
+      return;
+    }
+
+    // a new graph has no rows to take property values from
+    let ref_opt = self.transaction.node_statements.iter().flat_map( | stmt | stmt.property_refs.iter() )
+      .chain( self.transaction.edge_statements.iter().flat_map( | stmt | stmt.property_refs.iter() ))
+      .flat_map( | ( _, operand ) | operand.variables() )
+      .next();
+    if ref_opt.is_some() 
+    {
+      self.err_state = Some( format!( "Error: Variable `{}` not defined.", ref_opt.unwrap() ));
+      return;
+    }
+
+    // nothing is written when a row of the graph cannot be
+    let valid_res = self.validate_rows();
+    if valid_res.is_err() 
+    {
+      self.err_state = Some( valid_res.unwrap_err() );
+      return;
+    }
+
     let name_res = self.set_graph_name_uuid();
     if name_res.is_ok() 
     {
       let graph_name = &self.graph_name.as_ref().unwrap().clone();
       let mut planner = WriteNewGraphPlanner::new( self.path.to_string(), graph_name );
       planner.plan();
-      if planner.err_state == None { self.write( &planner ); }
+      if planner.err_state == None 
+      { 
+        self.write( &planner );
+        return;
+      }
       else 
       { 
         self.err_state = Some( planner.err_state.unwrap().to_string() ); 
@@ -73,45 +107,60 @@ impl WriteNewGraphExecutor<'_>
     let mut writer: BufWriter<File> = BufWriter::new( open_res.unwrap() );
     self.write_graph( planner, &mut writer );
 
-    let mut curr_query_order: u16 = 1;
     let mut graph_order: u64 = 0;
 
-    let page_write_result = self.write_data_page( planner, &mut writer ).unwrap();
-    CoreExecutor::writer_seek_back_to( page_write_result.position_start_empty, &mut writer );
-    loop 
+    let page_res = self.write_data_page( planner, &mut writer );
+    if page_res.is_err() 
     {
-      if self.transaction.query_order > curr_query_order 
+      self.err_state = Some( page_res.unwrap_err() );
+      return;
+    }
+
+    // refs are resolved by the edges next to them
+    for query_order in 1..self.transaction.query_order 
+    {
+      let node_stmt_opt = self.transaction.next_node_statement( query_order );
+      if node_stmt_opt.is_some() 
+      {
+        self.write_node( node_stmt_opt.unwrap(), graph_order, &mut writer );
+        graph_order += 1;
+      }
+
+      let edge_stmt_opt = self.transaction.next_edge_statement( query_order );
+      if edge_stmt_opt.is_some() 
       {
-        let node_stmt_opt = self.transaction.next_node_statement( curr_query_order );
-        if node_stmt_opt.is_some() 
+        let stmt = edge_stmt_opt.unwrap();
+        let left_id = self.node_id_at( query_order - 1 );
+        let right_id = self.node_id_at( query_order + 1 );
+        if left_id.is_none() || right_id.is_none() 
         {
-          self.write_node( node_stmt_opt.unwrap(), graph_order, &mut writer );
-          graph_order += 1;
-          curr_query_order += 1;
-          continue;
+          self.err_state = Some( String::from( "Error: Edge requires a node on both sides." ));
+          break;
         }
-
-        let edge_stmt_opt = self.transaction.next_edge_statement( curr_query_order );
-        if edge_stmt_opt.is_some() 
+        let left_uuid = UUID::new( left_id.unwrap() );
+        let right_uuid = UUID::new( right_id.unwrap() );
+        if left_uuid.is_err() || right_uuid.is_err() 
         {
-          let valid_uuids = self.validate_edge_statement( edge_stmt_opt.unwrap(), curr_query_order );
-          if valid_uuids.is_some() 
-          {
-            self.write_edge( 
-              &valid_uuids.as_ref().unwrap().0, 
-              edge_stmt_opt.as_ref().unwrap(), 
-              &valid_uuids.as_ref().unwrap().1,
-              DirectionType::Undirected, // !!!
-              graph_order,
-              &mut writer );
-            graph_order += 1;
-            curr_query_order += 1;
-            continue;
-          }
+          self.err_state = Some( String::from( "Error creating UUID." ));
+          break;
         }
+        self.write_edge( 
+          &left_uuid.unwrap(), 
+          stmt, 
+          &right_uuid.unwrap(),
+          stmt.direction.clone(),
+          graph_order,
+          &mut writer );
+        graph_order += 1;
       }
-      break;
+
+      if self.err_state.is_some() { break; }
     }
+
+    // close the remaining empty cells behind the last row
+    let close_res = self.page.as_mut().unwrap().close( &mut writer );
+    if close_res.is_err() && self.err_state.is_none() { self.err_state = Some( close_res.unwrap_err() ); }
+    let _ = writer.flush();
   }  
 }
 
@@ -130,30 +179,44 @@ impl WriteNewGraphExecutor<'_>
   }
 
 
-  /// Write new DataPage to end of file
+  /// Write new DataPage to end of file, or claim one of a deleted graph, rows are written to it until it is full
   pub fn write_data_page ( 
     &mut self, 
     planner: &WriteNewGraphPlanner, 
     writer: &mut BufWriter<File> ) -> Result<PageWriteResult, String> 
   {
-    CoreWriteExecutor::write_data_page( 
-      self.graph_uuid.as_ref().unwrap(), 
-      self.graph_name.as_ref().unwrap(), 
-      self.page_size, 
-      planner, writer )
+    let graph_uuid = self.graph_uuid.as_ref().unwrap();
+    let graph_name = self.graph_name.as_ref().unwrap();
+    let mut free_pages = VecDeque::from( planner.free_data_pages() );
+    if free_pages.len() == 0 
+    {
+      let res = CoreWriteExecutor::write_data_page( graph_uuid, graph_name, self.page_size, planner, writer )?;
+      self.page = Some( DataPageCursor::new( graph_uuid, graph_name, self.page_size, &res ));
+      return Ok( res );
+    }
+
+    let ( start_pos, cell_count ) = free_pages.pop_front().unwrap();
+    let res = CoreWriteExecutor::claim_data_page( graph_uuid, graph_name, start_pos, cell_count, writer )?;
+    let mut cursor = DataPageCursor::new( graph_uuid, graph_name, self.page_size, &res );
+    cursor.end_affix_pos = planner.end_pos.unwrap() - 8;
+    cursor.free_pages = free_pages;
+    self.page = Some( cursor );
+    Ok( res )
   }
 
   
   /// Write Node to current DataPage
   pub fn write_node ( &mut self, stmt: &NodeStatement, graph_order: u64, writer: &mut BufWriter<File> ) 
   {
-    let uuid_res = UUID::new( stmt.id.clone() );
-    let primary_label_res = Label::new( stmt.primary_label.clone() );
-    CoreWriteExecutor::write_node( 
-      &DGu64::new( graph_order ), 
-      &uuid_res.unwrap(), 
-      &primary_label_res.unwrap(), 
-      writer );
+    let ids_res = row_uuid_label( &stmt.id, &stmt.primary_label );
+    if ids_res.is_err() 
+    {
+      self.err_state = Some( ids_res.unwrap_err() );
+      return;
+    }
+    let ( uuid, primary_label ) = ids_res.unwrap();
+    let row = NodeRow::new( &DGu64::new( graph_order ), &uuid, &primary_label );
+    self.write_rows( &uuid, &row, &stmt.properties, writer );
   }
   
   
@@ -163,16 +226,35 @@ impl WriteNewGraphExecutor<'_>
     left_uuid: &UUID, stmt: &EdgeStatement, right_uuid: &UUID, edge_dir: DirectionType, graph_order: u64, 
     writer: &mut BufWriter<File> ) 
   {
-    let uuid_res = UUID::new( stmt.id.clone() );
-    let primary_label_res = Label::new( stmt.primary_label.clone() );
-    CoreWriteExecutor::write_edge ( 
+    let ids_res = row_uuid_label( &stmt.id, &stmt.primary_label );
+    if ids_res.is_err() 
+    {
+      self.err_state = Some( ids_res.unwrap_err() );
+      return;
+    }
+    let ( uuid, primary_label ) = ids_res.unwrap();
+    let row = EdgeRow::new( 
       &DGu64::new( graph_order ), 
-      &uuid_res.unwrap(), 
-      &primary_label_res.unwrap(), 
+      &uuid, 
+      &primary_label, 
       direction_to_str( &edge_dir ),
       left_uuid, 
-      right_uuid, 
-      writer )
+      right_uuid );
+    self.write_rows( &uuid, &row, &stmt.properties, writer );
+  }
+
+
+  /// Write a Node or Edge row followed by its KVPRows
+  fn write_rows ( &mut self, uuid: &UUID, row: &[u8], properties: &Vec<NEProperty>, writer: &mut BufWriter<File> ) 
+  {
+    if self.page.is_none() 
+    {
+      self.err_state = Some( String::from( "Error: No DataPage to write to." ));
+      return;
+    }
+
+    let res = CoreWriteExecutor::write_rows( uuid, row, properties, self.page.as_mut().unwrap(), writer );
+    if res.is_err() { self.err_state = Some( res.unwrap_err() ); }
   }
 
 
@@ -213,36 +295,67 @@ impl WriteNewGraphExecutor<'_>
     None
   }
 
-  /// Validate EdgeStatement by checking NodeRefStatements and TransactionLabels
-  pub fn validate_edge_statement ( &self, stmt: &EdgeStatement, curr_query_order: u16 ) -> Option<(UUID, UUID)> 
+  /// Check the labels, ids, properties and edge endpoints of every row before the first one is written
+  pub fn validate_rows ( &self ) -> Result<bool, String>
   {
-    let left_res = self.transaction.next_ref_statement( curr_query_order - 1 );
-    let right_res = self.transaction.next_ref_statement( curr_query_order + 1 );
-    if left_res.is_some() && right_res.is_some() 
+    for stmt in self.transaction.node_statements.iter() 
+    {
+      validate_row( &stmt.id, &stmt.primary_label, &stmt.properties )?;
+    }
+    for stmt in self.transaction.edge_statements.iter() 
     {
-      let left_node = self.transaction.find_node_by_transaction_label( &left_res.unwrap().transaction_label );
-      let right_node = self.transaction.find_node_by_transaction_label( &right_res.unwrap().transaction_label );
-      if left_node.is_some() && right_node.is_some() 
+      if self.node_id_at( stmt.query_order - 1 ).is_none() || self.node_id_at( stmt.query_order + 1 ).is_none() 
       {
-        let left_uuid = UUID::new( left_node.unwrap().id.clone() );
-        let right_uuid = UUID::new( right_node.unwrap().id.clone() );
-        if left_uuid.is_ok() && right_uuid.is_ok() 
-        {
-          return Some(( left_uuid.unwrap(), right_uuid.unwrap() ));
-        }
+        return Err( String::from( "Error: Edge requires a node on both sides." ));
       }
+      validate_row( &stmt.id, &stmt.primary_label, &stmt.properties )?;
+    }
+    Ok( true )
+  }
+
+  /// Id of the node statement at a query order, or of the node a NodeRefStatement there refers to
+  pub fn node_id_at ( &self, query_order: u16 ) -> Option<String>
+  {
+    let node_opt = self.transaction.next_node_statement( query_order );
+    if node_opt.is_some() { return Some( node_opt.unwrap().id.clone() ); }
+
+    let ref_opt = self.transaction.next_ref_statement( query_order );
+    if ref_opt.is_some() 
+    {
+      let node_opt = self.transaction.find_node_by_transaction_label( &ref_opt.unwrap().transaction_label );
+      if node_opt.is_some() { return Some( node_opt.unwrap().id.clone() ); }
     }
     None
   }
 }
 
+/// Check the id, primary label and properties of a Node or Edge row
+fn validate_row ( id: &String, primary_label: &String, properties: &Vec<NEProperty> ) -> Result<bool, String>
+{
+  let ( uuid, _ ) = row_uuid_label( id, primary_label )?;
+  CoreWriteExecutor::property_rows( &uuid, properties )?;
+  Ok( true )
+}
+
+/// UUID and primary Label of a Node or Edge row
+pub fn row_uuid_label ( id: &String, primary_label: &String ) -> Result<( UUID, Label ), String>
+{
+  let uuid_res = UUID::new( id.clone() );
+  if uuid_res.is_err() { return Err( String::from( "Error creating UUID." )); }
+  let label_res = Label::new( primary_label.clone() );
+  if label_res.is_err() { return Err( format!( "Error: Invalid label `{}`.", primary_label )); }
+  Ok(( uuid_res.unwrap(), label_res.unwrap() ))
+}
+
 #[cfg(test)]
 mod tests 
 {
   use super::*;
   use std::fs::{ metadata, remove_file };
+  use crate::common::LABEL_BYTES;
   use crate::planner::process_query;
   use crate::utils::create_file;
+  use crate::executor::reader::simple_match::SimpleMatchExecutor;
   use crate::executor::writer::new_db::WriteNewDBExecutor;
 
   const PAGE_SIZE: usize = 4096;
@@ -252,8 +365,8 @@ mod tests
   fn write_new_db ( path: &str )
   {
     let open_res = create_file( &PathBuf::from( path ));
-    //let mut stream = BufWriter::new( open_res.unwrap() );
-    //let _ = WriteNewDBExecutor::execute_write_new( &build_id(), &db_nickname(), PAGE_SIZE, &mut stream );
+    let mut stream = BufWriter::new( open_res.unwrap() );
+    let _ = WriteNewDBExecutor::execute_write_new( &build_id(), &db_nickname(), PAGE_SIZE, &mut stream );
   }
 
   #[test]
@@ -347,9 +460,14 @@ mod tests
       None, 
       String::from( "primary" ));
 
+    let name_label = Label::new( String::from( "devs" ));
+    let mut planner = WriteNewGraphPlanner::new( path_str.to_string(), name_label.as_ref().unwrap() );
+    planner.plan();
+
     let open_res = open_file( &PathBuf::from( &path_str ));
     let mut writer: BufWriter<File> = BufWriter::new( open_res.unwrap() );
 
+    let _ = write_executor.write_data_page( &planner, &mut writer );
     let _ = write_executor.write_node( &stmt, 0, &mut writer );
 
     assert_eq!( write_executor.err_state, None );
@@ -373,9 +491,14 @@ mod tests
 
     let stmt = EdgeStatement::new( String::from( cons_uuid() ), 0, None, String::from( "primary" ));
 
+    let name_label = Label::new( String::from( "devs" ));
+    let mut planner = WriteNewGraphPlanner::new( path_str.to_string(), name_label.as_ref().unwrap() );
+    planner.plan();
+
     let open_res = open_file( &PathBuf::from( &path_str ));
     let mut writer: BufWriter<File> = BufWriter::new( open_res.unwrap() );
 
+    let _ = write_executor.write_data_page( &planner, &mut writer );
     let _ = write_executor.write_edge( 
       &UUID::generate(), 
       &stmt, 
@@ -390,11 +513,9 @@ mod tests
   }
 
   #[test]
-  fn test_validate_edge_statement () 
+  fn test_node_id_at () 
   {
-    let path_str = "../test_data/WriteNewGraphExecutor_test_validate_edge_statement.sdb";
-    write_new_db( path_str );
-    assert_eq!( metadata( &PathBuf::from( path_str ) ).unwrap().len(), 4096 as u64 );
+    let path_str = "../test_data/WriteNewGraphExecutor_test_node_id_at.sdb";
     
     let query_string = "
       CREATE GRAPH devs
@@ -407,12 +528,43 @@ mod tests
     ";
     let t = process_query( &query_string, build_id(), db_nickname() );
     let writer = WriteNewGraphExecutor::new( &t, path_str, 4096 );
-    let res = writer.validate_edge_statement( t.edge_statements.get( 0 ).as_ref().unwrap(), 5 );
 
-    assert_eq!( res.is_some(), true );
-    assert_eq!( res.as_ref().unwrap().0.val, t.node_statements.get( 0 ).as_ref().unwrap().id );
-    assert_eq!( res.as_ref().unwrap().1.val, t.node_statements.get( 1 ).as_ref().unwrap().id );
-    
+    // node statements and refs on either side of the first edge
+    assert_eq!( writer.node_id_at( 1 ), Some( t.node_statements.get( 0 ).as_ref().unwrap().id.clone() ));
+    assert_eq!( writer.node_id_at( 4 ), Some( t.node_statements.get( 0 ).as_ref().unwrap().id.clone() ));
+    assert_eq!( writer.node_id_at( 6 ), Some( t.node_statements.get( 1 ).as_ref().unwrap().id.clone() ));
+    assert_eq!( writer.node_id_at( 5 ), None );
+  }
+
+  #[test]
+  fn test_create_graph_chained () 
+  {
+    let path_str = "../test_data/WriteNewGraphExecutor_test_create_graph_chained.sdb";
+    write_new_db( path_str );
+
+    let query_string = "CREATE GRAPH devs (a:P {name:'A'})-[:KNOWS]->(b:P {name:'B'}) (c:P)";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+
+    let t1 = process_query( "MATCH (n) FROM devs", build_id(), db_nickname() );
+    let mut reader = SimpleMatchExecutor::new( &t1, path_str, PAGE_SIZE );
+    reader.execute();
+    assert_eq!( reader.err_state, None );
+    assert_eq!( reader.nodes.len(), 3 );
+    assert_eq!( reader.edges.len(), 1 );
+    assert_eq!( reader.edges[0].left_id, t.node_statements[0].id );
+    assert_eq!( reader.edges[0].right_id, t.node_statements[1].id );
+
+    // an edge without a node on one side writes nothing
+    let written = std::fs::read( path_str ).unwrap();
+    let t2 = process_query( "CREATE GRAPH ops (a:P)-[:KNOWS]->(b)", build_id(), db_nickname() );
+    let mut writer2 = WriteNewGraphExecutor::new( &t2, path_str, PAGE_SIZE );
+    writer2.execute();
+    assert_eq!( writer2.err_state, Some( String::from( "Error: Edge requires a node on both sides." )));
+    assert_eq!( std::fs::read( path_str ).unwrap(), written );
+
     let _ = remove_file( PathBuf::from( path_str ));
   }
 
@@ -420,7 +572,7 @@ mod tests
   #[test]
   fn test_create_graph_2 () 
   {
-    let path_str = "../../test_data/test_create_graph_2.sdb";
+    let path_str = "../test_data/test_create_graph_2.sdb";
     write_new_db( path_str );
     
     let query_string = "
@@ -435,8 +587,82 @@ mod tests
     let mut writer = WriteNewGraphExecutor::new( &t, path_str, 4096 );
     writer.execute();
 
+    assert_eq!( writer.err_state, None );
+    assert_eq!( metadata( &PathBuf::from( path_str ) ).unwrap().len(), (PAGE_SIZE * 2) as u64 );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_create_graph_properties () 
+  {
+    let path_str = "../test_data/test_create_graph_properties.sdb";
+    write_new_db( path_str );
+    
+    let query_string = "
+      CREATE GRAPH devs
+        (alice:Developer {name: 'Alice', age: 38})
+        (bob:Administrator)
+        (alice)-[:KNOWS {since: 2019}]-(bob)
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+
+    assert_eq!( writer.err_state, None );
+    assert_eq!( metadata( &PathBuf::from( path_str ) ).unwrap().len(), (PAGE_SIZE * 2) as u64 );
+
+    let written = std::fs::read( path_str ).unwrap();
+    let long_val = "a".repeat( LABEL_BYTES + 1 );
+    let query_string1 = format!( "CREATE GRAPH devs2 (alice:Developer {{name: '{}'}})", long_val );
+    let t1 = process_query( &query_string1, build_id(), db_nickname() );
+    let mut writer1 = WriteNewGraphExecutor::new( &t1, path_str, PAGE_SIZE );
+    writer1.execute();
+
+    assert_eq!( writer1.err_state, Some( String::from( "KVPString invalid" )));
+
+    let long_label = "z".repeat( LABEL_BYTES + 20 );
+    let t3 = process_query( &format!( "CREATE GRAPH devs2 (alice:Developer) (z:{})", long_label ), build_id(), db_nickname() );
+    let mut writer3 = WriteNewGraphExecutor::new( &t3, path_str, PAGE_SIZE );
+    writer3.execute();
+    assert_eq!( writer3.err_state, Some( format!( "Error: Invalid label `{}`.", long_label )));
+
+    // the failed graphs wrote nothing and can be created once their rows fit
+    assert_eq!( std::fs::read( path_str ).unwrap(), written );
+    let t2 = process_query( "CREATE GRAPH devs2 (alice:Developer {name: 'Alice'})", build_id(), db_nickname() );
+    let mut writer2 = WriteNewGraphExecutor::new( &t2, path_str, PAGE_SIZE );
+    writer2.execute();
+    assert_eq!( writer2.err_state, None );
 
-    //let _ = remove_file( PathBuf::from( path_str ));
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_create_graph_overflow () 
+  {
+    let path_str = "../test_data/test_create_graph_overflow.sdb";
+    write_new_db( path_str );
+
+    // 60 nodes of 16 cells, each followed by a 23 cell KVPRow, overflow into 5 DataPages of 494 cells
+    let mut query_string = String::from( "CREATE GRAPH devs" );
+    for i in 0..60 { query_string.push_str( &format!( " (n{}:Developer {{name: 'dev{}'}})", i, i )); }
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+
+    assert_eq!( writer.err_state, None );
+    assert_eq!( writer.page.as_ref().unwrap().page_count, 5 );
+    assert_eq!( metadata( &PathBuf::from( path_str ) ).unwrap().len(), (PAGE_SIZE * 6) as u64 );
+
+    // a second graph is planned past every chained DataPage
+    let t1 = process_query( "CREATE GRAPH devs2 (alice:Developer)", build_id(), db_nickname() );
+    let mut writer1 = WriteNewGraphExecutor::new( &t1, path_str, PAGE_SIZE );
+    writer1.execute();
+
+    assert_eq!( writer1.err_state, None );
+    assert_eq!( metadata( &PathBuf::from( path_str ) ).unwrap().len(), (PAGE_SIZE * 7) as u64 );
+
+    let _ = remove_file( PathBuf::from( path_str ));
   }
 
   #[test]
@@ -452,7 +678,7 @@ mod tests
     writer.execute();
 
     assert_eq!( writer.err_state, None );
-    assert_eq!( metadata( &PathBuf::from( path_str ) ).unwrap().len(), 4096 as u64 );
+    assert_eq!( metadata( &PathBuf::from( path_str ) ).unwrap().len(), (PAGE_SIZE * 2) as u64 );
 
     let name_label = Label::new( String::from( "devs2" ));
     let mut planner = WriteNewGraphPlanner::new( path_str.to_string(), name_label.as_ref().unwrap() );
@@ -0,0 +1,627 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{ BufWriter, Write };
+use std::path::PathBuf;
+
+use crate::cmd::{ transaction::Transaction, RemoveStatement, SetStatement, SetType };
+use crate::common::NEProperty;
+use crate::datagramv2::internal_grams::{ Label, UUID };
+use crate::datagramv2::rows::{ KVPRow, LabelRow };
+use crate::result_set::{ ResultSet, Value };
+use crate::utils::{ open_file, parse_uuid_str };
+
+use crate::executor::core_planner::WriteNewGraphPlanner;
+use crate::executor::reader::simple_match::SimpleMatchExecutor;
+use crate::executor::writer::core::{ CoreWriteExecutor, DataPageCursor };
+
+/*
+MATCH (p:Person {name: 'Jennifer'}) SET p.birthdate = '1980-01-01' RETURN p
+
+MATCH (p:Person {name: 'Jennifer'}) SET p += {age: 44, admin: true} FROM devs
+
+MATCH (p:Person {name: 'Jennifer'}) SET p:Lead
+
+MATCH (p:Person:Lead) REMOVE p:Lead, p.birthdate
+*/
+
+/*
+  SetPropertiesExecutor
+    :: new()
+    :: with_reader()
+    :: execute()
+        :: remove()
+            :: collect_removals()
+            :: free_removals()
+        :: apply()
+            :: collect_changes()
+            :: write()
+                :: cons_rows()
+            :: apply_to_bindings()
+    :: result_set()
+*/
+pub struct SetPropertiesExecutor<'a>
+{
+  pub transaction: &'a Transaction,
+  pub path: &'a str,
+  pub page_size: usize,
+  pub reader: Option<SimpleMatchExecutor<'a>>, // MATCH the SET items refer to
+  pub property_changes: Vec<( String, NEProperty )>, // ( owner id, property ) to write
+  pub label_changes: Vec<( String, String )>, // ( node id, label ) to add
+  pub removals: Vec<( String, NEProperty, u64, usize )>, // ( owner id, stored property, position, cell count ) to free
+  pub skip_owners: Vec<String>, // nodes and edges SET items do not apply to
+  pub page: Option<DataPageCursor>, // DataPage relocated and new rows are written to
+  pub err_state: Option<String>,
+}
+
+impl SetPropertiesExecutor<'_>
+{
+  pub fn new<'a> ( t: &'a Transaction, path: &'a str, page_size: usize ) -> SetPropertiesExecutor<'a>
+  {
+    SetPropertiesExecutor
+    {
+      transaction: t,
+      path: path,
+      page_size: page_size,
+      reader: None,
+      property_changes: Vec::new(),
+      label_changes: Vec::new(),
+      removals: Vec::new(),
+      skip_owners: Vec::new(),
+      page: None,
+      err_state: None,
+    }
+  }
+
+  /// Apply SET items to the bindings of a reader that has already been executed
+  pub fn with_reader<'a> ( 
+    t: &'a Transaction, path: &'a str, page_size: usize, reader: SimpleMatchExecutor<'a> ) -> SetPropertiesExecutor<'a>
+  {
+    let mut executor = SetPropertiesExecutor::new( t, path, page_size );
+    executor.reader = Some( reader );
+    executor
+  }
+
+  pub fn execute ( &mut self )
+  {
+    if self.transaction.err_state.is_some()
+    {
+      self.err_state = self.transaction.err_state.clone();
+      return;
+    }
+
+    let mut reader = SimpleMatchExecutor::new( self.transaction, self.path, self.page_size );
+    reader.execute();
+    if reader.err_state.is_some()
+    {
+      self.err_state = reader.err_state;
+      return;
+    }
+    self.reader = Some( reader );
+
+    // REMOVE items are applied before SET items
+    let transaction = self.transaction;
+    self.remove( &transaction.remove_statements.iter().collect() );
+    if self.err_state.is_some() { return; }
+    self.apply( &transaction.set_statements.iter().collect() );
+  }
+
+  /// Free the rows removed by stmts and update the bindings
+  pub fn remove ( &mut self, stmts: &Vec<&RemoveStatement> )
+  {
+    let res = self.collect_removals( stmts );
+    if res.is_err()
+    {
+      self.err_state = Some( res.unwrap_err() );
+      return;
+    }
+    if self.removals.len() == 0 { return; }
+
+    let free_res = self.free_removals();
+    if free_res.is_err()
+    {
+      self.err_state = Some( free_res.unwrap_err() );
+      return;
+    }
+    self.apply_to_bindings();
+  }
+
+  /// Write the changes of stmts and update the bindings
+  pub fn apply ( &mut self, stmts: &Vec<&SetStatement> )
+  {
+    let res = self.collect_changes( stmts );
+    if res.is_err()
+    {
+      self.err_state = Some( res.unwrap_err() );
+      return;
+    }
+    if self.property_changes.len() == 0 && self.label_changes.len() == 0 { return; }
+
+    let write_res = self.write();
+    if write_res.is_err()
+    {
+      self.err_state = Some( write_res.unwrap_err() );
+      return;
+    }
+    self.apply_to_bindings();
+  }
+
+  /// Result of the MATCH with the SET items applied
+  pub fn result_set ( &self ) -> Result<ResultSet, String>
+  {
+    if self.err_state.is_some() { return Err( self.err_state.as_ref().unwrap().clone() ); }
+    if self.reader.is_none() { return Err( String::from( "Error: Query has not been executed." )); }
+    self.reader.as_ref().unwrap().result_set()
+  }
+}
+
+impl SetPropertiesExecutor<'_>
+{
+  /// Resolve every SET item against the matched nodes and edges, the last value set for a key wins
+  pub fn collect_changes ( &mut self, stmts: &Vec<&SetStatement> ) -> Result<bool, String>
+  {
+    let reader = self.reader.as_ref().unwrap();
+    let variables = self.variables();
+
+    let mut property_changes: Vec<( String, NEProperty )> = Vec::new();
+    let mut label_changes: Vec<( String, String )> = Vec::new();
+    for stmt in stmts.iter()
+    {
+      if variables.contains( &stmt.transaction_label ) == false
+      {
+        return Err( format!( "Error: Variable `{}` not defined.", stmt.transaction_label ));
+      }
+
+      for binding in reader.bindings.iter()
+      {
+        let bound = binding.iter().find( | ( var, _ ) | var == &stmt.transaction_label ).map( | ( _, val ) | val );
+        let ( owner, node ) = match bound
+        {
+          Some( Value::Node( node )) => ( node.id.clone(), Some( node )),
+          Some( Value::Edge( edge )) => ( edge.id.clone(), None ),
+          _ => { continue; }
+        };
+        if self.skip_owners.contains( &owner ) { continue; }
+
+        if stmt.set_type == Some( SetType::Label )
+        {
+          if node.is_none() { return Err( String::from( "Error: Labels can only be set on nodes." )); }
+          for label in stmt.labels.iter()
+          {
+            let change = ( owner.clone(), label.clone() );
+            if node.unwrap().has_label( label ) == false && label_changes.contains( &change ) == false
+            {
+              label_changes.push( change );
+            }
+          }
+          continue;
+        }
+
+        for prop in stmt.properties.iter()
+        {
+          let pos = property_changes.iter().position( | ( o, p ) | o == &owner && p.key() == prop.key() );
+          if pos.is_some() { property_changes[pos.unwrap()] = ( owner.clone(), prop.clone() ); }
+          else { property_changes.push(( owner.clone(), prop.clone() )); }
+        }
+      }
+    }
+
+    // values that are already stored need no write
+    property_changes.retain( | ( owner, prop ) |
+      reader.property_rows.iter().any( | ( _, _, o, p ) | o == owner && p == prop ) == false );
+
+    self.property_changes = property_changes;
+    self.label_changes = label_changes;
+    Ok( true )
+  }
+
+  /// Resolve every REMOVE item to the stored rows it frees, missing keys and labels are ignored
+  pub fn collect_removals ( &mut self, stmts: &Vec<&RemoveStatement> ) -> Result<bool, String>
+  {
+    let reader = self.reader.as_ref().unwrap();
+    let variables = self.variables();
+
+    let mut removals: Vec<( String, NEProperty, u64, usize )> = Vec::new();
+    for stmt in stmts.iter()
+    {
+      if variables.contains( &stmt.transaction_label ) == false
+      {
+        return Err( format!( "Error: Variable `{}` not defined.", stmt.transaction_label ));
+      }
+
+      for binding in reader.bindings.iter()
+      {
+        let bound = binding.iter().find( | ( var, _ ) | var == &stmt.transaction_label ).map( | ( _, val ) | val );
+        let ( owner, node ) = match bound
+        {
+          Some( Value::Node( node )) => ( node.id.clone(), Some( node )),
+          Some( Value::Edge( edge )) => ( edge.id.clone(), None ),
+          _ => { continue; }
+        };
+
+        let mut targets: Vec<NEProperty> = Vec::new();
+        if stmt.key.is_some()
+        {
+          let stored = reader.property_rows.iter()
+            .find( | ( _, _, o, p ) | o == &owner && p.key() == stmt.key.as_deref() );
+          if stored.is_some() { targets.push( stored.unwrap().3.clone() ); }
+        }
+        else
+        {
+          if node.is_none() { return Err( String::from( "Error: Labels can only be removed from nodes." )); }
+          for label in stmt.labels.iter()
+          {
+            if node.unwrap().primary_tag.as_ref() == Some( label )
+            {
+              return Err( format!( "Error: The primary label of `{}` cannot be removed.", stmt.transaction_label ));
+            }
+            targets.push( NEProperty::Tag( label.clone() ));
+          }
+        }
+
+        for target in targets.into_iter()
+        {
+          let stored = reader.property_rows.iter().find( | ( _, _, o, p ) | o == &owner && p == &target );
+          if stored.is_none() || removals.iter().any( | ( o, p, _, _ ) | o == &owner && p == &target ) { continue; }
+          let ( position, cell_count, _, _ ) = stored.unwrap();
+          removals.push(( owner.clone(), target, *position, *cell_count ));
+        }
+      }
+    }
+
+    self.removals = removals;
+    Ok( true )
+  }
+
+  /// Free the removed rows, later SET items must not rewrite them in place
+  pub fn free_removals ( &mut self ) -> Result<bool, String>
+  {
+    let open_res = open_file( &PathBuf::from( &self.path ));
+    if open_res.is_err() { return Err( String::from( "Error opening database file." )); }
+    let mut writer: BufWriter<File> = BufWriter::new( open_res.unwrap() );
+
+    let spans: Vec<( u64, usize )> = self.removals.iter().map( | ( _, _, position, cell_count ) | ( *position, *cell_count )).collect();
+    CoreWriteExecutor::free_spans( &spans, &mut writer )?;
+    if writer.flush().is_err() { return Err( String::from( "Error writing database file." )); }
+
+    let reader = self.reader.as_mut().unwrap();
+    reader.property_rows.retain( | ( position, _, _, _ ) | spans.iter().any( | ( p, _ ) | p == position ) == false );
+    Ok( true )
+  }
+
+  /// Variables of the MATCH the SET and REMOVE items may refer to
+  fn variables ( &self ) -> Vec<String>
+  {
+    self.transaction.variables()
+  }
+
+  /// Rewrite KVPRows of the same size in place, free and relocate the others, append new rows
+  pub fn write ( &mut self ) -> Result<bool, String>
+  {
+    let rows = self.cons_rows()?;
+
+    let open_res = open_file( &PathBuf::from( &self.path ));
+    if open_res.is_err() { return Err( String::from( "Error opening database file." )); }
+    let mut writer: BufWriter<File> = BufWriter::new( open_res.unwrap() );
+
+    let mut appends: Vec<( UUID, Vec<u8> )> = Vec::new();
+    for ( owner, stored, row ) in rows.into_iter()
+    {
+      if stored.is_some()
+      {
+        let ( position, cell_count ) = stored.unwrap();
+        if cell_count * 8 == row.len()
+        {
+          CoreWriteExecutor::write_at( position, &row, &mut writer )?;
+          continue;
+        }
+        CoreWriteExecutor::free_cells( position, cell_count, &mut writer )?;
+      }
+      appends.push(( owner, row ));
+    }
+    if writer.flush().is_err() { return Err( String::from( "Error writing database file." )); }
+    if appends.len() == 0 { return Ok( true ); }
+
+    // freed rows are planned as empty cells, so relocated rows may reuse them
+    let reader = self.reader.as_ref().unwrap();
+    let graph_name = reader.graph_name.as_ref().unwrap();
+    let mut planner = WriteNewGraphPlanner::for_existing( self.path.to_string(), graph_name );
+    planner.plan();
+    if planner.err_state.is_some() { return Err( planner.err_state.unwrap() ); }
+
+    let uuid_res = UUID::new( parse_uuid_str( reader.graph_uuid.as_ref().unwrap() ).to_string() );
+    if uuid_res.is_err() { return Err( String::from( "Error reading graph uuid." )); }
+
+    let mut page = DataPageCursor::from_regions(
+      &uuid_res.unwrap(),
+      graph_name,
+      self.page_size,
+      planner.empty_regions_by_graph( &graph_name.unwrap() ),
+      planner.end_pos.unwrap() - 8 );
+    page.free_pages = VecDeque::from( planner.free_data_pages() );
+    for ( _, row ) in appends.iter() { page.write_row( row, &mut writer )?; }
+    page.close( &mut writer )?;
+    self.page = Some( page );
+
+    if writer.flush().is_err() { return Err( String::from( "Error writing database file." )); }
+    Ok( true )
+  }
+
+  /// ( owner, ( position, cell count ) of the stored row, new row ) per change, built before anything is written
+  fn cons_rows ( &self ) -> Result<Vec<( UUID, Option<( u64, usize )>, Vec<u8> )>, String>
+  {
+    let reader = self.reader.as_ref().unwrap();
+    let mut rows: Vec<( UUID, Option<( u64, usize )>, Vec<u8> )> = Vec::new();
+    for ( owner, prop ) in self.property_changes.iter()
+    {
+      let uuid = UUID::new( owner.clone() )?;
+      let stored = reader.property_rows.iter()
+        .find( | ( _, _, o, p ) | o == owner && p.key().is_some() && p.key() == prop.key() )
+        .map( | ( position, cell_count, _, _ ) | ( *position, *cell_count ));
+      rows.push(( uuid.clone(), stored, KVPRow::from_property( &uuid, prop )? ));
+    }
+
+    for ( owner, label ) in self.label_changes.iter()
+    {
+      let uuid = UUID::new( owner.clone() )?;
+      let label_res = Label::new( label.clone() );
+      if label_res.is_err() { return Err( format!( "Error: Invalid label `{}`.", label )); }
+      rows.push(( uuid.clone(), None, LabelRow::new( &uuid, &label_res.unwrap() )));
+    }
+    Ok( rows )
+  }
+
+  /// Update the matched nodes and edges so RETURN sees the new values
+  pub fn apply_to_bindings ( &mut self )
+  {
+    let property_changes = &self.property_changes;
+    let label_changes = &self.label_changes;
+    let removals = &self.removals;
+    let reader = self.reader.as_mut().unwrap();
+    for binding in reader.bindings.iter_mut()
+    {
+      for ( _, value ) in binding.iter_mut()
+      {
+        match value
+        {
+          Value::Node( node ) =>
+          {
+            for ( owner, prop, _, _ ) in removals.iter()
+            {
+              if owner == &node.id { node.io_remove_property( prop ); }
+            }
+            for ( owner, prop ) in property_changes.iter()
+            {
+              if owner == &node.id { node.io_set_property( prop.clone() ); }
+            }
+            for ( owner, label ) in label_changes.iter()
+            {
+              if owner == &node.id { node.io_add_property( NEProperty::Tag( label.clone() )); }
+            }
+          }
+
+          Value::Edge( edge ) =>
+          {
+            for ( owner, prop, _, _ ) in removals.iter()
+            {
+              if owner == &edge.id { edge.io_remove_property( prop ); }
+            }
+            for ( owner, prop ) in property_changes.iter()
+            {
+              if owner == &edge.id { edge.io_set_property( prop.clone() ); }
+            }
+          }
+
+          _ => {}
+        }
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+  use std::fs::{ metadata, remove_file };
+  use crate::planner::process_query;
+  use crate::utils::create_file;
+  use crate::executor::writer::new_db::WriteNewDBExecutor;
+  use crate::executor::writer::new_graph::WriteNewGraphExecutor;
+
+  const PAGE_SIZE: usize = 4096;
+
+  fn build_id () -> UUID { UUID::new( String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" )).unwrap() }
+  fn db_nickname () -> Label { Label::new( String::from( "devs" ) ).unwrap() }
+  fn write_new_db ( path: &str )
+  {
+    let open_res = create_file( &PathBuf::from( path ));
+    let mut stream = BufWriter::new( open_res.unwrap() );
+    let _ = WriteNewDBExecutor::execute_write_new( &build_id(), &db_nickname(), PAGE_SIZE, &mut stream );
+  }
+
+  fn write_devs_graph ( path: &str )
+  {
+    let query_string = "
+      CREATE GRAPH devs
+        (alice:Developer {name: 'Alice', age: 38})
+        (bob:Administrator {name: 'Bob'})
+        (alice)-[:KNOWS {since: 2019}]->(bob)
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+  }
+
+  fn match_graph<'a> ( t: &'a Transaction, path: &'a str ) -> SimpleMatchExecutor<'a>
+  {
+    let mut reader = SimpleMatchExecutor::new( t, path, PAGE_SIZE );
+    reader.execute();
+    assert_eq!( reader.err_state, None );
+    reader
+  }
+
+  #[test]
+  fn test_set_property ()
+  {
+    let path_str = "../test_data/SetPropertiesExecutor_test_set_property.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    // same size rows are rewritten in place, a new key is appended
+    let query_string = "MATCH (p {name: 'Alice'}) SET p.age = 39, p.name = 'Alicia', p.admin = true FROM devs RETURN p.age";
+    let t = process_query( query_string, build_id(), db_nickname() );
+    let mut writer = SetPropertiesExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+
+    assert_eq!( writer.err_state, None );
+    assert_eq!( writer.property_changes.len(), 3 );
+    assert_eq!( writer.result_set().unwrap().rows[0].get( "p.age" ), Some( &Value::I64( 39 )));
+    assert_eq!( metadata( &PathBuf::from( path_str ) ).unwrap().len(), (PAGE_SIZE * 2) as u64 );
+
+    let t1 = process_query( "MATCH (p:Developer) FROM devs", build_id(), db_nickname() );
+    let reader = match_graph( &t1, path_str );
+    let node = reader.nodes.first().unwrap();
+    assert_eq!( node.properties.len(), 3 );
+    assert_eq!( node.property( "name" ), Some( &NEProperty::KvpString(( String::from( "name" ), String::from( "Alicia" )))));
+    assert_eq!( node.property( "admin" ), Some( &NEProperty::KvpBoolean(( String::from( "admin" ), true ))));
+
+    // a value of another size is relocated and its old cells are freed
+    let t2 = process_query( "MATCH (p:Developer) SET p.age = 'forty' FROM devs", build_id(), db_nickname() );
+    let mut writer2 = SetPropertiesExecutor::new( &t2, path_str, PAGE_SIZE );
+    writer2.execute();
+    assert_eq!( writer2.err_state, None );
+
+    let reader1 = match_graph( &t1, path_str );
+    let node1 = reader1.nodes.first().unwrap();
+    assert_eq!( node1.properties.len(), 3 );
+    assert_eq!( node1.property( "age" ), Some( &NEProperty::KvpString(( String::from( "age" ), String::from( "forty" )))));
+    assert_eq!( reader1.property_rows.len(), 5 );
+
+    // setting stored values again writes nothing
+    let mut writer3 = SetPropertiesExecutor::new( &t2, path_str, PAGE_SIZE );
+    writer3.execute();
+    assert_eq!( writer3.err_state, None );
+    assert_eq!( writer3.property_changes.len(), 0 );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_set_merge_and_labels ()
+  {
+    let path_str = "../test_data/SetPropertiesExecutor_test_set_merge_and_labels.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    let query_string = "MATCH (a)-[r:KNOWS]->(b) SET r += {since: 2020, weight: 0.5} b:Lead:Admin FROM devs RETURN r.since";
+    let t = process_query( query_string, build_id(), db_nickname() );
+    let mut writer = SetPropertiesExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+
+    assert_eq!( writer.err_state, None );
+    assert_eq!( writer.label_changes.len(), 2 );
+    assert_eq!( writer.result_set().unwrap().rows[0].get( "r.since" ), Some( &Value::I64( 2020 )));
+
+    let t1 = process_query( "MATCH (a)-[r:KNOWS]->(b) FROM devs", build_id(), db_nickname() );
+    let reader = match_graph( &t1, path_str );
+    assert_eq!( reader.edges[0].properties.len(), 2 );
+    let bob = reader.nodes.iter().find( | n | n.primary_tag == Some( String::from( "Administrator" ))).unwrap();
+    assert_eq!( bob.has_label( "Lead" ), true );
+    assert_eq!( bob.has_label( "Admin" ), true );
+
+    // labels already on the node are not written again
+    let t2 = process_query( "MATCH (b:Administrator) SET b:Lead FROM devs", build_id(), db_nickname() );
+    let mut writer2 = SetPropertiesExecutor::new( &t2, path_str, PAGE_SIZE );
+    writer2.execute();
+    assert_eq!( writer2.err_state, None );
+    assert_eq!( writer2.label_changes.len(), 0 );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_remove_labels_and_properties ()
+  {
+    let path_str = "../test_data/SetPropertiesExecutor_test_remove_labels_and_properties.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    let t = process_query( "MATCH (b:Administrator) SET b:Lead:Admin FROM devs", build_id(), db_nickname() );
+    let mut writer = SetPropertiesExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+
+    let query_string = "MATCH (n:Lead)<-[r:KNOWS]-(a) REMOVE n:Lead, r.since, a.age SET a.age = 'unknown' FROM devs RETURN n";
+    let t1 = process_query( query_string, build_id(), db_nickname() );
+    let mut writer1 = SetPropertiesExecutor::new( &t1, path_str, PAGE_SIZE );
+    writer1.execute();
+    assert_eq!( writer1.err_state, None );
+    assert_eq!( writer1.removals.len(), 3 );
+    let rs = writer1.result_set().unwrap();
+    match rs.rows[0].get( "n" ) 
+    {
+      Some( Value::Node( node )) => { assert_eq!( node.labels(), vec![ "Administrator", "Admin" ] ); }
+      _ => { panic!( "expected a node" ); }
+    }
+
+    let t2 = process_query( "MATCH (a)-[r:KNOWS]->(b) FROM devs", build_id(), db_nickname() );
+    let reader = match_graph( &t2, path_str );
+    assert_eq!( reader.edges[0].properties.len(), 0 );
+    let bob = reader.nodes.iter().find( | n | n.primary_tag == Some( String::from( "Administrator" ))).unwrap();
+    assert_eq!( bob.labels(), vec![ "Administrator", "Admin" ] );
+    let alice = reader.nodes.iter().find( | n | n.primary_tag == Some( String::from( "Developer" ))).unwrap();
+    assert_eq!( alice.property( "age" ), Some( &NEProperty::KvpString(( String::from( "age" ), String::from( "unknown" )))));
+
+    // removing what is not stored writes nothing
+    let t3 = process_query( "MATCH (n:Administrator) REMOVE n:Lead, n.missing FROM devs", build_id(), db_nickname() );
+    let mut writer3 = SetPropertiesExecutor::new( &t3, path_str, PAGE_SIZE );
+    writer3.execute();
+    assert_eq!( writer3.err_state, None );
+    assert_eq!( writer3.removals.len(), 0 );
+
+    let t4 = process_query( "MATCH (n:Administrator) REMOVE n:Administrator FROM devs", build_id(), db_nickname() );
+    let mut writer4 = SetPropertiesExecutor::new( &t4, path_str, PAGE_SIZE );
+    writer4.execute();
+    assert_eq!( writer4.err_state, Some( String::from( "Error: The primary label of `n` cannot be removed." )));
+
+    let t5 = process_query( "MATCH (a)-[r:KNOWS]->(b) REMOVE r:KNOWS FROM devs", build_id(), db_nickname() );
+    let mut writer5 = SetPropertiesExecutor::new( &t5, path_str, PAGE_SIZE );
+    writer5.execute();
+    assert_eq!( writer5.err_state, Some( String::from( "Error: Labels can only be removed from nodes." )));
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_set_errors ()
+  {
+    let path_str = "../test_data/SetPropertiesExecutor_test_set_errors.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    let t = process_query( "MATCH (p:Developer) SET q.age = 40 FROM devs", build_id(), db_nickname() );
+    let mut writer = SetPropertiesExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, Some( String::from( "Error: Variable `q` not defined." )));
+
+    let t1 = process_query( "MATCH (a)-[r:KNOWS]->(b) SET r:Old FROM devs", build_id(), db_nickname() );
+    let mut writer1 = SetPropertiesExecutor::new( &t1, path_str, PAGE_SIZE );
+    writer1.execute();
+    assert_eq!( writer1.err_state, Some( String::from( "Error: Labels can only be set on nodes." )));
+
+    let long_val = "a".repeat( crate::common::LABEL_BYTES + 1 );
+    let query_string = format!( "MATCH (p:Developer) SET p.age = 40, p.name = '{}' FROM devs", long_val );
+    let t2 = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer2 = SetPropertiesExecutor::new( &t2, path_str, PAGE_SIZE );
+    writer2.execute();
+    assert_eq!( writer2.err_state, Some( String::from( "KVPString invalid" )));
+
+    // nothing is written when a row fails to build
+    let t3 = process_query( "MATCH (p:Developer) FROM devs RETURN p.age", build_id(), db_nickname() );
+    let rs = match_graph( &t3, path_str ).result_set().unwrap();
+    assert_eq!( rs.rows[0].get( "p.age" ), Some( &Value::I64( 38 )));
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+}
@@ -1,27 +1,652 @@
-use crate::cmd::transaction::Transaction;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{ BufWriter, Write };
+use std::path::PathBuf;
 
+use crate::cmd::{ transaction::Transaction, EdgeStatement, NodeStatement };
+use crate::cmd::predicate::Operand;
+use crate::common::{ direction_to_str, DEFAULT_GRAPH, NEProperty, Node };
+use crate::datagramv2::internal_grams::{ DGu64, Label, UUID };
+use crate::datagramv2::rows::{ EdgeRow, NodeRow };
+use crate::result_set::Value;
+use crate::utils::{ cons_uuid, open_file, parse_uuid_str };
+
+use crate::executor::core_planner::WriteNewGraphPlanner;
+use crate::executor::reader::simple_match::{ matches_properties, SimpleMatchExecutor };
+use crate::executor::writer::core::{ CoreWriteExecutor, DataPageCursor };
+use crate::executor::writer::new_graph::row_uuid_label;
+
+/*
+CREATE (dave:Developer {name: 'Dave'}) FROM devs
+
+CREATE (dave:Developer) (alice {name: 'Alice'})-[:KNOWS]->(dave) FROM devs
+
+UNWIND ['Erin', 'Frank'] AS name CREATE (:Developer {name: name}) FROM devs
+
+MATCH (b {name: 'Bob'}) FROM devs CREATE (b)-[:KNOWS]->(erin:Developer {name: 'Erin'})
+*/
+
+/*
+  UpdateGraphExecutor
+    :: new()
+    :: execute()
+        :: set_graph_name()
+        :: validate_labels()
+        :: read_graph()
+        :: resolve_node_refs()
+        :: write()
+            :: next_row_ids()
+            :: write_node()
+            :: write_edge()
+                :: node_id_at()
+            :: row_properties()
+            :: write_rows()
+*/
 pub struct UpdateGraphExecutor<'a>
 {
   pub transaction: &'a Transaction,
   pub path: &'a str,
   pub page_size: usize,
+  pub graph_name: Option<Label>,
+  pub graph_uuid: Option<UUID>,
+  pub graph_order: u64, // graph order of the next written row
+  pub stored_nodes: Vec<Node>, // nodes already in the graph
+  pub node_refs: Vec<( String, String )>, // ( transaction label, node id ) of references to stored nodes
+  pub rows: Vec<Vec<( String, Value )>>, // UNWIND rows, the statements are written once per row
+  pub row_ids: Vec<( String, String )>, // ( statement id, id written for the current row )
+  pub page: Option<DataPageCursor>, // DataPage rows are written to
+  pub err_state: Option<String>,
 }
 
 impl UpdateGraphExecutor<'_>
 {
   pub fn new<'a> ( t: &'a Transaction, path: &'a str, page_size: usize ) -> UpdateGraphExecutor<'a>
   {
-    UpdateGraphExecutor 
+    UpdateGraphExecutor
     {
-      transaction: t, 
-      path: path, 
+      transaction: t,
+      path: path,
       page_size: page_size,
+      graph_name: None,
+      graph_uuid: None,
+      graph_order: 0,
+      stored_nodes: Vec::new(),
+      node_refs: Vec::new(),
+      rows: vec![ Vec::new() ],
+      row_ids: Vec::new(),
+      page: None,
+      err_state: None,
+    }
+  }
+
+  pub fn execute ( &mut self )
+  {
+    if self.transaction.err_state.is_some()
+    {
+      self.err_state = self.transaction.err_state.clone();
+      return;
+    }
+
+    let res = self.prepare();
+    if res.is_err()
+    {
+      self.err_state = Some( res.unwrap_err() );
+      return;
+    }
+
+    let graph_name = &self.graph_name.as_ref().unwrap().clone();
+    let mut planner = WriteNewGraphPlanner::for_existing( self.path.to_string(), graph_name );
+    planner.plan();
+    if planner.err_state.is_some()
+    {
+      self.err_state = planner.err_state;
+      return;
+    }
+    self.write( &planner );
+  }
+
+  /// Read the stored graph and resolve every node reference before anything is written
+  fn prepare ( &mut self ) -> Result<bool, String>
+  {
+    self.set_graph_name()?;
+    self.validate_labels()?;
+    self.read_graph()?;
+    self.resolve_node_refs()
+  }
+
+  pub fn write ( &mut self, planner: &WriteNewGraphPlanner )
+  {
+    let open_res = open_file( &PathBuf::from( &self.path ));
+    if open_res.is_err()
+    {
+      self.err_state = Some( String::from( "Error opening database file." ));
+      return;
     }
+    let mut writer: BufWriter<File> = BufWriter::new( open_res.unwrap() );
+
+    let graph_name = self.graph_name.as_ref().unwrap();
+    let mut cursor = DataPageCursor::from_regions(
+      self.graph_uuid.as_ref().unwrap(),
+      graph_name,
+      self.page_size,
+      planner.empty_regions_by_graph( &graph_name.unwrap() ),
+      planner.end_pos.unwrap() - 8 );
+    cursor.free_pages = VecDeque::from( planner.free_data_pages() );
+    self.page = Some( cursor );
+
+    let rows = std::mem::take( &mut self.rows );
+    for ( i, binding ) in rows.iter().enumerate()
+    {
+      if i > 0 { self.next_row_ids(); }
+      for query_order in 1..self.transaction.query_order
+      {
+        let node_stmt_opt = self.transaction.next_node_statement( query_order );
+        if node_stmt_opt.is_some() { self.write_node( node_stmt_opt.unwrap(), binding, &mut writer ); }
+
+        let edge_stmt_opt = self.transaction.next_edge_statement( query_order );
+        if edge_stmt_opt.is_some() { self.write_edge( edge_stmt_opt.unwrap(), binding, &mut writer ); }
+
+        if self.err_state.is_some() { break; }
+      }
+      if self.err_state.is_some() { break; }
+    }
+
+    // close the remaining empty cells behind the last row
+    let close_res = self.page.as_mut().unwrap().close( &mut writer );
+    if close_res.is_err() && self.err_state.is_none() { self.err_state = Some( close_res.unwrap_err() ); }
+    let _ = writer.flush();
   }
 }
 
+impl UpdateGraphExecutor<'_>
+{
+  /// Resolve graph name from FromClause, falls back to the default graph
+  pub fn set_graph_name ( &mut self ) -> Result<bool, String>
+  {
+    let mut name = String::from( DEFAULT_GRAPH );
+    if self.transaction.from_clause.is_some()
+    {
+      name = self.transaction.from_clause.as_ref().unwrap().graph_name.clone();
+    }
+
+    let label_res = Label::new( name );
+    if label_res.is_ok()
+    {
+      self.graph_name = Some( label_res.unwrap() );
+      return Ok( true );
+    }
+    Err( String::from( "Error finding graph name." ))
+  }
+
+  /// Check the id and primary label of every statement, so an invalid one fails before the first row is written
+  pub fn validate_labels ( &self ) -> Result<bool, String>
+  {
+    for stmt in self.transaction.node_statements.iter() { row_uuid_label( &stmt.id, &stmt.primary_label )?; }
+    for stmt in self.transaction.edge_statements.iter() { row_uuid_label( &stmt.id, &stmt.primary_label )?; }
+    Ok( true )
+  }
+
+  /// Read the nodes and graph order of the stored graph, and the rows of an UNWIND
+  pub fn read_graph ( &mut self ) -> Result<bool, String>
+  {
+    let mut reader = SimpleMatchExecutor::new( self.transaction, self.path, self.page_size );
+    reader.execute();
+    if reader.err_state.is_some() { return Err( reader.err_state.unwrap() ); }
+
+    let variables = self.transaction.variables();
+    let refs = self.transaction.node_statements.iter().flat_map( | stmt | stmt.property_refs.iter() )
+      .chain( self.transaction.edge_statements.iter().flat_map( | stmt | stmt.property_refs.iter() ));
+    for ( _, operand ) in refs
+    {
+      for var in operand.variables().iter()
+      {
+        if variables.contains( var ) == false { return Err( format!( "Error: Variable `{}` not defined.", var )); }
+      }
+    }
+    // MATCH and UNWIND rows each write the statements once
+    if self.transaction.read_clause.is_some() || self.transaction.stages.len() > 0 
+    { 
+      self.rows = std::mem::take( &mut reader.bindings ); 
+    }
+
+    let uuid_res = UUID::new( parse_uuid_str( reader.graph_uuid.as_ref().unwrap() ).to_string() );
+    if uuid_res.is_err() { return Err( String::from( "Error reading graph uuid." )); }
+
+    self.graph_uuid = Some( uuid_res.unwrap() );
+    self.graph_order = reader.next_graph_order;
+    self.stored_nodes = reader.nodes;
+    Ok( true )
+  }
+
+  /// Resolve references to nodes not created by the transaction against stored nodes by their properties, 
+  /// references to MATCH variables are bound per row
+  pub fn resolve_node_refs ( &mut self ) -> Result<bool, String>
+  {
+    let variables = self.transaction.variables();
+    for stmt in self.transaction.node_ref_statements.iter()
+    {
+      let label = &stmt.transaction_label;
+      if self.transaction.find_node_by_transaction_label( label ).is_some() || variables.contains( label ) { continue; }
+      if stmt.properties.len() == 0 || self.find_node_ref( label ).is_some() { continue; }
+
+      let matched: Vec<&Node> = self.stored_nodes.iter()
+        .filter( | node | matches_properties( &node.properties, &stmt.properties ))
+        .collect();
+      match matched.len()
+      {
+        0 => { return Err( format!( "Error: Node `{}` not found.", label )); }
+        1 => { self.node_refs.push(( label.clone(), matched.first().unwrap().id.clone() )); }
+        _ => { return Err( format!( "Error: Node `{}` matches more than one node.", label )); }
+      }
+    }
+
+    for stmt in self.transaction.node_ref_statements.iter()
+    {
+      let label = &stmt.transaction_label;
+      if self.transaction.find_node_by_transaction_label( label ).is_none() && self.find_node_ref( label ).is_none() 
+        && variables.contains( label ) == false
+      {
+        return Err( format!( "Error: Node `{}` not defined.", label ));
+      }
+    }
+    Ok( true )
+  }
+
+  /// Node id of a resolved reference to a stored node
+  pub fn find_node_ref ( &self, transaction_label: &str ) -> Option<&String>
+  {
+    self.node_refs.iter().find( | ( label, _ ) | label == transaction_label ).map( | ( _, id ) | id )
+  }
+
+  /// Node id of the node or node reference at query_order, references to MATCH variables take the node of the row
+  pub fn node_id_at ( &self, query_order: u16, binding: &Vec<( String, Value )> ) -> Option<String>
+  {
+    let node_opt = self.transaction.next_node_statement( query_order );
+    if node_opt.is_some() { return Some( self.row_id( &node_opt.unwrap().id )); }
+
+    let ref_opt = self.transaction.next_ref_statement( query_order );
+    if ref_opt.is_some()
+    {
+      let label = &ref_opt.unwrap().transaction_label;
+      let node_opt = self.transaction.find_node_by_transaction_label( label );
+      if node_opt.is_some() { return Some( self.row_id( &node_opt.unwrap().id )); }
+      if self.find_node_ref( label ).is_some() { return self.find_node_ref( label ).cloned(); }
+
+      let bound_opt = binding.iter().find( | ( variable, _ ) | variable == label );
+      if bound_opt.is_some() { return bound_opt.unwrap().1.as_node().map( | node | node.id.clone() ); }
+    }
+    None
+  }
+
+  /// Every row after the first writes new nodes and edges
+  pub fn next_row_ids ( &mut self )
+  {
+    let ids = self.transaction.node_statements.iter().map( | stmt | &stmt.id )
+      .chain( self.transaction.edge_statements.iter().map( | stmt | &stmt.id ));
+    self.row_ids = ids.map( | id | ( id.clone(), cons_uuid() )).collect();
+  }
+
+  /// Id written for a statement in the current row
+  pub fn row_id ( &self, id: &String ) -> String
+  {
+    self.row_ids.iter().find( | ( stmt_id, _ ) | stmt_id == id ).map( | ( _, row_id ) | row_id.clone() ).unwrap_or( id.clone() )
+  }
+}
+
+impl UpdateGraphExecutor<'_>
+{
+  /// Write Node to the graph
+  pub fn write_node ( &mut self, stmt: &NodeStatement, binding: &Vec<( String, Value )>, writer: &mut BufWriter<File> )
+  {
+    let properties_res = row_properties( &stmt.properties, &stmt.property_refs, binding );
+    if properties_res.is_err()
+    {
+      self.err_state = Some( properties_res.unwrap_err() );
+      return;
+    }
+
+    let ids_res = row_uuid_label( &self.row_id( &stmt.id ), &stmt.primary_label );
+    if ids_res.is_err()
+    {
+      self.err_state = Some( ids_res.unwrap_err() );
+      return;
+    }
+    let ( uuid, primary_label ) = ids_res.unwrap();
+    let row = NodeRow::new( &DGu64::new( self.graph_order ), &uuid, &primary_label );
+    self.write_rows( &uuid, &row, &properties_res.unwrap(), writer );
+  }
+
+  /// Write Edge between the nodes on either side of it
+  pub fn write_edge ( &mut self, stmt: &EdgeStatement, binding: &Vec<( String, Value )>, writer: &mut BufWriter<File> )
+  {
+    let properties_res = row_properties( &stmt.properties, &stmt.property_refs, binding );
+    if properties_res.is_err()
+    {
+      self.err_state = Some( properties_res.unwrap_err() );
+      return;
+    }
+
+    let left_id = self.node_id_at( stmt.query_order - 1, binding );
+    let right_id = self.node_id_at( stmt.query_order + 1, binding );
+    if left_id.is_none() || right_id.is_none()
+    {
+      self.err_state = Some( String::from( "Error: Edge requires a node on both sides." ));
+      return;
+    }
+
+    let ids_res = row_uuid_label( &self.row_id( &stmt.id ), &stmt.primary_label );
+    let left_uuid = UUID::new( left_id.unwrap() );
+    let right_uuid = UUID::new( right_id.unwrap() );
+    if ids_res.is_err()
+    {
+      self.err_state = Some( ids_res.unwrap_err() );
+      return;
+    }
+    if left_uuid.is_err() || right_uuid.is_err()
+    {
+      self.err_state = Some( String::from( "Error creating UUID." ));
+      return;
+    }
+    let ( uuid, primary_label ) = ids_res.unwrap();
+    let row = EdgeRow::new(
+      &DGu64::new( self.graph_order ),
+      &uuid,
+      &primary_label,
+      direction_to_str( &stmt.direction ),
+      &left_uuid.unwrap(),
+      &right_uuid.unwrap() );
+    self.write_rows( &uuid, &row, &properties_res.unwrap(), writer );
+  }
+
+  /// Write a Node or Edge row followed by its KVPRows
+  fn write_rows ( &mut self, uuid: &UUID, row: &[u8], properties: &Vec<NEProperty>, writer: &mut BufWriter<File> )
+  {
+    let res = CoreWriteExecutor::write_rows( uuid, row, properties, self.page.as_mut().unwrap(), writer );
+    if res.is_err()
+    {
+      self.err_state = Some( res.unwrap_err() );
+      return;
+    }
+    self.graph_order += 1;
+  }
+}
+
+/// Properties of a statement with the ones referring to variables resolved for a row, null values are not stored
+pub fn row_properties ( 
+  properties: &Vec<NEProperty>, property_refs: &Vec<( String, Operand )>, binding: &Vec<( String, Value )> ) -> Result<Vec<NEProperty>, String>
+{
+  let mut ret = properties.clone();
+  for ( key, operand ) in property_refs.iter()
+  {
+    let value = operand.resolve( binding );
+    if value.is_null() { continue; }
+    let property_opt = value.to_property( key.clone() );
+    if property_opt.is_none() { return Err( format!( "Error: Property `{}` cannot be stored.", key )); }
+    ret.push( property_opt.unwrap() );
+  }
+  Ok( ret )
+}
+
 #[cfg(test)]
-mod tests 
+mod tests
 {
   use super::*;
-}
\ No newline at end of file
+  use std::fs::{ metadata, remove_file };
+  use crate::common::{ DirectionType, LABEL_BYTES };
+  use crate::planner::{ process_query, process_query_with_params };
+  use crate::result_set::Value;
+  use crate::utils::create_file;
+  use crate::executor::writer::new_db::WriteNewDBExecutor;
+  use crate::executor::writer::new_graph::WriteNewGraphExecutor;
+
+  const PAGE_SIZE: usize = 4096;
+
+  fn build_id () -> UUID { UUID::new( String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" )).unwrap() }
+  fn db_nickname () -> Label { Label::new( String::from( "devs" ) ).unwrap() }
+  fn write_new_db ( path: &str )
+  {
+    let open_res = create_file( &PathBuf::from( path ));
+    let mut stream = BufWriter::new( open_res.unwrap() );
+    let _ = WriteNewDBExecutor::execute_write_new( &build_id(), &db_nickname(), PAGE_SIZE, &mut stream );
+  }
+
+  fn write_devs_graph ( path: &str )
+  {
+    let query_string = "
+      CREATE GRAPH devs
+        (alice:Developer {name: 'Alice'})
+        (bob:Administrator {name: 'Bob'})
+        (alice)-[:KNOWS]->(bob)
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = WriteNewGraphExecutor::new( &t, path, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+  }
+
+  fn match_graph<'a> ( t: &'a Transaction, path: &'a str ) -> SimpleMatchExecutor<'a>
+  {
+    let mut reader = SimpleMatchExecutor::new( t, path, PAGE_SIZE );
+    reader.execute();
+    assert_eq!( reader.err_state, None );
+    reader
+  }
+
+  #[test]
+  fn test_update_graph ()
+  {
+    let path_str = "../test_data/UpdateGraphExecutor_test_update_graph.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    let query_string = "
+      CREATE
+        (dave:Developer {name: 'Dave'})
+        (alice {name: 'Alice'})-[:KNOWS]->(dave)
+      FROM devs
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = UpdateGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+
+    assert_eq!( writer.err_state, None );
+    assert_eq!( writer.stored_nodes.len(), 2 );
+    assert_eq!( writer.graph_order, 5 );
+    assert_eq!( writer.page.as_ref().unwrap().page_count, 0 );
+    assert_eq!( metadata( &PathBuf::from( path_str ) ).unwrap().len(), (PAGE_SIZE * 2) as u64 );
+
+    let t1 = process_query( "MATCH (n:Developer) FROM devs RETURN n.name", build_id(), db_nickname() );
+    let rs = match_graph( &t1, path_str ).result_set().unwrap();
+    assert_eq!( rs.len(), 2 );
+    assert_eq!( rs.rows[1].get( "n.name" ), Some( &Value::String( String::from( "Dave" ))));
+
+    let t2 = process_query( "MATCH (a {name: 'Alice'})-[:KNOWS]->(b) FROM devs RETURN b.name", build_id(), db_nickname() );
+    let reader2 = match_graph( &t2, path_str );
+    assert_eq!( reader2.edges.len(), 2 );
+    assert_eq!( reader2.edges[1].direction, DirectionType::Right );
+    let rs2 = reader2.result_set().unwrap();
+    assert_eq!( rs2.len(), 2 );
+    assert_eq!( rs2.rows[0].get( "b.name" ), Some( &Value::String( String::from( "Bob" ))));
+    assert_eq!( rs2.rows[1].get( "b.name" ), Some( &Value::String( String::from( "Dave" ))));
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_update_graph_unwind ()
+  {
+    let path_str = "../test_data/UpdateGraphExecutor_test_update_graph_unwind.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    let query_string = "
+      UNWIND ['Erin', 'Frank', 'Grace'] AS name
+      CREATE (dev:Developer {name: name, langs: ['rust']})<-[:KNOWS]-(alice {name: 'Alice'})
+      FROM devs
+    ";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = UpdateGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+    assert_eq!( writer.graph_order, 9 );
+
+    let t1 = process_query( 
+      "MATCH (a {name: 'Alice'})-[:KNOWS]->(d:Developer) FROM devs RETURN d.name, d.langs ORDER BY d.name", build_id(), db_nickname() );
+    let rs = match_graph( &t1, path_str ).result_set().unwrap();
+    let names: Vec<Value> = rs.rows.iter().map( | row | row.get( "d.name" ).unwrap().clone() ).collect();
+    assert_eq!( names, vec![ Value::from( "Erin" ), Value::from( "Frank" ), Value::from( "Grace" ) ] );
+    assert_eq!( rs.rows[0].get( "d.langs" ), Some( &Value::List( vec![ Value::from( "rust" ) ] )));
+
+    // nulls are not stored, nested lists cannot be
+    let t2 = process_query( "UNWIND [null] AS name CREATE (:Developer {name: name}) FROM devs", build_id(), db_nickname() );
+    let mut writer2 = UpdateGraphExecutor::new( &t2, path_str, PAGE_SIZE );
+    writer2.execute();
+    assert_eq!( writer2.err_state, None );
+    let t3 = process_query( "UNWIND [[[1]]] AS n CREATE (:Developer {n: n}) FROM devs", build_id(), db_nickname() );
+    let mut writer3 = UpdateGraphExecutor::new( &t3, path_str, PAGE_SIZE );
+    writer3.execute();
+    assert_eq!( writer3.err_state, Some( String::from( "Error: Property `n` cannot be stored." )));
+
+    let t4 = process_query( "CREATE (:Developer {name: name}) FROM devs", build_id(), db_nickname() );
+    let mut writer4 = UpdateGraphExecutor::new( &t4, path_str, PAGE_SIZE );
+    writer4.execute();
+    assert_eq!( writer4.err_state, Some( String::from( "Error: Variable `name` not defined." )));
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_update_graph_params ()
+  {
+    let path_str = "../test_data/UpdateGraphExecutor_test_update_graph_params.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    let row = | name: &str, level: u8 | Value::Map( vec![ 
+      ( String::from( "name" ), Value::from( name )), ( String::from( "level" ), Value::U8( level )) ] );
+    let params = vec![ 
+      ( String::from( "rows" ), Value::List( vec![ row( "Erin", 3 ), row( "Frank", 7 ) ] )),
+      ( String::from( "name" ), Value::from( "Frank" )),
+    ];
+    let t = process_query_with_params( 
+      "UNWIND $rows AS row CREATE (:Developer {name: row.name, level: row.level}) FROM devs", &params, build_id(), db_nickname() );
+    let mut writer = UpdateGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+
+    // typed widths are stored as given
+    let t1 = process_query_with_params( "MATCH (d:Developer {name: $name}) FROM devs RETURN d.level", &params, build_id(), db_nickname() );
+    let rs = match_graph( &t1, path_str ).result_set().unwrap();
+    assert_eq!( rs.len(), 1 );
+    assert_eq!( rs.rows[0].get( "d.level" ), Some( &Value::U8( 7 )));
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_update_graph_overflow ()
+  {
+    let path_str = "../test_data/UpdateGraphExecutor_test_update_graph_overflow.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    let mut query_string = String::from( "CREATE" );
+    for i in 0..20 { query_string.push_str( &format!( " (n{}:Developer {{name: 'dev{}'}})", i, i )); }
+    query_string.push_str( " FROM devs" );
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = UpdateGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+
+    assert_eq!( writer.err_state, None );
+    assert_eq!( writer.page.as_ref().unwrap().page_count, 1 );
+    assert_eq!( metadata( &PathBuf::from( path_str ) ).unwrap().len(), (PAGE_SIZE * 3) as u64 );
+
+    // the second update fills the new DataPage before another is appended
+    let t1 = process_query( "CREATE (eve:Developer {name: 'Eve'}) FROM devs", build_id(), db_nickname() );
+    let mut writer1 = UpdateGraphExecutor::new( &t1, path_str, PAGE_SIZE );
+    writer1.execute();
+
+    assert_eq!( writer1.err_state, None );
+    assert_eq!( writer1.stored_nodes.len(), 22 );
+    assert_eq!( writer1.page.as_ref().unwrap().page_count, 0 );
+    assert_eq!( metadata( &PathBuf::from( path_str ) ).unwrap().len(), (PAGE_SIZE * 3) as u64 );
+
+    let t2 = process_query( "MATCH (n:Developer) FROM devs", build_id(), db_nickname() );
+    assert_eq!( match_graph( &t2, path_str ).nodes.len(), 22 );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_update_graph_match ()
+  {
+    let path_str = "../test_data/UpdateGraphExecutor_test_update_graph_match.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    let query_string = "MATCH (b {name: 'Bob'}) FROM devs CREATE (b)-[:KNOWS]->(erin:Developer {name: 'Erin'})";
+    let t = process_query( &query_string, build_id(), db_nickname() );
+    let mut writer = UpdateGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, None );
+
+    let t1 = process_query( "MATCH (b {name: 'Bob'})-[:KNOWS]->(e) FROM devs RETURN e.name", build_id(), db_nickname() );
+    let rs = match_graph( &t1, path_str ).result_set().unwrap();
+    assert_eq!( rs.len(), 1 );
+    assert_eq!( rs.rows[0].get( "e.name" ), Some( &Value::from( "Erin" )));
+
+    // once per matched row, nothing when no row matches
+    let query_string2 = "MATCH (n) FROM devs CREATE (n)-[:MENTORS]->(:Intern)";
+    let t2 = process_query( &query_string2, build_id(), db_nickname() );
+    let mut writer2 = UpdateGraphExecutor::new( &t2, path_str, PAGE_SIZE );
+    writer2.execute();
+    assert_eq!( writer2.err_state, None );
+    let t4 = process_query( "MATCH (n) FROM devs", build_id(), db_nickname() );
+    assert_eq!( match_graph( &t4, path_str ).nodes.len(), 6 );
+
+    let t3 = process_query( "MATCH (n {name: 'Zoe'}) FROM devs CREATE (n)-[:KNOWS]->(:Intern)", build_id(), db_nickname() );
+    let mut writer3 = UpdateGraphExecutor::new( &t3, path_str, PAGE_SIZE );
+    writer3.execute();
+    assert_eq!( writer3.err_state, None );
+    assert_eq!( match_graph( &t4, path_str ).nodes.len(), 6 );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+
+  #[test]
+  fn test_update_graph_errors ()
+  {
+    let path_str = "../test_data/UpdateGraphExecutor_test_update_graph_errors.sdb";
+    write_new_db( path_str );
+    write_devs_graph( path_str );
+
+    let t = process_query( "CREATE (dave:Developer) FROM movies", build_id(), db_nickname() );
+    let mut writer = UpdateGraphExecutor::new( &t, path_str, PAGE_SIZE );
+    writer.execute();
+    assert_eq!( writer.err_state, Some( String::from( "Error: Graph not found." )));
+
+    let t1 = process_query( "CREATE (dave:Developer) (zoe {name: 'Zoe'})-[:KNOWS]->(dave) FROM devs", build_id(), db_nickname() );
+    let mut writer1 = UpdateGraphExecutor::new( &t1, path_str, PAGE_SIZE );
+    writer1.execute();
+    assert_eq!( writer1.err_state, Some( String::from( "Error: Node `zoe` not found." )));
+
+    let t2 = process_query( "CREATE (dave:Developer) (alice)-[:KNOWS]->(dave) FROM devs", build_id(), db_nickname() );
+    let mut writer2 = UpdateGraphExecutor::new( &t2, path_str, PAGE_SIZE );
+    writer2.execute();
+    assert_eq!( writer2.err_state, Some( String::from( "Error: Node `alice` not defined." )));
+
+    // labels too long to store are an error, not a panic
+    let long_label = "z".repeat( LABEL_BYTES + 20 );
+    let t4 = process_query( &format!( "CREATE (dave:Developer) (z:{}) FROM devs", long_label ), build_id(), db_nickname() );
+    let mut writer4 = UpdateGraphExecutor::new( &t4, path_str, PAGE_SIZE );
+    writer4.execute();
+    assert_eq!( writer4.err_state, Some( format!( "Error: Invalid label `{}`.", long_label )));
+
+    // nothing is written when a reference or label fails
+    assert_eq!( metadata( &PathBuf::from( path_str ) ).unwrap().len(), (PAGE_SIZE * 2) as u64 );
+    let t3 = process_query( "MATCH (n) FROM devs", build_id(), db_nickname() );
+    assert_eq!( match_graph( &t3, path_str ).nodes.len(), 2 );
+
+    let _ = remove_file( PathBuf::from( path_str ));
+  }
+}
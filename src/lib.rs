@@ -1,4 +1,5 @@
 pub mod common;
+pub mod ast;
 pub mod cli;
 pub mod utils;
 pub mod tokenize;
@@ -10,6 +11,7 @@ pub mod datagramv2;
 pub mod executor;
 pub mod parser;
 pub mod planner;
+pub mod result_set;
 
 use std::fs;
 use std::path::PathBuf;
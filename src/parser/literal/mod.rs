@@ -0,0 +1,221 @@
+use crate::result_set::Value;
+
+/* @version 0.3.0 */
+
+/*
+  Literal text of a Value token to a typed Value, the types map onto the KVP grams in common::kvps.
+
+  Integers  42, -42, +42, 1_000_000, 0xFF, 0o17
+  Floats    1.5, .5, 2., 1e3, 1.5E-3, NaN, Infinity, -Infinity
+  Others    true, false, null (any case)
+
+  Width rule
+  1. A suffix picks the width: 38u8, -3i16, 0xFFu16, 1.5f32 (i8 ... i128, u8 ... u128, f32, f64).
+  2. Integers without a suffix are i64, i128 when they do not fit i64 and u128 when they do not fit i128.
+  3. Floats without a suffix are f64. A literal is a float when it has a fraction, an exponent or a float suffix.
+  4. A literal that does not fit its width is an error, a finite float that rounds to infinity overflows.
+
+  Underscores sit between digits, hex and octal literals are integers.
+
+  parse_literal()
+    :: split_suffix()
+    :: strip_underscores()
+    :: int_value()
+    :: float_value()
+*/
+
+const INT_SUFFIXES: [&str; 10] = [ "i8", "i16", "i32", "i64", "i128", "u8", "u16", "u32", "u64", "u128" ];
+const FLOAT_SUFFIXES: [&str; 2] = [ "f32", "f64" ];
+
+/// Typed value of a literal, None when the word is not a literal and an error when it does not fit its width
+pub fn parse_literal ( word: &str ) -> Option<Result<Value, String>>
+{
+  if word.eq_ignore_ascii_case( "true" ) { return Some( Ok( Value::Boolean( true ))); }
+  if word.eq_ignore_ascii_case( "false" ) { return Some( Ok( Value::Boolean( false ))); }
+  if word.eq_ignore_ascii_case( "null" ) { return Some( Ok( Value::Null )); }
+
+  let negative = word.starts_with( '-' );
+  let unsigned = word.strip_prefix( [ '-', '+' ] ).unwrap_or( word );
+  if unsigned == "NaN" { return Some( Ok( Value::F64( f64::NAN ))); }
+  if unsigned == "Infinity"
+  {
+    if negative { return Some( Ok( Value::F64( f64::NEG_INFINITY ))); }
+    return Some( Ok( Value::F64( f64::INFINITY )));
+  }
+
+  let ( radix, digits ) = match unsigned.get( ..2 )
+  {
+    Some( "0x" ) => ( 16, &unsigned[2..] ),
+    Some( "0o" ) => ( 8, &unsigned[2..] ),
+    _ => ( 10, unsigned ),
+  };
+  let ( digits, suffix ) = split_suffix( digits, radix );
+  let digits = strip_underscores( digits, radix )?;
+  if digits.starts_with( | c: char | c.is_digit( radix ) || ( radix == 10 && c == '.' )) == false { return None; }
+
+  let is_float = radix == 10 && ( digits.contains( [ '.', 'e', 'E' ] ) || FLOAT_SUFFIXES.contains( &suffix ));
+  if is_float { return float_value( word, negative, &digits, suffix ); }
+  if FLOAT_SUFFIXES.contains( &suffix ) || digits.chars().all( | c | c.is_digit( radix )) == false { return None; }
+  Some( int_value( word, negative, &digits, radix, suffix ))
+}
+
+/// Width suffix of the digits, hex digits include f so hex literals only take integer suffixes
+fn split_suffix ( digits: &str, radix: u32 ) -> ( &str, &str )
+{
+  let float_suffixes: &[&str] = if radix == 10 { &FLOAT_SUFFIXES } else { &[] };
+  for suffix in INT_SUFFIXES.iter().chain( float_suffixes.iter() )
+  {
+    if digits.len() > suffix.len() && digits.ends_with( suffix )
+    {
+      return ( &digits[..digits.len() - suffix.len()], suffix );
+    }
+  }
+  ( digits, "" )
+}
+
+/// 1_000 is 1000, an underscore at either end or next to another is not part of a literal
+fn strip_underscores ( digits: &str, radix: u32 ) -> Option<String>
+{
+  let chars: Vec<char> = digits.chars().collect();
+  for ( i, c ) in chars.iter().enumerate()
+  {
+    if *c != '_' { continue; }
+    let before = i > 0 && chars[i - 1].is_digit( radix );
+    let after = i + 1 < chars.len() && chars[i + 1].is_digit( radix );
+    if before == false || after == false { return None; }
+  }
+  Some( digits.replace( '_', "" ))
+}
+
+fn int_value ( word: &str, negative: bool, digits: &str, radix: u32, suffix: &str ) -> Result<Value, String>
+{
+  let overflow = | width: &str | format!( "Syntax Error: Literal `{}` overflows {}", word, width );
+  let magnitude_res = u128::from_str_radix( digits, radix );
+  if magnitude_res.is_err()
+  {
+    if suffix.len() > 0 { return Err( overflow( suffix )); }
+    return Err( overflow( "u128" ));
+  }
+
+  let magnitude = magnitude_res.unwrap();
+  let signed = if negative { 0i128.checked_sub_unsigned( magnitude ) } else { i128::try_from( magnitude ).ok() };
+  let unsigned = if negative && magnitude > 0 { None } else { Some( magnitude ) };
+  let value = match suffix
+  {
+    "i8" => signed.and_then( | n | i8::try_from( n ).ok() ).map( Value::I8 ),
+    "i16" => signed.and_then( | n | i16::try_from( n ).ok() ).map( Value::I16 ),
+    "i32" => signed.and_then( | n | i32::try_from( n ).ok() ).map( Value::I32 ),
+    "i64" => signed.and_then( | n | i64::try_from( n ).ok() ).map( Value::I64 ),
+    "i128" => signed.map( Value::I128 ),
+    "u8" => unsigned.and_then( | n | u8::try_from( n ).ok() ).map( Value::U8 ),
+    "u16" => unsigned.and_then( | n | u16::try_from( n ).ok() ).map( Value::U16 ),
+    "u32" => unsigned.and_then( | n | u32::try_from( n ).ok() ).map( Value::U32 ),
+    "u64" => unsigned.and_then( | n | u64::try_from( n ).ok() ).map( Value::U64 ),
+    "u128" => unsigned.map( Value::U128 ),
+    _ =>
+    {
+      // no suffix, the narrowest of i64, i128 and u128
+      let widened = signed.map( | n | i64::try_from( n ).map( Value::I64 ).unwrap_or( Value::I128( n )));
+      if negative { widened } else { widened.or( unsigned.map( Value::U128 )) }
+    }
+  };
+  if value.is_none()
+  {
+    if suffix.len() > 0 { return Err( overflow( suffix )); }
+    return Err( overflow( "i128" ));
+  }
+  Ok( value.unwrap() )
+}
+
+fn float_value ( word: &str, negative: bool, digits: &str, suffix: &str ) -> Option<Result<Value, String>>
+{
+  // only digits, one fraction and an exponent, parse() also accepts words such as inf
+  let valid = digits.chars().enumerate().all( | ( i, c ) |
+    c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E' ||
+    (( c == '-' || c == '+' ) && i > 0 && digits[..i].ends_with( [ 'e', 'E' ] )));
+  if valid == false { return None; }
+
+  let text = if negative { format!( "-{}", digits ) } else { digits.to_string() };
+  let overflow = | width: &str | format!( "Syntax Error: Literal `{}` overflows {}", word, width );
+  if suffix == "f32"
+  {
+    let float_res = text.parse::<f32>();
+    if float_res.is_err() { return None; }
+    if float_res.as_ref().unwrap().is_infinite() { return Some( Err( overflow( "f32" ))); }
+    return Some( Ok( Value::F32( float_res.unwrap() )));
+  }
+  if suffix.len() > 0 && suffix != "f64" { return None; }
+
+  let float_res = text.parse::<f64>();
+  if float_res.is_err() { return None; }
+  if float_res.as_ref().unwrap().is_infinite() { return Some( Err( overflow( "f64" ))); }
+  Some( Ok( Value::F64( float_res.unwrap() )))
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+
+  fn literal ( word: &str ) -> Value { parse_literal( word ).unwrap().unwrap() }
+
+  #[test]
+  fn test_parse_integer_literal ()
+  {
+    assert_eq!( literal( "42" ), Value::I64( 42 ));
+    assert_eq!( literal( "-42" ), Value::I64( -42 ));
+    assert_eq!( literal( "+42" ), Value::I64( 42 ));
+    assert_eq!( literal( "1_000_000" ), Value::I64( 1_000_000 ));
+    assert_eq!( literal( "0xFF" ), Value::I64( 255 ));
+    assert_eq!( literal( "-0x10" ), Value::I64( -16 ));
+    assert_eq!( literal( "0o17" ), Value::I64( 15 ));
+
+    // suffixes pick the width, unsuffixed integers widen past i64
+    assert_eq!( literal( "38u8" ), Value::U8( 38 ));
+    assert_eq!( literal( "-3i16" ), Value::I16( -3 ));
+    assert_eq!( literal( "0xFFu16" ), Value::U16( 255 ));
+    assert_eq!( literal( "-128i8" ), Value::I8( -128 ));
+    assert_eq!( literal( "9223372036854775808" ), Value::I128( 9223372036854775808 ));
+    assert_eq!( literal( "-9223372036854775808" ), Value::I64( i64::MIN ));
+    assert_eq!( literal( "340282366920938463463374607431768211455" ), Value::U128( u128::MAX ));
+    assert_eq!( literal( "-170141183460469231731687303715884105728" ), Value::I128( i128::MIN ));
+
+    assert_eq!( parse_literal( "300u8" ), Some( Err( String::from( "Syntax Error: Literal `300u8` overflows u8" ))));
+    assert_eq!( parse_literal( "-1u32" ), Some( Err( String::from( "Syntax Error: Literal `-1u32` overflows u32" ))));
+    assert_eq!( parse_literal( "128i8" ), Some( Err( String::from( "Syntax Error: Literal `128i8` overflows i8" ))));
+    assert_eq!( parse_literal( "340282366920938463463374607431768211456" ),
+      Some( Err( String::from( "Syntax Error: Literal `340282366920938463463374607431768211456` overflows u128" ))));
+    assert_eq!( parse_literal( "-170141183460469231731687303715884105729" ),
+      Some( Err( String::from( "Syntax Error: Literal `-170141183460469231731687303715884105729` overflows i128" ))));
+  }
+
+  #[test]
+  fn test_parse_float_literal ()
+  {
+    assert_eq!( literal( "19.99" ), Value::F64( 19.99 ));
+    assert_eq!( literal( "-.5" ), Value::F64( -0.5 ));
+    assert_eq!( literal( "2." ), Value::F64( 2.0 ));
+    assert_eq!( literal( "1e3" ), Value::F64( 1000.0 ));
+    assert_eq!( literal( "1.5E-3" ), Value::F64( 0.0015 ));
+    assert_eq!( literal( "1_000.5" ), Value::F64( 1000.5 ));
+    assert_eq!( literal( "1.5f32" ), Value::F32( 1.5 ));
+    assert_eq!( literal( "2f64" ), Value::F64( 2.0 ));
+    assert_eq!( literal( "-Infinity" ), Value::F64( f64::NEG_INFINITY ));
+    assert_eq!( literal( "NaN" ).as_f64().unwrap().is_nan(), true );
+
+    assert_eq!( parse_literal( "1e400" ), Some( Err( String::from( "Syntax Error: Literal `1e400` overflows f64" ))));
+    assert_eq!( parse_literal( "1e39f32" ), Some( Err( String::from( "Syntax Error: Literal `1e39f32` overflows f32" ))));
+  }
+
+  #[test]
+  fn test_parse_other_literal ()
+  {
+    assert_eq!( literal( "true" ), Value::Boolean( true ));
+    assert_eq!( literal( "FALSE" ), Value::Boolean( false ));
+    assert_eq!( literal( "null" ), Value::Null );
+
+    // not literals at all
+    let words = vec![ "3x", "name", "_1", "1__0", "1_", "0x", "0xZZ", "0x1.5", "1..3", "1e", "e3", "inf", "nan", "1.5i8", "u8", "-" ];
+    for word in words.into_iter() { assert_eq!( parse_literal( word ), None, "{}", word ); }
+  }
+}
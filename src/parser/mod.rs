@@ -1,4 +1,8 @@
-use crate::tokenize::{ TokenType, token_type, SyntaxToken, SyntaxTokenType };
+pub mod literal;
+
+use crate::cmd::label_expression::is_label_char;
+use crate::parser::literal::parse_literal;
+use crate::tokenize::{ TokenType, token_type, Span, SyntaxToken, SyntaxTokenType };
 
 /* @version 0.3.0 */
 
@@ -7,34 +11,91 @@ pub fn parse_syntax ( content: &str ) -> Vec<SyntaxToken>
 {
   let mut tokens = Vec::new();
   let mut acc = Vec::new();
+  let mut acc_idx: Vec<usize> = Vec::new(); // char index in the query of every char of acc
   let mut quote_acc = Vec::new();
+  let mut quote_idx: Vec<usize> = Vec::new();
   let mut props_mode = false;
   let mut quote_mode = false;
-  for c in content.chars() 
+  let mut label_acc: Option<String> = None; // text after a colon outside of properties
+  let mut label_idx: Vec<usize> = Vec::new();
+  let mut label_depth: u16 = 0;
+  let mut label_space = false;
+  let mut brackets: Vec<SyntaxTokenType> = Vec::new(); // open [ kinds, innermost last
+  let mut prev_c: Option<char> = None;
+  let chars: Vec<char> = content.chars().collect();
+  let positions = char_positions( content );
+  let mut unclosed: Option<usize> = None; // char index of a /* without */
+  let mut i = 0;
+  while i < chars.len() 
   { 
+    let mut c = chars[i];
+    i += 1;
+
+    // a comment ends the word before it like whitespace
+    let comment_opt = if quote_mode { None } else { comment_end( &chars, i - 1 ) };
+    if comment_opt.is_some() 
+    {
+      let ( end, closed ) = comment_opt.unwrap();
+      if closed == false { unclosed = Some( i - 1 ); }
+      i = end;
+      c = ' ';
+    }
+
+    let prev = prev_c.replace( c );
     if quote_mode == true && token_type( &c ) != TokenType::Quote 
     {
-      quote_acc.push( c.to_string() );
+      push_char( &mut quote_acc, &mut quote_idx, c, i - 1 );
       continue;  
     }
 
+    if label_acc.is_some() 
+    {
+      let label_len = label_acc.as_ref().unwrap().len();
+      if add_label_char( &c, label_acc.as_mut().unwrap(), &mut label_depth, &mut label_space ) 
+      { 
+        if label_acc.as_ref().unwrap().len() > label_len { label_idx.push( i - 1 ); }
+        continue; 
+      }
+      let label = label_acc.take().unwrap();
+      tokens.append( &mut place_tokens( parse_label_acc( &label ), &label, &label_idx, &positions ));
+      label_depth = 0;
+      label_space = false;
+    }
+
     match token_type( &c ) 
     {
-      TokenType::Char => { acc.push( c.to_string() ); },
+      TokenType::Char => 
+      { 
+        // [r*1..3], the variable is complete before the hop range
+        if c == '*' && props_mode == false && acc.len() > 0 
+        {
+          let token_opt = parse_syntax_token( &acc, props_mode );
+          if token_opt.is_some() { tokens.push( place_token( token_opt.unwrap(), &acc.join( "" ), &acc_idx, &positions )); }
+          acc = Vec::new();
+        }
+        push_char( &mut acc, &mut acc_idx, c, i - 1 ); 
+      },
       
       TokenType::Space | TokenType::NewLine => 
       {
         let token_opt = parse_syntax_token( &acc, props_mode );
         if token_opt.is_some() 
         { 
-          tokens.push( token_opt.unwrap() );
+          tokens.push( place_token( token_opt.unwrap(), &acc.join( "" ), &acc_idx, &positions ));
           acc = Vec::new();
         }
       },
       
       TokenType::OpenParen => 
       { 
-        tokens.push( SyntaxToken::new( SyntaxTokenType::OpenNode, c.to_string() ));  
+        // count(n), the function name is complete
+        let token_opt = parse_syntax_token( &acc, props_mode );
+        if token_opt.is_some() 
+        { 
+          tokens.push( place_token( token_opt.unwrap(), &acc.join( "" ), &acc_idx, &positions ));
+          acc = Vec::new();
+        }
+        tokens.push( char_token( SyntaxTokenType::OpenNode, c, i - 1, &positions ));  
       },
       
       TokenType::CloseParen => 
@@ -42,45 +103,73 @@ pub fn parse_syntax ( content: &str ) -> Vec<SyntaxToken>
         let token_opt = parse_syntax_token( &acc, props_mode );
         if token_opt.is_some() 
         { 
-          tokens.push( token_opt.unwrap() );
+          tokens.push( place_token( token_opt.unwrap(), &acc.join( "" ), &acc_idx, &positions ));
           acc = Vec::new();
         }
-        tokens.push(SyntaxToken::new( SyntaxTokenType::CloseNode, c.to_string() )); 
+        tokens.push( char_token( SyntaxTokenType::CloseNode, c, i - 1, &positions )); 
       },
       
       TokenType::OpenBrace => 
       {
         props_mode = true;
-        tokens.push( SyntaxToken::new( SyntaxTokenType::OpenBrace, c.to_string() ));
+        tokens.push( char_token( SyntaxTokenType::OpenBrace, c, i - 1, &positions ));
       },
 
       TokenType::CloseBrace => 
       {
+        let token_opt = parse_syntax_token( &acc, props_mode );
+        if token_opt.is_some() 
+        { 
+          tokens.push( place_token( token_opt.unwrap(), &acc.join( "" ), &acc_idx, &positions ));
+          acc = Vec::new();
+        }
         props_mode = false;
-        tokens.push( SyntaxToken::new( SyntaxTokenType::CloseBrace, c.to_string() ));
+        tokens.push( char_token( SyntaxTokenType::CloseBrace, c, i - 1, &positions ));
       },
 
-      TokenType::OpenBracket => { tokens.push( SyntaxToken::new( SyntaxTokenType::OpenEdge, c.to_string() )); },
-      TokenType::CloseBracket => 
-      {
+      TokenType::OpenBracket => 
+      { 
+        // -[ starts a relationship, list[0] indexes, anything else is a list
+        let indexing = acc.len() > 0 || prev == Some( ')' ) || prev == Some( ']' );
         let token_opt = parse_syntax_token( &acc, props_mode );
         if token_opt.is_some() 
         { 
-          tokens.push( token_opt.unwrap() );
+          tokens.push( place_token( token_opt.unwrap(), &acc.join( "" ), &acc_idx, &positions ));
           acc = Vec::new();
-        } 
-        tokens.push( SyntaxToken::new( SyntaxTokenType::CloseEdge, c.to_string() )); 
+        }
+
+        let after_edge = tokens.last().map( | t | t.token_type == SyntaxTokenType::EdgeDirection && t.val == "-" );
+        let bracket_type = 
+          if props_mode == false && indexing == false && after_edge == Some( true ) { SyntaxTokenType::OpenEdge }
+          else if props_mode == false && indexing { SyntaxTokenType::OpenIndex }
+          else { SyntaxTokenType::OpenBracket };
+        brackets.push( bracket_type.clone() );
+        tokens.push( char_token( bracket_type, c, i - 1, &positions )); 
+      },
+      TokenType::CloseBracket => 
+      {
+        let bracket_type = brackets.pop().unwrap_or( SyntaxTokenType::OpenEdge );
+        if bracket_type == SyntaxTokenType::OpenIndex && props_mode == false { tokens.append( &mut place_tokens( parse_range_acc( &acc ), &acc.join( "" ), &acc_idx, &positions )); }
+        else 
+        {
+          let token_opt = parse_syntax_token( &acc, props_mode );
+          if token_opt.is_some() { tokens.push( place_token( token_opt.unwrap(), &acc.join( "" ), &acc_idx, &positions )); }
+        }
+        acc = Vec::new();
+
+        if bracket_type == SyntaxTokenType::OpenEdge { tokens.push( char_token( SyntaxTokenType::CloseEdge, c, i - 1, &positions )); }
+        else { tokens.push( char_token( SyntaxTokenType::CloseBracket, c, i - 1, &positions )); }
       },
 
       TokenType::Colon => 
       {
         if props_mode  == true 
         {
-          acc.push( c.to_string() );
+          push_char( &mut acc, &mut acc_idx, c, i - 1 );
           let token_opt = parse_syntax_token( &acc, props_mode );
           if token_opt.is_some() 
           { 
-            tokens.push( token_opt.unwrap() );
+            tokens.push( place_token( token_opt.unwrap(), &acc.join( "" ), &acc_idx, &positions ));
             acc = Vec::new();
           }
         }
@@ -89,10 +178,11 @@ pub fn parse_syntax ( content: &str ) -> Vec<SyntaxToken>
           let token_opt = parse_syntax_token( &acc, props_mode );
           if token_opt.is_some() 
           { 
-            tokens.push( token_opt.unwrap() );
+            tokens.push( place_token( token_opt.unwrap(), &acc.join( "" ), &acc_idx, &positions ));
             acc = Vec::new();
           }
-          acc.push( c.to_string() );
+          label_acc = Some( String::new() );
+          label_idx.clear();
         }
       },
       
@@ -105,44 +195,191 @@ pub fn parse_syntax ( content: &str ) -> Vec<SyntaxToken>
           let token_opt = parse_quote_acc( &quote_acc );
           if token_opt.is_some() 
           { 
-            tokens.push( token_opt.unwrap() );
+            tokens.push( place_token( token_opt.unwrap(), &quote_acc.join( "" ), &quote_idx, &positions ));
             quote_acc = Vec::new();
           }
         }
       },
 
-      TokenType::Comma => { continue; },
-      TokenType::Hyphen => { tokens.push( SyntaxToken::new( SyntaxTokenType::EdgeDirection, c.to_string() ));},
-      TokenType::LT => { tokens.push( SyntaxToken::new( SyntaxTokenType::EdgeDirection, c.to_string() ));},
-      TokenType::GT => { tokens.push( SyntaxToken::new( SyntaxTokenType::EdgeDirection, c.to_string() ));},
-      TokenType::Pipe => { tokens.push( SyntaxToken::new( SyntaxTokenType::KeywordOr, c.to_string() ));},
-      TokenType::Ampersand => { tokens.push( SyntaxToken::new( SyntaxTokenType::KeywordAnd, c.to_string() ));},
+      TokenType::Comma => 
+      {
+        let token_opt = parse_syntax_token( &acc, props_mode );
+        if token_opt.is_some() 
+        { 
+          tokens.push( place_token( token_opt.unwrap(), &acc.join( "" ), &acc_idx, &positions ));
+          acc = Vec::new();
+        }
+      },
+      TokenType::Hyphen => 
+      { 
+        // {age: -3} is a negative value, there are no relationships inside { }, 1e-3 is an exponent
+        if props_mode || is_exponent( &acc ) { push_char( &mut acc, &mut acc_idx, c, i - 1 ); }
+        else { tokens.push( char_token( SyntaxTokenType::EdgeDirection, c, i - 1, &positions )); }
+      },
+      TokenType::LT | TokenType::GT => 
+      {
+        // n.age>30 compares, the word before the operator is complete
+        let token_opt = parse_syntax_token( &acc, props_mode );
+        if token_opt.is_some() 
+        { 
+          tokens.push( place_token( token_opt.unwrap(), &acc.join( "" ), &acc_idx, &positions ));
+          acc = Vec::new();
+        }
+        tokens.push( char_token( SyntaxTokenType::EdgeDirection, c, i - 1, &positions ));
+      },
+      TokenType::Semicolon => 
+      {
+        // nothing left open carries over into the next statement
+        let token_opt = parse_syntax_token( &acc, props_mode );
+        if token_opt.is_some() { tokens.push( place_token( token_opt.unwrap(), &acc.join( "" ), &acc_idx, &positions )); }
+        acc = Vec::new();
+        props_mode = false;
+        brackets.clear();
+        tokens.push( char_token( SyntaxTokenType::EndStatement, c, i - 1, &positions ));
+      },
+      TokenType::Pipe => { tokens.push( char_token( SyntaxTokenType::KeywordOr, c, i - 1, &positions ));},
+      TokenType::Ampersand => { tokens.push( char_token( SyntaxTokenType::KeywordAnd, c, i - 1, &positions ));},
+
+      TokenType::Equals => 
+      {
+        // += merges a map into existing properties
+        let merge = acc.len() > 0 && acc[acc.len() - 1] == "+";
+        let plus_idx = if merge == true { acc.pop(); acc_idx.pop() } else { None };
+
+        let token_opt = parse_syntax_token( &acc, props_mode );
+        if token_opt.is_some() 
+        { 
+          tokens.push( place_token( token_opt.unwrap(), &acc.join( "" ), &acc_idx, &positions ));
+          acc = Vec::new();
+        }
+
+        if merge == true 
+        { 
+          let mut token = SyntaxToken::new( SyntaxTokenType::PlusEquals, String::from( "+=" ));
+          token.span = span_of( &positions, plus_idx.unwrap_or( i - 1 ), i );
+          tokens.push( token ); 
+        }
+        else { tokens.push( char_token( SyntaxTokenType::Equals, c, i - 1, &positions )); }
+      },
 
       _ => {}
     }
   }
 
+  if label_acc.is_some() 
+  { 
+    let label = label_acc.take().unwrap();
+    tokens.append( &mut place_tokens( parse_label_acc( &label ), &label, &label_idx, &positions )); 
+  }
+
   if acc.len() > 0 
   {
     let token_opt = parse_syntax_token( &acc, props_mode );
     if token_opt.is_some() 
     { 
-      tokens.push( token_opt.unwrap() );
+      tokens.push( place_token( token_opt.unwrap(), &acc.join( "" ), &acc_idx, &positions ));
       acc = Vec::new();
     } 
   }
 
+  // the parser reports the /* that comments out the rest of the query
+  if unclosed.is_some() 
+  {
+    let mut token = SyntaxToken::new( SyntaxTokenType::UnclosedComment, String::from( "/*" ));
+    token.span = span_of( &positions, unclosed.unwrap(), unclosed.unwrap() + 2 );
+    tokens.push( token );
+  }
   tokens
 }
 
+/// Index after the comment that starts at i and whether it is closed, // runs to the end of the line and /* to */
+fn comment_end ( chars: &[char], i: usize ) -> Option<( usize, bool )> 
+{
+  if chars.get( i ) != Some( &'/' ) { return None; }
+  match chars.get( i + 1 ) 
+  {
+    Some( '/' ) => Some(( chars[i..].iter().position( | c | *c == '\n' ).map( | p | i + p ).unwrap_or( chars.len() ), true )),
+    Some( '*' ) => 
+    {
+      let close = chars[i + 2..].windows( 2 ).position( | w | w == [ '*', '/' ] );
+      Some( close.map( | p | ( i + p + 4, true )).unwrap_or(( chars.len(), false )))
+    }
+    _ => None,
+  }
+}
+
+/// ( byte offset, line, column ) of every char of the query and of its end
+fn char_positions ( content: &str ) -> Vec<( usize, usize, usize )> 
+{
+  let mut positions: Vec<( usize, usize, usize )> = Vec::new();
+  let ( mut line, mut column ) = ( 1, 1 );
+  for ( b, c ) in content.char_indices() 
+  {
+    positions.push(( b, line, column ));
+    if c == '\n' { line += 1; column = 1; } 
+    else { column += 1; }
+  }
+  positions.push(( content.len(), line, column ));
+  positions
+}
+
+/// Span from the char at start up to the char at end
+fn span_of ( positions: &[( usize, usize, usize )], start: usize, end: usize ) -> Span 
+{
+  let ( byte, line, column ) = positions[start.min( positions.len() - 1 )];
+  Span { start: byte, end: positions[end.min( positions.len() - 1 )].0, line: line, column: column }
+}
+
+/// Token of the single char at index
+fn char_token ( token_type: SyntaxTokenType, c: char, index: usize, positions: &[( usize, usize, usize )] ) -> SyntaxToken 
+{
+  let mut token = SyntaxToken::new( token_type, c.to_string() );
+  token.span = span_of( positions, index, index + 1 );
+  token
+}
+
+/// Add a char to the word being read, index is where it is in the query
+fn push_char ( acc: &mut Vec<String>, acc_idx: &mut Vec<usize>, c: char, index: usize ) 
+{
+  if acc.len() == 0 { acc_idx.clear(); }
+  acc.push( c.to_string() );
+  acc_idx.push( index );
+}
+
+fn place_token ( token: SyntaxToken, text: &str, index: &[usize], positions: &[( usize, usize, usize )] ) -> SyntaxToken 
+{
+  place_tokens( vec![ token ], text, index, positions ).pop().unwrap()
+}
+
+/// Spans of the tokens read from text, in order, index holds where every char of text is in the query
+/// the colon of a key is part of text but not of the token, the span of a parameter starts at its $
+fn place_tokens ( 
+  tokens: Vec<SyntaxToken>, text: &str, index: &[usize], positions: &[( usize, usize, usize )] ) -> Vec<SyntaxToken> 
+{
+  let chars: Vec<char> = text.chars().collect();
+  if chars.len() == 0 || chars.len() != index.len() { return tokens; }
+
+  let mut from = 0;
+  let mut ret: Vec<SyntaxToken> = Vec::new();
+  for mut token in tokens.into_iter() 
+  {
+    let val: Vec<char> = token.val.chars().collect();
+    let start = ( from..chars.len() ).find( | s | chars[*s..].starts_with( &val )).unwrap_or( from ).min( chars.len() - 1 );
+    let last = ( start + val.len().max( 1 ) - 1 ).min( chars.len() - 1 );
+    let first = if token.token_type == SyntaxTokenType::Parameter && start > 0 && chars[start - 1] == '$' { start - 1 } else { start };
+    token.span = span_of( positions, index[first], index[last] + 1 );
+    from = last + 1;
+    ret.push( token );
+  }
+  ret
+}
+
 fn parse_syntax_token ( acc: &Vec<String>, props_mode: bool ) -> Option<SyntaxToken>
 {
   if props_mode == true { return parse_props_mode( acc ); }
 
   let word = acc.join( "" );
 
-  if word == ":" { return None; }
-
   if word.to_lowercase() == "create" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordCreate, word ));}
   if word.to_lowercase() == "graph" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordGraph, word ));}
   if word.to_lowercase() == "match" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordMatch, word ));}
@@ -155,17 +392,125 @@ fn parse_syntax_token ( acc: &Vec<String>, props_mode: bool ) -> Option<SyntaxTo
   if word.to_lowercase() == "starts" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordStarts, word ));}
   if word.to_lowercase() == "ends" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordEnds, word ));}
   if word.to_lowercase() == "with" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordWith, word ));}
-  
-  if word.len() > 0 && acc[0] == ":" 
-  {
-    return Some( SyntaxToken::new( SyntaxTokenType::PrimaryLabel, word[1..(word.len())].to_string() ));
+  if word.to_lowercase() == "set" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordSet, word ));}
+  if word.to_lowercase() == "delete" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordDelete, word ));}
+  if word.to_lowercase() == "detach" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordDetach, word ));}
+  if word.to_lowercase() == "merge" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordMerge, word ));}
+  if word.to_lowercase() == "on" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordOn, word ));}
+  if word.to_lowercase() == "remove" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordRemove, word ));}
+  if word.to_lowercase() == "and" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordAnd, word ));}
+  if word.to_lowercase() == "or" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordOr, word ));}
+  if word.to_lowercase() == "not" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordNot, word ));}
+  if word.to_lowercase() == "xor" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordXor, word ));}
+  if word.to_lowercase() == "contains" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordContains, word ));}
+  if word.to_lowercase() == "in" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordIn, word ));}
+  if word.to_lowercase() == "is" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordIs, word ));}
+  if word.to_lowercase() == "null" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordNull, word ));}
+  if word.to_lowercase() == "distinct" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordDistinct, word ));}
+  if word.to_lowercase() == "order" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordOrder, word ));}
+  if word.to_lowercase() == "by" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordBy, word ));}
+  if word.to_lowercase() == "asc" || word.to_lowercase() == "ascending" 
+  { 
+    return Some( SyntaxToken::new( SyntaxTokenType::KeywordAsc, word ));
+  }
+  if word.to_lowercase() == "desc" || word.to_lowercase() == "descending" 
+  { 
+    return Some( SyntaxToken::new( SyntaxTokenType::KeywordDesc, word ));
   }
+  if word.to_lowercase() == "skip" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordSkip, word ));}
+  if word.to_lowercase() == "limit" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordLimit, word ));}
+  if word.to_lowercase() == "optional" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordOptional, word ));}
+  if word.to_lowercase() == "unwind" { return Some( SyntaxToken::new( SyntaxTokenType::KeywordUnwind, word ));}
+  
+  if is_literal( &word ) { return Some( SyntaxToken::new( SyntaxTokenType::Value, word )); }
+  if is_parameter( &word ) { return Some( SyntaxToken::new( SyntaxTokenType::Parameter, word[1..].to_string() )); }
+  // a lone * stays a label for RETURN * and count(*)
+  if word.starts_with( '*' ) && word.len() > 1 { return Some( SyntaxToken::new( SyntaxTokenType::HopRange, word )); }
 
   if word.len() > 0 { return Some( SyntaxToken::new( SyntaxTokenType::Label, word )); }
 
   None
 }
 
+/// 1..3, ..3 and 1.. inside list[ ], the bounds either side of the range
+fn parse_range_acc ( acc: &Vec<String> ) -> Vec<SyntaxToken> 
+{
+  let word = acc.join( "" );
+  let range = word.find( ".." );
+  if range.is_none() { return parse_syntax_token( acc, false ).into_iter().collect(); }
+
+  let ( start, end ) = ( &word[..range.unwrap()], &word[range.unwrap() + 2..] );
+  let mut tokens: Vec<SyntaxToken> = parse_syntax_token( &vec![ start.to_string() ], false ).into_iter().collect();
+  tokens.push( SyntaxToken::new( SyntaxTokenType::Range, String::from( ".." )));
+  tokens.extend( parse_syntax_token( &vec![ end.to_string() ], false ));
+  tokens
+}
+
+/// Collect label text, false when the character ends the labels and still needs to be processed
+fn add_label_char ( c: &char, text: &mut String, depth: &mut u16, space: &mut bool ) -> bool 
+{
+  // after whitespace only an operator (or a label following one) continues the expression: (n:A | B)
+  let continues = *space == false || text.len() == 0 || text.ends_with( [ '&', '|', '!', ':', '(' ] );
+  match token_type( c ) 
+  {
+    TokenType::Space | TokenType::NewLine => { *space = true; }
+    TokenType::Ampersand | TokenType::Pipe => { text.push( *c ); }
+    TokenType::Char | TokenType::Colon => 
+    {
+      if continues == false { return false; }
+      text.push( *c );
+    }
+    TokenType::OpenParen => 
+    {
+      if continues == false { return false; }
+      *depth += 1;
+      text.push( *c );
+    }
+    TokenType::CloseParen => 
+    {
+      if *depth == 0 { return false; }
+      *depth -= 1;
+      text.push( *c );
+    }
+    _ => { return false; }
+  }
+  if token_type( c ) != TokenType::Space && token_type( c ) != TokenType::NewLine { *space = false; }
+  true
+}
+
+/// :A and :A:B are primary labels, anything with operators is a single label expression, *1..3 is a hop range
+fn parse_label_acc ( text: &str ) -> Vec<SyntaxToken> 
+{
+  let star = text.find( '*' );
+  if star.is_some() 
+  {
+    let mut tokens = parse_label_acc( &text[..star.unwrap()] );
+    tokens.push( SyntaxToken::new( SyntaxTokenType::HopRange, text[star.unwrap()..].to_string() ));
+    return tokens;
+  }
+
+  if text.len() == 0 { return Vec::new(); }
+  if text.chars().all( | c | c == ':' || is_label_char( &c )) 
+  {
+    return text.split( ':' )
+      .filter( | label | label.len() > 0 )
+      .map( | label | SyntaxToken::new( SyntaxTokenType::PrimaryLabel, label.to_string() ))
+      .collect();
+  }
+  vec![ SyntaxToken::new( SyntaxTokenType::LabelExpression, text.to_string() ) ]
+}
+
+/// Numbers and booleans outside of property maps (e.g. SET n.age = 38), literals that overflow are still values
+fn is_literal ( word: &str ) -> bool { parse_literal( word ).is_some() }
+
+/// Decimal digits up to an e, the hyphen is the sign of the exponent
+fn is_exponent ( acc: &Vec<String> ) -> bool 
+{
+  let word = acc.join( "" );
+  word.starts_with( | c: char | c.is_ascii_digit() || c == '.' ) && word.starts_with( "0x" ) == false && 
+    word.ends_with( [ 'e', 'E' ] ) && word[..word.len() - 1].chars().all( | c | c.is_ascii_digit() || c == '.' || c == '_' )
+}
+
 fn parse_props_mode ( acc: &Vec<String> ) -> Option<SyntaxToken> 
 {
   if acc.len() == 0 { return None; }
@@ -177,11 +522,18 @@ fn parse_props_mode ( acc: &Vec<String> ) -> Option<SyntaxToken>
     return Some( SyntaxToken::new( SyntaxTokenType::Key, word[0..(word.len() - 1)].to_string() ));
   }
   
+  if is_parameter( &word ) { return Some( SyntaxToken::new( SyntaxTokenType::Parameter, word[1..].to_string() )); }
   if word.len() > 0 { return Some( SyntaxToken::new( SyntaxTokenType::Value, word ));}
   
   None
 }
 
+/// $name or $name.key
+fn is_parameter ( word: &str ) -> bool 
+{ 
+  word.len() > 1 && word.starts_with( '$' ) && word[1..].starts_with( | c: char | c.is_alphabetic() || c == '_' )
+}
+
 fn parse_quote_acc ( acc: &Vec<String> ) -> Option<SyntaxToken> 
 {
   let word = acc.join( "" );
@@ -214,6 +566,142 @@ mod tests
     assert_eq!( tokens.len(), 3 );
   }
 
+  #[test]
+  fn test_set_tokens () 
+  {
+    let tokens: Vec<SyntaxToken> = parse_syntax( "SET p.age = 38, p += {admin: true} p:Lead" );
+    assert_eq!( tokens.len(), 12 );
+    assert_eq!( tokens.get( 0 ).unwrap().token_type, SyntaxTokenType::KeywordSet );
+    assert_eq!( tokens.get( 1 ).unwrap().val, String::from( "p.age" ));
+    assert_eq!( tokens.get( 2 ).unwrap().token_type, SyntaxTokenType::Equals );
+    assert_eq!( tokens.get( 3 ).unwrap().token_type, SyntaxTokenType::Value );
+    assert_eq!( tokens.get( 5 ).unwrap().token_type, SyntaxTokenType::PlusEquals );
+    assert_eq!( tokens.get( 11 ).unwrap().token_type, SyntaxTokenType::PrimaryLabel );
+
+    let tokens1: Vec<SyntaxToken> = parse_syntax( "SET p.name='Jennifer',p+={age:38}" );
+    assert_eq!( tokens1.len(), 10 );
+    assert_eq!( tokens1.get( 3 ).unwrap().token_type, SyntaxTokenType::StringValue );
+    assert_eq!( tokens1.get( 5 ).unwrap().token_type, SyntaxTokenType::PlusEquals );
+  }
+
+  #[test]
+  fn test_delete_tokens ()
+  {
+    let tokens: Vec<SyntaxToken> = parse_syntax( "MATCH (n) DETACH DELETE n, r" );
+    assert_eq!( tokens.len(), 8 );
+    assert_eq!( tokens.get( 4 ).unwrap().token_type, SyntaxTokenType::KeywordDetach );
+    assert_eq!( tokens.get( 5 ).unwrap().token_type, SyntaxTokenType::KeywordDelete );
+    assert_eq!( tokens.get( 6 ).unwrap().val, String::from( "n" ));
+
+    let tokens1: Vec<SyntaxToken> = parse_syntax( "delete graph devs" );
+    assert_eq!( tokens1.len(), 3 );
+    assert_eq!( tokens1.get( 1 ).unwrap().token_type, SyntaxTokenType::KeywordGraph );
+  }
+
+  #[test]
+  fn test_merge_tokens ()
+  {
+    let tokens: Vec<SyntaxToken> = parse_syntax( "MERGE (n:Person) ON CREATE SET n.new = true ON MATCH SET n.new = false" );
+    assert_eq!( tokens.len(), 17 );
+    assert_eq!( tokens.get( 0 ).unwrap().token_type, SyntaxTokenType::KeywordMerge );
+    assert_eq!( tokens.get( 5 ).unwrap().token_type, SyntaxTokenType::KeywordOn );
+    assert_eq!( tokens.get( 6 ).unwrap().token_type, SyntaxTokenType::KeywordCreate );
+    assert_eq!( tokens.get( 11 ).unwrap().token_type, SyntaxTokenType::KeywordOn );
+    assert_eq!( tokens.get( 12 ).unwrap().token_type, SyntaxTokenType::KeywordMatch );
+  }
+
+  #[test]
+  fn test_remove_tokens ()
+  {
+    let tokens: Vec<SyntaxToken> = parse_syntax( "MATCH (n:Person) REMOVE n:Lead, n.age" );
+    assert_eq!( tokens.get( 5 ).unwrap().token_type, SyntaxTokenType::KeywordRemove );
+    assert_eq!( tokens.get( 6 ).unwrap().token_type, SyntaxTokenType::Label );
+    assert_eq!( tokens.get( 7 ).unwrap().token_type, SyntaxTokenType::PrimaryLabel );
+    assert_eq!( tokens.get( 7 ).unwrap().val, String::from( "Lead" ));
+  }
+
+  #[test]
+  fn test_return_tokens ()
+  {
+    let tokens: Vec<SyntaxToken> = parse_syntax( "RETURN DISTINCT n.name AS name, n.age ORDER BY n.age DESC SKIP 1 LIMIT 5" );
+    assert_eq!( tokens.len(), 14 );
+    assert_eq!( tokens.get( 1 ).unwrap().token_type, SyntaxTokenType::KeywordDistinct );
+    assert_eq!( tokens.get( 6 ).unwrap().token_type, SyntaxTokenType::KeywordOrder );
+    assert_eq!( tokens.get( 7 ).unwrap().token_type, SyntaxTokenType::KeywordBy );
+    assert_eq!( tokens.get( 9 ).unwrap().token_type, SyntaxTokenType::KeywordDesc );
+    assert_eq!( tokens.get( 10 ).unwrap().token_type, SyntaxTokenType::KeywordSkip );
+    assert_eq!( tokens.get( 11 ).unwrap().token_type, SyntaxTokenType::Value );
+    assert_eq!( tokens.get( 12 ).unwrap().token_type, SyntaxTokenType::KeywordLimit );
+  }
+
+  #[test]
+  fn test_function_tokens ()
+  {
+    let tokens: Vec<SyntaxToken> = parse_syntax( "RETURN count(*), count(DISTINCT n.age), percentileCont(n.age, 0.5)" );
+    assert_eq!( tokens.len(), 15 );
+    assert_eq!( tokens.get( 1 ).unwrap().val, String::from( "count" ));
+    assert_eq!( tokens.get( 2 ).unwrap().token_type, SyntaxTokenType::OpenNode );
+    assert_eq!( tokens.get( 3 ).unwrap().val, String::from( "*" ));
+    assert_eq!( tokens.get( 7 ).unwrap().token_type, SyntaxTokenType::KeywordDistinct );
+    assert_eq!( tokens.get( 13 ).unwrap().token_type, SyntaxTokenType::Value );
+  }
+
+  #[test]
+  fn test_hop_range_tokens ()
+  {
+    let tokens: Vec<SyntaxToken> = parse_syntax( "MATCH p = (a)-[:KNOWS*1..3]->(b)-[r*2]-(c)-[*]-(d)" );
+    assert_eq!( tokens.len(), 33 );
+    assert_eq!( tokens.get( 1 ).unwrap().val, String::from( "p" ));
+    assert_eq!( tokens.get( 2 ).unwrap().token_type, SyntaxTokenType::Equals );
+    assert_eq!( tokens.get( 8 ).unwrap().val, String::from( "KNOWS" ));
+    assert_eq!( tokens.get( 9 ).unwrap().token_type, SyntaxTokenType::HopRange );
+    assert_eq!( tokens.get( 9 ).unwrap().val, String::from( "*1..3" ));
+    assert_eq!( tokens.get( 18 ).unwrap().val, String::from( "r" ));
+    assert_eq!( tokens.get( 19 ).unwrap().token_type, SyntaxTokenType::HopRange );
+    assert_eq!( tokens.get( 19 ).unwrap().val, String::from( "*2" ));
+    assert_eq!( tokens.get( 27 ).unwrap().token_type, SyntaxTokenType::Label );
+    assert_eq!( tokens.get( 27 ).unwrap().val, String::from( "*" ));
+  }
+
+  #[test]
+  fn test_optional_tokens ()
+  {
+    let tokens: Vec<SyntaxToken> = parse_syntax( "MATCH (p) OPTIONAL MATCH (p)-->(m)" );
+    assert_eq!( tokens.len(), 15 );
+    assert_eq!( tokens.get( 4 ).unwrap().token_type, SyntaxTokenType::KeywordOptional );
+    assert_eq!( tokens.get( 5 ).unwrap().token_type, SyntaxTokenType::KeywordMatch );
+  }
+
+  #[test]
+  fn test_where_tokens ()
+  {
+    let tokens: Vec<SyntaxToken> = parse_syntax( "WHERE n.age>=30 AND NOT n.name IN ['Bob', 'Eve'] OR n.x IS NULL" );
+    assert_eq!( tokens.len(), 17 );
+    assert_eq!( tokens.get( 1 ).unwrap().val, String::from( "n.age" ));
+    assert_eq!( tokens.get( 2 ).unwrap().token_type, SyntaxTokenType::EdgeDirection );
+    assert_eq!( tokens.get( 3 ).unwrap().token_type, SyntaxTokenType::Equals );
+    assert_eq!( tokens.get( 5 ).unwrap().token_type, SyntaxTokenType::KeywordAnd );
+    assert_eq!( tokens.get( 6 ).unwrap().token_type, SyntaxTokenType::KeywordNot );
+    assert_eq!( tokens.get( 8 ).unwrap().token_type, SyntaxTokenType::KeywordIn );
+    assert_eq!( tokens.get( 9 ).unwrap().token_type, SyntaxTokenType::OpenBracket );
+    assert_eq!( tokens.get( 12 ).unwrap().token_type, SyntaxTokenType::CloseBracket );
+    assert_eq!( tokens.get( 13 ).unwrap().token_type, SyntaxTokenType::KeywordOr );
+    assert_eq!( tokens.get( 15 ).unwrap().token_type, SyntaxTokenType::KeywordIs );
+    assert_eq!( tokens.get( 16 ).unwrap().token_type, SyntaxTokenType::KeywordNull );
+  }
+
+  #[test]
+  fn test_props_values ()
+  {
+    let tokens: Vec<SyntaxToken> = parse_syntax( "(n {name: 'Alice',age:38,admin: true})" );
+    assert_eq!( tokens.len(), 11 );
+    assert_eq!( tokens.get( 6 ).unwrap().token_type, SyntaxTokenType::Value );
+    assert_eq!( tokens.get( 6 ).unwrap().val, String::from( "38" ));
+    assert_eq!( tokens.get( 8 ).unwrap().token_type, SyntaxTokenType::Value );
+    assert_eq!( tokens.get( 8 ).unwrap().val, String::from( "true" ));
+    assert_eq!( tokens.get( 9 ).unwrap().token_type, SyntaxTokenType::CloseBrace );
+  }
+
     #[test]
   fn test_se2 () 
   {
@@ -257,6 +745,13 @@ mod tests
     // ---
     let tokens6 = parse_syntax( "CREATE GRAPH movies" );
     assert_eq!( tokens6.len(), 3 );
+
+    // ---
+    let tokens7 = parse_syntax( "(n { colors: ['Pink', 'Yellow'], offsets: [-1, 2] })" );
+    assert_eq!( tokens7.len(), 15 );
+    assert_eq!( tokens7.get( 4 ).unwrap().token_type, SyntaxTokenType::OpenBracket );
+    assert_eq!( tokens7.get( 7 ).unwrap().token_type, SyntaxTokenType::CloseBracket );
+    assert_eq!( tokens7.get( 10 ).unwrap().val, String::from( "-1" ));
   }
 
   #[test]
@@ -272,7 +767,8 @@ mod tests
   {    
     // ---
     let tokens2: Vec<SyntaxToken> = parse_syntax( "MATCH () FROM devs;" );
-    assert_eq!( tokens2.len(), 5 );
+    assert_eq!( tokens2.len(), 6 );
+    assert_eq!( tokens2.get( 5 ).unwrap().token_type, SyntaxTokenType::EndStatement );
     
     
     // ---
@@ -301,17 +797,28 @@ mod tests
 
     // -- 
     let tokens7: Vec<SyntaxToken> = parse_syntax( "MATCH (n:(TrainStation & BusStation))" );
-    assert_eq!( tokens7.len(), 9 );
+    assert_eq!( tokens7.len(), 5 );
+    assert_eq!( tokens7.get( 3 ).unwrap().token_type, SyntaxTokenType::LabelExpression );
+    assert_eq!( tokens7.get( 3 ).unwrap().val, String::from( "(TrainStation&BusStation)" ));
     
     
     // -- 
     let tokens8: Vec<SyntaxToken> = parse_syntax( "MATCH (n:(TrainStation | BusStation))" );
-    assert_eq!( tokens8.len(), 9 );
+    assert_eq!( tokens8.len(), 5 );
 
 
     // -- 
     let tokens9: Vec<SyntaxToken> = parse_syntax( "MATCH (n:(TrainStation & BusStation) | StationGroup)" );
-    assert_eq!( tokens9.len(), 11 );
+    assert_eq!( tokens9.len(), 5 );
+    assert_eq!( tokens9.get( 3 ).unwrap().val, String::from( "(TrainStation&BusStation)|StationGroup" ));
+
+    // -- 
+    let tokens9_1: Vec<SyntaxToken> = parse_syntax( "MATCH (n:!Developer {name: 'Bob'}) SET n:Lead:Admin RETURN n" );
+    assert_eq!( tokens9_1.len(), 15 );
+    assert_eq!( tokens9_1.get( 3 ).unwrap().token_type, SyntaxTokenType::LabelExpression );
+    assert_eq!( tokens9_1.get( 3 ).unwrap().val, String::from( "!Developer" ));
+    assert_eq!( tokens9_1.get( 11 ).unwrap().token_type, SyntaxTokenType::PrimaryLabel );
+    assert_eq!( tokens9_1.get( 12 ).unwrap().val, String::from( "Admin" ));
 
 
     // -- 
@@ -396,4 +903,111 @@ mod tests
     //for token in tokens4.iter() { println!( "{:?}", token ); }
     assert_eq!( tokens4.len(), 12 );
   }
+
+  #[test]
+  fn test_list_syntax () 
+  {
+    let tokens = parse_syntax( "UNWIND [1, 2] AS x MATCH (a)-[r]->(b) RETURN a.tags[0], x[1..3], nodes(p)[-1], [y IN a.tags | y]" );
+    let types: Vec<SyntaxTokenType> = tokens.iter().map( | t | t.token_type.clone() ).collect();
+    assert_eq!( types[0], SyntaxTokenType::KeywordUnwind );
+    assert_eq!( types[1], SyntaxTokenType::OpenBracket );
+    assert_eq!( types[4], SyntaxTokenType::CloseBracket );
+    assert_eq!( types[12], SyntaxTokenType::OpenEdge );
+    assert_eq!( types[14], SyntaxTokenType::CloseEdge );
+
+    // a.tags[0]
+    assert_eq!( types[21], SyntaxTokenType::Label );
+    assert_eq!( types[22], SyntaxTokenType::OpenIndex );
+    assert_eq!( types[24], SyntaxTokenType::CloseBracket );
+
+    // x[1..3]
+    assert_eq!( types[26], SyntaxTokenType::OpenIndex );
+    assert_eq!( tokens[27].val, String::from( "1" ));
+    assert_eq!( types[28], SyntaxTokenType::Range );
+    assert_eq!( tokens[29].val, String::from( "3" ));
+
+    // nodes(p)[-1]
+    assert_eq!( types[35], SyntaxTokenType::OpenIndex );
+
+    // [y IN a.tags | y]
+    assert_eq!( types[39], SyntaxTokenType::OpenBracket );
+    assert_eq!( types[41], SyntaxTokenType::KeywordIn );
+    assert_eq!( types.len(), 46 );
+  }
+
+  #[test]
+  fn test_parameter_syntax () 
+  {
+    let tokens = parse_syntax( "MATCH (n {name: $name}) WHERE n.age > $min.age RETURN n LIMIT $limit" );
+    assert_eq!( tokens[5].token_type, SyntaxTokenType::Parameter );
+    assert_eq!( tokens[5].val, String::from( "name" ));
+    assert_eq!( tokens[11].token_type, SyntaxTokenType::Parameter );
+    assert_eq!( tokens[11].val, String::from( "min.age" ));
+    assert_eq!( tokens[15].token_type, SyntaxTokenType::Parameter );
+    assert_eq!( tokens[15].val, String::from( "limit" ));
+
+    // a lone $ or $ before a digit is not a parameter
+    assert_eq!( parse_syntax( "RETURN $" )[1].token_type, SyntaxTokenType::Label );
+    assert_eq!( parse_syntax( "RETURN $1" )[1].token_type, SyntaxTokenType::Label );
+  }
+
+  #[test]
+  fn test_token_spans () 
+  {
+    let query = "MATCH (n:A | B {name: 'Bob'})\n  WHERE n.age >= $min RETURN n";
+    let tokens = parse_syntax( query );
+    let spans: Vec<( &str, usize, usize )> = tokens.iter()
+      .map( | t | ( &query[t.span.start..t.span.end], t.span.line, t.span.column )).collect();
+    assert_eq!( spans[0], ( "MATCH", 1, 1 ));
+    assert_eq!( spans[3], ( "A | B", 1, 10 ));
+    assert_eq!( spans[6], ( "Bob", 1, 24 ));
+    assert_eq!( spans[9], ( "WHERE", 2, 3 ));
+    assert_eq!( spans[13], ( "$min", 2, 18 ));
+    assert_eq!( spans[15], ( "n", 2, 30 ));
+
+    // comment markers inside strings do not move the spans of the tokens after them
+    let query1 = "MATCH (n {url: '//', note: '/* x */'})\n  RETURN n.url, x";
+    let tokens1 = parse_syntax( query1 );
+    assert_eq!( tokens1.iter().all( | t | &query1[t.span.start..t.span.end] == t.val ), true );
+    let last = tokens1.last().unwrap();
+    assert_eq!(( last.val.as_str(), last.span.line, last.span.column ), ( "x", 2, 17 ));
+  }
+
+  #[test]
+  fn test_comment_syntax () 
+  {
+    let query = "MATCH (n) // all nodes\n/* of devs; */ FROM devs; RETURN n.url // 'http://x'";
+    let tokens = parse_syntax( query );
+    let vals: Vec<( SyntaxTokenType, String )> = tokens.iter().map( | t | ( t.token_type.clone(), t.val.clone() )).collect();
+    assert_eq!( vals.len(), 9 );
+    assert_eq!( vals[4], ( SyntaxTokenType::KeywordFrom, String::from( "FROM" )));
+    assert_eq!( vals[6], ( SyntaxTokenType::EndStatement, String::from( ";" )));
+    assert_eq!( vals[8], ( SyntaxTokenType::Label, String::from( "n.url" )));
+    assert_eq!(( tokens[4].span.line, tokens[4].span.column ), ( 2, 16 ));
+
+    // comment markers inside strings are text, a comment ends the word before it
+    let tokens1 = parse_syntax( "MATCH (n {url: 'http://x/*y*/'}) RETURN n/* n */" );
+    assert_eq!( tokens1[5].val, String::from( "http://x/*y*/" ));
+    assert_eq!( tokens1[9].val, String::from( "n" ));
+    assert_eq!( tokens1.len(), 10 );
+
+    // an unclosed block comment ends the tokens with an error token at its /*
+    let tokens2 = parse_syntax( "MATCH (n) /* RETURN n" );
+    assert_eq!( tokens2.len(), 5 );
+    assert_eq!( tokens2[4].token_type, SyntaxTokenType::UnclosedComment );
+    assert_eq!(( tokens2[4].span.start, tokens2[4].span.end, tokens2[4].span.column ), ( 10, 12, 11 ));
+  }
+
+  #[test]
+  fn test_literal_syntax () 
+  {
+    let tokens = parse_syntax( "WHERE n.x > 1.5e-3 AND n.y = 300u8 AND n.z = -0xFF RETURN x" );
+    let vals: Vec<( SyntaxTokenType, String )> = tokens.iter().map( | t | ( t.token_type.clone(), t.val.clone() )).collect();
+    assert_eq!( vals[3], ( SyntaxTokenType::Value, String::from( "1.5e-3" )));
+    assert_eq!( vals[7], ( SyntaxTokenType::Value, String::from( "300u8" )));
+    assert_eq!( vals[11], ( SyntaxTokenType::EdgeDirection, String::from( "-" )));
+    assert_eq!( vals[12], ( SyntaxTokenType::Value, String::from( "0xFF" )));
+    assert_eq!( vals[14], ( SyntaxTokenType::Label, String::from( "x" )));
+    assert_eq!( vals.len(), 15 );
+  }
 }
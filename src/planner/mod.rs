@@ -1,12 +1,18 @@
-use std::collections::VecDeque;
+use crate::ast::error::QueryError;
+use crate::ast::{ 
+  parse_query, split_statements, Clause, Count, NodePattern, Pattern, Projection, Query, RelationshipPattern, RemoveItem, SetItem };
 use crate::cmd::{ 
-  BracketStatement, CreateStatement, EdgeStatement, MatchStatement, NodeRefStatement, NodeStatement, ParenStatement, 
-  ReadClause };
+  CreateStatement, DeleteStatement, EdgeStatement, FromClause, MatchStatement, MergeAction, BracketStatement, 
+  NodeRefStatement, NodeStatement, ParenStatement, QueryStage, ReadClause, RemoveStatement, ReturnClause, ReturnItem, SetStatement, 
+  SetType, SortItem, UnwindClause, WithClause };
+use crate::cmd::predicate::{ param_value, Operand, Predicate };
 use crate::cmd::transaction::Transaction;
-use crate::common::DirectionType;
+use crate::common::NEProperty;
+use crate::common::kvps::{ KeyValString };
 use crate::parser::{ parse_syntax };
-use crate::tokenize::{ SyntaxToken, SyntaxTokenType };
 use crate::datagramv2::internal_grams::{ Label, UUID };
+use crate::result_set::Value;
+use crate::tokenize::{ Span, SyntaxToken };
 use crate::utils::cons_uuid;
 
 /* @version 0.3.0 */
@@ -21,358 +27,754 @@ use crate::utils::cons_uuid;
 
 pub fn process_query ( query: &str, build_id: UUID, nickname: Label ) -> Transaction 
 {
-  let mut transaction_builder = TransactionBuilder::new(); 
-  let tokens = parse_syntax( query );
-  for token in tokens.into_iter() { transaction_builder.add_token( token ); }
-  
+  process_query_with_params( query, &Vec::new(), build_id, nickname )
+}
+
+/// Plan a query with $name parameters bound to typed values, values never become part of the query text
+pub fn process_query_with_params ( 
+  query: &str, params: &Vec<( String, Value )>, build_id: UUID, nickname: Label ) -> Transaction 
+{
+  process_tokens( &parse_syntax( query ), params, build_id, nickname )
+}
+
+/// Plan every ;-separated statement of a script in order, with the span of the statement in the script
+pub fn process_script ( 
+  script: &str, params: &Vec<( String, Value )>, build_id: UUID, nickname: Label ) -> Vec<( Span, Transaction )> 
+{
+  let mut transactions: Vec<( Span, Transaction )> = Vec::new();
+  for tokens in split_statements( &parse_syntax( script )).iter() 
+  {
+    let span = tokens[0].span.to( &tokens[tokens.len() - 1].span );
+    transactions.push(( span, process_tokens( tokens, params, build_id.clone(), nickname.clone() )));
+  }
+  transactions
+}
+
+fn process_tokens ( tokens: &Vec<SyntaxToken>, params: &Vec<( String, Value )>, build_id: UUID, nickname: Label ) -> Transaction 
+{
+  let mut transaction_builder = TransactionBuilder::new( params.clone() ); 
+  let build_res = parse_query( tokens ).and_then( | query | transaction_builder.add_query( &query ));
+  if build_res.is_err() 
+  {
+    let mut transaction = Transaction::new( build_id, nickname, 0 );
+    let err = build_res.unwrap_err();
+    transaction.err_state = Some( err.message.clone() );
+    transaction.error = Some( err );
+    return transaction;
+  }
   transaction_builder.close( build_id, nickname )
 }
 
 
 // ---------------------------------------------------------------------------------------------------------------------
 /* 
+  Lowers the clauses of an ast::Query into a Transaction. Every statement takes the next query order from 
+  take_orders(), a pattern takes one order per node and relationship and each element's order is its position 
+  in the pattern.
+
   TransactionBuilder
     :: new()
+    :: take_orders()
+    :: add_query()
+        :: add_clause()
+            :: add_match()
+            :: add_merge()
+                :: add_read_patterns()
+                    :: pattern_orders()
+                    :: element_order()
+                    :: check_shortest()
+                    :: lower_match_node()
+                    :: lower_match_relationship()
+            :: add_create()
+                :: check_write_pattern()
+                :: add_create_node()
+                :: add_create_relationship()
+            :: add_with()
+                :: take_read_clause()
+                :: lower_projection()
+            :: add_unwind()
+            :: add_set()
+            :: add_remove()
+            :: add_delete()
+            :: add_return()
+                :: lower_projection()
+                    :: lower_count()
     :: close()
-    :: add_token()
-        :: add_match_token()
-        :: add_create()
-        :: add_open_node()
-          :: close_statement()
-        :: add_close_node()
-        :: add_x_label()
-            :: try_update_paren_statements()
-            :: try_update_bracket_statements()
-            :: try_update_match_statements()
-        :: find_open_bracket_statement()
-        :: try_update_bracket_statements()
-        :: add_bracket()
-
-    :: close_statement()
-        :: find_open_match_statement()
-        :: find_open_paren_statement()
-        :: find_open_bracket_statement
+
+  property_entries()
+  lower_properties()
+
+  Errors with a place in the query point at the pattern, node, relationship or value they are about, the others
+  point at the keyword of their clause.
 */
 #[derive( Debug )]
 pub struct TransactionBuilder 
 { 
-  pub current_order: u16,
-  pub err_state: Option<String>,
+  pub current_order: u16, // next free query order, taken with take_orders()
+  pub params: Vec<( String, Value )>, // $name values, bound into the plan as it is built
 
   // create
   pub create_statement: Option<CreateStatement>,
-
-  // nodes and edges
-  pub paren_statements: VecDeque<ParenStatement>,
-  pub bracket_statements: VecDeque<BracketStatement>,
-
-  // read clause
-  pub read_clause_order: Option<u16>,
-  pub match_statements: VecDeque<MatchStatement>
+  pub node_statements: Vec<NodeStatement>,
+  pub node_ref_statements: Vec<NodeRefStatement>,
+  pub edge_statements: Vec<EdgeStatement>,
+
+  // read clause and stages
+  pub read_clause: Option<ReadClause>, // MATCH clauses since the last WITH or UNWIND
+  pub stages: Vec<QueryStage>, // completed stages, in query order
+  pub merge: bool, // read_clause holds the MERGE pattern
+
+  // writes
+  pub set_statements: Vec<SetStatement>,
+  pub remove_statements: Vec<RemoveStatement>,
+  pub delete_statement: Option<DeleteStatement>,
+
+  pub from_clause: Option<FromClause>,
+  pub return_clause: Option<ReturnClause>,
 }
 
 impl TransactionBuilder
 {
-  pub fn new () -> TransactionBuilder 
+  pub fn new ( params: Vec<( String, Value )> ) -> TransactionBuilder 
   { 
     TransactionBuilder 
     { 
       current_order: 0, 
-      err_state: None, 
-      
+      params: params,
+
       create_statement: None,
+      node_statements: Vec::new(),
+      node_ref_statements: Vec::new(),
+      edge_statements: Vec::new(),
 
-      paren_statements: VecDeque::new(),
-      bracket_statements: VecDeque::new(),
+      read_clause: None,
+      stages: Vec::new(),
+      merge: false,
 
-      read_clause_order: None, 
-      match_statements: VecDeque::new() 
+      set_statements: Vec::new(),
+      remove_statements: Vec::new(),
+      delete_statement: None,
+
+      from_clause: None,
+      return_clause: None,
     }
   }
 
-  pub fn close ( &mut self, build_id: UUID, nickname: Label ) -> Transaction 
+  /// Errors of a clause without a place of their own point at its keyword
+  pub fn add_query ( &mut self, query: &Query ) -> Result<bool, QueryError> 
   {
-    let mut transaction = Transaction::new( build_id, nickname, self.current_order );
-    
-    if self.create_statement.is_some() 
+    if query.graph.is_some() { self.from_clause = Some( FromClause::new( query.graph.clone().unwrap() )); }
+
+    // every $name must be defined, the error points at the first one that is not
+    for ( name, span ) in query.parameters.iter() 
+    {
+      param_value( name, &self.params ).map_err( | e | lowering_error( e, *span, None, Some( format!( "`${}`", name ))))?;
+    }
+    for ( i, clause ) in query.clauses.iter().enumerate() 
+    { 
+      self.add_clause( clause ).map_err( | err | err.or_at( &query.keywords[i] ))?;
+    }
+    Ok( true )
+  }
+
+  pub fn add_clause ( &mut self, clause: &Clause ) -> Result<bool, QueryError> 
+  {
+    match clause 
     {
-      transaction.create_statement = Some( self.create_statement.as_ref().unwrap().clone() );
+      Clause::Match { optional, patterns, predicate } => self.add_match( *optional, patterns, predicate ),
+      Clause::Merge { patterns, actions } => self.add_merge( patterns, actions ),
+      Clause::Create { graph, patterns } => self.add_create( graph, patterns ),
+      Clause::With { projection, predicate } => self.add_with( projection, predicate ),
+      Clause::Unwind { operand, variable } => self.add_unwind( operand, variable ),
+      Clause::Set( items ) => self.add_set( items, None ),
+      Clause::Remove( items ) => self.add_remove( items ),
+      Clause::Delete { detach, graph, variables } => self.add_delete( *detach, graph, variables ),
+      Clause::Return( projection ) => self.add_return( projection ),
     }
+  }
+
+  pub fn close ( &mut self, build_id: UUID, nickname: Label ) -> Transaction 
+  {
+    let mut transaction = Transaction::new( build_id, nickname, self.current_order );
+    self.take_read_clause();
 
-    if self.match_statements.len() > 0 && self.read_clause_order.is_some()
+    // the first stage is read unless the query starts with UNWIND, WITH requires MATCH before it
+    let mut stages = std::mem::take( &mut self.stages );
+    if stages.len() > 0 
     {
-      let mut read_clause = ReadClause::new( self.read_clause_order.unwrap() );
-      while self.match_statements.len() > 0 
+      match stages.remove( 0 ) 
       {
-        let stmt_opt = self.match_statements.pop_front();
-        if stmt_opt.is_some() { read_clause.add_match_statement( stmt_opt.unwrap() ); }
+        QueryStage::Read( read_clause ) => { transaction.read_clause = Some( read_clause ); }
+        stage => { stages.insert( 0, stage ); }
       }
-      transaction.read_clause = Some( read_clause );
     }
+    transaction.stages = stages;
+
+    transaction.create_statement = self.create_statement.take();
+    transaction.node_statements = std::mem::take( &mut self.node_statements );
+    transaction.node_ref_statements = std::mem::take( &mut self.node_ref_statements );
+    transaction.edge_statements = std::mem::take( &mut self.edge_statements );
+    transaction.set_statements = std::mem::take( &mut self.set_statements );
+    transaction.remove_statements = std::mem::take( &mut self.remove_statements );
+    transaction.delete_statement = self.delete_statement.take();
+    transaction.merge = self.merge;
+    transaction.from_clause = self.from_clause.take();
+    transaction.return_clause = self.return_clause.take();
+    transaction
+  }
+}
 
-    for paren in self.paren_statements.iter() 
+impl TransactionBuilder 
+{
+  /// Reserve count query orders and return the first. The executor works by query order: the writers find the 
+  /// nodes of an edge at its order - 1 and + 1, the readers take the nearest nodes before and after a relationship 
+  /// as its ends, stages and OPTIONAL MATCH clauses are order ranges
+  fn take_orders ( &mut self, count: u16 ) -> u16 
+  {
+    let first = self.current_order;
+    self.current_order += count;
+    first
+  }
+
+  /// MATCH or WITH came before, later clauses can refer to their variables
+  pub fn is_reading ( &self ) -> bool { self.read_clause.is_some() || self.stages.len() > 0 }
+
+  /// OPTIONAL MATCH keeps rows its pattern does not match, with the pattern variables null
+  pub fn add_match ( &mut self, optional: bool, patterns: &Vec<Pattern>, predicate: &Option<Predicate> ) -> Result<bool, QueryError> 
+  {
+    if self.merge && optional { return Err( QueryError::new( String::from( "Syntax Error: Optional" ))); }
+    if self.merge { return Err( QueryError::new( String::from( "Syntax Error: Match" ))); }
+    self.add_read_patterns( optional, patterns, predicate )
+  }
+
+  /// MERGE matches its pattern like MATCH, it cannot be combined with MATCH or CREATE
+  pub fn add_merge ( &mut self, patterns: &Vec<Pattern>, actions: &Vec<( MergeAction, Vec<SetItem> )> ) -> Result<bool, QueryError> 
+  {
+    if self.merge || self.is_reading() || self.create_statement.is_some() { return Err( QueryError::new( String::from( "Syntax Error: Merge" ))); }
+    for pattern in patterns.iter() { check_write_pattern( pattern )?; }
+
+    self.add_read_patterns( false, patterns, &None )?;
+    self.merge = true;
+    for ( action, items ) in actions.iter() { self.add_set( items, Some( action.clone() ))?; }
+    Ok( true )
+  }
+
+  /// Patterns of a MATCH or MERGE join the ReadClause of the current stage
+  fn add_read_patterns ( &mut self, optional: bool, patterns: &Vec<Pattern>, predicate: &Option<Predicate> ) -> Result<bool, QueryError> 
+  {
+    if self.read_clause.is_none() 
+    { 
+      let order = self.take_orders( 1 );
+      self.read_clause = Some( ReadClause::new( order ));
+    }
+    let mut read_clause = self.read_clause.take().unwrap();
+    let start = self.current_order;
+    read_clause.clauses.push(( start, optional ));
+
+    // inline WHERE in the order they are written, then the WHERE of the clause
+    let mut predicates: Vec<Predicate> = Vec::new();
+    for pattern in patterns.iter() 
     {
-      if paren.is_ref() == true 
-      {
-        transaction.node_ref_statements.push( 
-          NodeRefStatement::new( 
-            paren.order, 
-            None, 
-            paren.transaction_label.clone().unwrap() ));   
+      let first = self.take_orders( pattern_orders( pattern ));
+      if pattern.variable.is_some() { read_clause.path_variables.push(( pattern.variable.clone().unwrap(), first )); }
+      if pattern.shortest.is_some() 
+      { 
+        check_shortest( pattern )?;
+        read_clause.shortest_paths.push(( first, pattern.shortest.unwrap() )); 
       }
-      else 
+
+      read_clause.add_match_statement( self.lower_match_node( first, &pattern.nodes[0] )? );
+      if pattern.nodes[0].predicate.is_some() { predicates.push( pattern.nodes[0].predicate.clone().unwrap() ); }
+      for ( j, rel ) in pattern.relationships.iter().enumerate() 
       {
-        transaction.node_statements.push( 
-          NodeStatement::new( 
-            cons_uuid(), 
-            paren.order, 
-            paren.transaction_label.clone(), 
-            paren.primary_label.clone().unwrap() ));
+        read_clause.add_bracket_statement( self.lower_match_relationship( element_order( first, 2 * j + 1 ), rel )? );
+        if rel.predicate.is_some() { predicates.push( rel.predicate.clone().unwrap() ); }
+
+        let node = &pattern.nodes[j + 1];
+        read_clause.add_match_statement( self.lower_match_node( element_order( first, 2 * j + 2 ), node )? );
+        if node.predicate.is_some() { predicates.push( node.predicate.clone().unwrap() ); }
       }
     }
-    
-    for bracket in self.bracket_statements.iter() 
+    if predicate.is_some() { predicates.push( predicate.clone().unwrap() ); }
+
+    // WHERE of an OPTIONAL MATCH only decides whether the optional pattern matched
+    for mut predicate in predicates.into_iter() 
     {
-      transaction.edge_statements.push( 
-        EdgeStatement::new(
-          cons_uuid(), 
-          bracket.order, 
-          bracket.transaction_label.clone(), 
-          bracket.primary_label.clone().unwrap() ));
+      predicate.bind_params( &self.params ).map_err( QueryError::new )?;
+      if optional { read_clause.optional_predicates.push(( start, predicate )); }
+      else { read_clause.predicates.push( predicate ); }
     }
-
-    if self.err_state.is_some() { transaction.err_state = Some( self.err_state.as_ref().unwrap().clone() ); }
-    transaction
+    self.read_clause = Some( read_clause );
+    Ok( true )
   }
 
-  pub fn add_token ( &mut self, token: SyntaxToken ) 
+  /// Nodes are matched by properties known up front, literals and parameters, strings are also kept as KeyValStrings
+  fn lower_match_node ( &self, order: u16, node: &NodePattern ) -> Result<MatchStatement, QueryError> 
   {
-    match token.token_type 
+    let mut stmt = MatchStatement::new( order, false, node.variable.clone(), node.labels.first().cloned() );
+    stmt.labels = node.labels.iter().skip( 1 ).cloned().collect();
+    stmt.label_expression = node.label_expression.clone();
+    let properties = property_entries( &node.properties, &node.properties_param, &self.params )?;
+    stmt.labels_complete = properties.len() > 0;
+    stmt.kvps_complete = properties.len() > 0;
+    for ( key, value, span ) in properties.iter() 
     {
-      SyntaxTokenType::KeywordMatch => { self.add_match_token(); }
-      SyntaxTokenType::KeywordCreate => { self.add_create_token(); }
-      SyntaxTokenType::OpenNode => { self.add_open_node( token ); }
-      SyntaxTokenType::CloseNode => { self.add_close_node( &token ); }
-      SyntaxTokenType::Label => { self.add_x_label( token ); }
-      SyntaxTokenType::PrimaryLabel => { self.add_x_label( token ); }
-      SyntaxTokenType::OpenBrace => { self.add_open_brace( &token ); }
-      SyntaxTokenType::CloseBrace => { self.add_close_brace( &token ); }
-      SyntaxTokenType::Key => { self.add_key( &token); }
-      SyntaxTokenType::StringValue => { self.add_string_value( &token ); }
-      SyntaxTokenType::EdgeDirection => 
+      match value 
       {
-        if self.find_open_bracket_statement().is_some() { self.try_update_bracket_statements( &token ); }
-        else { self.add_open_bracket( &token ); }
-      },
-      _ => {}
+        Operand::Literal( literal ) if literal.as_str().is_some() => 
+        { 
+          stmt.kv_str.push( KeyValString::new( key.clone(), Some( literal.as_str().unwrap().to_string() ))); 
+        }
+        Operand::Literal( _ ) | Operand::Parameter( _ ) => {}
+        Operand::List( _ ) => 
+        { 
+          return Err( lowering_error( String::from( "Syntax Error: Match Statement" ), *span, Some( "a literal or a parameter" ), 
+            Some( String::from( "a list" )))); 
+        }
+        _ => 
+        { 
+          let text = operand_text( value );
+          return Err( lowering_error( format!( "Syntax Error: Property Value `{}`", text ), *span, Some( "a literal or a parameter" ), 
+            Some( format!( "`{}`", text )))); 
+        }
+      }
     }
+    stmt.properties = lower_properties( &properties, &self.params )?.0;
+    Ok( stmt )
   }
-}
 
-impl TransactionBuilder 
-{
-  pub fn add_match_token ( &mut self ) 
+  /// Relationships are matched by properties known up front like nodes
+  fn lower_match_relationship ( &self, order: u16, rel: &RelationshipPattern ) -> Result<BracketStatement, QueryError> 
   {
-    if self.read_clause_order.is_none() 
+    let mut stmt = BracketStatement::new( order, false, rel.variable.clone(), rel.rel_type.clone(), rel.direction.clone() );
+    stmt.hops = rel.hops.clone();
+    let refer = rel.properties.iter().find( | ( _, v, _ ) | matches!( v, Operand::Variable( _ ) | Operand::Property( _, _ )));
+    if refer.is_some() 
     { 
-      self.read_clause_order = Some( self.current_order );
-      self.current_order += 1;
+      let ( key, value, span ) = refer.unwrap();
+      return Err( lowering_error( format!( "Syntax Error: Property Value `{}`", key ), *span, Some( "a literal or a parameter" ), 
+        Some( format!( "`{}`", operand_text( value ))))); 
     }
-    self.match_statements.push_back( MatchStatement::new( self.current_order, true, None, None ));
-    self.current_order += 1;
+    let properties = property_entries( &rel.properties, &rel.properties_param, &self.params )?;
+    stmt.properties = lower_properties( &properties, &self.params )?.0;
+    Ok( stmt )
   }
 
-  pub fn add_create_token ( &mut self ) 
+  /// Nodes with a label are created, nodes without one refer to a node created before them. 
+  /// CREATE (a) CREATE (b) creates both in one statement, only the first CREATE can name the graph
+  pub fn add_create ( &mut self, graph: &Option<String>, patterns: &Vec<Pattern> ) -> Result<bool, QueryError> 
   {
-    if self.create_statement.is_none() == true 
+    if self.merge { return Err( QueryError::new( String::from( "Syntax Error: Create" ))); }
+    if self.create_statement.is_some() && graph.is_some() 
+    { 
+      let mut err = QueryError::new( String::from( "Syntax Error: Create" ));
+      err.expected = Some( String::from( "a pattern, only the first `CREATE` names the graph" ));
+      return Err( err ); 
+    }
+    if self.create_statement.is_none() 
     {
-      self.create_statement = Some( CreateStatement::new( self.current_order, None ));
-      self.current_order += 1;
-      return;
+      let mut create_statement = CreateStatement::new( self.take_orders( 1 ), graph.clone() );
+      create_statement.is_open = false;
+      self.create_statement = Some( create_statement );
     }
-    self.err_state = Some( String::from( "Syntax Error: Create" ));
-  }
 
-  pub fn add_open_node ( &mut self, token: SyntaxToken ) 
-  { 
-    if self.try_update_match_statements( &token ) == true 
+    for pattern in patterns.iter() 
     {
-      self.current_order += 1;
-      return;
+      check_write_pattern( pattern )?;
+      let first = self.take_orders( pattern_orders( pattern ));
+      self.add_create_node( first, &pattern.nodes[0] )?;
+      for ( i, rel ) in pattern.relationships.iter().enumerate() 
+      {
+        self.add_create_relationship( element_order( first, 2 * i + 1 ), rel )?;
+        self.add_create_node( element_order( first, 2 * i + 2 ), &pattern.nodes[i + 1] )?;
+      }
     }
-
-    self.close_statement( &token );
-    self.paren_statements.push_back( ParenStatement::new( self.current_order, true, None, None ));
-    self.current_order += 1; 
+    Ok( true )
   }
 
-  pub fn close_statement ( &mut self, token: &SyntaxToken ) 
+  fn add_create_node ( &mut self, order: u16, node: &NodePattern ) -> Result<bool, QueryError> 
   {
-    if self.create_statement.is_some() == true && self.create_statement.as_ref().unwrap().is_open == true
-    {
-      self.create_statement.as_mut().unwrap().is_open = false;
-      return;
+    if node.label_expression.is_some() 
+    { 
+      return Err( lowering_error( String::from( "Syntax Error: Label" ), node.span, Some( "labels like `:A:B`" ), 
+        Some( String::from( "a label expression" )))); 
     }
 
-    if self.try_update_match_statements( &token ) == true { return; }
+    let mut paren = ParenStatement::new( order, false, node.variable.clone(), node.labels.first().cloned() );
+    paren.labels = node.labels.iter().skip( 1 ).cloned().collect();
+    let entries = property_entries( &node.properties, &node.properties_param, &self.params )?;
+    let ( properties, property_refs ) = lower_properties( &entries, &self.params )?;
+    paren.properties = properties;
+    paren.property_refs = property_refs;
 
-    let paren_res = self.find_open_paren_statement();
-    if paren_res.is_some() 
+    if paren.is_ref() == false 
     {
-      self.paren_statements.get_mut( paren_res.unwrap() ).unwrap().is_open = false;
-      return;
+      self.node_statements.push( paren.to_node_statement().map_err( QueryError::new )? );
+      return Ok( true );
+    }
+    // () has nothing to refer to
+    if paren.is_empty() 
+    { 
+      return Err( lowering_error( String::from( "Syntax Error: Paren Statement" ), node.span, Some( "a variable or a label" ), 
+        Some( String::from( "`()`" )))); 
     }
+    self.node_ref_statements.push( paren.to_node_ref_statement( None ).map_err( QueryError::new )? );
+    Ok( true )
+  }
 
-    let bracket_res = self.find_open_bracket_statement();
-    if bracket_res.is_some() 
-    {
-      self.bracket_statements.get_mut( bracket_res.unwrap() ).unwrap().is_open = false;
-      return;
+  fn add_create_relationship ( &mut self, order: u16, rel: &RelationshipPattern ) -> Result<bool, QueryError> 
+  {
+    if rel.rel_type.is_none() 
+    { 
+      return Err( lowering_error( String::from( "Syntax Error: Edge requires a type" ), rel.span, Some( "a type like `[:KNOWS]`" ), None )); 
+    }
+    if rel.hops.is_some() 
+    { 
+      let hops = rel.hops.as_ref().unwrap();
+      return Err( lowering_error( format!( "Syntax Error: Hop Range `{}`", hops ), rel.span, Some( "a single relationship" ), 
+        Some( format!( "`{}`", hops )))); 
     }
+
+    let entries = property_entries( &rel.properties, &rel.properties_param, &self.params )?;
+    let ( properties, property_refs ) = lower_properties( &entries, &self.params )?;
+    let mut edge_stmt = EdgeStatement::new( cons_uuid(), order, rel.variable.clone(), rel.rel_type.clone().unwrap() );
+    edge_stmt.direction = rel.direction.clone();
+    edge_stmt.properties = properties;
+    edge_stmt.property_refs = property_refs;
+    self.edge_statements.push( edge_stmt );
+    Ok( true )
   }
-  
-  pub fn add_close_node ( &mut self, token: &SyntaxToken ) { self.close_statement( token ); }
-  
-  pub fn add_x_label ( &mut self, token: SyntaxToken ) 
+
+  /// WITH ends the stage of the MATCH clauses before it, expressions other than a variable need an alias
+  pub fn add_with ( &mut self, projection: &Projection, predicate: &Option<Predicate> ) -> Result<bool, QueryError> 
   {
-    if self.try_update_paren_statements( &token ) == true { return; }
-    if self.try_update_bracket_statements( &token ) == true { return; }
-    if self.try_update_match_statements( &token ) == true { return; }
+    if self.merge || self.is_reading() == false || self.return_clause.is_some() { return Err( QueryError::new( String::from( "Syntax Error: With" ))); }
+    self.take_read_clause();
 
-    if self.create_statement.is_some() && self.create_statement.as_ref().unwrap().is_open == true 
+    for item in projection.items.iter() 
     {
-      if token.token_type == SyntaxTokenType::Label 
+      if item.alias.is_none() && ( item.aggregate.is_some() || matches!( item.operand, Operand::Variable( _ )) == false ) 
       {
-        let new_create = CreateStatement::from( &self.create_statement.as_ref().unwrap(), token );
-        if new_create.is_ok() 
-        {
-          self.create_statement = Some( new_create.unwrap() );
-          return;
-        }
+        return Err( lowering_error( format!( "Syntax Error: With `{}` requires an alias", item.expression ), item.span, 
+          Some( "`AS` and an alias" ), None ));
       }
-      self.err_state = Some( String::from( "Syntax Error: Create Statement Label" ));
-      return;
     }
+    let mut with_clause = WithClause::new( self.take_orders( 1 ));
+    with_clause.projection = lower_projection( projection, &self.params )?;
 
-    self.err_state = Some( String::from( "Syntax Error: Label" ));
+    // WHERE directly after WITH filters the projected rows
+    if predicate.is_some() 
+    {
+      let mut predicate = predicate.clone().unwrap();
+      predicate.bind_params( &self.params ).map_err( QueryError::new )?;
+      with_clause.predicates.push( predicate );
+    }
+    self.stages.push( QueryStage::With( with_clause ));
+    Ok( true )
   }
-  
-  pub fn add_open_bracket ( &mut self, token: &SyntaxToken ) 
+
+  /// ReadClause of the MATCH clauses since the last WITH or UNWIND becomes a stage
+  pub fn take_read_clause ( &mut self ) 
   {
-    self.bracket_statements.push_back( 
-      BracketStatement::new( self.current_order, true, None, None, TransactionBuilder::token_dir_type( token )));
-    self.current_order += 1;
+    let read_opt = self.read_clause.take();
+    if read_opt.is_some() { self.stages.push( QueryStage::Read( read_opt.unwrap() )); }
   }
 
-  pub fn add_open_brace ( &mut self, token: &SyntaxToken ) { self.try_update_match_statements( token ); }
-  pub fn add_close_brace ( &mut self, token: &SyntaxToken ) { self.try_update_match_statements( token );  }
-  pub fn add_key ( &mut self, token: &SyntaxToken ) { self.try_update_match_statements( token );  }
-  pub fn add_string_value ( &mut self, token: &SyntaxToken ) { self.try_update_match_statements( token );  }
-
-  pub fn try_update_paren_statements ( &mut self, token: &SyntaxToken ) -> bool 
+  /// UNWIND ends the stage before it like WITH
+  pub fn add_unwind ( &mut self, operand: &Operand, variable: &String ) -> Result<bool, QueryError> 
   {
-    let paren_res = self.find_open_paren_statement();
-    if paren_res.is_some() 
+    if self.merge || self.create_statement.is_some() || self.return_clause.is_some() 
     {
-      let remove_res = self.paren_statements.remove( *paren_res.as_ref().unwrap() );
-      if remove_res.is_some() 
-      {
-        let replace_res = ParenStatement::from( remove_res.unwrap(), token );
-        if replace_res.is_ok() 
-        {
-          self.paren_statements.insert( *paren_res.as_ref().unwrap(), replace_res.unwrap() );
-          return true;
-        }
-      }
+      return Err( QueryError::new( String::from( "Syntax Error: Unwind" )));
     }
-    false
+    self.take_read_clause();
+
+    let mut operand = operand.clone();
+    operand.bind_params( &self.params ).map_err( QueryError::new )?;
+    let order = self.take_orders( 1 );
+    self.stages.push( QueryStage::Unwind( UnwindClause { order: order, operand: operand, variable: variable.clone() }));
+    Ok( true )
   }
 
-  pub fn try_update_bracket_statements ( &mut self, token: &SyntaxToken ) -> bool 
+  /// Every SET item is a statement, ON CREATE SET / ON MATCH SET items carry their action
+  pub fn add_set ( &mut self, items: &Vec<SetItem>, action: Option<MergeAction> ) -> Result<bool, QueryError> 
   {
-    let bracket_res = self.find_open_bracket_statement();
-    if bracket_res.is_some() 
+    for item in items.iter() 
     {
-      let remove_res = self.bracket_statements.remove( *bracket_res.as_ref().unwrap() );
-      if remove_res.is_some() 
+      let order = self.take_orders( 1 );
+      let mut stmt = match item 
       {
-        let replace_res = BracketStatement::from( remove_res.unwrap(), token );
-        if replace_res.is_ok() 
+        SetItem::Property { variable, key, value, span } => 
         {
-          self.bracket_statements.insert( *bracket_res.as_ref().unwrap(), replace_res.unwrap() );
-          return true;
+          if *value == Operand::Literal( Value::Null ) 
+          { 
+            return Err( lowering_error( String::from( "Syntax Error: Property Value `null`" ), *span, Some( "a value" ), 
+              Some( String::from( "`null`" )))); 
+          }
+          let mut stmt = SetStatement::new( order, variable.clone() );
+          stmt.set_type = Some( SetType::Property );
+          let ( properties, property_refs ) = lower_properties( &vec![ ( key.clone(), value.clone(), *span ) ], &self.params )?;
+          stmt.properties = properties;
+          stmt.property_refs = property_refs;
+          stmt
         }
-      }
+        SetItem::Merge { variable, properties, properties_param } => 
+        {
+          // only parameters are known when the properties are set
+          let refer = properties.iter().find( | ( _, v, _ ) | matches!( v, Operand::Variable( _ ) | Operand::Property( _, _ )));
+          if refer.is_some() 
+          { 
+            let text = operand_text( &refer.unwrap().1 );
+            return Err( lowering_error( format!( "Syntax Error: Property Value `{}`", text ), refer.unwrap().2, 
+              Some( "a literal or a parameter" ), Some( format!( "`{}`", text )))); 
+          }
+          let mut stmt = SetStatement::new( order, variable.clone() );
+          stmt.set_type = Some( SetType::Merge );
+          let entries = property_entries( properties, properties_param, &self.params )?;
+          let ( properties, property_refs ) = lower_properties( &entries, &self.params )?;
+          stmt.properties = properties;
+          stmt.property_refs = property_refs;
+          stmt
+        }
+        SetItem::Labels { variable, labels } => 
+        {
+          let mut stmt = SetStatement::new( order, variable.clone() );
+          stmt.set_type = Some( SetType::Label );
+          stmt.labels = labels.clone();
+          stmt
+        }
+      };
+      stmt.is_open = false;
+      stmt.merge_action = action.clone();
+      self.set_statements.push( stmt );
     }
-    false
+    Ok( true )
   }
-  
-  pub fn try_update_match_statements ( &mut self, token: &SyntaxToken ) -> bool
+
+  pub fn add_remove ( &mut self, items: &Vec<RemoveItem> ) -> Result<bool, QueryError> 
   {
-    let match_res = self.find_open_match_statement();
-    if match_res.is_some() 
+    if self.is_reading() == false || self.merge { return Err( QueryError::new( String::from( "Syntax Error: Remove" ))); }
+    for item in items.iter() 
     {
-      let remove_res = self.match_statements.remove( *match_res.as_ref().unwrap() );
-      if remove_res.is_some() 
+      let ( variable, key, labels ) = match item 
       {
-        let match_stmt = remove_res.unwrap();
-        let add_res = MatchStatement::from( match_stmt, token );
-        if add_res.is_ok() 
-        {
-          self.match_statements.insert( match_res.unwrap() , add_res.unwrap() );
-          return true;
-        }
-      }
+        RemoveItem::Property { variable, key } => ( variable, Some( key.clone() ), Vec::new() ),
+        RemoveItem::Labels { variable, labels } => ( variable, None, labels.clone() ),
+      };
+      let order = self.take_orders( 1 );
+      self.remove_statements.push( RemoveStatement 
+      { 
+        order: order, transaction_label: variable.clone(), key: key, labels: labels 
+      });
+    }
+    Ok( true )
+  }
+
+  pub fn add_delete ( &mut self, detach: bool, graph: &Option<String>, variables: &Vec<String> ) -> Result<bool, QueryError> 
+  {
+    if self.delete_statement.is_some() { return Err( QueryError::new( String::from( "Syntax Error: Delete" ))); }
+    let mut stmt = DeleteStatement::new( self.take_orders( 1 ), detach );
+    stmt.graph_name = graph.clone();
+    stmt.transaction_labels = variables.clone();
+    self.delete_statement = Some( stmt );
+    Ok( true )
+  }
+
+  pub fn add_return ( &mut self, projection: &Projection ) -> Result<bool, QueryError> 
+  {
+    if self.return_clause.is_some() 
+    { 
+      let mut err = QueryError::new( String::from( "Syntax Error: Return" ));
+      err.expected = Some( String::from( "end of query" ));
+      return Err( err ); 
     }
-    false
+    self.return_clause = Some( lower_projection( projection, &self.params )? );
+    Ok( true )
   }
 }
 
-impl TransactionBuilder 
+/// ReturnClause of RETURN or WITH items, parameters are bound and SKIP / LIMIT become row counts
+pub fn lower_projection ( projection: &Projection, params: &Vec<( String, Value )> ) -> Result<ReturnClause, QueryError> 
+{
+  let mut return_clause = ReturnClause::new();
+  return_clause.distinct = projection.distinct;
+  for item in projection.items.iter() 
+  {
+    let mut operand = item.operand.clone();
+    operand.bind_params( params ).map_err( | e | lowering_error( e, item.span, None, None ))?;
+    return_clause.items.push( ReturnItem { 
+      expression: item.expression.clone(), 
+      operand: operand, 
+      aggregate: item.aggregate.clone(), 
+      alias: item.alias.clone() 
+    });
+  }
+  for key in projection.order_by.iter() 
+  {
+    return_clause.order_by.push( SortItem { expression: key.expression.clone(), operand: key.operand.clone(), descending: key.descending });
+  }
+  return_clause.skip = lower_count( &projection.skip, params )?;
+  return_clause.limit = lower_count( &projection.limit, params )?;
+  Ok( return_clause )
+}
+
+/// Rows of SKIP or LIMIT, a $n parameter must be a non-negative integer
+fn lower_count ( count: &Option<( Count, Span )>, params: &Vec<( String, Value )> ) -> Result<Option<usize>, QueryError> 
 {
-  pub fn find_open_match_statement ( &self ) -> Option<usize>
+  match count 
   {
-    for ( i, stmt ) in self.match_statements.iter().enumerate()
+    None => Ok( None ),
+    Some(( Count::Value( rows ), _ )) => Ok( Some( *rows )),
+    Some(( Count::Parameter( name ), span )) => 
     {
-      if stmt.is_open == true 
-      {
-        return Some( i ); 
+      let found = Some( format!( "`${}`", name ));
+      let value = param_value( name, params ).map_err( | e | lowering_error( e, *span, None, found.clone() ))?;
+      let rows = value.as_i128().and_then( | n | usize::try_from( n ).ok() );
+      if rows.is_none() 
+      { 
+        return Err( lowering_error( format!( "Error: Parameter `${}` must be a non-negative integer.", name ), *span, 
+          Some( "a non-negative integer" ), found )); 
       }
+      Ok( rows )
     }
-    None
   }
+}
+
+/// Entries of { key: value ... } or of a $props map, every key of the map is a $props.key parameter at the span of $props
+fn property_entries ( 
+  entries: &Vec<( String, Operand, Span )>, param: &Option<( String, Span )>, 
+  params: &Vec<( String, Value )> ) -> Result<Vec<( String, Operand, Span )>, QueryError> 
+{
+  if param.is_none() { return Ok( entries.clone() ); }
+  let ( name, span ) = param.as_ref().unwrap();
+  let found = Some( format!( "`${}`", name ));
+  match param_value( name, params ).map_err( | e | lowering_error( e, *span, None, found.clone() ))? 
+  {
+    Value::Map( map ) => Ok( map.iter().map( | ( key, _ ) | ( key.clone(), Operand::Parameter( format!( "{}.{}", name, key )), *span )).collect() ),
+    _ => Err( lowering_error( format!( "Error: Parameter `${}` must be a map.", name ), *span, Some( "a map" ), found )),
+  }
+}
 
-  pub fn find_open_paren_statement ( &self ) -> Option<usize>
+/// Literal, list and parameter values become typed properties, null is left out, variables are kept as refs
+fn lower_properties ( 
+  entries: &Vec<( String, Operand, Span )>, params: &Vec<( String, Value )> ) -> Result<( Vec<NEProperty>, Vec<( String, Operand )> ), QueryError> 
+{
+  let mut properties: Vec<NEProperty> = Vec::new();
+  let mut property_refs: Vec<( String, Operand )> = Vec::new();
+  for ( key, value, span ) in entries.iter() 
   {
-    for ( i, stmt ) in self.paren_statements.iter().enumerate()
+    let literal = match value 
     {
-      if stmt.is_open == true 
+      Operand::Literal( literal ) => literal.clone(),
+      Operand::List( vals ) => Value::List( vals.clone() ),
+      Operand::Parameter( name ) => 
       {
-        return Some( i ); 
+        param_value( name, params ).map_err( | e | lowering_error( e, *span, None, Some( format!( "`${}`", name ))))?
+      }
+      _ => 
+      { 
+        property_refs.push(( key.clone(), value.clone() )); 
+        continue;
       }
+    };
+    if literal.is_null() { continue; }
+
+    let property = literal.to_property( key.clone() );
+    if property.is_some() 
+    { 
+      properties.push( property.unwrap() ); 
+      continue;
     }
-    None
+    let message = match value 
+    {
+      Operand::Parameter( _ ) => format!( "Error: Property `{}` cannot be stored.", key ),
+      Operand::List( _ ) => format!( "Syntax Error: List Property `{}` mixes types", key ),
+      _ => format!( "Syntax Error: Property Value `{}`", key ),
+    };
+    return Err( lowering_error( message, *span, Some( "a value of a single type" ), None ));
+  }
+  Ok(( properties, property_refs ))
+}
+
+/// Error at a span of the query, with what was expected and found there
+/// A pattern takes an order for each node and relationship
+fn pattern_orders ( pattern: &Pattern ) -> u16 { ( pattern.nodes.len() + pattern.relationships.len() ) as u16 }
+
+/// Order of the element at position i of a pattern, nodes are at even positions and relationships between them at odd
+fn element_order ( first: u16, i: usize ) -> u16 { first + i as u16 }
+
+fn lowering_error ( message: String, span: Span, expected: Option<&str>, found: Option<String> ) -> QueryError 
+{
+  let mut err = QueryError::at( message, span, found );
+  err.expected = expected.map( | e | e.to_string() );
+  err
+}
+
+/// MERGE and CREATE patterns are not paths and have no inline WHERE
+fn check_write_pattern ( pattern: &Pattern ) -> Result<bool, QueryError> 
+{
+  if pattern.variable.is_some() 
+  { 
+    return Err( lowering_error( String::from( "Syntax Error: Path `=`" ), pattern.span, Some( "a pattern" ), 
+      Some( format!( "`{} =`", pattern.variable.as_ref().unwrap() )))); 
+  }
+  if pattern.shortest.is_some() 
+  { 
+    let name = if pattern.shortest == Some( true ) { "allShortestPaths" } else { "shortestPath" };
+    return Err( lowering_error( format!( "Syntax Error: `{}`", name ), pattern.span, Some( "a pattern" ), Some( format!( "`{}`", name )))); 
   }
 
-  pub fn find_open_bracket_statement ( &self ) -> Option<usize>
+  let node = pattern.nodes.iter().find( | n | n.predicate.is_some() ).map( | n | n.span );
+  let rel = pattern.relationships.iter().find( | r | r.predicate.is_some() ).map( | r | r.span );
+  let inline_where = match ( node, rel ) 
   {
-    for ( i, stmt ) in self.bracket_statements.iter().enumerate()
-    {
-      if stmt.is_open == true { return Some( i ); }
-    }
-    None
+    ( Some( a ), Some( b )) => Some( if a.start < b.start { a } else { b } ),
+    ( a, b ) => a.or( b ),
+  };
+  if inline_where.is_some() 
+  { 
+    return Err( lowering_error( String::from( "Syntax Error: Where" ), inline_where.unwrap(), Some( "properties like `{key: value}`" ), 
+      Some( String::from( "`WHERE`" )))); 
+  }
+  Ok( true )
+}
+
+/// shortestPath( ... ) and allShortestPaths( ... ) search a single relationship with a hop range starting at 0 or 1
+fn check_shortest ( pattern: &Pattern ) -> Result<bool, QueryError> 
+{
+  let message = String::from( "Error: shortestPath requires a single variable-length relationship." );
+  if pattern.relationships.len() == 0 
+  { 
+    return Err( lowering_error( message, pattern.span, Some( "a variable-length relationship" ), None )); 
+  }
+  if pattern.relationships.len() > 1 
+  { 
+    return Err( lowering_error( message, pattern.relationships[1].span, Some( "`)`" ), Some( String::from( "a second relationship" )))); 
   }
 
-  // !!! this needs to find a new home
-  pub fn token_dir_type ( token: &SyntaxToken ) -> DirectionType 
+  let rel = &pattern.relationships[0];
+  if rel.hops.is_none() 
+  { 
+    return Err( lowering_error( message, rel.span, Some( "a hop range like `[*]`" ), Some( String::from( "a single hop" )))); 
+  }
+  let hops = rel.hops.as_ref().unwrap();
+  if hops.min > 1 
+  { 
+    return Err( lowering_error( format!( "Error: shortestPath lower bound `{}` must be 0 or 1.", hops ), rel.span, 
+      Some( "a lower bound of 0 or 1" ), Some( format!( "`{}`", hops )))); 
+  }
+  Ok( true )
+}
+
+/// n or n.key as written
+fn operand_text ( operand: &Operand ) -> String 
+{
+  match operand 
   {
-    if token.token_type == SyntaxTokenType::EdgeDirection 
-    {
-      if token.val == "-" { return DirectionType::Bidirectional; }
-      if token.val == "<" { return DirectionType::Left; }
-      if token.val == ">" { return DirectionType::Right; }
-    }
-    DirectionType::Undirected
+    Operand::Variable( variable ) => variable.clone(),
+    Operand::Property( variable, key ) => format!( "{}.{}", variable, key ),
+    Operand::Parameter( name ) => format!( "${}", name ),
+    _ => format!( "{:?}", operand ),
   }
 }
 // ---------------------------------------------------------------------------------------------------------------------
@@ -381,254 +783,832 @@ impl TransactionBuilder
 mod tests 
 {
   use super::*;
+  use crate::cmd::aggregate::AggregateFunction;
+  use crate::cmd::function::ScalarFunction;
+  use crate::cmd::predicate::{ CompareOp, Operand };
+  use crate::cmd::HopRange;
+  use crate::cmd::SetType;
+  use crate::common::{ DirectionType, NEProperty };
 
   fn build_id () -> UUID { UUID::new( String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" )).unwrap() }
   fn db_nickname () -> Label { Label::new( String::from( "devs" ) ).unwrap() }
 
   #[test]
-  fn test_add_match_token () 
+  fn test_process_match_query () 
   {
-    let mut tb = TransactionBuilder::new();
-    tb.add_match_token();
-    
-    assert_eq!( tb.match_statements.len(), 1 );
-    assert_eq!( tb.current_order, 2 );
-
-    tb.add_match_token();
+    let query_string = "MATCH (:Movie {title: 'Wall Street'})";
+    let t = process_query( &query_string, build_id(), db_nickname() );
     
-    assert_eq!( tb.match_statements.len(), 2 );
-    assert_eq!( tb.current_order, 3 );
-  
-    assert_eq!( tb.match_statements.get(0).unwrap().order, 1 );
-    assert_eq!( tb.match_statements.get(1).unwrap().order, 2 );
+    assert_eq!( t.err_state, None );
+    assert_eq!( t.read_clause.as_ref().unwrap().match_statements.len(), 1 );
+    assert_eq!( t.read_clause.as_ref().unwrap().match_statements.get(0).as_ref().unwrap().order, 1 );
+    assert_eq!( t.read_clause.as_ref().unwrap().match_statements.get(0).as_ref().unwrap().is_open, false );
+    assert_eq!( t.read_clause.as_ref().unwrap().match_statements.get(0).as_ref().unwrap().labels_complete, true );
+    assert_eq!( t.read_clause.as_ref().unwrap().match_statements.get(0).as_ref().unwrap().kvps_complete, true );
+    assert_eq!( t.read_clause.as_ref().unwrap().match_statements.get(0).as_ref().unwrap().transaction_label, None );
+    assert_eq!( 
+      t.read_clause.as_ref().unwrap().match_statements.get(0).as_ref().unwrap().primary_label, 
+      Some( String::from( "Movie" )));
+    assert_eq!( 
+      t.read_clause.as_ref().unwrap().match_statements.get(0).as_ref().unwrap().kv_str.len(), 1 );
+    assert_eq!( 
+      t.read_clause.as_ref().unwrap().match_statements.get(0).as_ref().unwrap().kv_str.get(0).as_ref().unwrap().key, 
+      String::from( "title" ));
+    assert_eq!( 
+      t.read_clause.as_ref().unwrap().match_statements.get(0).as_ref().unwrap().kv_str.get(0).as_ref().unwrap().val, 
+      Some( String::from( "Wall Street" )));
   }
 
   #[test]
-  fn test_add_create () 
+  fn test_process_from_query () 
   {
-    let mut tb = TransactionBuilder::new();
-    tb.add_create_token();
-    
-    assert_eq!( tb.create_statement.is_some(), true );
-    assert_eq!( tb.current_order, 1 );
+    let t = process_query( "MATCH (n:Developer) FROM devs", build_id(), db_nickname() );
 
-    tb.add_create_token();
-    
-    assert_eq!( tb.err_state.is_some(), true );
+    assert_eq!( t.err_state, None );
+    assert_eq!( t.read_clause.as_ref().unwrap().match_statements.len(), 1 );
+    assert_eq!( t.from_clause.as_ref().unwrap().graph_name, String::from( "devs" ));
   }
 
   #[test]
-  fn test_add_open_node () 
+  fn test_process_return_query () 
   {
-    let mut tb = TransactionBuilder::new();
-    tb.add_open_node( SyntaxToken::new(SyntaxTokenType::OpenNode, String::from( "(" )));
+    let t = process_query( "MATCH (n:Developer) FROM devs RETURN n AS Developer", build_id(), db_nickname() );
 
-    assert_eq!( tb.paren_statements.len(), 1 );
-    assert_eq!( tb.current_order, 1 );
+    assert_eq!( t.err_state, None );
+    assert_eq!( t.from_clause.as_ref().unwrap().graph_name, String::from( "devs" ));
+    assert_eq!( t.return_clause.as_ref().unwrap().items[0].expression, String::from( "n" ));
+    assert_eq!( t.return_clause.as_ref().unwrap().items[0].alias, Some( String::from( "Developer" )));
   }
 
   #[test]
-  fn test_close_statement () 
+  fn test_process_return_projection () 
   {
-    let mut tb = TransactionBuilder::new();
-    tb.add_create_token();
-    tb.close_statement( &SyntaxToken::new( SyntaxTokenType::CloseNode, String::from( "(" )) );
+    let query_string = "MATCH (n:Developer) FROM devs RETURN DISTINCT n.name AS name, n.age ORDER BY n.age DESC, name SKIP 1 LIMIT 5";
+    let t = process_query( query_string, build_id(), db_nickname() );
+    assert_eq!( t.err_state, None );
+    let return_clause = t.return_clause.as_ref().unwrap();
+    assert_eq!( return_clause.distinct, true );
+    assert_eq!( return_clause.items.iter().map( | item | item.column().clone() ).collect::<Vec<String>>(), 
+      vec![ String::from( "name" ), String::from( "n.age" ) ] );
+    assert_eq!( return_clause.order_by.len(), 2 );
+    assert_eq!( return_clause.order_by[0].descending, true );
+    assert_eq!( return_clause.order_by[1].descending, false );
+    assert_eq!( return_clause.skip, Some( 1 ));
+    assert_eq!( return_clause.limit, Some( 5 ));
+
+    // FROM may follow RETURN items
+    let t1 = process_query( "MATCH (p:Person) RETURN p FROM movies LIMIT 5", build_id(), db_nickname() );
+    assert_eq!( t1.err_state, None );
+    assert_eq!( t1.from_clause.as_ref().unwrap().graph_name, String::from( "movies" ));
+    assert_eq!( t1.return_clause.as_ref().unwrap().limit, Some( 5 ));
+
+    let t2 = process_query( "MATCH (p:Person) RETURN p LIMIT", build_id(), db_nickname() );
+    assert_eq!( t2.err_state, Some( String::from( "Syntax Error: Return" )));
+
+    let t3 = process_query( "MATCH (p:Person) RETURN p LIMIT 'five'", build_id(), db_nickname() );
+    assert_eq!( t3.err_state.is_some(), true );
+
+    let t4 = process_query( "MATCH (p:Person) RETURN p SKIP -1", build_id(), db_nickname() );
+    assert_eq!( t4.err_state.is_some(), true );
+
+    let t5 = process_query( "MATCH (p:Person) RETURN ORDER BY p", build_id(), db_nickname() );
+    assert_eq!( t5.err_state, Some( String::from( "Syntax Error: Return `ORDER`" )));
+
+    let t6 = process_query( "MATCH (p:Person) LIMIT 5", build_id(), db_nickname() );
+    assert_eq!( t6.err_state, Some( String::from( "Syntax Error: Return `LIMIT`" )));
+  }
 
-    assert_eq!( tb.create_statement.as_ref().unwrap().is_open, false );
-    assert_eq!( tb.err_state.is_some(), false );
+  #[test]
+  fn test_process_return_aggregates () 
+  {
+    let query_string = "MATCH (n)-[r:KNOWS]->(f) RETURN n.name, count(r) As numberOfFriends, percentileCont(f.age, 0.9)";
+    let t = process_query( query_string, build_id(), db_nickname() );
+    assert_eq!( t.err_state, None );
+    let items = &t.return_clause.as_ref().unwrap().items;
+    assert_eq!( items.len(), 3 );
+    assert_eq!( items[0].aggregate, None );
+    assert_eq!( items[1].aggregate.as_ref().unwrap().function, AggregateFunction::Count );
+    assert_eq!( items[1].column(), &String::from( "numberOfFriends" ));
+    assert_eq!( items[2].column(), &String::from( "percentileCont(f.age, 0.9)" ));
 
-    tb.add_match_token();
-    tb.close_statement( &SyntaxToken::new( SyntaxTokenType::CloseNode, String::from( "(" )) );
+    let t1 = process_query( "MATCH (n) RETURN count(DISTINCT *)", build_id(), db_nickname() );
+    assert_eq!( t1.err_state, Some( String::from( "Syntax Error: Return `)`" )));
 
-    assert_eq!( tb.match_statements.len(), 1 );
-    assert_eq!( tb.match_statements.get(0).unwrap().is_open, false );
-    assert_eq!( tb.err_state.is_some(), false );
+    let t2 = process_query( "MATCH (n) RETURN size(n)", build_id(), db_nickname() );
+    assert_eq!( t2.err_state, Some( String::from( "Syntax Error: Return `(`" )));
 
-    tb.add_open_node( SyntaxToken::new(SyntaxTokenType::OpenNode, String::from( "(" )));
-    tb.close_statement( &SyntaxToken::new( SyntaxTokenType::CloseNode, String::from( "(" )) );
+    let t3 = process_query( "MATCH (n) RETURN count(n", build_id(), db_nickname() );
+    assert_eq!( t3.err_state, Some( String::from( "Syntax Error: Return" )));
+  }
 
-    assert_eq!( tb.paren_statements.len(), 1 );
-    assert_eq!( tb.paren_statements.get(0).unwrap().is_open, false );
-    assert_eq!( tb.err_state.is_some(), false );
+  #[test]
+  fn test_process_path () 
+  {
+    let t = process_query( "MATCH (a), p = (b)-[r:KNOWS*1..3]->(c) WHERE length(p) > 1 RETURN nodes(p), length(p)", 
+      build_id(), db_nickname() );
+    assert_eq!( t.err_state, None );
+    let read_clause = t.read_clause.as_ref().unwrap();
+    assert_eq!( read_clause.match_statements.len(), 3 );
+    assert_eq!( read_clause.path_variables.len(), 1 );
+
+    let ( variable, start ) = read_clause.path_variables.first().unwrap();
+    assert_eq!( variable, &String::from( "p" ));
+    let first = read_clause.match_statements.iter().find( | stmt | stmt.order == *start ).unwrap();
+    assert_eq!( first.transaction_label, Some( String::from( "b" )));
+    assert_eq!( read_clause.path_brackets( *start ).len(), 1 );
+    assert_eq!( read_clause.bracket_statements[0].hops, Some( HopRange { min: 1, max: Some( 3 ) }));
+    assert_eq!( read_clause.variables(), vec![ "a", "b", "c", "r", "p" ] );
+
+    let items = &t.return_clause.as_ref().unwrap().items;
+    assert_eq!( items[0].column(), &String::from( "nodes(p)" ));
+    assert_eq!( items[1].operand, Operand::Function( ScalarFunction::Length, Box::new( Operand::Variable( String::from( "p" )))));
+
+    let t1 = process_query( "CREATE GRAPH devs (a:Developer)-[:KNOWS*2]->(b:Developer)", build_id(), db_nickname() );
+    assert_eq!( t1.err_state, Some( String::from( "Syntax Error: Hop Range `*2`" )));
+
+    let t2 = process_query( "MATCH (a:Developer) = (b) RETURN a", build_id(), db_nickname() );
+    assert_eq!( t2.err_state, Some( String::from( "Syntax Error: Path `=`" )));
+  }
 
-    tb.add_open_bracket( &SyntaxToken::new( SyntaxTokenType::OpenBracket, String::from( "{" )));
-    tb.close_statement( &SyntaxToken::new( SyntaxTokenType::CloseNode, String::from( "(" )) );
+  #[test]
+  fn test_process_shortest_path () 
+  {
+    let t = process_query( "MATCH (c), p = allShortestPaths((a)-[:ROUTE*]->(b)) WHERE a.city = c.city RETURN p", 
+      build_id(), db_nickname() );
+    assert_eq!( t.err_state, None );
+    let read_clause = t.read_clause.as_ref().unwrap();
+    assert_eq!( read_clause.match_statements.len(), 3 );
+    assert_eq!( read_clause.bracket_statements.len(), 1 );
+    assert_eq!( read_clause.predicates.len(), 1 );
+
+    let ( start, all ) = read_clause.shortest_paths.first().unwrap();
+    assert_eq!( all, &true );
+    assert_eq!( read_clause.path_variables, vec![ ( String::from( "p" ), *start ) ] );
+    let first = read_clause.match_statements.iter().find( | stmt | stmt.order == *start ).unwrap();
+    assert_eq!( first.transaction_label, Some( String::from( "a" )));
+
+    let t1 = process_query( "MATCH shortestPath((a)-[*]->(b)) RETURN a", build_id(), db_nickname() );
+    assert_eq!( t1.err_state, None );
+    assert_eq!( t1.read_clause.as_ref().unwrap().shortest_paths.first().unwrap().1, false );
+
+    let t2 = process_query( "MATCH p = shortestPath((a)-[*]->(b) RETURN p", build_id(), db_nickname() );
+    assert_eq!( t2.err_state, Some( String::from( "Syntax Error: shortestPath" )));
+
+    let t3 = process_query( "MATCH (a)-[r]->shortestPath((b)-[*]->(c)) RETURN a", build_id(), db_nickname() );
+    assert_eq!( t3.err_state, Some( String::from( "Syntax Error: `shortestPath`" )));
+
+    let t4 = process_query( "MATCH shortestPath((a)-[:TRAVEL_ROUTE]->(b)) FROM routes RETURN a", build_id(), db_nickname() );
+    assert_eq!( t4.err_state, Some( String::from( "Error: shortestPath requires a single variable-length relationship." )));
+    assert_eq!( t4.error.as_ref().unwrap().note(), Some( String::from( "expected a hop range like `[*]`, found a single hop" )));
+    assert_eq!( t4.error.as_ref().unwrap().span.unwrap().column, 23 );
+
+    let t5 = process_query( "MATCH shortestPath((a)-[*]->(b)-[*]->(c)) RETURN a", build_id(), db_nickname() );
+    assert_eq!( t5.error.as_ref().unwrap().span.unwrap().column, 32 );
+    let t6 = process_query( "MATCH shortestPath((a)-[*2..]->(b)) FROM routes RETURN a", build_id(), db_nickname() );
+    assert_eq!( t6.err_state, Some( String::from( "Error: shortestPath lower bound `*2..` must be 0 or 1." )));
+    assert_eq!( t6.error.as_ref().unwrap().found, Some( String::from( "`*2..`" )));
+    let t7 = process_query( "MATCH shortestPath((a)-[*3..5]->(b)) FROM routes RETURN a", build_id(), db_nickname() );
+    assert_eq!( t7.err_state, Some( String::from( "Error: shortestPath lower bound `*3..5` must be 0 or 1." )));
+  }
 
-    assert_eq!( tb.bracket_statements.len(), 1 );
-    assert_eq!( tb.bracket_statements.get(0).unwrap().is_open, false );
-    assert_eq!( tb.err_state.is_some(), false );
+  #[test]
+  fn test_process_optional_match () 
+  {
+    let query_string = "MATCH (p:Person) WHERE p.age > 30 OPTIONAL MATCH (p)-[:ACTED_IN]->(m) WHERE m.year > 2000 RETURN p.name, m.title";
+    let t = process_query( query_string, build_id(), db_nickname() );
+    assert_eq!( t.err_state, None );
+    let read_clause = t.read_clause.as_ref().unwrap();
+    assert_eq!( read_clause.match_statements.len(), 3 );
+    assert_eq!( read_clause.clauses.len(), 2 );
+    assert_eq!( read_clause.clauses[0].1, false );
+    assert_eq!( read_clause.clauses[1].1, true );
+    assert_eq!( read_clause.predicates.len(), 1 );
+    assert_eq!( read_clause.optional_predicates.len(), 1 );
+    assert_eq!( read_clause.optional_predicates[0].0, read_clause.clauses[1].0 );
+
+    let ranges = read_clause.clause_ranges();
+    assert_eq!( ranges[0], ( read_clause.clauses[0].0, Some( read_clause.clauses[1].0 ), false ));
+    assert_eq!( ranges[1], ( read_clause.clauses[1].0, None, true ));
+
+    let t1 = process_query( "MATCH (p) OPTIONAL RETURN p", build_id(), db_nickname() );
+    assert_eq!( t1.err_state, Some( String::from( "Syntax Error: Optional `RETURN`" )));
+
+    let t2 = process_query( "MATCH (p) OPTIONAL OPTIONAL MATCH (p)", build_id(), db_nickname() );
+    assert_eq!( t2.err_state, Some( String::from( "Syntax Error: Optional" )));
   }
 
   #[test]
-  fn test_find_open_match_statement () 
+  fn test_process_with () 
   {
-    let mut tb = TransactionBuilder::new();
-    tb.add_match_token();
-    let res = tb.find_open_match_statement();
+    let query_string = "MATCH (a)-[r:KNOWS]->(b) WITH a, count(r) AS c WHERE c > 2 MATCH (a)-->(x) RETURN a, c, x";
+    let t = process_query( query_string, build_id(), db_nickname() );
+    assert_eq!( t.err_state, None );
+    let read_clause = t.read_clause.as_ref().unwrap();
+    assert_eq!( read_clause.match_statements.len(), 2 );
+    assert_eq!( read_clause.bracket_statements.len(), 1 );
+    assert_eq!( read_clause.predicates.len(), 0 );
+
+    assert_eq!( t.stages.len(), 2 );
+    match &t.stages[0] 
+    {
+      QueryStage::With( with_clause ) => 
+      {
+        assert_eq!( with_clause.columns(), vec![ String::from( "a" ), String::from( "c" ) ] );
+        assert_eq!( with_clause.projection.items[1].aggregate.is_some(), true );
+        assert_eq!( with_clause.predicates.len(), 1 );
+        assert_eq!( with_clause.order > read_clause.order, true );
+      }
+      stage => { panic!( "unexpected stage {:?}", stage ); }
+    }
+    match &t.stages[1] 
+    {
+      QueryStage::Read( next_clause ) => 
+      {
+        assert_eq!( next_clause.match_statements.len(), 2 );
+        assert_eq!( next_clause.bracket_statements.len(), 1 );
+        assert_eq!( next_clause.variables(), vec![ String::from( "a" ), String::from( "x" ) ] );
+      }
+      stage => { panic!( "unexpected stage {:?}", stage ); }
+    }
+    assert_eq!( t.variables(), vec![ String::from( "a" ), String::from( "c" ), String::from( "x" ) ] );
+
+    let t1 = process_query( "MATCH (n) WITH DISTINCT n.name AS name ORDER BY name LIMIT 2 WITH name RETURN name", build_id(), db_nickname() );
+    assert_eq!( t1.err_state, None );
+    assert_eq!( t1.stages.len(), 2 );
+
+    let errors = vec![
+      ( "MATCH (n) WITH n.name RETURN n", "Syntax Error: With `n.name` requires an alias" ),
+      ( "MATCH (n) WITH count(n) RETURN n", "Syntax Error: With `count(n)` requires an alias" ),
+      ( "WITH n RETURN n", "Syntax Error: With" ),
+      ( "MATCH (n) RETURN n WITH n", "Syntax Error: With" ),
+      ( "MERGE (n:Developer) WITH n RETURN n", "Syntax Error: With" ),
+    ];
+    for ( query, err ) in errors.into_iter() 
+    {
+      let t2 = process_query( query, build_id(), db_nickname() );
+      assert_eq!( t2.err_state, Some( String::from( err )), "{}", query );
+    }
+
+    // the item without an alias
+    let t3 = process_query( "MATCH (n) WITH n, count(n) RETURN n", build_id(), db_nickname() );
+    assert_eq!( t3.error.as_ref().unwrap().span.unwrap().column, 19 );
+    assert_eq!( t3.error.as_ref().unwrap().expected, Some( String::from( "`AS` and an alias" )));
+  }
 
-    assert_eq!( res, Some(0) );
-    assert_eq!( tb.match_statements.len(), 1 );
-    assert_eq!( tb.match_statements.get(0).unwrap().is_open, true );
-    assert_eq!( tb.err_state.is_some(), false );
+  #[test]
+  fn test_process_unwind () 
+  {
+    let query_string = "UNWIND ['Erin', 'Frank'] AS name CREATE (:Developer {name: name, tags: ['new']}) FROM devs";
+    let t = process_query( query_string, build_id(), db_nickname() );
+    assert_eq!( t.err_state, None );
+    assert_eq!( t.read_clause.is_none(), true );
+    assert_eq!( t.stages.len(), 1 );
+    match &t.stages[0] 
+    {
+      QueryStage::Unwind( unwind_clause ) => 
+      {
+        assert_eq!( unwind_clause.variable, String::from( "name" ));
+        assert_eq!( matches!( unwind_clause.operand, Operand::List( _ )), true );
+      }
+      stage => { panic!( "unexpected stage {:?}", stage ); }
+    }
+    let node_stmt = t.node_statements.get( 0 ).unwrap();
+    assert_eq!( node_stmt.properties, vec![ NEProperty::ListString(( String::from( "tags" ), vec![ String::from( "new" ) ] )) ] );
+    assert_eq!( node_stmt.property_refs, vec![ ( String::from( "name" ), Operand::Variable( String::from( "name" ))) ] );
+    assert_eq!( t.variables(), vec![ String::from( "name" ) ] );
+
+    let t1 = process_query( 
+      "MATCH (n) UNWIND n.tags[1..] AS tag WITH tag, count(n) AS c RETURN tag, [x IN [1, 2] | c][0] AS first", 
+      build_id(), db_nickname() );
+    assert_eq!( t1.err_state, None );
+    assert_eq!( t1.stages.len(), 2 );
+    assert_eq!( t1.variables(), vec![ String::from( "tag" ), String::from( "c" ) ] );
+
+    let t2 = process_query( "MATCH (n) RETURN n.tags[0], [1, 2][-1] AS last, [t IN n.tags WHERE t <> 'x'] ORDER BY last", build_id(), db_nickname() );
+    assert_eq!( t2.err_state, None );
+    let columns: Vec<String> = t2.return_clause.as_ref().unwrap().items.iter().map( | item | item.column().clone() ).collect();
+    assert_eq!( columns, vec![ String::from( "n.tags[0]" ), String::from( "last" ), String::from( "[t IN n.tags WHERE t <> 'x']" ) ] );
+
+    let errors = vec![
+      ( "UNWIND [1, 2] AS x WHERE x > 1 RETURN x", "Syntax Error: Where" ),
+      ( "UNWIND [1, 2] RETURN 1", "Syntax Error: Unwind" ),
+      ( "UNWIND [1, 2] AS n.x RETURN 1", "Syntax Error: Unwind `n.x`" ),
+      ( "MATCH (n) RETURN n.tags[0] AS t, count(n)[0]", "Syntax Error: Return `[0]`" ),
+      ( "MATCH (n {tags: ['a']}) RETURN n", "Syntax Error: Match Statement" ),
+    ];
+    for ( query, err ) in errors.into_iter() 
+    {
+      let t3 = process_query( query, build_id(), db_nickname() );
+      assert_eq!( t3.err_state, Some( String::from( err )), "{}", query );
+    }
   }
 
   #[test]
-  fn test_find_open_paren_statement () 
+  fn test_process_params () 
   {
-    let mut tb = TransactionBuilder::new();
-    tb.add_open_node( SyntaxToken::new(SyntaxTokenType::OpenNode, String::from( "(" )));
-    let res = tb.find_open_paren_statement();
+    let params = vec![ 
+      ( String::from( "name" ), Value::from( "Erin" )),
+      ( String::from( "age" ), Value::U8( 30 )),
+      ( String::from( "skip" ), Value::I32( 1 )),
+      ( String::from( "limit" ), Value::U64( 5 )),
+      ( String::from( "rows" ), Value::List( vec![ Value::Map( vec![ ( String::from( "name" ), Value::from( "Frank" )) ] ) ] )),
+      ( String::from( "since" ), Value::F32( 1.5 )),
+      ( String::from( "props" ), Value::Map( vec![ 
+        ( String::from( "name" ), Value::from( "Grace" )), ( String::from( "age" ), Value::U8( 41 )) ] )),
+    ];
+    let query_string = "CREATE (:Developer {name: $name, age: $age})-[:KNOWS {since: $since}]->(:Developer {name: 'Bob'}) FROM devs";
+    let t = process_query_with_params( query_string, &params, build_id(), db_nickname() );
+    assert_eq!( t.err_state, None );
+    let node_stmt = t.node_statements.get( 0 ).unwrap();
+    assert_eq!( node_stmt.properties, vec![ 
+      NEProperty::KvpString(( String::from( "name" ), String::from( "Erin" ))),
+      NEProperty::KvpU8(( String::from( "age" ), 30 )),
+    ]);
+    assert_eq!( node_stmt.property_refs.len(), 0 );
+    assert_eq!( t.edge_statements.get( 0 ).unwrap().properties, vec![ NEProperty::KvpF32(( String::from( "since" ), 1.5 )) ] );
+
+    let t1 = process_query_with_params( 
+      "MATCH (n {name: $name}) WHERE n.age > $age RETURN n.name, $age AS age SKIP $skip LIMIT $limit", 
+      &params, build_id(), db_nickname() );
+    assert_eq!( t1.err_state, None );
+    let read_clause = t1.read_clause.as_ref().unwrap();
+    assert_eq!( read_clause.match_statements.get( 0 ).unwrap().properties, vec![ 
+      NEProperty::KvpString(( String::from( "name" ), String::from( "Erin" ))) ] );
+    assert_eq!( read_clause.predicates[0], Predicate::Compare( 
+      Operand::Property( String::from( "n" ), String::from( "age" )), CompareOp::Gt, Operand::Literal( Value::U8( 30 ))));
+    let return_clause = t1.return_clause.as_ref().unwrap();
+    assert_eq!( return_clause.skip, Some( 1 ));
+    assert_eq!( return_clause.limit, Some( 5 ));
+    assert_eq!( return_clause.items[1].operand, Operand::Literal( Value::U8( 30 )));
+
+    let t2 = process_query_with_params( "UNWIND $rows AS row CREATE (:Developer {name: row.name}) FROM devs", &params, build_id(), db_nickname() );
+    assert_eq!( t2.err_state, None );
+    match &t2.stages[0] 
+    {
+      QueryStage::Unwind( unwind_clause ) => { assert_eq!( matches!( unwind_clause.operand, Operand::Literal( Value::List( _ ))), true ); }
+      stage => { panic!( "unexpected stage {:?}", stage ); }
+    }
+
+    let errors = vec![
+      ( "MATCH (n {name: $nickname}) RETURN n", "Error: Parameter `$nickname` not defined." ),
+      ( "MATCH (n) WHERE n.age > $min RETURN n", "Error: Parameter `$min` not defined." ),
+      ( "MATCH (n) RETURN n LIMIT $name", "Error: Parameter `$name` must be a non-negative integer." ),
+      ( "UNWIND $names AS name RETURN name", "Error: Parameter `$names` not defined." ),
+      ( "CREATE (:Developer {tags: $rows}) FROM devs", "Error: Property `tags` cannot be stored." ),
+      ( "MATCH (n {name: other}) RETURN n", "Syntax Error: Property Value `other`" ),
+      ( "MATCH (a)-[r {since: year}]->(b) RETURN a", "Syntax Error: Property Value `since`" ),
+      ( "CREATE (:Developer $name) FROM devs", "Error: Parameter `$name` must be a map." ),
+      ( "MATCH (n) SET n += $rows", "Error: Parameter `$rows` must be a map." ),
+    ];
+    for ( query, err ) in errors.into_iter() 
+    {
+      let t3 = process_query_with_params( query, &params, build_id(), db_nickname() );
+      assert_eq!( t3.err_state, Some( String::from( err )), "{}", query );
+    }
 
-    assert_eq!( res, Some(0) );
-    assert_eq!( tb.paren_statements.len(), 1 );
-    assert_eq!( tb.paren_statements.get(0).unwrap().is_open, true );
-    assert_eq!( tb.err_state.is_some(), false );
+    // parameter errors point at the $name
+    for query in [ "MATCH (n) WHERE n.age > $min RETURN n", "MATCH (n) RETURN n LIMIT $name", "MATCH (n) SET n += $missing" ] 
+    {
+      let err = process_query_with_params( query, &params, build_id(), db_nickname() ).error.unwrap();
+      let span = err.span.unwrap();
+      assert_eq!( Some( format!( "`{}`", &query[span.start..span.end] )), err.found, "{}", query );
+    }
+    assert_eq!( process_query( "MATCH (n {name: $name}) RETURN n", build_id(), db_nickname() ).err_state, 
+      Some( String::from( "Error: Parameter `$name` not defined." )));
   }
 
   #[test]
-  fn test_find_open_bracket_statement () 
+  fn test_process_map_params () 
   {
-    let mut tb = TransactionBuilder::new();
-    tb.add_open_bracket( &SyntaxToken::new( SyntaxTokenType::OpenBracket, String::from( "{" )));
-    let res = tb.find_open_bracket_statement();
+    let params = vec![ ( String::from( "props" ), Value::Map( vec![ 
+      ( String::from( "name" ), Value::from( "Grace" )), ( String::from( "age" ), Value::U8( 41 )) ] )) ];
+    let grace = vec![ 
+      NEProperty::KvpString(( String::from( "name" ), String::from( "Grace" ))),
+      NEProperty::KvpU8(( String::from( "age" ), 41 )),
+    ];
+
+    let t = process_query_with_params( "CREATE (:Developer $props)-[:KNOWS $props]->(:Developer) FROM devs", &params, build_id(), db_nickname() );
+    assert_eq!( t.err_state, None );
+    assert_eq!( t.node_statements.get( 0 ).unwrap().properties, grace );
+    assert_eq!( t.edge_statements.get( 0 ).unwrap().properties.len(), 2 );
+
+    let t1 = process_query_with_params( "MATCH (n:Developer) SET n += $props", &params, build_id(), db_nickname() );
+    assert_eq!( t1.err_state, None );
+    assert_eq!( t1.set_statements.get( 0 ).unwrap().set_type, Some( SetType::Merge ));
+    assert_eq!( t1.set_statements.get( 0 ).unwrap().properties, grace );
 
-    assert_eq!( res, Some(0) );
-    assert_eq!( tb.bracket_statements.len(), 1 );
-    assert_eq!( tb.bracket_statements.get(0).unwrap().is_open, true );
-    assert_eq!( tb.err_state.is_some(), false );
+    let t2 = process_query_with_params( "MATCH (n:Developer $props) RETURN n", &params, build_id(), db_nickname() );
+    assert_eq!( t2.err_state, None );
+    assert_eq!( t2.read_clause.as_ref().unwrap().match_statements.get( 0 ).unwrap().properties, grace );
   }
-  
+
   #[test]
-  fn test_add_close_node () 
+  fn test_process_create_properties () 
   {
-    let mut tb = TransactionBuilder::new();
-    tb.add_match_token();
-    tb.add_open_node( SyntaxToken::new(SyntaxTokenType::OpenNode, String::from( "(" )));
-    tb.close_statement( &SyntaxToken::new( SyntaxTokenType::CloseNode, String::from( "(" )) );
+    let t = process_query( 
+      "CREATE GRAPH devs (alice:Developer {name: 'Alice', age: 38, admin: true})-[:KNOWS {since: 1.5}]-(bob:Developer)", 
+      build_id(), db_nickname() );
 
-    assert_eq!( tb.match_statements.len(), 1 );
-    assert_eq!( tb.paren_statements.len(), 1 );
-    assert_eq!( tb.current_order, 3 );
+    assert_eq!( t.err_state, None );
+    assert_eq!( t.node_statements.len(), 2 );
+    assert_eq!( t.node_statements.get( 0 ).unwrap().properties, vec![
+      NEProperty::KvpString(( String::from( "name" ), String::from( "Alice" ))),
+      NEProperty::KvpI64(( String::from( "age" ), 38 )),
+      NEProperty::KvpBoolean(( String::from( "admin" ), true )),
+    ]);
+    assert_eq!( t.node_statements.get( 1 ).unwrap().properties.len(), 0 );
+    assert_eq!( t.edge_statements.get( 0 ).unwrap().properties, vec![
+      NEProperty::KvpF64(( String::from( "since" ), 1.5 )),
+    ]);
+
+    let t1 = process_query( 
+      "CREATE GRAPH devs (alice:Developer {ratings: [5, -2], colors: ['Pink', 'Black'], scale: [5.0]})", build_id(), db_nickname() );
+    assert_eq!( t1.err_state, None );
+    assert_eq!( t1.node_statements.get( 0 ).unwrap().properties, vec![
+      NEProperty::ListI64(( String::from( "ratings" ), vec![ 5, -2 ] )),
+      NEProperty::ListString(( String::from( "colors" ), vec![ String::from( "Pink" ), String::from( "Black" ) ] )),
+      NEProperty::ListFloat(( String::from( "scale" ), vec![ 5.0 ] )),
+    ]);
+
+    let t3 = process_query( "CREATE GRAPH devs (alice:Developer {ratings: [5, 'x']})", build_id(), db_nickname() );
+    assert_eq!( t3.err_state, Some( String::from( "Syntax Error: List Property `ratings` mixes types" )));
+
+    let t4 = process_query( "CREATE GRAPH devs (alice:Developer {ratings: [[5]]})", build_id(), db_nickname() );
+    assert_eq!( t4.err_state, Some( String::from( "Syntax Error: Nested list properties are not supported" )));
+
+    let t2 = process_query( "CREATE GRAPH devs (alice:Developer {age: 3x})", build_id(), db_nickname() );
+    assert_eq!( t2.err_state, Some( String::from( "Syntax Error: Property Value `3x`" )));
+  }
 
-    assert_eq!( tb.match_statements.get(0).unwrap().is_open, false );
-    assert_eq!( tb.paren_statements.get(0).unwrap().is_open, false );
+  #[test]
+  fn test_process_literal_widths () 
+  {
+    let t = process_query( 
+      "CREATE GRAPH books (b:Book {isbn: 4242u16, price: 19.99f32, stock: 0xFFu8, offset: -1_000i32, big: 9223372036854775808, 
+        active: TRUE, gone: null, scale: 1.5e-3})", 
+      build_id(), db_nickname() );
+    assert_eq!( t.err_state, None );
+    assert_eq!( t.node_statements.get( 0 ).unwrap().properties, vec![
+      NEProperty::KvpU16(( String::from( "isbn" ), 4242 )),
+      NEProperty::KvpF32(( String::from( "price" ), 19.99 )),
+      NEProperty::KvpU8(( String::from( "stock" ), 255 )),
+      NEProperty::KvpI32(( String::from( "offset" ), -1000 )),
+      NEProperty::KvpI128(( String::from( "big" ), 9223372036854775808 )),
+      NEProperty::KvpBoolean(( String::from( "active" ), true )),
+      NEProperty::KvpF64(( String::from( "scale" ), 0.0015 )),
+    ]);
+
+    let t1 = process_query( "MATCH (b:Book) FROM books WHERE b.price > 1e-3 AND b.isbn <> 0o17 SET b.stock = 7u8 RETURN b", 
+      build_id(), db_nickname() );
+    assert_eq!( t1.err_state, None );
+    assert_eq!( t1.read_clause.as_ref().unwrap().predicates[0], Predicate::And( 
+      Box::new( Predicate::Compare( Operand::Property( String::from( "b" ), String::from( "price" )), CompareOp::Gt, 
+        Operand::Literal( Value::F64( 0.001 )))),
+      Box::new( Predicate::Compare( Operand::Property( String::from( "b" ), String::from( "isbn" )), CompareOp::Ne, 
+        Operand::Literal( Value::I64( 15 ))))));
+    assert_eq!( t1.set_statements.get( 0 ).unwrap().properties, vec![ NEProperty::KvpU8(( String::from( "stock" ), 7 )) ] );
+
+    // ( query, error, the literal the error points at )
+    let errors = vec![
+      ( "CREATE GRAPH books (b:Book {stock: 300u8})", "Syntax Error: Literal `300u8` overflows u8", "300u8" ),
+      ( "CREATE GRAPH books (b:Book {offset: -1u64})", "Syntax Error: Literal `-1u64` overflows u64", "-1u64" ),
+      ( "CREATE GRAPH books (b:Book {sizes: [1u8, 300u8]})", "Syntax Error: Literal `300u8` overflows u8", "300u8" ),
+      ( "MATCH (b:Book) WHERE b.price > 1e400 RETURN b", "Syntax Error: Literal `1e400` overflows f64", "1e400" ),
+      ( "MATCH (b:Book) WHERE b.stock > - 129i8 RETURN b", "Syntax Error: Literal `-129i8` overflows i8", "- 129i8" ),
+      ( "MATCH (b:Book) WHERE b.stock IN [1u8, 256u8] RETURN b", "Syntax Error: Literal `256u8` overflows u8", "256u8" ),
+      ( "MATCH (b:Book) SET b.stock = 256u8", "Syntax Error: Literal `256u8` overflows u8", "256u8" ),
+      ( "MATCH (b:Book) SET b.offset = - 129i8", "Syntax Error: Literal `-129i8` overflows i8", "- 129i8" ),
+    ];
+    for ( query, err, literal ) in errors.into_iter() 
+    {
+      let t2 = process_query( query, build_id(), db_nickname() );
+      assert_eq!( t2.err_state, Some( String::from( err )), "{}", query );
+      let span = t2.error.unwrap().span.unwrap();
+      assert_eq!( &query[span.start..span.end], literal, "{}", query );
+    }
   }
-  
+
   #[test]
-  fn test_try_update_paren_statements () 
+  fn test_process_match_properties () 
   {
-    let mut tb = TransactionBuilder::new();
-    tb.add_open_node( SyntaxToken::new(SyntaxTokenType::OpenNode, String::from( "(" )));
-    let res = tb.try_update_paren_statements( &SyntaxToken::new( SyntaxTokenType::Label, String::from( "abc" )));
+    let t = process_query( "MATCH (n {name: 'Alice', age: 38}) RETURN n", build_id(), db_nickname() );
 
-    assert_eq!( res, true );
-    assert_eq!( tb.paren_statements.len(), 1 );
-    assert_eq!( tb.paren_statements.get(0).unwrap().is_open, true );
-    assert_eq!( tb.paren_statements.get(0).unwrap().transaction_label, Some( String::from( "abc" )));
-    assert_eq!( tb.err_state.is_some(), false );
+    assert_eq!( t.err_state, None );
+    let stmt = t.read_clause.as_ref().unwrap().match_statements.get( 0 ).unwrap();
+    assert_eq!( stmt.properties, vec![
+      NEProperty::KvpString(( String::from( "name" ), String::from( "Alice" ))),
+      NEProperty::KvpI64(( String::from( "age" ), 38 )),
+    ]);
   }
 
   #[test]
-  fn test_try_update_bracket_statements () 
+  fn test_process_edge_direction () 
   {
-    let mut tb = TransactionBuilder::new();
-    tb.add_open_bracket( &SyntaxToken::new( SyntaxTokenType::OpenBracket, String::from( "{" )));
+    let t = process_query( 
+      "CREATE GRAPH devs (alice:Developer) (bob:Developer) (alice)-[:KNOWS]->(bob) (alice)<-[:KNOWS]-(bob) (alice)-[:KNOWS]-(bob)", 
+      build_id(), db_nickname() );
 
-    let res = tb.try_update_bracket_statements( &SyntaxToken::new( SyntaxTokenType::Label, String::from( "abc" )));
-    
-    assert_eq!( res, true );
-    assert_eq!( tb.bracket_statements.len(), 1 );
-    assert_eq!( tb.bracket_statements.get(0).unwrap().is_open, true );
-    assert_eq!( tb.bracket_statements.get(0).unwrap().transaction_label, Some( String::from( "abc" )));
-    assert_eq!( tb.err_state.is_some(), false );
+    assert_eq!( t.err_state, None );
+    assert_eq!( t.edge_statements.len(), 3 );
+    assert_eq!( t.edge_statements.get( 0 ).unwrap().direction, DirectionType::Right );
+    assert_eq!( t.edge_statements.get( 1 ).unwrap().direction, DirectionType::Left );
+    assert_eq!( t.edge_statements.get( 2 ).unwrap().direction, DirectionType::Undirected );
+
+    let t1 = process_query( "CREATE GRAPH devs (alice:Developer) (bob:Developer) (alice)-->(bob)", build_id(), db_nickname() );
+    assert_eq!( t1.err_state, Some( String::from( "Syntax Error: Edge requires a type" )));
   }
 
   #[test]
-  fn test_try_update_match_statements () 
+  fn test_process_match_relationship () 
   {
-    let mut tb = TransactionBuilder::new();
-    tb.add_match_token();
+    let t = process_query( "MATCH (a:Developer)<-[r:KNOWS {since: 2019}]-(b) FROM devs RETURN b", build_id(), db_nickname() );
 
-    let res = tb.try_update_match_statements( &SyntaxToken::new( SyntaxTokenType::Label, String::from( "abc" )));
-    
-    assert_eq!( res, true );
-    assert_eq!( tb.match_statements.len(), 1 );
-    assert_eq!( tb.match_statements.get(0).unwrap().is_open, true );
-    assert_eq!( tb.match_statements.get(0).unwrap().transaction_label, Some( String::from( "abc" )));
-    assert_eq!( tb.err_state.is_some(), false );
+    assert_eq!( t.err_state, None );
+    let read_clause = t.read_clause.as_ref().unwrap();
+    assert_eq!( read_clause.match_statements.len(), 2 );
+    assert_eq!( read_clause.bracket_statements.len(), 1 );
+
+    let bracket = read_clause.bracket_statements.get( 0 ).unwrap();
+    assert_eq!( bracket.transaction_label, Some( String::from( "r" )));
+    assert_eq!( bracket.primary_label, Some( String::from( "KNOWS" )));
+    assert_eq!( bracket.edge_dir, DirectionType::Left );
+    assert_eq!( bracket.properties.len(), 1 );
+
+    let ( left, right ) = read_clause.bracket_neighbors( bracket );
+    assert_eq!( left.unwrap().transaction_label, Some( String::from( "a" )));
+    assert_eq!( right.unwrap().transaction_label, Some( String::from( "b" )));
+    assert_eq!( t.node_statements.len(), 0 );
+    assert_eq!( t.node_ref_statements.len(), 0 );
+
+    // comma separated patterns stay in the MATCH
+    let t = process_query( "MATCH (m)-[:MANAGES]->(b), (b)-[:KNOWS]->(c) FROM devs RETURN c", build_id(), db_nickname() );
+    assert_eq!( t.err_state, None );
+    let read_clause = t.read_clause.as_ref().unwrap();
+    assert_eq!( read_clause.match_statements.len(), 4 );
+    assert_eq!( read_clause.bracket_statements.len(), 2 );
+    assert_eq!( t.node_statements.len(), 0 );
   }
 
   #[test]
-  fn test_add_x_label () 
+  fn test_process_set () 
   {
-    // !!! needs rest of branches
-    let mut tb = TransactionBuilder::new();
-    tb.add_open_node( SyntaxToken::new(SyntaxTokenType::OpenNode, String::from( "(" )));
-    tb.add_x_label( SyntaxToken::new( SyntaxTokenType::Label, String::from( "abc" )) );
+    let query_string = "
+      MATCH (p:Person {name: 'Jennifer'}) 
+      SET p.birthdate = '1980-01-01', p += {age: 44, admin: true} p:Lead:Admin
+      FROM devs 
+      RETURN p";
+    let t = process_query( query_string, build_id(), db_nickname() );
 
-    assert_eq!( tb.paren_statements.len(), 1 );
-    assert_eq!( tb.paren_statements.get(0).unwrap().is_open, true );
-    assert_eq!( tb.paren_statements.get(0).unwrap().transaction_label, Some( String::from( "abc" )));
-    assert_eq!( tb.err_state.is_some(), false );
+    assert_eq!( t.err_state, None );
+    assert_eq!( t.has_writes(), true );
+    assert_eq!( t.set_statements.len(), 3 );
+    assert_eq!( t.set_statements[0].set_type, Some( SetType::Property ));
+    assert_eq!( t.set_statements[0].properties, 
+      vec![ NEProperty::KvpString(( String::from( "birthdate" ), String::from( "1980-01-01" ))) ] );
+    assert_eq!( t.set_statements[1].set_type, Some( SetType::Merge ));
+    assert_eq!( t.set_statements[1].properties.len(), 2 );
+    assert_eq!( t.set_statements[2].set_type, Some( SetType::Label ));
+    assert_eq!( t.set_statements[2].labels, vec![ String::from( "Lead" ), String::from( "Admin" ) ] );
+    assert_eq!( t.from_clause.as_ref().unwrap().graph_name, String::from( "devs" ));
+    assert_eq!( t.return_clause.as_ref().unwrap().items[0].expression, String::from( "p" ));
+    assert_eq!( t.read_clause.as_ref().unwrap().match_statements[0].properties.len(), 1 );
+
+    let t1 = process_query( "MATCH (p) SET p.name = Jennifer", build_id(), db_nickname() );
+    assert_eq!( t1.err_state, Some( String::from( "Syntax Error: Set `Jennifer`" )));
+
+    let t2 = process_query( "MATCH (p) SET p.name = RETURN p", build_id(), db_nickname() );
+    assert_eq!( t2.err_state, Some( String::from( "Syntax Error: Set `p`" )));
+
+    let t3 = process_query( "MATCH (p) SET p = {name: 'Jennifer'}", build_id(), db_nickname() );
+    assert_eq!( t3.err_state, Some( String::from( "Syntax Error: Set requires a property key" )));
   }
 
   #[test]
-  fn test_add_bracket () 
+  fn test_process_merge ()
   {
-    let mut tb = TransactionBuilder::new();
-    tb.add_open_bracket( &SyntaxToken::new( SyntaxTokenType::OpenBracket, String::from( "{" )));
+    let query_string = "
+      MERGE (n:Person {name: 'Alice'})
+      ON CREATE SET n.created = true
+      ON MATCH SET n.seen = 2, n:Known
+      SET n.active = true
+      FROM devs
+      RETURN n";
+    let t = process_query( query_string, build_id(), db_nickname() );
+    assert_eq!( t.err_state, None );
+    assert_eq!( t.merge, true );
+    assert_eq!( t.has_writes(), true );
+    assert_eq!( t.read_clause.as_ref().unwrap().match_statements[0].properties.len(), 1 );
+    assert_eq!( t.set_statements.len(), 4 );
+    assert_eq!( t.set_statements[0].merge_action, Some( MergeAction::OnCreate ));
+    assert_eq!( t.set_statements[1].merge_action, Some( MergeAction::OnMatch ));
+    assert_eq!( t.set_statements[2].merge_action, Some( MergeAction::OnMatch ));
+    assert_eq!( t.set_statements[3].merge_action, None );
+
+    let t1 = process_query( "MERGE (a:Person)-[:KNOWS]->(b:Person) FROM devs", build_id(), db_nickname() );
+    assert_eq!( t1.err_state, None );
+    assert_eq!( t1.read_clause.as_ref().unwrap().bracket_statements.len(), 1 );
+
+    let t2 = process_query( "MATCH (a) MERGE (b:Person)", build_id(), db_nickname() );
+    assert_eq!( t2.err_state, Some( String::from( "Syntax Error: Merge" )));
+
+    let t3 = process_query( "MERGE (b:Person) ON SET b.x = 1", build_id(), db_nickname() );
+    assert_eq!( t3.err_state, Some( String::from( "Syntax Error: On" )));
+
+    let t4 = process_query( "MERGE (b:Person) ON CREATE", build_id(), db_nickname() );
+    assert_eq!( t4.err_state, Some( String::from( "Syntax Error: On" )));
+
+    let t5 = process_query( "MATCH (b:Person) ON CREATE SET b.x = 1", build_id(), db_nickname() );
+    assert_eq!( t5.err_state, Some( String::from( "Syntax Error: On" )));
+  }
 
-    assert_eq!( tb.bracket_statements.len(), 1 );
-    assert_eq!( tb.bracket_statements.get(0).unwrap().is_open, true );
-    assert_eq!( tb.err_state.is_some(), false );
+  #[test]
+  fn test_process_where ()
+  {
+    let t = process_query( 
+      "MATCH (n:Station WHERE n.name STARTS WITH 'Preston') FROM rail RETURN n", build_id(), db_nickname() );
+    assert_eq!( t.err_state, None );
+    let read_clause = t.read_clause.as_ref().unwrap();
+    assert_eq!( read_clause.match_statements.len(), 1 );
+    assert_eq!( read_clause.match_statements[0].is_open, false );
+    assert_eq!( read_clause.match_statements[0].primary_label, Some( String::from( "Station" )));
+    assert_eq!( read_clause.predicates.len(), 1 );
+    assert_eq!( t.from_clause.as_ref().unwrap().graph_name, String::from( "rail" ));
+
+    let t1 = process_query( 
+      "MATCH (a)-[r:KNOWS WHERE r.since < 2000]->(b) WHERE a.age > 30 AND (b.name IN ['Bob'] OR b.age IS NULL) SET a.x = 1",
+      build_id(), db_nickname() );
+    assert_eq!( t1.err_state, None );
+    assert_eq!( t1.read_clause.as_ref().unwrap().bracket_statements[0].primary_label, Some( String::from( "KNOWS" )));
+    assert_eq!( t1.read_clause.as_ref().unwrap().bracket_statements[0].edge_dir, DirectionType::Right );
+    assert_eq!( t1.read_clause.as_ref().unwrap().predicates.len(), 2 );
+    assert_eq!( t1.read_clause.as_ref().unwrap().predicates[1].variables(), vec![ String::from( "a" ), String::from( "b" ) ] );
+    assert_eq!( t1.set_statements.len(), 1 );
+
+    let t2 = process_query( "MATCH (n) WHERE FROM devs", build_id(), db_nickname() );
+    assert_eq!( t2.err_state, Some( String::from( "Syntax Error: Where" )));
+
+    let t3 = process_query( "CREATE GRAPH devs (n:Person) WHERE n.x = 1", build_id(), db_nickname() );
+    assert_eq!( t3.err_state, Some( String::from( "Syntax Error: Where" )));
+
+    let t4 = process_query( "MERGE (n:Person) WHERE n.x = 1", build_id(), db_nickname() );
+    assert_eq!( t4.err_state, Some( String::from( "Syntax Error: Where" )));
   }
-  // -------------------------------------------------------------------------------------------------------------------
 
   #[test]
-  fn test_process_match_query () 
+  fn test_process_remove ()
   {
-    let query_string = "MATCH (:Movie {title: 'Wall Street'})";
-    let t = process_query( &query_string, build_id(), db_nickname() );
-    
+    let t = process_query( "MATCH (n:Person:Employee) REMOVE n:Employee:Intern, n.age RETURN n", build_id(), db_nickname() );
     assert_eq!( t.err_state, None );
-    assert_eq!( t.read_clause.as_ref().unwrap().match_statements.len(), 1 );
-    assert_eq!( t.read_clause.as_ref().unwrap().match_statements.get(0).as_ref().unwrap().order, 1 );
-    assert_eq!( t.read_clause.as_ref().unwrap().match_statements.get(0).as_ref().unwrap().is_open, false );
-    assert_eq!( t.read_clause.as_ref().unwrap().match_statements.get(0).as_ref().unwrap().labels_complete, true );
-    assert_eq!( t.read_clause.as_ref().unwrap().match_statements.get(0).as_ref().unwrap().kvps_complete, true );
-    assert_eq!( t.read_clause.as_ref().unwrap().match_statements.get(0).as_ref().unwrap().transaction_label, None );
-    assert_eq!( 
-      t.read_clause.as_ref().unwrap().match_statements.get(0).as_ref().unwrap().primary_label, 
-      Some( String::from( "Movie" )));
-    assert_eq!( 
-      t.read_clause.as_ref().unwrap().match_statements.get(0).as_ref().unwrap().kv_str.len(), 1 );
-    assert_eq!( 
-      t.read_clause.as_ref().unwrap().match_statements.get(0).as_ref().unwrap().kv_str.get(0).as_ref().unwrap().key, 
-      String::from( "title" ));
-    assert_eq!( 
-      t.read_clause.as_ref().unwrap().match_statements.get(0).as_ref().unwrap().kv_str.get(0).as_ref().unwrap().val, 
-      Some( String::from( "Wall Street" )));
+    assert_eq!( t.has_writes(), true );
+    assert_eq!( t.read_clause.as_ref().unwrap().match_statements[0].primary_label, Some( String::from( "Person" )));
+    assert_eq!( t.read_clause.as_ref().unwrap().match_statements[0].labels, vec![ String::from( "Employee" ) ] );
+    assert_eq!( t.remove_statements.len(), 2 );
+    assert_eq!( t.remove_statements[0].labels, vec![ String::from( "Employee" ), String::from( "Intern" ) ] );
+    assert_eq!( t.remove_statements[1].key, Some( String::from( "age" )));
+    assert_eq!( t.return_clause.as_ref().unwrap().items[0].expression, String::from( "n" ));
+
+    let t1 = process_query( "MATCH (n) REMOVE n", build_id(), db_nickname() );
+    assert_eq!( t1.err_state, Some( String::from( "Syntax Error: Remove `n`" )));
+
+    let t2 = process_query( "MATCH (n) REMOVE n.age:Employee", build_id(), db_nickname() );
+    assert_eq!( t2.err_state, Some( String::from( "Syntax Error: Remove `Employee`" )));
+
+    let t3 = process_query( "REMOVE n:Employee", build_id(), db_nickname() );
+    assert_eq!( t3.err_state.is_some(), true );
   }
 
   #[test]
-  fn test_se1 () 
+  fn test_process_delete ()
   {
-    let mut tb = TransactionBuilder::new();
-    tb.add_match_token();
-    tb.add_open_node( SyntaxToken::new(SyntaxTokenType::OpenNode, String::from( "(" )));
-    tb.close_statement( &SyntaxToken::new( SyntaxTokenType::CloseNode, String::from( "(" )) );
-    
-    println!( "{:?}", tb );
+    let t = process_query( "MATCH (a)-[r:KNOWS]->(b) DETACH DELETE a, r FROM devs", build_id(), db_nickname() );
+    assert_eq!( t.err_state, None );
+    assert_eq!( t.has_writes(), true );
+    assert_eq!( t.delete_statement.as_ref().unwrap().detach, true );
+    assert_eq!( t.delete_statement.as_ref().unwrap().transaction_labels, vec![ String::from( "a" ), String::from( "r" ) ] );
+    assert_eq!( t.from_clause.as_ref().unwrap().graph_name, String::from( "devs" ));
+    assert_eq!( t.read_clause.as_ref().unwrap().bracket_statements.len(), 1 );
+
+    let t1 = process_query( "DELETE GRAPH devs", build_id(), db_nickname() );
+    assert_eq!( t1.err_state, None );
+    assert_eq!( t1.delete_statement.as_ref().unwrap().graph_name, Some( String::from( "devs" )));
+
+    let t2 = process_query( "MATCH (n) DELETE", build_id(), db_nickname() );
+    assert_eq!( t2.err_state, Some( String::from( "Syntax Error: Delete" )));
+
+    let t3 = process_query( "MATCH (n) DETACH RETURN n", build_id(), db_nickname() );
+    assert_eq!( t3.err_state, Some( String::from( "Syntax Error: Detach" )));
+
+    let t4 = process_query( "MATCH (n) DELETE n.name", build_id(), db_nickname() );
+    assert_eq!( t4.err_state, Some( String::from( "Syntax Error: Delete `n.name`" )));
+  }
+
+  #[test]
+  fn test_add_clause () 
+  {
+    let query = parse_query( &parse_syntax( "MATCH (a)-[r]->(b) RETURN a" )).unwrap();
+    let mut builder = TransactionBuilder::new( Vec::new() );
+    assert_eq!( builder.add_clause( &query.clauses[0] ), Ok( true ));
+
+    let read_clause = builder.read_clause.as_ref().unwrap();
+    assert_eq!( read_clause.order, 0 );
+    assert_eq!( read_clause.match_statements[0].order, 1 );
+    assert_eq!( read_clause.bracket_statements[0].order, 2 );
+    assert_eq!( read_clause.match_statements[1].order, 3 );
+    assert_eq!( builder.current_order, 4 );
+    assert_eq!( builder.is_reading(), true );
+
+    assert_eq!( builder.add_clause( &query.clauses[1] ), Ok( true ));
+    assert_eq!( builder.add_clause( &query.clauses[1] ).map_err( | err | err.message ), Err( String::from( "Syntax Error: Return" )));
+  }
+
+  #[test]
+  fn test_clause_errors () 
+  {
+    // a repeated CREATE adds its patterns to the first one
+    let t1 = process_query( "CREATE GRAPH devs (a:Person) CREATE (b:Person)-[:KNOWS]->(a)", build_id(), db_nickname() );
+    assert_eq!( t1.err_state, None );
+    assert_eq!( t1.create_statement.as_ref().unwrap().graph_name, Some( String::from( "devs" )));
+    assert_eq!( t1.node_statements.iter().map( | n | n.query_order ).collect::<Vec<u16>>(), vec![ 1, 2 ] );
+    assert_eq!( t1.edge_statements[0].query_order, 3 );
+    assert_eq!( t1.node_ref_statements[0].query_order, 4 );
+    assert_eq!( t1.query_order, 5 );
+
+    let t5 = process_query( "CREATE (a:Person) CREATE GRAPH devs (b:Person)", build_id(), db_nickname() );
+    assert_eq!( t5.err_state, Some( String::from( "Syntax Error: Create" )));
+
+    let t2 = process_query( "FROM devs FROM ops MATCH (n) RETURN n", build_id(), db_nickname() );
+    assert_eq!( t2.err_state, Some( String::from( "Syntax Error: From" )));
+
+    let t3 = process_query( "MATCH (n) RETURN n AS a AS b", build_id(), db_nickname() );
+    assert_eq!( t3.err_state, Some( String::from( "Syntax Error: Return `AS`" )));
+
+    let t4 = process_query( "CREATE ()", build_id(), db_nickname() );
+    assert_eq!( t4.err_state, Some( String::from( "Syntax Error: Paren Statement" )));
+  }
+
+  #[test]
+  fn test_error_spans () 
+  {
+    let query = "MATCH (n)\nCREATE (a:Person)\nCREATE GRAPH devs (b:Person)";
+    let t = process_query( query, build_id(), db_nickname() );
+    assert_eq!( t.err_state, Some( String::from( "Syntax Error: Create" )));
+    let err = t.error.unwrap();
+    assert_eq!( err.render( query ), String::from( 
+      "Syntax Error: Create\n --> line 3, column 1\n  |\n3 | CREATE GRAPH devs (b:Person)\n  | ^^^^^^ expected a pattern, only the first `CREATE` names the graph, found `CREATE`" ));
+
+    let t1 = process_query( "MATCH (n WHERE n.age > 3 RETURN n", build_id(), db_nickname() );
+    assert_eq!( t1.error.as_ref().unwrap().expected, Some( String::from( "`)`" )));
+    assert_eq!( t1.error.as_ref().unwrap().span.unwrap().column, 26 );
+
+    // lowering errors point at the node, relationship or value they are about
+    let query2 = "MATCH (a)-[r:KNOWS {since: year}]->(b) RETURN a";
+    let err2 = process_query( query2, build_id(), db_nickname() ).error.unwrap();
+    assert_eq!( err2.render( query2 ).ends_with( "  |                            ^^^^ expected a literal or a parameter, found `year`" ), true );
+
+    let query3 = "MERGE (a:Person)-[:KNOWS WHERE r.since > 1]->(b)";
+    let err3 = process_query( query3, build_id(), db_nickname() ).error.unwrap();
+    assert_eq!(( err3.span.unwrap().column, err3.found ), ( 17, Some( String::from( "`WHERE`" ))));
+
+    let err4 = process_query( "CREATE GRAPH devs (a:Developer)-[:KNOWS*2]->(b:Developer)", build_id(), db_nickname() ).error.unwrap();
+    assert_eq!(( err4.span.unwrap().column, err4.note() ), ( 32, Some( String::from( "expected a single relationship, found `*2`" ))));
+  }
+
+  #[test]
+  fn test_process_script () 
+  {
+    let script = "
+      // seed data
+      CREATE GRAPH devs (alice:Developer) (bob:Developer);
+      /* later */ MATCH (n:Developer) FROM devs SET n.team = $team;
+      MATCH (n) FROM devs RETURN n
+    ";
+    let params = vec![ ( String::from( "team" ), Value::from( "core" )) ];
+    let statements = process_script( script, &params, build_id(), db_nickname() );
+    assert_eq!( statements.len(), 3 );
+    assert_eq!( statements[0].1.node_statements.len(), 2 );
+    assert_eq!( statements[1].1.set_statements[0].properties.len(), 1 );
+    assert_eq!( statements[2].1.return_clause.is_some(), true );
+    assert_eq!(( statements[1].0.line, statements[1].0.column ), ( 4, 19 ));
+    assert_eq!( &script[statements[2].0.start..statements[2].0.end], "MATCH (n) FROM devs RETURN n" );
+
+    // every statement is planned on its own
+    let statements1 = process_script( "MATCH (n) RETURN n; MATCH (n RETURN n", &Vec::new(), build_id(), db_nickname() );
+    assert_eq!( statements1[0].1.err_state, None );
+    assert_eq!( statements1[1].1.error.as_ref().unwrap().span.unwrap().column, 30 );
+  }
 
-    //let t = process_query( "MATCH ()", build_id(), db_nickname() );
-    //assert_eq!( t.read_clause.is_some(), true );
-    //assert_eq!( t.read_clause.as_ref().unwrap().order, 0 );
-    //assert_eq!( t.read_clause.as_ref().unwrap().match_statements.len(), 1 );
+  #[test]
+  fn test_se1 () 
+  {
+    let t = process_query( "MATCH ()", build_id(), db_nickname() );
+    assert_eq!( t.read_clause.is_some(), true );
+    assert_eq!( t.read_clause.as_ref().unwrap().order, 0 );
+    assert_eq!( t.read_clause.as_ref().unwrap().match_statements.len(), 1 );
 
-    //let stmt_res = t.read_clause.as_ref().unwrap().match_statements.get( 0 );
-    //assert_eq!( stmt_res.is_some(), true );
-    //assert_eq!( stmt_res.as_ref().unwrap().order, 1 );
-    //assert_eq!( stmt_res.as_ref().unwrap().is_open, false );
+    let stmt_res = t.read_clause.as_ref().unwrap().match_statements.get( 0 );
+    assert_eq!( stmt_res.is_some(), true );
+    assert_eq!( stmt_res.as_ref().unwrap().order, 1 );
+    assert_eq!( stmt_res.as_ref().unwrap().is_open, false );
   }
 
   #[test]
@@ -0,0 +1,515 @@
+use crate::common::{ Edge, NEProperty, Node };
+
+/* @version 0.3.0 */
+
+/*
+  Query output.
+
+  ResultSet
+    :: columns    (names in RETURN order, aliases applied)
+    :: rows       (one Value per column)
+
+  Value covers every external KVP type plus graph values (node, edge, path), lists, maps and null.
+*/
+
+/// Ordered walk through a graph: nodes[0] edges[0] nodes[1] ... nodes[n]
+#[derive( Debug, Clone, PartialEq )]
+pub struct Path
+{
+  pub nodes: Vec<Node>,
+  pub edges: Vec<Edge>,
+}
+impl Path
+{
+  pub fn new ( nodes: Vec<Node>, edges: Vec<Edge> ) -> Path { Path { nodes: nodes, edges: edges } }
+
+  /// Number of relationships in the path
+  pub fn len ( &self ) -> usize { self.edges.len() }
+}
+
+/// Single query output value
+#[derive( Debug, Clone, PartialEq )]
+pub enum Value
+{
+  Null,
+  String( String ),
+  Boolean( bool ),
+
+  I8( i8 ), I16( i16 ), I32( i32 ), I64( i64 ), I128( i128 ),
+  U8( u8 ), U16( u16 ), U32( u32 ), U64( u64 ), U128( u128 ),
+  F32( f32 ), F64( f64 ),
+
+  List( Vec<Value> ),
+  Map( Vec<( String, Value )> ), // query parameters, keys in the order given
+
+  Node( Node ),
+  Edge( Edge ),
+  Path( Path ),
+}
+
+impl Value
+{
+  pub fn is_null ( &self ) -> bool { *self == Value::Null }
+
+  pub fn as_str ( &self ) -> Option<&str>
+  {
+    match self
+    {
+      Value::String( s ) => Some( s ),
+      _ => None
+    }
+  }
+
+  pub fn as_bool ( &self ) -> Option<bool>
+  {
+    match self
+    {
+      Value::Boolean( b ) => Some( *b ),
+      _ => None
+    }
+  }
+
+  /// Any integer that fits in an i64
+  pub fn as_i64 ( &self ) -> Option<i64>
+  {
+    match self
+    {
+      Value::I8( v ) => Some( *v as i64 ),
+      Value::I16( v ) => Some( *v as i64 ),
+      Value::I32( v ) => Some( *v as i64 ),
+      Value::I64( v ) => Some( *v ),
+      Value::I128( v ) => i64::try_from( *v ).ok(),
+      Value::U8( v ) => Some( *v as i64 ),
+      Value::U16( v ) => Some( *v as i64 ),
+      Value::U32( v ) => Some( *v as i64 ),
+      Value::U64( v ) => i64::try_from( *v ).ok(),
+      Value::U128( v ) => i64::try_from( *v ).ok(),
+      _ => None
+    }
+  }
+
+  /// Any integer that fits in a u64
+  pub fn as_u64 ( &self ) -> Option<u64>
+  {
+    match self
+    {
+      Value::U8( v ) => Some( *v as u64 ),
+      Value::U16( v ) => Some( *v as u64 ),
+      Value::U32( v ) => Some( *v as u64 ),
+      Value::U64( v ) => Some( *v ),
+      Value::U128( v ) => u64::try_from( *v ).ok(),
+      _ =>
+      {
+        let signed = self.as_i128();
+        if signed.is_some() { return u64::try_from( signed.unwrap() ).ok(); }
+        None
+      }
+    }
+  }
+
+  /// Any integer that fits in an i128
+  pub fn as_i128 ( &self ) -> Option<i128>
+  {
+    match self
+    {
+      Value::I128( v ) => Some( *v ),
+      Value::U64( v ) => Some( *v as i128 ),
+      Value::U128( v ) => i128::try_from( *v ).ok(),
+      _ =>
+      {
+        let small = self.as_i64();
+        if small.is_some() { return Some( small.unwrap() as i128 ); }
+        None
+      }
+    }
+  }
+
+  /// Any non-negative integer as a u128
+  pub fn as_u128 ( &self ) -> Option<u128>
+  {
+    match self
+    {
+      Value::U128( v ) => Some( *v ),
+      _ =>
+      {
+        let signed = self.as_i128();
+        if signed.is_some() { return u128::try_from( signed.unwrap() ).ok(); }
+        None
+      }
+    }
+  }
+
+  /// Any float or integer as an f64
+  pub fn as_f64 ( &self ) -> Option<f64>
+  {
+    match self
+    {
+      Value::F32( v ) => Some( *v as f64 ),
+      Value::F64( v ) => Some( *v ),
+      Value::U128( v ) => Some( *v as f64 ),
+      _ =>
+      {
+        let int = self.as_i128();
+        if int.is_some() { return Some( int.unwrap() as f64 ); }
+        None
+      }
+    }
+  }
+
+  pub fn as_list ( &self ) -> Option<&Vec<Value>>
+  {
+    match self
+    {
+      Value::List( l ) => Some( l ),
+      _ => None
+    }
+  }
+
+  pub fn as_map ( &self ) -> Option<&Vec<( String, Value )>>
+  {
+    match self
+    {
+      Value::Map( m ) => Some( m ),
+      _ => None
+    }
+  }
+
+  /// Value of a map key, null for missing keys and other values
+  pub fn get ( &self, key: &str ) -> Value
+  {
+    match self
+    {
+      Value::Map( m ) => m.iter().find( | ( k, _ ) | k == key ).map( | ( _, v ) | v.clone() ).unwrap_or( Value::Null ),
+      _ => Value::Null
+    }
+  }
+
+  pub fn as_node ( &self ) -> Option<&Node>
+  {
+    match self
+    {
+      Value::Node( n ) => Some( n ),
+      _ => None
+    }
+  }
+
+  pub fn as_edge ( &self ) -> Option<&Edge>
+  {
+    match self
+    {
+      Value::Edge( e ) => Some( e ),
+      _ => None
+    }
+  }
+
+  pub fn as_path ( &self ) -> Option<&Path>
+  {
+    match self
+    {
+      Value::Path( p ) => Some( p ),
+      _ => None
+    }
+  }
+
+  /// Value equality across numeric widths (1 = 1.0), None when either side is null
+  pub fn equals ( &self, other: &Value ) -> Option<bool>
+  {
+    if self.is_null() || other.is_null() { return None; }
+
+    let int_a = self.as_i128();
+    let int_b = other.as_i128();
+    if int_a.is_some() && int_b.is_some() { return Some( int_a.unwrap() == int_b.unwrap() ); }
+
+    if self.is_numeric() && other.is_numeric()
+    {
+      if self.as_u128().is_some() && other.as_u128().is_some() { return Some( self.as_u128() == other.as_u128() ); }
+      return Some( self.as_f64().unwrap() == other.as_f64().unwrap() );
+    }
+
+    // lists are equal element by element, unknown when any pair is
+    if self.as_list().is_some() && other.as_list().is_some()
+    {
+      let ( a, b ) = ( self.as_list().unwrap(), other.as_list().unwrap() );
+      if a.len() != b.len() { return Some( false ); }
+      let mut unknown = false;
+      for ( x, y ) in a.iter().zip( b.iter() )
+      {
+        match x.equals( y )
+        {
+          Some( false ) => { return Some( false ); }
+          None => { unknown = true; }
+          _ => {}
+        }
+      }
+      if unknown { return None; }
+      return Some( true );
+    }
+    Some( self == other )
+  }
+
+  pub fn is_numeric ( &self ) -> bool { self.as_f64().is_some() }
+
+  /// Key-value property holding the value, None for null and values that cannot be stored (nodes, mixed lists)
+  pub fn to_property ( &self, key: String ) -> Option<NEProperty>
+  {
+    match self
+    {
+      Value::String( v ) => Some( NEProperty::KvpString(( key, v.clone() ))),
+      Value::Boolean( v ) => Some( NEProperty::KvpBoolean(( key, *v ))),
+      Value::I8( v ) => Some( NEProperty::KvpI8(( key, *v ))),
+      Value::I16( v ) => Some( NEProperty::KvpI16(( key, *v ))),
+      Value::I32( v ) => Some( NEProperty::KvpI32(( key, *v ))),
+      Value::I64( v ) => Some( NEProperty::KvpI64(( key, *v ))),
+      Value::I128( v ) => Some( NEProperty::KvpI128(( key, *v ))),
+      Value::U8( v ) => Some( NEProperty::KvpU8(( key, *v ))),
+      Value::U16( v ) => Some( NEProperty::KvpU16(( key, *v ))),
+      Value::U32( v ) => Some( NEProperty::KvpU32(( key, *v ))),
+      Value::U64( v ) => Some( NEProperty::KvpU64(( key, *v ))),
+      Value::U128( v ) => Some( NEProperty::KvpU128(( key, *v ))),
+      Value::F32( v ) => Some( NEProperty::KvpF32(( key, *v ))),
+      Value::F64( v ) => Some( NEProperty::KvpF64(( key, *v ))),
+      Value::List( vals ) =>
+      {
+        // an empty list has no element type, it is stored as a list of strings
+        if vals.iter().all( | v | v.as_str().is_some() )
+        {
+          return Some( NEProperty::ListString(( key, vals.iter().map( | v | v.as_str().unwrap().to_string() ).collect() )));
+        }
+        if vals.iter().all( | v | v.as_bool().is_some() )
+        {
+          return Some( NEProperty::ListBool(( key, vals.iter().map( | v | v.as_bool().unwrap() ).collect() )));
+        }
+        if vals.iter().all( | v | v.as_i64().is_some() )
+        {
+          return Some( NEProperty::ListI64(( key, vals.iter().map( | v | v.as_i64().unwrap() ).collect() )));
+        }
+        // integers mixed with floats are stored as floats
+        if vals.iter().all( | v | v.is_numeric() )
+        {
+          return Some( NEProperty::ListFloat(( key, vals.iter().map( | v | v.as_f64().unwrap() ).collect() )));
+        }
+        None
+      }
+      _ => None,
+    }
+  }
+}
+
+impl From<&NEProperty> for Value
+{
+  fn from ( v: &NEProperty ) -> Value
+  {
+    match v
+    {
+      NEProperty::Tag( _ ) => Value::Null,
+      NEProperty::KvpString(( _, v )) => Value::String( v.clone() ),
+      NEProperty::KvpBoolean(( _, v )) => Value::Boolean( *v ),
+      NEProperty::KvpI8(( _, v )) => Value::I8( *v ),
+      NEProperty::KvpI16(( _, v )) => Value::I16( *v ),
+      NEProperty::KvpI32(( _, v )) => Value::I32( *v ),
+      NEProperty::KvpI64(( _, v )) => Value::I64( *v ),
+      NEProperty::KvpI128(( _, v )) => Value::I128( *v ),
+      NEProperty::KvpU8(( _, v )) => Value::U8( *v ),
+      NEProperty::KvpU16(( _, v )) => Value::U16( *v ),
+      NEProperty::KvpU32(( _, v )) => Value::U32( *v ),
+      NEProperty::KvpU64(( _, v )) => Value::U64( *v ),
+      NEProperty::KvpU128(( _, v )) => Value::U128( *v ),
+      NEProperty::KvpF32(( _, v )) => Value::F32( *v ),
+      NEProperty::KvpF64(( _, v )) => Value::F64( *v ),
+      NEProperty::ListString(( _, v )) => Value::List( v.iter().map( | s | Value::String( s.clone() )).collect() ),
+      NEProperty::ListBool(( _, v )) => Value::List( v.iter().map( | b | Value::Boolean( *b )).collect() ),
+      NEProperty::ListI64(( _, v )) => Value::List( v.iter().map( | i | Value::I64( *i )).collect() ),
+      NEProperty::ListFloat(( _, v )) => Value::List( v.iter().map( | f | Value::F64( *f )).collect() ),
+    }
+  }
+}
+
+impl From<&str> for Value { fn from ( v: &str ) -> Value { Value::String( v.to_string() ) } }
+impl From<String> for Value { fn from ( v: String ) -> Value { Value::String( v ) } }
+impl From<bool> for Value { fn from ( v: bool ) -> Value { Value::Boolean( v ) } }
+impl From<i8> for Value { fn from ( v: i8 ) -> Value { Value::I8( v ) } }
+impl From<i16> for Value { fn from ( v: i16 ) -> Value { Value::I16( v ) } }
+impl From<i32> for Value { fn from ( v: i32 ) -> Value { Value::I32( v ) } }
+impl From<i64> for Value { fn from ( v: i64 ) -> Value { Value::I64( v ) } }
+impl From<i128> for Value { fn from ( v: i128 ) -> Value { Value::I128( v ) } }
+impl From<u8> for Value { fn from ( v: u8 ) -> Value { Value::U8( v ) } }
+impl From<u16> for Value { fn from ( v: u16 ) -> Value { Value::U16( v ) } }
+impl From<u32> for Value { fn from ( v: u32 ) -> Value { Value::U32( v ) } }
+impl From<u64> for Value { fn from ( v: u64 ) -> Value { Value::U64( v ) } }
+impl From<u128> for Value { fn from ( v: u128 ) -> Value { Value::U128( v ) } }
+impl From<f32> for Value { fn from ( v: f32 ) -> Value { Value::F32( v ) } }
+impl From<f64> for Value { fn from ( v: f64 ) -> Value { Value::F64( v ) } }
+impl From<Node> for Value { fn from ( v: Node ) -> Value { Value::Node( v ) } }
+impl From<Edge> for Value { fn from ( v: Edge ) -> Value { Value::Edge( v ) } }
+impl From<Path> for Value { fn from ( v: Path ) -> Value { Value::Path( v ) } }
+impl From<Vec<Value>> for Value { fn from ( v: Vec<Value> ) -> Value { Value::List( v ) } }
+impl From<Vec<( String, Value )>> for Value { fn from ( v: Vec<( String, Value )> ) -> Value { Value::Map( v ) } }
+
+// ---------------------------------------------------------------------------------------------------------------------
+
+/// Single output row, values are in column order
+#[derive( Debug, Clone, PartialEq )]
+pub struct Row
+{
+  pub columns: Vec<String>,
+  pub values: Vec<Value>,
+}
+impl Row
+{
+  pub fn new ( columns: Vec<String>, values: Vec<Value> ) -> Row { Row { columns: columns, values: values } }
+
+  /// Value by column name
+  pub fn get ( &self, column: &str ) -> Option<&Value>
+  {
+    for ( i, name ) in self.columns.iter().enumerate()
+    {
+      if name == column { return self.values.get( i ); }
+    }
+    None
+  }
+
+  /// Value by column index
+  pub fn get_index ( &self, index: usize ) -> Option<&Value> { self.values.get( index ) }
+
+  pub fn len ( &self ) -> usize { self.values.len() }
+}
+
+/// Query output
+#[derive( Debug, Clone, PartialEq )]
+pub struct ResultSet
+{
+  pub columns: Vec<String>,
+  pub rows: Vec<Row>,
+}
+impl ResultSet
+{
+  pub fn new ( columns: Vec<String> ) -> ResultSet { ResultSet { columns: columns, rows: Vec::new() } }
+
+  /// Add a row, values must be in column order
+  pub fn push ( &mut self, values: Vec<Value> ) -> Result<bool, String>
+  {
+    if values.len() != self.columns.len() { return Err( String::from( "Error: Row does not match columns." )); }
+    self.rows.push( Row::new( self.columns.clone(), values ));
+    Ok( true )
+  }
+
+  pub fn len ( &self ) -> usize { self.rows.len() }
+  pub fn is_empty ( &self ) -> bool { self.rows.len() == 0 }
+
+  pub fn column_index ( &self, column: &str ) -> Option<usize>
+  {
+    self.columns.iter().position( | name | name == column )
+  }
+
+  /// All values of a single column
+  pub fn column ( &self, column: &str ) -> Vec<&Value>
+  {
+    let mut ret: Vec<&Value> = Vec::new();
+    let index = self.column_index( column );
+    if index.is_some()
+    {
+      for row in self.rows.iter() { ret.push( row.values.get( index.unwrap() ).unwrap() ); }
+    }
+    ret
+  }
+}
+
+#[cfg(test)]
+mod tests
+{
+  use super::*;
+  use crate::common::DirectionType;
+
+  #[test]
+  fn test_value_accessors ()
+  {
+    assert_eq!( Value::from( "abc" ).as_str(), Some( "abc" ));
+    assert_eq!( Value::from( true ).as_bool(), Some( true ));
+    assert_eq!( Value::Null.is_null(), true );
+    assert_eq!( Value::from( 1 ).is_null(), false );
+
+    assert_eq!( Value::from( -5i8 ).as_i64(), Some( -5 ));
+    assert_eq!( Value::from( 5u32 ).as_i64(), Some( 5 ));
+    assert_eq!( Value::from( u64::MAX ).as_i64(), None );
+    assert_eq!( Value::from( u64::MAX ).as_u64(), Some( u64::MAX ));
+    assert_eq!( Value::from( -1i16 ).as_u64(), None );
+    assert_eq!( Value::from( i128::MIN ).as_i128(), Some( i128::MIN ));
+    assert_eq!( Value::from( u128::MAX ).as_u128(), Some( u128::MAX ));
+    assert_eq!( Value::from( 7i32 ).as_f64(), Some( 7.0 ));
+    assert_eq!( Value::from( 1.5f32 ).as_f64(), Some( 1.5 ));
+    assert_eq!( Value::from( "abc" ).as_i64(), None );
+
+    let list = Value::from( vec![ Value::from( 1 ), Value::Null ] );
+    assert_eq!( list.as_list().unwrap().len(), 2 );
+  }
+
+  #[test]
+  fn test_value_graph_accessors ()
+  {
+    let node = Node::new( String::from( "id" ), Some( String::from( "Person" )), Vec::new() );
+    let edge = Edge::new(
+      String::from( "eid" ), Some( String::from( "KNOWS" )), String::from( "a" ), String::from( "b" ),
+      DirectionType::Right, Vec::new() );
+
+    assert_eq!( Value::from( node.clone() ).as_node().unwrap().id, String::from( "id" ));
+    assert_eq!( Value::from( edge.clone() ).as_edge().unwrap().id, String::from( "eid" ));
+
+    let path = Path::new( vec![ node.clone(), node ], vec![ edge ] );
+    assert_eq!( Value::from( path ).as_path().unwrap().len(), 1 );
+  }
+
+  #[test]
+  fn test_value_equals ()
+  {
+    assert_eq!( Value::from( 38u8 ).equals( &Value::from( 38i64 )), Some( true ));
+    assert_eq!( Value::from( 1i64 ).equals( &Value::from( 1.0f64 )), Some( true ));
+    assert_eq!( Value::from( u128::MAX ).equals( &Value::from( u128::MAX )), Some( true ));
+    assert_eq!( Value::from( "a" ).equals( &Value::from( "b" )), Some( false ));
+    assert_eq!( Value::from( "1" ).equals( &Value::from( 1 )), Some( false ));
+    assert_eq!( Value::Null.equals( &Value::from( 1 )), None );
+
+    let prop = NEProperty::KvpI64(( String::from( "age" ), 38 ));
+    assert_eq!( Value::from( &prop ), Value::I64( 38 ));
+
+    let ints = Value::from( vec![ Value::from( 1 ), Value::from( 2 ) ] );
+    assert_eq!( ints.equals( &Value::from( vec![ Value::from( 1.0 ), Value::from( 2u8 ) ] )), Some( true ));
+    assert_eq!( ints.equals( &Value::from( vec![ Value::from( 1 ) ] )), Some( false ));
+    assert_eq!( ints.equals( &Value::from( vec![ Value::from( 1 ), Value::Null ] )), None );
+  }
+
+  #[test]
+  fn test_value_to_property ()
+  {
+    let key = || String::from( "k" );
+    assert_eq!( Value::from( 38u8 ).to_property( key() ), Some( NEProperty::KvpU8(( key(), 38 ))));
+    assert_eq!( Value::Null.to_property( key() ), None );
+
+    let colors = Value::from( vec![ Value::from( "Pink" ), Value::from( "Black" ) ] );
+    let prop = colors.to_property( key() ).unwrap();
+    assert_eq!( prop, NEProperty::ListString(( key(), vec![ String::from( "Pink" ), String::from( "Black" ) ] )));
+    assert_eq!( Value::from( &prop ), colors );
+
+    let mixed = Value::from( vec![ Value::from( 1 ), Value::from( 2.5 ) ] );
+    assert_eq!( mixed.to_property( key() ), Some( NEProperty::ListFloat(( key(), vec![ 1.0, 2.5 ] ))));
+    assert_eq!( Value::from( vec![ Value::from( 1 ), Value::from( "a" ) ] ).to_property( key() ), None );
+  }
+
+  #[test]
+  fn test_result_set ()
+  {
+    let mut rs = ResultSet::new( vec![ String::from( "name" ), String::from( "age" ) ] );
+    assert_eq!( rs.is_empty(), true );
+
+    assert_eq!( rs.push( vec![ Value::from( "Alice" ), Value::from( 38u8 ) ] ).is_ok(), true );
+    assert_eq!( rs.push( vec![ Value::from( "Bob" ), Value::Null ] ).is_ok(), true );
+    assert_eq!( rs.push( vec![ Value::from( "Chris" ) ] ).is_err(), true );
+
+    assert_eq!( rs.len(), 2 );
+    assert_eq!( rs.column_index( "age" ), Some( 1 ));
+    assert_eq!( rs.rows.get( 0 ).unwrap().get( "name" ).unwrap().as_str(), Some( "Alice" ));
+    assert_eq!( rs.rows.get( 0 ).unwrap().get( "age" ).unwrap().as_u64(), Some( 38 ));
+    assert_eq!( rs.rows.get( 1 ).unwrap().get( "age" ).unwrap().is_null(), true );
+    assert_eq!( rs.rows.get( 1 ).unwrap().get( "missing" ), None );
+    assert_eq!( rs.column( "name" ).len(), 2 );
+  }
+}
@@ -5,12 +5,37 @@ pub struct SyntaxToken
 { 
   pub token_type: SyntaxTokenType,
   pub val: String,
+  pub span: Span, // where the token is written in the query
 }
 impl SyntaxToken 
 {
   pub fn new ( token_type: SyntaxTokenType, val: String ) -> SyntaxToken 
   {
-    SyntaxToken { token_type: token_type, val: val }
+    SyntaxToken { token_type: token_type, val: val, span: Span::default() }
+  }
+
+  /// -number written as a - token and a number token, spanning both
+  pub fn negated ( minus: &SyntaxToken, number: &SyntaxToken ) -> SyntaxToken 
+  {
+    SyntaxToken { token_type: SyntaxTokenType::Value, val: format!( "-{}", number.val ), span: minus.span.to( &number.span ) }
+  }
+}
+
+/// Byte range of a token in the query, line and column (in characters) start at 1
+#[derive( Debug, PartialEq, Clone, Copy, Default )]
+pub struct Span 
+{
+  pub start: usize,
+  pub end: usize,
+  pub line: usize,
+  pub column: usize,
+}
+impl Span 
+{
+  /// Span from the start of self to the end of other
+  pub fn to ( &self, other: &Span ) -> Span 
+  {
+    Span { start: self.start, end: other.end.max( self.end ), line: self.line, column: self.column }
   }
 }
 
@@ -22,15 +47,24 @@ pub enum SyntaxTokenType
 {
   KeywordCreate, KeywordGraph, KeywordReturn, KeywordAs, KeywordWhere,
   KeywordFrom, KeywordInsert, KeywordInto, KeywordMatch, KeywordStarts,
-  KeywordEnds, KeywordWith, KeywordAnd, KeywordOr,
+  KeywordEnds, KeywordWith, KeywordAnd, KeywordOr, KeywordSet,
+  KeywordDelete, KeywordDetach, KeywordMerge, KeywordOn, KeywordRemove,
+  KeywordNot, KeywordXor, KeywordContains, KeywordIn, KeywordIs, KeywordNull,
+  KeywordDistinct, KeywordOrder, KeywordBy, KeywordAsc, KeywordDesc, KeywordSkip, KeywordLimit,
+  KeywordOptional, KeywordUnwind,
 
-  Label, PrimaryLabel,
+  Label, PrimaryLabel, LabelExpression, HopRange,
   OpenNode, CloseNode,
   OpenEdge, CloseEdge,
   EdgeDirection, EdgeLeft, EdgeRight,
   OpenBrace, CloseBrace,
   OpenBracket, CloseBracket,
+  OpenIndex, Range, // list[0], list[1..3]
   Key, Value, StringValue,
+  Parameter, // $name, the value comes from the parameter map
+  Equals, PlusEquals,
+  EndStatement, // ; between the statements of a script
+  UnclosedComment, // /* without */, parsing the query fails at it
 
   SyntaxNodeRef,
 }
@@ -50,7 +84,8 @@ pub enum TokenType
   OpenBrace, CloseBrace,
   Quote,
   FrontSlash,
-  Hyphen, Colon, Comma, Pipe, Ampersand,
+  Hyphen, Colon, Comma, Semicolon, Pipe, Ampersand,
+  Equals,
   Char,
   LT, GT,
 }
@@ -71,10 +106,12 @@ pub fn token_type ( c: &char ) -> TokenType
   if is_squote( c ) { return TokenType::Quote }
   if is_dquote( c ) { return TokenType::Quote }
   if is_comma( c ) { return TokenType::Comma }
+  if is_semicolon( c ) { return TokenType::Semicolon }
   if is_open_lt( c ) { return TokenType::LT }
   if is_close_gt( c ) { return TokenType::GT }
   if is_pipe( c ) { return TokenType::Pipe }
   if is_ampersand( c ) { return TokenType::Ampersand }
+  if is_equals( c ) { return TokenType::Equals }
   TokenType::Char
 }
 
@@ -3,6 +3,7 @@ use std::path::PathBuf;
 use std::fs::{ exists, File, OpenOptions };
 use std::io::Error;
 use uuid::Uuid;
+use crate::common::RAW_UUID_BYTES;
 
 /* @version 0.3.0 */
 
@@ -71,6 +72,13 @@ pub fn parse_padded_str ( padded_str: &str ) -> &str
   split[0]
 }
 
+/// Strip padding from a stored UUID
+pub fn parse_uuid_str ( padded_uuid: &str ) -> &str
+{
+  if padded_uuid.len() > RAW_UUID_BYTES { return &padded_uuid[0..RAW_UUID_BYTES]; }
+  padded_uuid
+}
+
 /// Path is File
 pub fn is_file ( path: &PathBuf ) -> bool { path.is_file() }
 
@@ -248,6 +256,17 @@ mod tests
     assert_eq!( pad3_str, String::from( "no-padding" ));
   }
 
+  #[test]
+  fn test_parse_uuid_str () 
+  {
+    assert_eq!( 
+      parse_uuid_str( "67e55044-10b1-426f-9247-bb680e5fe0c8::::" ), 
+      String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" ));
+    assert_eq!( 
+      parse_uuid_str( "67e55044-10b1-426f-9247-bb680e5fe0c8" ), 
+      String::from( "67e55044-10b1-426f-9247-bb680e5fe0c8" ));
+  }
+
   #[test]
   fn test_u32_binary () 
   {